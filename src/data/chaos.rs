@@ -0,0 +1,81 @@
+use std::io;
+use std::sync::Mutex;
+use crate::utils::SeededRng;
+
+/// Kinds of storage failure that can be injected into `DataPersistence`
+#[derive(Debug, Clone, PartialEq)]
+pub enum FailureMode {
+    DiskFull,     // Write fails before anything reaches the file
+    PartialWrite, // Only part of the content is written before the write fails
+    CorruptRead,  // Read succeeds but returns damaged content
+}
+
+/// Failure injection for chaos testing of the persistence layer.
+///
+/// Intended for tests and development builds only. Each file operation that
+/// matches one of the configured modes fails with probability `rate`, using a
+/// seeded generator so runs are reproducible.
+#[derive(Debug)]
+pub struct FailureInjector {
+    modes: Vec<FailureMode>,
+    rate: f64,
+    rng: Mutex<SeededRng>,
+}
+
+impl FailureInjector {
+    pub fn new(modes: Vec<FailureMode>, rate: f64, seed: u64) -> Self {
+        Self {
+            modes,
+            rate: rate.clamp(0.0, 1.0),
+            rng: Mutex::new(SeededRng::new(seed)),
+        }
+    }
+
+    /// Inject the given failure on every operation
+    pub fn always(mode: FailureMode) -> Self {
+        Self::new(vec![mode], 1.0, 1)
+    }
+
+    fn should_fail(&self, mode: &FailureMode) -> bool {
+        self.modes.contains(mode) && self.rate > 0.0 && self.rng.lock().unwrap().next_f64() < self.rate
+    }
+
+    /// Decide how a write of `content` should behave.
+    /// Returns the bytes to actually write and the error to report afterwards, if any.
+    pub fn on_write<'a>(&self, path: &str, content: &'a str) -> (Option<&'a str>, Option<io::Error>) {
+        if self.should_fail(&FailureMode::DiskFull) {
+            let error = io::Error::new(
+                io::ErrorKind::StorageFull,
+                format!("No space left on device while writing {} (injected)", path),
+            );
+            return (None, Some(error));
+        }
+
+        if self.should_fail(&FailureMode::PartialWrite) {
+            let mut cut = content.len() / 2;
+            while !content.is_char_boundary(cut) {
+                cut -= 1;
+            }
+            let error = io::Error::new(
+                io::ErrorKind::WriteZero,
+                format!("Write to {} interrupted after {} of {} bytes (injected)", path, cut, content.len()),
+            );
+            return (Some(&content[..cut]), Some(error));
+        }
+
+        (Some(content), None)
+    }
+
+    /// Possibly damage content that was read from disk
+    pub fn on_read(&self, content: String) -> String {
+        if self.should_fail(&FailureMode::CorruptRead) {
+            let mut cut = content.len() / 3;
+            while !content.is_char_boundary(cut) {
+                cut -= 1;
+            }
+            format!("{}\u{0}#corrupt", &content[..cut])
+        } else {
+            content
+        }
+    }
+}
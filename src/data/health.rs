@@ -0,0 +1,62 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum HealthStatus {
+    Pass,
+    Warn,
+    Fail,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HealthCheck {
+    pub name: String,
+    pub status: HealthStatus,
+    pub detail: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct HealthReport {
+    pub checks: Vec<HealthCheck>,
+}
+
+impl HealthCheck {
+    pub fn new(name: &str, status: HealthStatus, detail: String) -> Self {
+        Self {
+            name: name.to_string(),
+            status,
+            detail,
+        }
+    }
+
+    pub fn get_status_display(&self) -> String {
+        match self.status {
+            HealthStatus::Pass => "PASS ✅".to_string(),
+            HealthStatus::Warn => "WARN ⚠️".to_string(),
+            HealthStatus::Fail => "FAIL ❌".to_string(),
+        }
+    }
+}
+
+impl HealthReport {
+    pub fn add(&mut self, check: HealthCheck) {
+        self.checks.push(check);
+    }
+
+    pub fn count(&self, status: HealthStatus) -> usize {
+        self.checks.iter().filter(|c| c.status == status).count()
+    }
+
+    pub fn has_failures(&self) -> bool {
+        self.count(HealthStatus::Fail) > 0
+    }
+
+    pub fn get_summary(&self) -> String {
+        format!(
+            "{} checks: {} passed, {} warnings, {} failed",
+            self.checks.len(),
+            self.count(HealthStatus::Pass),
+            self.count(HealthStatus::Warn),
+            self.count(HealthStatus::Fail)
+        )
+    }
+}
@@ -0,0 +1,73 @@
+use std::collections::HashMap;
+use uuid::Uuid;
+use crate::modules::flight::Flight;
+
+/// Positions of flights in `database.flights`, keyed by id, flight number and route, so
+/// lookups don't scan every flight. The index only knows about flights added through the
+/// manager. Every hit is checked against the flight now at that position; if the vector
+/// has been changed behind its back (a length mismatch, or a hit that no longer matches)
+/// lookups fall back to a scan until the index is rebuilt.
+#[derive(Debug, Clone, Default)]
+pub struct FlightIndex {
+    by_id: HashMap<Uuid, usize>,
+    by_number: HashMap<String, Vec<usize>>,
+    by_route: HashMap<(String, String), Vec<usize>>,
+    indexed: usize,
+}
+
+impl FlightIndex {
+    pub fn build(flights: &[Flight]) -> Self {
+        let mut index = Self::default();
+        for (position, flight) in flights.iter().enumerate() {
+            index.insert(position, flight);
+        }
+        index
+    }
+
+    /// Record the flight just pushed onto the end of the vector at `position`
+    pub fn insert(&mut self, position: usize, flight: &Flight) {
+        self.by_id.insert(flight.id, position);
+        self.by_number.entry(flight.flight_number.clone()).or_default().push(position);
+        self.by_route
+            .entry((flight.origin.clone(), flight.destination.clone()))
+            .or_default()
+            .push(position);
+        self.indexed = self.indexed.max(position + 1);
+    }
+
+    fn is_current(&self, flights: &[Flight]) -> bool {
+        self.indexed == flights.len()
+    }
+
+    pub fn position_by_id(&self, flights: &[Flight], flight_id: Uuid) -> Option<usize> {
+        self.by_id
+            .get(&flight_id)
+            .copied()
+            .filter(|&i| self.is_current(flights) && flights[i].id == flight_id)
+            .or_else(|| flights.iter().position(|f| f.id == flight_id))
+    }
+
+    /// With duplicate flight numbers the earliest flight that still carries the number wins
+    pub fn position_by_number(&self, flights: &[Flight], flight_number: &str) -> Option<usize> {
+        self.by_number
+            .get(flight_number)
+            .filter(|_| self.is_current(flights))
+            .and_then(|positions| positions.iter().copied().find(|&i| flights[i].flight_number == flight_number))
+            .or_else(|| flights.iter().position(|f| f.flight_number == flight_number))
+    }
+
+    /// Every flight from `origin` to `destination`, in vector order
+    pub fn route<'a>(&self, flights: &'a [Flight], origin: &str, destination: &str) -> Vec<&'a Flight> {
+        let on_route = |f: &Flight| f.origin == origin && f.destination == destination;
+        if self.is_current(flights) {
+            let indexed: Vec<&Flight> = self.by_route
+                .get(&(origin.to_string(), destination.to_string()))
+                .map(|positions| positions.iter().map(|&i| &flights[i]).collect())
+                .unwrap_or_default();
+            if indexed.iter().all(|f| on_route(f)) {
+                return indexed;
+            }
+        }
+        flights.iter().filter(|f| on_route(f)).collect()
+    }
+}
@@ -1,48 +1,281 @@
 use std::error::Error;
+use std::path::Path;
+use std::collections::{HashMap, HashSet};
 use uuid::Uuid;
-use chrono::{DateTime, Utc, Duration, Timelike};
+use serde::Serialize;
+use chrono::{DateTime, NaiveDate, Utc, Duration, Timelike};
 use crate::modules::{
-    flight::{Flight, FlightStatus, SeatClass},
-    aircraft::{Aircraft, AircraftStatus},
-    booking::{Booking, Passenger, PassengerType, BookingStatus},
+    flight::{self, Flight, FlightStatus, FlightTemplate, PriceBreakdown, SeatClass, WaitlistEntry},
+    aircraft::{Aircraft, AircraftStatus, SeatConfiguration},
+    booking::{self, Booking, MealPreference, Passenger, PassengerType, BookingStatus},
     airport::Airport,
-    admin::{AdminPanel, AdminUser, AdminLevel, PricingRule, SystemMetrics},
+    admin::{AdminAction, AdminLevel, AdminPanel, AdminUser, PricingRule, RoutePreference, SystemMetrics},
+    loyalty::{self, LoyaltyAccount, LoyaltyTier},
+    currency::{CurrencyConverter, ExchangeQuote},
 };
-use crate::data::persistence::{DataPersistence, AirportDatabase};
+use crate::data::persistence::{DataPersistence, AirportDatabase, StorageMode, DATA_FILES};
+use crate::data::health::{HealthCheck, HealthReport, HealthStatus};
+use crate::data::messages::MessageSink;
+use crate::data::index::FlightIndex;
+use crate::errors::{self, AirportError};
+
+/// An option offered to a passenger when their preferred seat class is sold out
+#[derive(Debug, Clone, PartialEq)]
+pub enum ClassAlternative {
+    Class {
+        class: SeatClass,
+        price: f64,
+        price_difference: f64, // Relative to the requested class; negative for downgrades
+        seats_available: u32,
+    },
+    Waitlist,
+}
+
+/// Orders for flight search results
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortKey {
+    DepartureTime,
+    Duration,
+    PriceEconomy,
+    LoadFactor,
+}
+
+/// Outcome of cancelling a flight, including the messages to send to affected passengers
+#[derive(Debug, Clone, Default)]
+pub struct FlightCancellation {
+    pub flight_number: String,
+    pub reason: String,
+    pub already_cancelled: bool,
+    pub bookings_cancelled: usize,
+    pub total_refunded: f64,
+    pub notifications: Vec<String>,
+}
+
+/// A flight whose status moved during a simulation update
+#[derive(Debug, Clone, PartialEq)]
+pub struct FlightStatusChange {
+    pub flight_number: String,
+    pub from: FlightStatus,
+    pub to: FlightStatus,
+}
+
+/// An aircraft whose status moved during a simulation update
+#[derive(Debug, Clone, PartialEq)]
+pub struct AircraftStatusChange {
+    pub registration: String,
+    pub from: AircraftStatus,
+    pub to: AircraftStatus,
+}
+
+/// What a simulation update changed; empty when nothing moved or the update was skipped
+#[derive(Debug, Clone, Default)]
+pub struct SimulationReport {
+    pub flight_changes: Vec<FlightStatusChange>,
+    pub aircraft_changes: Vec<AircraftStatusChange>,
+    pub cancellations: Vec<String>, // Flight numbers cancelled by the simulation
+    pub notifications: Vec<String>,
+}
+
+impl SimulationReport {
+    pub fn is_empty(&self) -> bool {
+        self.flight_changes.is_empty()
+            && self.aircraft_changes.is_empty()
+            && self.cancellations.is_empty()
+            && self.notifications.is_empty()
+    }
+
+    pub fn get_summary(&self) -> String {
+        format!(
+            "{} flight and {} aircraft status changes, {} cancellations",
+            self.flight_changes.len(),
+            self.aircraft_changes.len(),
+            self.cancellations.len()
+        )
+    }
+}
+
+/// One passenger requirement on a flight, as seen by catering and ground staff
+#[derive(Debug, Clone)]
+pub struct SpecialServiceItem {
+    pub ticket_number: String,
+    pub passenger_name: String,
+    pub requirement: String,
+    pub category: String,
+    pub critical: bool,
+    pub fulfilled: bool,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SpecialServicesReport {
+    pub flight_number: String,
+    pub items: Vec<SpecialServiceItem>,
+}
+
+impl SpecialServicesReport {
+    /// Requirement categories with their counts, most common first
+    pub fn counts(&self) -> Vec<(String, usize)> {
+        let mut counts: Vec<(String, usize)> = Vec::new();
+        for item in &self.items {
+            match counts.iter_mut().find(|(category, _)| *category == item.category) {
+                Some((_, count)) => *count += 1,
+                None => counts.push((item.category.clone(), 1)),
+            }
+        }
+        counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        counts
+    }
+
+    /// e.g. "3 wheelchair, 2 vegetarian"
+    pub fn get_summary(&self) -> String {
+        self.counts()
+            .iter()
+            .map(|(category, count)| format!("{} {}", count, category))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    pub fn unfulfilled_critical(&self) -> Vec<&SpecialServiceItem> {
+        self.items.iter().filter(|i| i.critical && !i.fulfilled).collect()
+    }
+}
+
+/// Meals catering must load for a flight
+#[derive(Debug, Clone, Default)]
+pub struct CateringRequirements {
+    pub flight_number: String,
+    pub meal_service: bool,
+    pub meal_options: Vec<String>,
+    pub economy_meals: u32,
+    pub business_meals: u32,
+    pub first_class_meals: u32,
+    pub special_meals: Vec<(String, u32)>, // Dietary category and count, included in the class totals
+}
+
+impl CateringRequirements {
+    pub fn total_meals(&self) -> u32 {
+        self.economy_meals + self.business_meals + self.first_class_meals
+    }
+}
+
+/// Outcome of `run_booking_simulation`
+#[derive(Debug, Clone, Default)]
+pub struct BookingSimulationStats {
+    pub attempted: u32,
+    pub booked: u32,
+    pub rejected: u32,
+    pub waitlisted: u32,                  // Requested class and every alternative were full
+    pub revenue: f64,
+    pub load_factors: Vec<(String, f64)>, // Flight number and percentage of seats sold
+    pub invariant_violations: Vec<String>,
+}
+
+/// One day of a flexible-date search
+#[derive(Debug, Clone, PartialEq)]
+pub struct DateFare {
+    pub date: NaiveDate,
+    pub cheapest: Option<(String, f64)>, // Flight number and fare; None when nothing is bookable that day
+}
+
+/// A complimentary upgrade granted by `process_upgrades`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Upgrade {
+    pub ticket_number: String,
+    pub passenger_name: String,
+    pub tier: LoyaltyTier,
+    pub from: SeatClass,
+    pub to: SeatClass,
+}
+
+/// A waitlisted passenger given a seat freed by a cancellation
+#[derive(Debug, Clone, PartialEq)]
+pub struct WaitlistPromotion {
+    pub ticket_number: String,
+    pub passenger_name: String,
+    pub seat_class: SeatClass,
+}
+
+/// Follow-ups from cancelling a booking
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BookingCancellation {
+    pub refund: f64,                           // Returned to the passenger under the refund policy
+    pub companion_ticket: Option<String>,      // Other leg of a round trip, still cancellable
+    pub promoted: Option<WaitlistPromotion>,   // Who got the freed seat
+}
+
+/// Everything stored about one passenger, for data access requests
+#[derive(Debug, Clone, Serialize)]
+pub struct PassengerDataExport {
+    pub email: String,
+    pub exported_at: DateTime<Utc>,
+    pub profiles: Vec<Passenger>, // One per distinct passenger record using the email
+    pub bookings: Vec<Booking>,   // Including seat, baggage and payment details
+    pub loyalty: Option<LoyaltyAccount>, // Frequent flyer account registered to the email
+}
 
 pub struct DataManager {
     pub database: AirportDatabase,
     pub persistence: DataPersistence,
     pub admin_panel: AdminPanel,
+    pub loyalty: Vec<LoyaltyAccount>,
+    pub currency: CurrencyConverter,
+    display_currency: String,
+    messages: MessageSink,
     last_simulation_update: DateTime<Utc>,
+    flight_index: FlightIndex,
 }
 
 impl DataManager {
     pub async fn new() -> Result<Self, Box<dyn Error>> {
-        println!("🔧 Initializing Rust International Airport Data Manager...");
+        Self::with_messages(MessageSink::Stdout).await
+    }
+
+    /// A manager that prints nothing, for use as a library or behind another UI
+    pub async fn new_quiet() -> Result<Self, Box<dyn Error>> {
+        Self::with_messages(MessageSink::Quiet).await
+    }
+
+    /// A manager whose status messages, and those of its persistence layer, go to `messages`
+    pub async fn with_messages(messages: MessageSink) -> Result<Self, Box<dyn Error>> {
+        Self::new_with_dir(None, messages).await
+    }
+
+    /// A manager storing its data in `data_dir`, or in `config::DATA_DIR` when `None`
+    pub async fn new_with_dir(data_dir: Option<&str>, messages: MessageSink) -> Result<Self, Box<dyn Error>> {
+        messages.say("🔧 Initializing Rust International Airport Data Manager...");
         
-        let persistence = DataPersistence::new();
+        let persistence = data_dir.map_or_else(DataPersistence::new, DataPersistence::with_dir);
+        let mut persistence = persistence.with_messages(messages.clone());
         
         // Initialize data persistence and create sample data if needed
         persistence.initialize().await?;
         
         // Load all data from files
         let database = persistence.load_all_data().await?;
+        let loyalty = persistence.load_loyalty_accounts().await?;
         
         // Validate data integrity
         let issues = persistence.validate_data_integrity().await?;
         if !issues.is_empty() {
-            println!("⚠️ Data integrity issues found:");
+            messages.say("⚠️ Data integrity issues found:");
             for issue in &issues {
-                println!("  - {}", issue);
+                messages.say(format!("  - {}", issue));
             }
         }
         
         let mut admin_panel = AdminPanel::new();
+        admin_panel.restore_log(persistence.load_audit_log().await?);
+        let credentials = persistence.load_admin_credentials().await?;
+        if credentials.is_empty() {
+            // First run: keep the demo accounts and store them hashed
+            persistence.save_admin_credentials(&admin_panel.credentials).await?;
+        } else {
+            admin_panel.credentials = credentials;
+        }
         
         // Initialize system metrics
         admin_panel.system_metrics.update_flight_metrics(&database.flights);
         admin_panel.system_metrics.update_aircraft_metrics(&database.aircraft);
+        admin_panel.system_metrics.update_load_factor(&database.flights);
         admin_panel.system_metrics.total_bookings = database.bookings.len() as u32;
         
         // Add some default pricing rules
@@ -74,22 +307,149 @@ impl DataManager {
             admin_panel.pricing_rules.push(rule);
         }
 
-        println!("✅ Data Manager initialized successfully!");
-        println!("📊 Loaded: {} flights, {} aircraft, {} bookings, {} airports", 
+        let route_preferences = persistence.load_route_preferences().await?;
+        if route_preferences.is_empty() {
+            // First run: widebodies on long-haul, narrowbodies on the domestic trunk route
+            let widebodies = || vec!["Airbus A380".to_string(), "Boeing 777-300".to_string()];
+            admin_panel.route_preferences = vec![
+                RoutePreference::new("London long-haul".to_string(), "*-LHR".to_string(), widebodies(), None),
+                RoutePreference::new("Tokyo long-haul".to_string(), "*-NRT".to_string(), widebodies(), None),
+                RoutePreference::new(
+                    "Transcontinental".to_string(),
+                    "LAX-JFK".to_string(),
+                    vec!["Boeing 737-800".to_string(), "Airbus A320".to_string()],
+                    None,
+                ),
+            ];
+            persistence.save_route_preferences(&admin_panel.route_preferences).await?;
+        } else {
+            admin_panel.route_preferences = route_preferences;
+        }
+
+        messages.say("✅ Data Manager initialized successfully!");
+        messages.say(format!("📊 Loaded: {} flights, {} aircraft, {} bookings, {} airports", 
             database.flights.len(), 
             database.aircraft.len(), 
             database.bookings.len(), 
             database.airports.len()
-        );
+        ));
 
-        Ok(Self {
+        // Exchange rates for display; the built-in ones fill any gaps
+        let rates_path = format!("{}/{}", persistence.data_dir(), crate::config::currency::RATES_FILE);
+        let currency = if Path::new(&rates_path).exists() {
+            CurrencyConverter::load_from_file(&rates_path).unwrap_or_else(|e| {
+                messages.say(format!("⚠️ Ignoring {}: {} - using built-in exchange rates", rates_path, e));
+                CurrencyConverter::new()
+            })
+        } else {
+            CurrencyConverter::new()
+        };
+
+        let flight_index = FlightIndex::build(&database.flights);
+        let mut manager = Self {
             database,
             persistence,
             admin_panel,
+            loyalty,
+            currency,
+            display_currency: crate::config::currency::BASE_CURRENCY.to_string(),
+            messages,
             last_simulation_update: Utc::now(),
+            flight_index,
+        };
+
+        // Flights saved before fares depended on distance still carry the flat defaults
+        let migrated = manager.migrate_distance_fares();
+        if migrated > 0 {
+            manager.messages.say(format!("💱 Migrated {} flights to distance-based fares", migrated));
+        }
+        manager.update_fuel_metrics();
+
+        Ok(manager)
+    }
+
+    /// Currency prices are shown in. Bookings are still charged in USD.
+    pub fn display_currency(&self) -> &str {
+        &self.display_currency
+    }
+
+    pub fn set_display_currency(&mut self, currency: &str) -> Result<(), String> {
+        let currency = currency.trim().to_uppercase();
+        if self.currency.rate(&currency).is_none() {
+            return Err(format!(
+                "No exchange rate for {} - choose one of {}",
+                currency,
+                self.currency.currencies().join(", ")
+            ));
+        }
+        self.display_currency = currency;
+        Ok(())
+    }
+
+    /// The display currency and its rate, or None while showing USD
+    pub fn display_quote(&self) -> Option<ExchangeQuote> {
+        let rate = self.currency.rate(&self.display_currency)?;
+        (self.display_currency != crate::config::currency::BASE_CURRENCY).then(|| ExchangeQuote {
+            currency: self.display_currency.clone(),
+            rate,
         })
     }
 
+    /// Estimated fuel cost of every flight yet to depart, at the configured fuel price.
+    /// Flights with an unknown aircraft or airport are left out.
+    pub fn projected_fuel_cost(&self) -> f64 {
+        self.database.flights
+            .iter()
+            .filter(|f| matches!(f.status, FlightStatus::OnTime | FlightStatus::Delayed(_) | FlightStatus::Boarding))
+            .filter_map(|f| {
+                let aircraft = self.get_aircraft_by_id(f.aircraft_id)?;
+                let distance = self.route_distance_km(&f.origin, &f.destination)?;
+                Some(f.fuel_cost(distance, aircraft, crate::config::FUEL_PRICE_PER_LITER))
+            })
+            .sum()
+    }
+
+    fn update_fuel_metrics(&mut self) {
+        self.admin_panel.system_metrics.projected_fuel_cost = self.projected_fuel_cost();
+    }
+
+    /// Great-circle distance between two known airports
+    pub fn route_distance_km(&self, origin: &str, destination: &str) -> Option<f64> {
+        let origin = self.get_airport_by_code(origin)?;
+        let destination = self.get_airport_by_code(destination)?;
+        Some(origin.get_distance_to(destination))
+    }
+
+    /// Points along the great circle a flight follows, for drawing it on a map. None if the
+    /// flight or either airport is unknown.
+    pub fn flight_path(&self, flight_number: &str, segments: usize) -> Option<Vec<(f64, f64)>> {
+        let flight = self.get_flight_by_number(flight_number)?;
+        let from = &self.get_airport_by_code(&flight.origin)?.coordinates;
+        let to = &self.get_airport_by_code(&flight.destination)?.coordinates;
+        Some(crate::utils::great_circle_path(from.latitude, from.longitude, to.latitude, to.longitude, segments))
+    }
+
+    /// Check the flight's aircraft has the range to fly its route
+    pub fn validate_flight_range(&self, flight: &Flight) -> Result<(), String> {
+        self.database.validate_flight_range(flight)
+    }
+
+    /// Recompute base fares from route distance for flights still on flat fares.
+    /// Flights on routes with unknown airports are left alone. Returns how many changed.
+    fn migrate_distance_fares(&mut self) -> usize {
+        let distances: Vec<(usize, f64)> = self.database.flights
+            .iter()
+            .enumerate()
+            .filter(|(_, f)| !f.pricing.distance_based)
+            .filter_map(|(i, f)| self.route_distance_km(&f.origin, &f.destination).map(|d| (i, d)))
+            .collect();
+
+        for &(i, distance) in &distances {
+            self.database.flights[i].recompute_base_fares(distance);
+        }
+        distances.len()
+    }
+
     // Flight Operations
     pub fn search_flights(
         &self, 
@@ -97,8 +457,12 @@ impl DataManager {
         destination: Option<&str>, 
         date: Option<DateTime<Utc>>
     ) -> Vec<&Flight> {
-        self.database.flights
-            .iter()
+        let candidates: Vec<&Flight> = match (origin, destination) {
+            (Some(org), Some(dest)) => self.flight_index.route(&self.database.flights, org, dest),
+            _ => self.database.flights.iter().collect(),
+        };
+        candidates
+            .into_iter()
             .filter(|flight| {
                 if let Some(org) = origin {
                     if flight.origin != org {
@@ -122,12 +486,219 @@ impl DataManager {
             .collect()
     }
 
+    /// `search_flights`, keeping only flights with a seat left in `seat_class` when one is given
+    pub fn search_flights_with_class(
+        &self,
+        origin: Option<&str>,
+        destination: Option<&str>,
+        date: Option<DateTime<Utc>>,
+        seat_class: Option<&SeatClass>,
+    ) -> Vec<&Flight> {
+        let mut flights = self.search_flights(origin, destination, date);
+        if let Some(class) = seat_class {
+            flights.retain(|f| f.get_available_seats(class) > 0);
+        }
+        flights
+    }
+
+    /// Flights with a `seat_class` seat whose current fare (dynamic and demand pricing
+    /// included) is at most `max_price`
+    pub fn search_flights_by_price(
+        &self,
+        origin: Option<&str>,
+        destination: Option<&str>,
+        date: Option<DateTime<Utc>>,
+        seat_class: &SeatClass,
+        max_price: f64,
+    ) -> Vec<&Flight> {
+        let mut flights = self.search_flights_with_class(origin, destination, date, Some(seat_class));
+        flights.retain(|f| self.get_fare(f, seat_class) <= max_price);
+        flights
+    }
+
+    /// Sort search results by `key`. Ties are broken by flight number (always A-Z) so the
+    /// order is the same every time.
+    pub fn sort_flights(&self, flights: &mut Vec<&Flight>, key: SortKey, ascending: bool) {
+        let load = |f: &Flight| {
+            let available = f.seat_availability.economy + f.seat_availability.business + f.seat_availability.first_class;
+            crate::utils::calculate_load_factor(f.total_capacity.saturating_sub(available), f.total_capacity)
+        };
+
+        flights.sort_by(|a, b| {
+            let order = match key {
+                SortKey::DepartureTime => a.departure_time.cmp(&b.departure_time),
+                SortKey::Duration => a.duration().cmp(&b.duration()),
+                SortKey::PriceEconomy => self.get_fare(a, &SeatClass::Economy).total_cmp(&self.get_fare(b, &SeatClass::Economy)),
+                SortKey::LoadFactor => load(a).total_cmp(&load(b)),
+            };
+            let order = if ascending { order } else { order.reverse() };
+            order.then_with(|| a.flight_number.cmp(&b.flight_number))
+        });
+    }
+
+    /// Itineraries from `origin` to `destination` with at most `max_stops` connections,
+    /// fastest first. Each connection leaves at least the connecting airport's minimum
+    /// connection time after the previous leg lands, and no itinerary passes through the
+    /// same airport twice.
+    pub fn find_connecting_flights(&self, origin: &str, destination: &str, max_stops: usize) -> Vec<Vec<&Flight>> {
+        let mut itineraries = Vec::new();
+        let mut chain = Vec::new();
+        let mut visited = vec![origin];
+        self.extend_itinerary(origin, destination, max_stops, &mut chain, &mut visited, &mut itineraries);
+
+        itineraries.sort_by_key(|legs: &Vec<&Flight>| {
+            (legs[legs.len() - 1].arrival_time - legs[0].departure_time, legs.len())
+        });
+        itineraries
+    }
+
+    fn extend_itinerary<'a: 'v, 'v>(
+        &'a self,
+        at: &str,
+        destination: &str,
+        stops_left: usize,
+        chain: &mut Vec<&'a Flight>,
+        visited: &mut Vec<&'v str>,
+        itineraries: &mut Vec<Vec<&'a Flight>>,
+    ) {
+        let previous = chain.last().copied();
+
+        for flight in &self.database.flights {
+            if flight.origin != at
+                || !flight.is_available_for_booking()
+                || previous.is_some_and(|previous| {
+                    flight.departure_time < previous.arrival_time + Duration::minutes(self.connection_minutes(previous, flight))
+                })
+                || visited.contains(&flight.destination.as_str())
+            {
+                continue;
+            }
+
+            chain.push(flight);
+            if flight.destination == destination {
+                itineraries.push(chain.clone());
+            } else if stops_left > 0 {
+                visited.push(&flight.destination);
+                self.extend_itinerary(&flight.destination, destination, stops_left - 1, chain, visited, itineraries);
+                visited.pop();
+            }
+            chain.pop();
+        }
+    }
+
+    /// Minutes needed to get from `inbound` onto `outbound` where they meet
+    fn connection_minutes(&self, inbound: &Flight, outbound: &Flight) -> i64 {
+        let Some(hub) = self.get_airport_by_code(&inbound.destination) else {
+            return crate::config::MIN_CONNECTION_MINUTES;
+        };
+        let abroad = |code: &str| self.get_airport_by_code(code).is_some_and(|a| a.country != hub.country);
+        hub.connection_minutes(abroad(&inbound.origin) && abroad(&outbound.destination))
+    }
+
+    /// Cheapest bookable flight and fare for each day within `window_days` of `center_date`, sorted by date
+    pub fn search_flexible_dates(
+        &self,
+        origin: &str,
+        destination: &str,
+        center_date: DateTime<Utc>,
+        window_days: u32,
+        class: &SeatClass,
+    ) -> Vec<DateFare> {
+        let window = window_days as i64;
+        (-window..=window)
+            .map(|offset| {
+                let day = center_date + Duration::days(offset);
+                let cheapest = self.search_flights(Some(origin), Some(destination), Some(day))
+                    .into_iter()
+                    .filter(|f| f.is_available_for_booking() && f.get_available_seats(class) > 0)
+                    .map(|f| (f.flight_number.clone(), self.get_fare(f, class)))
+                    .min_by(|a, b| a.1.total_cmp(&b.1));
+
+                DateFare {
+                    date: day.date_naive(),
+                    cheapest,
+                }
+            })
+            .collect()
+    }
+
+    /// Flights operated by aircraft whose model contains `model`, ignoring case ("a380", "737")
+    pub fn search_flights_by_aircraft_model(&self, model: &str) -> Vec<&Flight> {
+        let model = model.trim().to_lowercase();
+        if model.is_empty() {
+            return Vec::new();
+        }
+
+        let aircraft_ids: HashSet<Uuid> = self.database.aircraft
+            .iter()
+            .filter(|a| a.model.to_lowercase().contains(&model))
+            .map(|a| a.id)
+            .collect();
+
+        self.database.flights
+            .iter()
+            .filter(|f| aircraft_ids.contains(&f.aircraft_id))
+            .collect()
+    }
+
     pub fn get_flight_by_id(&self, flight_id: Uuid) -> Option<&Flight> {
-        self.database.flights.iter().find(|f| f.id == flight_id)
+        self.flight_index
+            .position_by_id(&self.database.flights, flight_id)
+            .map(|i| &self.database.flights[i])
     }
 
     pub fn get_flight_by_number(&self, flight_number: &str) -> Option<&Flight> {
-        self.database.flights.iter().find(|f| f.flight_number == flight_number)
+        self.flight_index
+            .position_by_number(&self.database.flights, flight_number)
+            .map(|i| &self.database.flights[i])
+    }
+
+    /// Give a flight a gate at its origin that no other flight is using within
+    /// `GATE_BUFFER_MINUTES` of its departure. A gate the flight already holds is kept
+    /// if it's still free. Returns the gate.
+    pub fn assign_gate(&mut self, flight_number: &str) -> Result<String, String> {
+        let position = self.flight_index
+            .position_by_number(&self.database.flights, flight_number)
+            .ok_or("Flight not found")?;
+        let flight = &self.database.flights[position];
+        let airport = self.get_airport_by_code(&flight.origin)
+            .ok_or(format!("Unknown origin airport {}", flight.origin))?;
+
+        // Two gate windows overlap when the departures are less than two buffers apart
+        let buffer = Duration::minutes(crate::config::GATE_BUFFER_MINUTES);
+        let conflicts: Vec<&Flight> = self.database.flights
+            .iter()
+            .filter(|other| other.id != flight.id && other.origin == flight.origin && other.gate.is_some())
+            .filter(|other| !matches!(other.status, FlightStatus::Cancelled | FlightStatus::Departed | FlightStatus::Arrived))
+            .filter(|other| (other.departure_time - flight.departure_time).abs() < buffer * 2)
+            .collect();
+        let occupied: Vec<&str> = conflicts.iter().filter_map(|f| f.gate.as_deref()).collect();
+
+        let gate = match flight.gate.as_deref() {
+            Some(current) if !occupied.contains(&current) && airport.get_all_gates().iter().any(|g| g == current) => current.to_string(),
+            _ => airport.find_available_gate(&occupied).ok_or_else(|| {
+                let busy: Vec<String> = conflicts
+                    .iter()
+                    .map(|f| format!("{} ({} at {})", f.gate.as_deref().unwrap_or_default(), f.flight_number, f.departure_time.format("%H:%M")))
+                    .collect();
+                format!(
+                    "No free gate at {} between {} and {} - in use: {}",
+                    flight.origin,
+                    (flight.departure_time - buffer).format("%H:%M"),
+                    (flight.departure_time + buffer).format("%H:%M"),
+                    busy.join(", ")
+                )
+            })?,
+        };
+
+        self.database.flights[position].set_gate(gate.clone());
+        self.messages.say(format!("🚪 Flight {} assigned to gate {}", flight_number, gate));
+        Ok(gate)
+    }
+
+    /// Re-index flights after `database.flights` has been changed directly
+    pub fn rebuild_flight_index(&mut self) {
+        self.flight_index = FlightIndex::build(&self.database.flights);
     }
 
     pub fn get_available_flights(&self) -> Vec<&Flight> {
@@ -137,51 +708,321 @@ impl DataManager {
             .collect()
     }
 
+    /// Next unused flight number after `flight_number`, keeping its airline prefix and digit width
+    pub fn next_available_flight_number(&self, flight_number: &str) -> Option<String> {
+        let prefix_len = flight_number.chars().take_while(|c| c.is_ascii_uppercase()).count();
+        let (prefix, digits) = flight_number.split_at(prefix_len);
+        let width = digits.len();
+        let start: u32 = digits.parse().ok()?;
+        let max = 10u32.pow(width as u32) - 1;
+
+        (start + 1..=max)
+            .map(|n| format!("{}{:0width$}", prefix, n, width = width))
+            .find(|candidate| self.get_flight_by_number(candidate).is_none())
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_flight(
+        &mut self,
+        flight_number: String,
+        airline: String,
+        origin: String,
+        destination: String,
+        departure_time: DateTime<Utc>,
+        arrival_time: DateTime<Utc>,
+        aircraft_id: Uuid,
+    ) -> errors::Result<Uuid> {
+        let admin_id = match self.admin_panel.current_admin.as_ref() {
+            Some(admin) if admin.can_manage_flights() => admin.id,
+            _ => return Err(AirportError::InsufficientPermissions { operation: "create flight".to_string() }),
+        };
+        let invalid = |message: String| AirportError::ValidationError { message };
+
+        if !crate::utils::validate_flight_number(&flight_number) {
+            return Err(invalid(format!(
+                "Invalid flight number '{}' - expected an airline prefix followed by digits (e.g. RIA101)",
+                flight_number
+            )));
+        }
+
+        if self.get_flight_by_number(&flight_number).is_some() {
+            return Err(invalid(match self.next_available_flight_number(&flight_number) {
+                Some(suggestion) => format!(
+                    "Flight number {} already exists - next available: {}",
+                    flight_number, suggestion
+                ),
+                None => format!("Flight number {} already exists", flight_number),
+            }));
+        }
+
+        if arrival_time <= departure_time {
+            return Err(invalid("Arrival time must be after departure time".to_string()));
+        }
+
+        if origin == destination {
+            return Err(invalid("Origin and destination must be different airports".to_string()));
+        }
+
+        for code in [&origin, &destination] {
+            if self.get_airport_by_code(code).is_none() {
+                return Err(AirportError::AirportNotFound { code: code.clone() });
+            }
+        }
+
+        // Both ends must be open, judged by each airport's local clock
+        for (code, time, movement) in [(&origin, departure_time, "Departure"), (&destination, arrival_time, "Arrival")] {
+            if let Some(reason) = self.get_airport_by_code(code).and_then(|a| a.closed_at(time)) {
+                return Err(invalid(format!("{} not possible - {}", movement, reason)));
+            }
+        }
+
+        let aircraft = self.get_aircraft_by_id(aircraft_id).ok_or(AirportError::AircraftNotFound { aircraft_id })?;
+        let distance = self.route_distance_km(&origin, &destination);
+
+        let mut flight = Flight::new(
+            flight_number.clone(),
+            airline,
+            origin,
+            destination,
+            departure_time,
+            arrival_time,
+            aircraft,
+        );
+        self.validate_flight_range(&flight).map_err(invalid)?;
+        if let Some(distance) = distance {
+            flight.recompute_base_fares(distance);
+        }
+        let flight_id = flight.id;
+        self.flight_index.insert(self.database.flights.len(), &flight);
+        self.database.flights.push(flight);
+
+        self.admin_panel.log_action(
+            admin_id,
+            "CREATE_FLIGHT".to_string(),
+            format!("Created flight {}", flight_number),
+            Some(flight_id),
+            None,
+            Some(flight_number),
+        );
+        self.admin_panel.system_metrics.update_flight_metrics(&self.database.flights);
+        self.update_fuel_metrics();
+
+        Ok(flight_id)
+    }
+
+    /// A flight for every day from `start` to `end` (inclusive) that the template runs on.
+    /// The first takes the template's flight number when it's free and later ones the next
+    /// available number. Each flight goes through `create_flight`, so the same checks apply;
+    /// the first failure stops generation and flights already created are kept.
+    pub fn generate_from_template(
+        &mut self,
+        template: &FlightTemplate,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> Result<Vec<Uuid>, String> {
+        if end < start {
+            return Err("End date must not be before the start date".to_string());
+        }
+        if template.days.is_empty() {
+            return Err("Template must run on at least one day of the week".to_string());
+        }
+        if template.duration_minutes <= 0 {
+            return Err("Flight duration must be positive".to_string());
+        }
+
+        let mut created = Vec::new();
+        for date in start.iter_days().take_while(|date| *date <= end).filter(|date| template.runs_on(*date)) {
+            let flight_number = if self.get_flight_by_number(&template.flight_number).is_none() {
+                template.flight_number.clone()
+            } else {
+                self.next_available_flight_number(&template.flight_number)
+                    .ok_or_else(|| format!("No flight numbers left after {}", template.flight_number))?
+            };
+            let (departure, arrival) = template.times_on(date);
+
+            let flight_id = self.create_flight(
+                flight_number,
+                template.airline.clone(),
+                template.origin.clone(),
+                template.destination.clone(),
+                departure,
+                arrival,
+                template.aircraft_id,
+            ).map_err(|e| format!("{}: {} ({} flights created before stopping)", date, e, created.len()))?;
+            created.push(flight_id);
+        }
+
+        Ok(created)
+    }
+
+    fn fare_breakdown(&self, flight: &Flight, class: &SeatClass, passenger_type: &PassengerType) -> PriceBreakdown {
+        let base = match class {
+            SeatClass::Economy => flight.pricing.economy,
+            SeatClass::Business => flight.pricing.business,
+            SeatClass::FirstClass => flight.pricing.first_class,
+        };
+        let mut breakdown = PriceBreakdown::start(format!("Base fare ({:?})", class), base);
+
+        if flight.pricing.dynamic_multiplier != 1.0 {
+            breakdown.apply("Dynamic pricing".to_string(), flight.pricing.dynamic_multiplier);
+        }
+
+        let demand = flight.demand_multiplier(class);
+        if demand != 1.0 {
+            breakdown.apply(format!("Demand ({:.0}% full)", flight.load_factor(class) * 100.0), demand);
+        }
+
+        let rules = self.admin_panel.get_applicable_rules(
+            &flight.origin,
+            &flight.destination,
+            flight.departure_time.hour() as u8,
+        );
+        for rule in rules {
+            breakdown.apply(format!("Pricing rule: {}", rule.rule_name), rule.multiplier);
+        }
+
+        if passenger_type.fare_rate() != 1.0 {
+            breakdown.apply(format!("{:?} fare", passenger_type), passenger_type.fare_rate());
+        }
+
+        breakdown
+    }
+
+    /// Itemized fare for a passenger, listing each component and the running total
+    pub fn price_breakdown(
+        &self,
+        flight_id: Uuid,
+        class: &SeatClass,
+        passenger_type: &PassengerType,
+    ) -> Result<PriceBreakdown, String> {
+        let flight = self.get_flight_by_id(flight_id).ok_or("Flight not found")?;
+        Ok(self.fare_breakdown(flight, class, passenger_type))
+    }
+
+    /// Fare for a class including the flight's dynamic multiplier and matching pricing rules
+    pub fn get_fare(&self, flight: &Flight, class: &SeatClass) -> f64 {
+        self.fare_breakdown(flight, class, &PassengerType::Adult).total
+    }
+
+    /// Fare for a specific passenger type, after child/infant/senior discounts
+    pub fn get_passenger_fare(&self, flight: &Flight, class: &SeatClass, passenger_type: &PassengerType) -> f64 {
+        self.fare_breakdown(flight, class, passenger_type).total
+    }
+
+    /// Suggest other classes with open seats when `requested` is sold out, nearest class first.
+    /// Falls back to offering the waitlist when nothing else is available.
+    pub fn suggest_alternative_class(
+        &self,
+        flight_id: Uuid,
+        requested: &SeatClass,
+    ) -> Result<Vec<ClassAlternative>, String> {
+        let flight = self.get_flight_by_id(flight_id).ok_or("Flight not found")?;
+
+        if flight.get_available_seats(requested) > 0 {
+            return Ok(Vec::new());
+        }
+
+        let candidates = match requested {
+            SeatClass::Economy => [SeatClass::Business, SeatClass::FirstClass],
+            SeatClass::Business => [SeatClass::Economy, SeatClass::FirstClass],
+            SeatClass::FirstClass => [SeatClass::Business, SeatClass::Economy],
+        };
+
+        let requested_price = self.get_fare(flight, requested);
+        let mut alternatives: Vec<ClassAlternative> = candidates
+            .into_iter()
+            .filter(|class| flight.get_available_seats(class) > 0)
+            .map(|class| {
+                let price = self.get_fare(flight, &class);
+                ClassAlternative::Class {
+                    seats_available: flight.get_available_seats(&class),
+                    price_difference: price - requested_price,
+                    price,
+                    class,
+                }
+            })
+            .collect();
+
+        if alternatives.is_empty() {
+            alternatives.push(ClassAlternative::Waitlist);
+        }
+
+        Ok(alternatives)
+    }
+
+    /// The nearest cheaper class with open seats and its fare, to offer when `requested`
+    /// is sold out
+    pub fn suggest_downgrade(&self, flight_id: Uuid, requested: &SeatClass) -> Option<(SeatClass, f64)> {
+        let is_lower = |class: &SeatClass| matches!(
+            (requested, class),
+            (SeatClass::FirstClass, SeatClass::Business | SeatClass::Economy) | (SeatClass::Business, SeatClass::Economy)
+        );
+        self.suggest_alternative_class(flight_id, requested)
+            .ok()?
+            .into_iter()
+            .find_map(|alternative| match alternative {
+                ClassAlternative::Class { class, price, .. } if is_lower(&class) => Some((class, price)),
+                _ => None,
+            })
+    }
+
     // Booking Operations
     pub fn create_booking(
         &mut self,
         flight_id: Uuid,
         passenger: Passenger,
         seat_class: SeatClass,
-    ) -> Result<Uuid, String> {
+    ) -> errors::Result<Uuid> {
         // Find the flight
         let flight_idx = self.database.flights
             .iter()
             .position(|f| f.id == flight_id)
-            .ok_or("Flight not found")?;
+            .ok_or(AirportError::FlightNotFound { flight_id })?;
 
         // Check if flight is available for booking
         if !self.database.flights[flight_idx].is_available_for_booking() {
-            return Err("Flight is not available for booking".to_string());
+            return Err(AirportError::FlightNotAvailable {
+                flight_number: self.database.flights[flight_idx].flight_number.clone(),
+            });
         }
 
+        // Lap infants travel without a seat of their own
+        let needs_seat = passenger.passenger_type.occupies_seat();
+
         // Check seat availability
-        if self.database.flights[flight_idx].get_available_seats(&seat_class) == 0 {
-            return Err("No seats available in the selected class".to_string());
+        if needs_seat && self.database.flights[flight_idx].get_available_seats(&seat_class) == 0 {
+            return Err(AirportError::NoSeatsAvailable { class: seat_class });
         }
 
-        // Calculate price with dynamic multipliers
-        let base_price = self.database.flights[flight_idx].get_price(&seat_class);
-        let multiplier = self.admin_panel.get_applicable_multiplier(
-            &self.database.flights[flight_idx].origin,
-            &self.database.flights[flight_idx].destination,
-            self.database.flights[flight_idx].departure_time.hour() as u8,
+        // Calculate price with dynamic multipliers and passenger discounts
+        let final_price = self.get_passenger_fare(
+            &self.database.flights[flight_idx],
+            &seat_class,
+            &passenger.passenger_type,
         );
-        let final_price = base_price * multiplier;
 
         // Create booking
-        let booking = Booking::new(
+        let mut booking = Booking::new(
             flight_id,
             passenger,
             seat_class.clone(),
             final_price,
             "Credit Card".to_string(),
         );
+        booking.ticket_number = Booking::generate_ticket_number(&self.database.bookings);
+        booking.payment.quote = self.display_quote();
+        // The bags included in the fare need room in the hold too
+        self.check_baggage_room(flight_id, 0, booking.checked_baggage_weight_kg())
+            .map_err(|message| AirportError::ValidationError { message })?;
 
         let booking_id = booking.id;
 
         // Reserve seat on flight
-        self.database.flights[flight_idx].book_seat(&seat_class)?;
+        if needs_seat {
+            self.database.flights[flight_idx]
+                .book_seat(&seat_class)
+                .map_err(|_| AirportError::NoSeatsAvailable { class: seat_class.clone() })?;
+        }
 
         // Add booking to database
         self.database.bookings.push(booking);
@@ -191,263 +1032,2617 @@ impl DataManager {
         self.admin_panel.system_metrics.revenue_today += final_price;
         self.admin_panel.system_metrics.revenue_month += final_price;
 
-        println!("🎫 Booking created: {} for ${:.2}", booking_id, final_price);
+        self.messages.say(format!("🎫 Booking created: {} for ${:.2}", booking_id, final_price));
 
         Ok(booking_id)
     }
 
-    pub fn get_booking_by_ticket(&self, ticket_number: &str) -> Option<&Booking> {
-        self.database.bookings.iter().find(|b| b.ticket_number == ticket_number)
-    }
-
-    pub fn get_booking_by_id(&self, booking_id: Uuid) -> Option<&Booking> {
-        self.database.bookings.iter().find(|b| b.id == booking_id)
-    }
-
-    pub fn cancel_booking(&mut self, ticket_number: &str) -> Result<(), String> {
-        let booking_idx = self.database.bookings
+    /// Declare the total weight of a booking's checked bags. Weight over the class
+    /// allowance is charged per kg and added to the booking's total; declaring again
+    /// only charges (or credits) the difference. Returns the booking's overweight fee.
+    pub fn declare_checked_baggage(&mut self, ticket_number: &str, total_weight_kg: u32) -> Result<f64, String> {
+        let booking = self.database.bookings
             .iter()
-            .position(|b| b.ticket_number == ticket_number)
+            .find(|b| b.ticket_number == ticket_number)
             .ok_or("Booking not found")?;
+        if !booking.can_be_modified() {
+            return Err(format!("Baggage can't be changed on a booking that is {}", booking.get_status_display()));
+        }
+        let mut declared = booking.clone();
+        declared.declare_checked_baggage(total_weight_kg);
+        self.check_baggage_room(booking.flight_id, booking.checked_baggage_weight_kg(), declared.checked_baggage_weight_kg())?;
 
-        // Cancel the booking
-        self.database.bookings[booking_idx].cancel()?;
+        let booking = self.database.bookings
+            .iter_mut()
+            .find(|b| b.ticket_number == ticket_number)
+            .ok_or("Booking not found")?;
 
-        // Find the associated flight and free up the seat
-        let flight_id = self.database.bookings[booking_idx].flight_id;
-        let seat_class = self.database.bookings[booking_idx].seat_class.clone();
+        let fee = booking.calculate_baggage_fee(total_weight_kg);
+        let charge = fee - booking.calculate_baggage_fee(booking.checked_baggage_kg);
+        booking.declare_checked_baggage(total_weight_kg);
+        booking.payment.total_amount += charge;
 
-        if let Some(flight) = self.database.flights.iter_mut().find(|f| f.id == flight_id) {
-            // Add seat back to availability
-            match seat_class {
-                SeatClass::Economy => flight.seat_availability.economy += 1,
-                SeatClass::Business => flight.seat_availability.business += 1,
-                SeatClass::FirstClass => flight.seat_availability.first_class += 1,
-            }
+        self.admin_panel.system_metrics.revenue_today += charge;
+        self.admin_panel.system_metrics.revenue_month += charge;
+        if charge > 0.0 {
+            self.messages.say(format!("🧳 Overweight baggage on {}: ${:.2}", ticket_number, charge));
         }
-
-        println!("❌ Booking cancelled: {}", ticket_number);
-        Ok(())
+        Ok(fee)
     }
 
-    // Aircraft Operations
-    pub fn get_aircraft_by_id(&self, aircraft_id: Uuid) -> Option<&Aircraft> {
-        self.database.aircraft.iter().find(|a| a.id == aircraft_id)
+    /// Total checked baggage in the hold for a flight, in kg. Declared weights are used
+    /// where known, otherwise each bag counts at the planning average.
+    pub fn flight_baggage_weight(&self, flight_number: &str) -> u32 {
+        self.get_flight_by_number(flight_number)
+            .map(|flight| self.hold_weight(flight.id))
+            .unwrap_or(0)
     }
 
-    pub fn get_available_aircraft(&self) -> Vec<&Aircraft> {
-        self.database.aircraft
+    fn hold_weight(&self, flight_id: Uuid) -> u32 {
+        self.database.bookings
             .iter()
-            .filter(|a| a.is_available_for_flight())
-            .collect()
+            .filter(|b| b.flight_id == flight_id && b.is_active())
+            .map(|b| b.checked_baggage_weight_kg())
+            .sum()
     }
 
-    pub fn get_aircraft_for_flight(&self, flight_id: Uuid) -> Option<&Aircraft> {
-        if let Some(flight) = self.get_flight_by_id(flight_id) {
-            self.get_aircraft_by_id(flight.aircraft_id)
-        } else {
-            None
-        }
+    /// Hold space left on a flight's aircraft, in kg
+    pub fn baggage_room_remaining(&self, flight_number: &str) -> Option<u32> {
+        let flight = self.get_flight_by_number(flight_number)?;
+        let aircraft = self.get_aircraft_by_id(flight.aircraft_id)?;
+        Some(aircraft.baggage_capacity_kg.saturating_sub(self.hold_weight(flight.id)))
     }
 
-    // Airport Operations
-    pub fn get_airport_by_code(&self, code: &str) -> Option<&Airport> {
-        self.database.airports.iter().find(|a| a.code == code)
+    /// Refuse a baggage change from `current_kg` to `new_kg` that would overflow the
+    /// aircraft's baggage capacity. Reductions are always allowed.
+    fn check_baggage_room(&self, flight_id: Uuid, current_kg: u32, new_kg: u32) -> Result<(), String> {
+        if new_kg <= current_kg {
+            return Ok(());
+        }
+        let Some(aircraft) = self.get_aircraft_for_flight(flight_id) else {
+            return Ok(());
+        };
+        let room = aircraft.baggage_capacity_kg.saturating_sub(self.hold_weight(flight_id));
+        let extra = new_kg - current_kg;
+        if extra > room {
+            return Err(format!(
+                "Not enough baggage capacity: {} kg more requested but only {} kg of room remains",
+                extra, room
+            ));
+        }
+        Ok(())
     }
 
-    pub fn get_all_airports(&self) -> &Vec<Airport> {
-        &self.database.airports
+    /// Add checked bags to a booking, returning the hold space left on the flight in kg
+    pub fn add_checked_bags(&mut self, ticket_number: &str, count: u32) -> Result<u32, String> {
+        let booking = self.database.bookings
+            .iter()
+            .find(|b| b.ticket_number == ticket_number)
+            .ok_or("Booking not found")?;
+        if !booking.can_be_modified() {
+            return Err(format!("Baggage can't be changed on a booking that is {}", booking.get_status_display()));
+        }
+        let flight_id = booking.flight_id;
+        let mut updated = booking.clone();
+        updated.add_checked_bags(count);
+        self.check_baggage_room(flight_id, booking.checked_baggage_weight_kg(), updated.checked_baggage_weight_kg())?;
+
+        let fee = updated.excess_bag_fee() - booking.excess_bag_fee();
+        updated.payment.total_amount += fee;
+        let booking = self.database.bookings
+            .iter_mut()
+            .find(|b| b.ticket_number == ticket_number)
+            .ok_or("Booking not found")?;
+        *booking = updated;
+        self.admin_panel.system_metrics.revenue_today += fee;
+        self.admin_panel.system_metrics.revenue_month += fee;
+
+        let flight_number = self.get_flight_by_id(flight_id).map(|f| f.flight_number.clone()).unwrap_or_default();
+        Ok(self.baggage_room_remaining(&flight_number).unwrap_or(0))
     }
 
-    pub fn get_departures_from_airport(&self, airport_code: &str) -> Vec<&Flight> {
-        self.database.flights
+    /// Book a party onto one flight under a single reservation. Each passenger gets
+    /// their own ticket sharing the returned group id. Seats are reserved all or
+    /// nothing: if any passenger cannot be booked, the whole party is rolled back.
+    pub fn create_group_booking(
+        &mut self,
+        flight_id: Uuid,
+        passengers: Vec<Passenger>,
+        seat_class: SeatClass,
+    ) -> errors::Result<Uuid> {
+        if passengers.is_empty() {
+            return Err(AirportError::ValidationError { message: "A group booking needs at least one passenger".to_string() });
+        }
+
+        let flight = self.get_flight_by_id(flight_id).ok_or(AirportError::FlightNotFound { flight_id })?;
+        let seats_needed = passengers.iter().filter(|p| p.passenger_type.occupies_seat()).count() as u32;
+        let seats_left = flight.get_available_seats(&seat_class);
+        if seats_needed > seats_left {
+            return Err(AirportError::ValidationError {
+                message: format!(
+                    "Only {} {:?} seats left - not enough for a party needing {}",
+                    seats_left, seat_class, seats_needed
+                ),
+            });
+        }
+
+        let group_id = Uuid::new_v4();
+        let mut created = Vec::new();
+        for passenger in passengers {
+            match self.create_booking(flight_id, passenger, seat_class.clone()) {
+                Ok(booking_id) => created.push(booking_id),
+                Err(e) => {
+                    self.roll_back_bookings(&created);
+                    return Err(e);
+                }
+            }
+        }
+
+        for booking in self.database.bookings.iter_mut().filter(|b| created.contains(&b.id)) {
+            booking.group_id = Some(group_id);
+        }
+
+        self.messages.say(format!("👥 Group booking created: {} ({} passengers)", group_id, created.len()));
+        Ok(group_id)
+    }
+
+    /// Book the outbound and return legs of a trip together. Both bookings share the
+    /// returned trip id and are discounted by `pricing::ROUND_TRIP_DISCOUNT`. If either
+    /// leg cannot be booked, neither is.
+    pub fn create_round_trip(
+        &mut self,
+        outbound_id: Uuid,
+        return_id: Uuid,
+        passenger: Passenger,
+        seat_class: SeatClass,
+    ) -> errors::Result<Uuid> {
+        let outbound = self.get_flight_by_id(outbound_id).ok_or(AirportError::FlightNotFound { flight_id: outbound_id })?;
+        let inbound = self.get_flight_by_id(return_id).ok_or(AirportError::FlightNotFound { flight_id: return_id })?;
+        let invalid = |message: String| AirportError::ValidationError { message };
+
+        if inbound.origin != outbound.destination || inbound.destination != outbound.origin {
+            return Err(invalid(format!(
+                "{} does not return from {} to {}",
+                inbound.flight_number, outbound.destination, outbound.origin
+            )));
+        }
+        if inbound.departure_time <= outbound.arrival_time {
+            return Err(invalid(format!(
+                "{} departs before {} arrives",
+                inbound.flight_number, outbound.flight_number
+            )));
+        }
+        if passenger.passenger_type.occupies_seat() {
+            for flight in [outbound, inbound] {
+                if flight.get_available_seats(&seat_class) == 0 {
+                    return Err(invalid(format!("No {:?} seats available on {}", seat_class, flight.flight_number)));
+                }
+            }
+        }
+
+        let outbound_booking = self.create_booking(outbound_id, passenger.clone(), seat_class.clone())?;
+        let return_booking = match self.create_booking(return_id, passenger, seat_class) {
+            Ok(booking_id) => booking_id,
+            Err(e) => {
+                self.roll_back_bookings(&[outbound_booking]);
+                return Err(e);
+            }
+        };
+
+        let trip_id = Uuid::new_v4();
+        let mut savings = 0.0;
+        for booking in self.database.bookings.iter_mut().filter(|b| b.id == outbound_booking || b.id == return_booking) {
+            let discount = booking.payment.total_amount * crate::config::pricing::ROUND_TRIP_DISCOUNT;
+            booking.payment.total_amount -= discount;
+            booking.trip_id = Some(trip_id);
+            savings += discount;
+        }
+        self.admin_panel.system_metrics.revenue_today -= savings;
+        self.admin_panel.system_metrics.revenue_month -= savings;
+
+        self.messages.say(format!("🔁 Round trip booked: {} (saved ${:.2})", trip_id, savings));
+        Ok(trip_id)
+    }
+
+    /// Both legs of a round trip, outbound first
+    pub fn get_trip_bookings(&self, trip_id: Uuid) -> Vec<&Booking> {
+        self.database.bookings
             .iter()
-            .filter(|f| f.origin == airport_code)
+            .filter(|b| b.trip_id == Some(trip_id))
             .collect()
     }
 
-    pub fn get_arrivals_to_airport(&self, airport_code: &str) -> Vec<&Flight> {
-        self.database.flights
+    /// The other leg of a round trip booking, if it has one
+    pub fn get_trip_companion(&self, booking: &Booking) -> Option<&Booking> {
+        let trip_id = booking.trip_id?;
+        self.get_trip_bookings(trip_id).into_iter().find(|b| b.id != booking.id)
+    }
+
+    /// Bookable flights back from an outbound flight's destination, leaving after it lands
+    pub fn get_return_flights(&self, outbound_id: Uuid) -> Vec<&Flight> {
+        let Some(outbound) = self.get_flight_by_id(outbound_id) else {
+            return Vec::new();
+        };
+        let mut flights: Vec<&Flight> = self.get_available_flights()
+            .into_iter()
+            .filter(|f| {
+                f.origin == outbound.destination
+                    && f.destination == outbound.origin
+                    && f.departure_time > outbound.arrival_time
+            })
+            .collect();
+        flights.sort_by_key(|f| f.departure_time);
+        flights
+    }
+
+    /// Undo freshly created bookings as if they had never been made
+    fn roll_back_bookings(&mut self, booking_ids: &[Uuid]) {
+        let (removed, kept): (Vec<Booking>, Vec<Booking>) = std::mem::take(&mut self.database.bookings)
+            .into_iter()
+            .partition(|b| booking_ids.contains(&b.id));
+        self.database.bookings = kept;
+
+        for booking in removed {
+            if booking.passenger.passenger_type.occupies_seat() {
+                self.release_seat(booking.flight_id, &booking.seat_class);
+            }
+            self.admin_panel.system_metrics.revenue_today -= booking.payment.total_amount;
+            self.admin_panel.system_metrics.revenue_month -= booking.payment.total_amount;
+        }
+        self.admin_panel.system_metrics.total_bookings = self.database.bookings.len() as u32;
+    }
+
+    fn release_seat(&mut self, flight_id: Uuid, seat_class: &SeatClass) {
+        if let Some(flight) = self.database.flights.iter_mut().find(|f| f.id == flight_id) {
+            match seat_class {
+                SeatClass::Economy => flight.seat_availability.economy += 1,
+                SeatClass::Business => flight.seat_availability.business += 1,
+                SeatClass::FirstClass => flight.seat_availability.first_class += 1,
+            }
+        }
+    }
+
+    /// Every booking made under a group reservation, in booking order
+    pub fn get_group_bookings(&self, group_id: Uuid) -> Vec<&Booking> {
+        self.database.bookings
             .iter()
-            .filter(|f| f.destination == airport_code)
+            .filter(|b| b.group_id == Some(group_id))
             .collect()
     }
 
-    // Admin Operations
-    pub fn authenticate_admin(&mut self, username: &str, password: &str) -> Result<AdminUser, String> {
-        self.admin_panel.authenticate(username, password)
+    /// Cancel every booking in a group, freeing their seats, or none of them when any
+    /// can't be cancelled. Bookings already cancelled are skipped. Returns the outcome for
+    /// each booking cancelled.
+    pub fn cancel_group_booking(&mut self, group_id: Uuid) -> errors::Result<Vec<BookingCancellation>> {
+        let group = self.get_group_bookings(group_id);
+        let invalid = |message: String| AirportError::ValidationError { message };
+        if group.is_empty() {
+            return Err(invalid("Group booking not found".to_string()));
+        }
+
+        // Check every booking first so the group is never left half cancelled
+        let mut tickets = Vec::new();
+        for booking in group.iter().filter(|b| !matches!(b.status, BookingStatus::Cancelled)) {
+            if !booking.can_be_modified() {
+                return Err(invalid(format!(
+                    "Booking {} is {} - no bookings in the group were cancelled",
+                    booking.ticket_number, booking.get_status_display()
+                )));
+            }
+            if let Some(flight) = self.get_flight_by_id(booking.flight_id)
+                .filter(|f| matches!(f.status, FlightStatus::Departed | FlightStatus::Arrived))
+            {
+                return Err(invalid(format!(
+                    "Flight {} of booking {} has already departed - no bookings in the group were cancelled",
+                    flight.flight_number, booking.ticket_number
+                )));
+            }
+            tickets.push(booking.ticket_number.clone());
+        }
+        if tickets.is_empty() {
+            return Err(invalid("No bookings in this group can be cancelled".to_string()));
+        }
+
+        tickets
+            .iter()
+            .map(|ticket_number| self.cancel_booking(ticket_number))
+            .collect()
     }
 
-    pub fn is_admin_authenticated(&self) -> bool {
-        self.admin_panel.is_authenticated()
+    pub fn get_booking_by_ticket(&self, ticket_number: &str) -> Option<&Booking> {
+        self.database.bookings.iter().find(|b| b.ticket_number == ticket_number)
     }
 
-    pub fn logout_admin(&mut self) {
-        self.admin_panel.logout();
+    /// Bookings whose passenger name or email contains `query`, ignoring case. A blank
+    /// query matches nothing.
+    pub fn find_bookings_by_passenger(&self, query: &str) -> Vec<&Booking> {
+        let query = query.trim().to_lowercase();
+        if query.is_empty() {
+            return Vec::new();
+        }
+        self.database.bookings
+            .iter()
+            .filter(|b| {
+                b.passenger.full_name().to_lowercase().contains(&query)
+                    || b.passenger.email.to_lowercase().contains(&query)
+            })
+            .collect()
     }
 
-    pub fn set_flight_delay(&mut self, flight_number: &str, delay_minutes: i32) -> Result<(), String> {
-        if !self.admin_panel.is_authenticated() {
-            return Err("Admin authentication required".to_string());
+    pub fn get_booking_by_id(&self, booking_id: Uuid) -> Option<&Booking> {
+        self.database.bookings.iter().find(|b| b.id == booking_id)
+    }
+
+    /// Plain-text confirmation message for a booking, ready to preview or save
+    pub fn confirmation_message(&self, ticket_number: &str) -> Result<String, String> {
+        let booking = self.get_booking_by_ticket(ticket_number).ok_or("Booking not found")?;
+        let flight = self.get_flight_by_id(booking.flight_id).ok_or("Flight not found")?;
+        let fare = self.fare_breakdown(flight, &booking.seat_class, &booking.passenger.passenger_type);
+        Ok(booking.render_confirmation_message(flight, &self.database.airports, &fare))
+    }
+
+    /// Save a booking's confirmation message to the exports directory
+    pub async fn save_confirmation_message(&self, ticket_number: &str) -> Result<String, Box<dyn Error>> {
+        let message = self.confirmation_message(ticket_number)?;
+        self.persistence.save_export(&format!("confirmation_{}.txt", ticket_number), &message).await
+    }
+
+    /// Check in a confirmed booking. Check-in opens `CHECK_IN_WINDOW_HOURS` before departure
+    /// and closes when the flight leaves.
+    pub fn check_in_booking(&mut self, ticket_number: &str) -> Result<(), String> {
+        let booking = self.get_booking_by_ticket(ticket_number).ok_or("Booking not found")?;
+        let flight = self.get_flight_by_id(booking.flight_id).ok_or("Flight not found")?;
+
+        if !matches!(flight.status, FlightStatus::OnTime | FlightStatus::Delayed(_) | FlightStatus::Boarding) {
+            return Err(format!("Check-in is closed - flight {} is {}", flight.flight_number, flight.get_status_display()));
+        }
+        let opens_at = flight.departure_time - Duration::hours(crate::config::CHECK_IN_WINDOW_HOURS);
+        let now = Utc::now();
+        if now < opens_at {
+            return Err(format!("Check-in opens {} hours before departure ({})",
+                crate::config::CHECK_IN_WINDOW_HOURS, opens_at.format("%Y-%m-%d %H:%M UTC")));
+        }
+        if now >= flight.departure_time {
+            return Err("Check-in is closed - the flight has already left".to_string());
         }
 
-        let current_admin = self.admin_panel.current_admin.as_ref().unwrap();
-        if !current_admin.can_manage_flights() {
-            return Err("Insufficient permissions to manage flights".to_string());
+        let booking = self.database.bookings
+            .iter_mut()
+            .find(|b| b.ticket_number == ticket_number)
+            .ok_or("Booking not found")?;
+        booking.check_in()?;
+
+        self.messages.say(format!("🎫 {} checked in for ticket {}", booking.passenger.full_name(), booking.ticket_number));
+        Ok(())
+    }
+
+    /// Board a checked-in passenger while their flight is boarding
+    pub fn board_booking(&mut self, ticket_number: &str) -> Result<(), String> {
+        let booking = self.get_booking_by_ticket(ticket_number).ok_or("Booking not found")?;
+        let flight = self.get_flight_by_id(booking.flight_id).ok_or("Flight not found")?;
+
+        if flight.status != FlightStatus::Boarding {
+            return Err(format!("Flight {} is not boarding (status: {})", flight.flight_number, flight.get_status_display()));
         }
 
-        let flight = self.database.flights
+        let booking = self.database.bookings
             .iter_mut()
-            .find(|f| f.flight_number == flight_number)
-            .ok_or("Flight not found")?;
+            .find(|b| b.ticket_number == ticket_number)
+            .ok_or("Booking not found")?;
+        booking.board()?;
 
-        let old_status = flight.get_status_display();
-        flight.set_delay(delay_minutes);
-        let new_status = flight.get_status_display();
+        self.messages.say(format!("✈️ {} boarded with ticket {}", booking.passenger.full_name(), booking.ticket_number));
+        Ok(())
+    }
 
-        // Log the action
-        self.admin_panel.log_action(
-            current_admin.id,
-            "SET_DELAY".to_string(),
-            format!("Set delay for flight {}", flight_number),
-            Some(flight.id),
-            Some(old_status),
-            Some(new_status),
-        );
+    /// Boarding pass for a checked-in or boarded passenger
+    pub fn boarding_pass(&self, ticket_number: &str) -> Result<String, String> {
+        let booking = self.get_booking_by_ticket(ticket_number).ok_or("Booking not found")?;
+        if !matches!(booking.status, BookingStatus::CheckedIn | BookingStatus::Boarded) {
+            return Err(format!("Boarding passes are issued after check-in (booking is {})", booking.get_status_display()));
+        }
+        let flight = self.get_flight_by_id(booking.flight_id).ok_or("Flight not found")?;
+        Ok(booking.generate_boarding_pass(flight))
+    }
 
-        println!("⏰ Flight {} delay set to {} minutes", flight_number, delay_minutes);
+    /// Write a booking's boarding pass to `path`
+    pub fn export_boarding_pass(&self, ticket_number: &str, path: &Path) -> Result<(), Box<dyn Error>> {
+        let pass = self.boarding_pass(ticket_number)?;
+        if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(path, pass)?;
+        self.messages.say(format!("🎫 Saved boarding pass: {}", path.display()));
         Ok(())
     }
 
-    pub fn set_dynamic_pricing(&mut self, flight_number: &str, multiplier: f64) -> Result<(), String> {
+    fn bookings_for_email<'a>(&'a self, email: &'a str) -> impl Iterator<Item = &'a Booking> + 'a {
+        self.database.bookings
+            .iter()
+            .filter(move |b| b.passenger.email.eq_ignore_ascii_case(email))
+    }
+
+    /// All data held for a passenger - profiles, bookings and payments - as JSON
+    pub fn export_passenger_data(&self, email: &str) -> Result<String, String> {
+        let email = email.trim();
+        if email.is_empty() {
+            return Err("Email address is required".to_string());
+        }
+
+        let bookings: Vec<Booking> = self.bookings_for_email(email).cloned().collect();
+        let loyalty = self.get_loyalty_account(email).cloned();
+        if bookings.is_empty() && loyalty.is_none() {
+            return Err(format!("No data stored for {}", email));
+        }
+
+        let mut seen = HashSet::new();
+        let profiles = bookings
+            .iter()
+            .filter(|b| seen.insert(b.passenger.id))
+            .map(|b| b.passenger.clone())
+            .collect();
+
+        let export = PassengerDataExport {
+            email: email.to_string(),
+            exported_at: Utc::now(),
+            profiles,
+            bookings,
+            loyalty,
+        };
+        serde_json::to_string_pretty(&export).map_err(|e| format!("Failed to export data: {}", e))
+    }
+
+    /// Erase a passenger's personal data from their past bookings. Fares, classes and
+    /// statuses stay so revenue and statistics are unchanged. Refused while the
+    /// passenger still has a trip ahead, since check-in and boarding need their details.
+    pub fn anonymize_passenger(&mut self, email: &str) -> Result<usize, String> {
         if !self.admin_panel.is_authenticated() {
             return Err("Admin authentication required".to_string());
         }
+        let admin_id = self.admin_panel.current_admin.as_ref().unwrap().id;
 
-        let current_admin = self.admin_panel.current_admin.as_ref().unwrap();
-        if !current_admin.can_manage_pricing() {
-            return Err("Insufficient permissions to manage pricing".to_string());
+        let email = email.trim();
+        if email.is_empty() {
+            return Err("Email address is required".to_string());
         }
 
-        let flight = self.database.flights
-            .iter_mut()
-            .find(|f| f.flight_number == flight_number)
-            .ok_or("Flight not found")?;
+        let active: Vec<&str> = self.bookings_for_email(email)
+            .filter(|b| b.is_active())
+            .map(|b| b.ticket_number.as_str())
+            .collect();
+        if !active.is_empty() {
+            return Err(format!(
+                "Cannot delete passenger data while bookings are active: {}. Cancel them or wait until travel is complete.",
+                active.join(", ")
+            ));
+        }
 
-        let old_multiplier = flight.pricing.dynamic_multiplier;
-        flight.pricing.dynamic_multiplier = multiplier;
+        let mut anonymized = 0;
+        for booking in self.database.bookings.iter_mut().filter(|b| b.passenger.email.eq_ignore_ascii_case(email)) {
+            booking.anonymize();
+            anonymized += 1;
+        }
+        let account = self.loyalty.iter_mut().find(|a| a.email.eq_ignore_ascii_case(email));
+        let had_account = account.is_some();
+        if let Some(account) = account {
+            account.anonymize();
+        }
+        if anonymized == 0 && !had_account {
+            return Err(format!("No data stored for {}", email));
+        }
 
-        // Log the action
+        // The log must not keep the email we just erased
         self.admin_panel.log_action(
-            current_admin.id,
-            "SET_PRICING".to_string(),
-            format!("Set pricing multiplier for flight {}", flight_number),
-            Some(flight.id),
-            Some(old_multiplier.to_string()),
-            Some(multiplier.to_string()),
+            admin_id,
+            "ANONYMIZE_PASSENGER".to_string(),
+            format!("Anonymized passenger data on {} bookings", anonymized),
+            None,
+            None,
+            None,
         );
 
-        println!("💰 Flight {} pricing multiplier set to {:.2}", flight_number, multiplier);
-        Ok(())
+        self.messages.say(format!("🧹 Passenger data anonymized on {} bookings", anonymized));
+        Ok(anonymized)
     }
 
-    // Real-time Simulation
-    pub async fn update_simulation(&mut self) -> Result<(), Box<dyn Error>> {
-        let now = Utc::now();
-        
-        // Only update every minute
-        if now.signed_duration_since(self.last_simulation_update).num_seconds() < 60 {
-            return Ok(());
+    /// What a passenger gets back for cancelling now: the full fare more than
+    /// `FULL_REFUND_HOURS` before departure, `PARTIAL_REFUND_RATE` of it down to
+    /// `PARTIAL_REFUND_HOURS`, nothing after that. First Class is always fully refundable,
+    /// and so is any booking on a flight the airline has cancelled.
+    pub fn calculate_refund(&self, booking: &Booking, flight: &Flight) -> f64 {
+        use crate::config::refunds::{FULL_REFUND_HOURS, PARTIAL_REFUND_HOURS, PARTIAL_REFUND_RATE};
+
+        let refundable = booking.payment.total_amount - booking.payment.refunded_amount;
+        if flight.status == FlightStatus::Cancelled {
+            return refundable;
         }
+        let notice = flight.departure_time - Utc::now();
+        let rate = if booking.seat_class == SeatClass::FirstClass || notice > Duration::hours(FULL_REFUND_HOURS) {
+            1.0
+        } else if notice >= Duration::hours(PARTIAL_REFUND_HOURS) {
+            PARTIAL_REFUND_RATE
+        } else {
+            0.0
+        };
+        refundable * rate
+    }
 
-        let mut updates_made = false;
+    /// Cancel a booking, refund it under the refund policy and free its seat, offering
+    /// it to the waitlist. For a round trip leg, the result names the other leg while
+    /// it can still be cancelled, so the caller can offer to cancel it too.
+    pub fn cancel_booking(&mut self, ticket_number: &str) -> errors::Result<BookingCancellation> {
+        let booking_idx = self.database.bookings
+            .iter()
+            .position(|b| b.ticket_number == ticket_number)
+            .ok_or_else(|| AirportError::BookingNotFound { ticket_number: ticket_number.to_string() })?;
 
-        // Update flight statuses based on current time
-        for flight in &mut self.database.flights {
-            let time_to_departure = flight.departure_time.signed_duration_since(now);
-            let time_since_departure = now.signed_duration_since(flight.departure_time);
-            let time_to_arrival = flight.arrival_time.signed_duration_since(now);
+        let booking = &self.database.bookings[booking_idx];
+        let flight = self.get_flight_by_id(booking.flight_id);
+        if let Some(flight) = flight.filter(|f| matches!(f.status, FlightStatus::Departed | FlightStatus::Arrived)) {
+            return Err(AirportError::FlightAlreadyDeparted { flight_number: flight.flight_number.clone() });
+        }
+        // Only flights still on the ground have a seat to give back
+        let flight_open = flight.is_some_and(|f| matches!(f.status, FlightStatus::OnTime | FlightStatus::Delayed(_) | FlightStatus::Boarding));
+        let refund = flight.map_or(0.0, |flight| self.calculate_refund(booking, flight));
 
-            match flight.status {
-                FlightStatus::OnTime | FlightStatus::Delayed(_) => {
-                    if time_to_departure <= Duration::minutes(30) && time_to_departure > Duration::minutes(0) {
-                        flight.status = FlightStatus::Boarding;
-                        updates_made = true;
-                    } else if time_since_departure >= Duration::minutes(0) && time_to_arrival > Duration::minutes(0) {
-                        flight.status = FlightStatus::Departed;
-                        updates_made = true;
-                    } else if time_to_arrival <= Duration::minutes(0) {
-                        flight.status = FlightStatus::Arrived;
-                        updates_made = true;
-                    }
-                }
-                FlightStatus::Boarding => {
-                    if time_since_departure >= Duration::minutes(0) {
-                        flight.status = FlightStatus::Departed;
-                        updates_made = true;
-                    }
-                }
-                FlightStatus::Departed => {
-                    if time_to_arrival <= Duration::minutes(0) {
-                        flight.status = FlightStatus::Arrived;
-                        updates_made = true;
-                    }
-                }
-                _ => {} // No updates needed for other statuses
-            }
+        // Cancel the booking
+        self.database.bookings[booking_idx]
+            .cancel()
+            .map_err(|message| AirportError::ValidationError { message })?;
+        self.database.bookings[booking_idx].payment.refunded_amount += refund;
+        self.admin_panel.system_metrics.record_refund(refund);
+
+        // Find the associated flight and free up the seat
+        let flight_id = self.database.bookings[booking_idx].flight_id;
+        let seat_class = self.database.bookings[booking_idx].seat_class.clone();
+        let held_seat = self.database.bookings[booking_idx].passenger.passenger_type.occupies_seat();
+
+        self.messages.say(format!("❌ Booking cancelled: {} (${:.2} refunded)", ticket_number, refund));
+        let companion_ticket = self.get_trip_companion(&self.database.bookings[booking_idx])
+            .filter(|b| b.can_be_modified())
+            .map(|b| b.ticket_number.clone());
+
+        let mut promoted = None;
+        if held_seat && flight_open {
+            // Add seat back to availability, then let the waitlist claim it
+            self.release_seat(flight_id, &seat_class);
+            promoted = self.promote_from_waitlist(flight_id, &seat_class);
         }
 
-        // Update aircraft statuses based on flight status
-        for aircraft in &mut self.database.aircraft {
-            let has_active_flight = self.database.flights
-                .iter()
-                .any(|f| f.aircraft_id == aircraft.id && 
-                         matches!(f.status, FlightStatus::Boarding | FlightStatus::Departed));
+        Ok(BookingCancellation { refund, companion_ticket, promoted })
+    }
 
-            match aircraft.status {
-                AircraftStatus::Active => {
-                    if has_active_flight {
-                        aircraft.status = AircraftStatus::InFlight;
-                        updates_made = true;
-                    }
-                }
-                AircraftStatus::InFlight => {
-                    if !has_active_flight {
-                        aircraft.status = AircraftStatus::Active;
-                        updates_made = true;
-                    }
-                }
-                _ => {} // No automatic updates for maintenance or retired aircraft
-            }
+    /// Move a booking to another cabin on the same flight. The fare difference at today's
+    /// prices is charged, or credited when moving down, and returned. The old seat goes
+    /// back to the waitlist and the passenger picks a new seat in the new cabin.
+    pub fn change_seat_class(&mut self, ticket: &str, new_class: SeatClass) -> Result<f64, String> {
+        let booking_idx = self.database.bookings
+            .iter()
+            .position(|b| b.ticket_number == ticket)
+            .ok_or("Booking not found")?;
+        let booking = &self.database.bookings[booking_idx];
+        let flight_idx = self.database.flights
+            .iter()
+            .position(|f| f.id == booking.flight_id)
+            .ok_or("Flight not found")?;
+        let flight = &self.database.flights[flight_idx];
+
+        if matches!(flight.status, FlightStatus::Departed | FlightStatus::Arrived) {
+            return Err(format!("Flight {} has already departed", flight.flight_number));
+        }
+        if !booking.can_be_modified() || !flight.is_available_for_booking() {
+            return Err("Booking can no longer be changed".to_string());
+        }
+        let old_class = booking.seat_class.clone();
+        if old_class == new_class {
+            return Err(format!("Booking is already in {:?}", new_class));
         }
 
-        if updates_made {
-            // Update system metrics
-            self.admin_panel.system_metrics.update_flight_metrics(&self.database.flights);
-            self.admin_panel.system_metrics.update_aircraft_metrics(&self.database.aircraft);
-            
-            println!("🔄 Simulation updated - {} flights, {} aircraft statuses updated", 
-                self.database.flights.len(), self.database.aircraft.len());
+        let passenger_type = booking.passenger.passenger_type.clone();
+        let charge = self.get_passenger_fare(flight, &new_class, &passenger_type)
+            - self.get_passenger_fare(flight, &old_class, &passenger_type);
+        let held_seat = passenger_type.occupies_seat();
+        if held_seat {
+            self.database.flights[flight_idx].move_seat(&old_class, &new_class)?;
         }
 
-        self.last_simulation_update = now;
-        Ok(())
-    }
+        let booking = &mut self.database.bookings[booking_idx];
+        booking.seat_class = new_class.clone();
+        booking.seat_assignment = None;
+        booking.payment.total_amount += charge;
+        booking.record_event(format!("Class changed from {:?} to {:?}", old_class, new_class));
+        self.admin_panel.system_metrics.revenue_today += charge;
+        self.admin_panel.system_metrics.revenue_month += charge;
 
-    // Data Persistence Operations
-    pub async fn save_all_data(&self) -> Result<(), Box<dyn Error>> {
-        self.persistence.save_all_data(&self.database).await?;
-        Ok(())
+        if held_seat {
+            let flight_id = self.database.flights[flight_idx].id;
+            self.promote_from_waitlist(flight_id, &old_class);
+        }
+        self.messages.say(format!("💺 {} moved from {:?} to {:?} (${:.2})", ticket, old_class, new_class, charge));
+        Ok(charge)
     }
 
-    pub async fn create_backup(&self) -> Result<String, Box<dyn Error>> {
-        let backup_path = self.persistence.create_backup().await?;
-        Ok(backup_path)
+    /// Flights a passenger on `flight_number` could be moved to: same route, still open for
+    /// booking and departing no earlier than the original, soonest first
+    pub fn rebooking_options(&self, flight_number: &str) -> Vec<&Flight> {
+        let Some(original) = self.get_flight_by_number(flight_number) else {
+            return Vec::new();
+        };
+        let mut options: Vec<&Flight> = self.flight_index
+            .route(&self.database.flights, &original.origin, &original.destination)
+            .into_iter()
+            .filter(|f| f.id != original.id && f.departure_time >= original.departure_time && f.is_available_for_booking())
+            .collect();
+        options.sort_by_key(|f| f.departure_time);
+        options
     }
 
-    // Statistics and Reporting
-    pub fn get_system_metrics(&self) -> &SystemMetrics {
-        &self.admin_panel.system_metrics
+    /// Move a booking to another flight on the same route in the same class, e.g. when its
+    /// flight is about to be cancelled. The fare is unchanged. The old seat goes back to the
+    /// waitlist and the passenger picks a new seat on the new flight.
+    pub fn rebook(&mut self, ticket: &str, new_flight_number: &str) -> Result<(), String> {
+        let booking_idx = self.database.bookings
+            .iter()
+            .position(|b| b.ticket_number == ticket)
+            .ok_or("Booking not found")?;
+        let booking = &self.database.bookings[booking_idx];
+        if !booking.can_be_modified() {
+            return Err("Booking can no longer be changed".to_string());
+        }
+        let old_flight = self.get_flight_by_id(booking.flight_id).ok_or("Flight not found")?;
+        if matches!(old_flight.status, FlightStatus::Departed | FlightStatus::Arrived) {
+            return Err(format!("Flight {} has already departed", old_flight.flight_number));
+        }
+        let new_flight = self.get_flight_by_number(new_flight_number).ok_or("New flight not found")?;
+        if new_flight.id == old_flight.id {
+            return Err(format!("Booking is already on flight {}", new_flight_number));
+        }
+        if (&new_flight.origin, &new_flight.destination) != (&old_flight.origin, &old_flight.destination) {
+            return Err(format!(
+                "Flight {} flies {} → {}, not {} → {}",
+                new_flight_number, new_flight.origin, new_flight.destination, old_flight.origin, old_flight.destination
+            ));
+        }
+        if new_flight.departure_time < old_flight.departure_time {
+            return Err(format!("Flight {} departs before the original flight", new_flight_number));
+        }
+        // Checked for every passenger, including infants who take no seat of their own
+        if !new_flight.is_available_for_booking() {
+            return Err(format!("Flight {} is not open for booking", new_flight_number));
+        }
+
+        let (old_flight_id, new_flight_id) = (old_flight.id, new_flight.id);
+        let old_flight_number = old_flight.flight_number.clone();
+        let old_flight_open = matches!(old_flight.status, FlightStatus::OnTime | FlightStatus::Delayed(_) | FlightStatus::Boarding);
+        let seat_class = booking.seat_class.clone();
+        let held_seat = booking.passenger.passenger_type.occupies_seat();
+        self.check_baggage_room(new_flight_id, 0, booking.checked_baggage_weight_kg())?;
+
+        if held_seat {
+            let new_idx = self.flight_index
+                .position_by_id(&self.database.flights, new_flight_id)
+                .ok_or("New flight not found")?;
+            self.database.flights[new_idx].book_seat(&seat_class)?;
+        }
+
+        let booking = &mut self.database.bookings[booking_idx];
+        booking.flight_id = new_flight_id;
+        booking.seat_assignment = None;
+        booking.record_event(format!("Rebooked from {} to {}", old_flight_number, new_flight_number));
+
+        if held_seat && old_flight_open {
+            self.release_seat(old_flight_id, &seat_class);
+            self.promote_from_waitlist(old_flight_id, &seat_class);
+        }
+
+        if let Some(admin_id) = self.admin_panel.current_admin.as_ref().map(|a| a.id) {
+            self.admin_panel.log_action(
+                admin_id,
+                "REBOOK".to_string(),
+                format!("Moved booking {} from {} to {}", ticket, old_flight_number, new_flight_number),
+                Some(self.database.bookings[booking_idx].id),
+                Some(old_flight_number.clone()),
+                Some(new_flight_number.to_string()),
+            );
+        }
+        self.messages.say(format!("🔁 {} rebooked from {} to {}", ticket, old_flight_number, new_flight_number));
+        Ok(())
+    }
+
+    /// Close out a booking once its flight has left
+    pub fn complete_booking(&mut self, ticket_number: &str) -> Result<(), String> {
+        let booking = self.get_booking_by_ticket(ticket_number).ok_or("Booking not found")?;
+        let flight = self.get_flight_by_id(booking.flight_id).ok_or("Flight not found")?;
+        if !matches!(flight.status, FlightStatus::Departed | FlightStatus::Arrived) {
+            return Err(format!("Flight {} has not departed yet", flight.flight_number));
+        }
+
+        let booking = self.database.bookings
+            .iter_mut()
+            .find(|b| b.ticket_number == ticket_number)
+            .ok_or("Booking not found")?;
+        booking.complete()?;
+
+        self.messages.say(format!("🛬 Booking completed: {}", ticket_number));
+        self.award_miles(ticket_number);
+        Ok(())
+    }
+
+    // Seat Selection
+
+    /// Every seat on the flight's aircraft, front row first, flagged `true` when taken
+    pub fn get_seat_map(&self, flight_id: Uuid) -> Vec<(String, bool)> {
+        let Some(aircraft) = self.get_aircraft_for_flight(flight_id) else {
+            return Vec::new();
+        };
+        let taken = self.taken_seats(flight_id);
+
+        [SeatClass::FirstClass, SeatClass::Business, SeatClass::Economy]
+            .iter()
+            .flat_map(|class| flight::cabin_seat_blocks(&aircraft.seat_configuration, class))
+            .flatten()
+            .map(|seat| {
+                let is_taken = taken.contains(&seat);
+                (seat, is_taken)
+            })
+            .collect()
+    }
+
+    /// Whether an active booking on the flight already holds `seat_number`
+    pub fn is_seat_taken(&self, flight_id: Uuid, seat_number: &str) -> bool {
+        self.database.bookings
+            .iter()
+            .filter(|b| b.flight_id == flight_id && b.is_active())
+            .any(|b| b.seat_assignment.as_ref().is_some_and(|s| s.seat_number.eq_ignore_ascii_case(seat_number)))
+    }
+
+    fn taken_seats(&self, flight_id: Uuid) -> HashSet<String> {
+        self.database.bookings
+            .iter()
+            .filter(|b| b.flight_id == flight_id && b.is_active())
+            .filter_map(|b| b.seat_assignment.as_ref().map(|s| s.seat_number.clone()))
+            .collect()
+    }
+
+    /// Give a booking the seat the passenger picked from the seat map. The seat must
+    /// be in the booked class and free; once chosen it is kept when seats are reshuffled.
+    /// Nobody travelling with a lap infant may sit in an emergency exit row.
+    pub fn assign_specific_seat(&mut self, ticket: &str, seat_number: &str) -> Result<(), String> {
+        let seat_number = seat_number.trim().to_uppercase();
+        let booking = self.get_booking_by_ticket(ticket).ok_or("Booking not found")?;
+
+        if !booking.can_be_modified() {
+            return Err("Seats can only be chosen before boarding".to_string());
+        }
+        if !booking.passenger.passenger_type.occupies_seat() {
+            return Err("Lap infants travel without a seat of their own".to_string());
+        }
+
+        let aircraft = self.get_aircraft_for_flight(booking.flight_id).ok_or("Aircraft not found for this flight")?;
+        let seat_class = [SeatClass::FirstClass, SeatClass::Business, SeatClass::Economy]
+            .into_iter()
+            .find(|class| {
+                flight::cabin_seat_blocks(&aircraft.seat_configuration, class)
+                    .iter()
+                    .flatten()
+                    .any(|seat| *seat == seat_number)
+            })
+            .ok_or_else(|| format!("Seat {} does not exist on this aircraft", seat_number))?;
+        if seat_class != booking.seat_class {
+            return Err(format!("Seat {} is in {:?}, but this booking is for {:?}", seat_number, seat_class, booking.seat_class));
+        }
+
+        let already_mine = booking.seat_assignment.as_ref().is_some_and(|s| s.seat_number == seat_number);
+        if !already_mine && self.is_seat_taken(booking.flight_id, &seat_number) {
+            return Err(format!("Seat {} is already taken", seat_number));
+        }
+
+        let with_infant = booking.group_id.is_some_and(|group_id| {
+            self.get_group_bookings(group_id)
+                .iter()
+                .any(|b| b.flight_id == booking.flight_id && b.is_active() && matches!(b.passenger.passenger_type, PassengerType::Infant))
+        });
+        if with_infant && booking::SeatAssignment::new(seat_number.clone(), seat_class).is_emergency_exit {
+            return Err(format!("Seat {} is in an emergency exit row, which is not allowed when travelling with an infant", seat_number));
+        }
+
+        let booking = self.database.bookings
+            .iter_mut()
+            .find(|b| b.ticket_number == ticket)
+            .ok_or("Booking not found")?;
+        booking.select_seat(seat_number.clone());
+
+        self.messages.say(format!("💺 Seat {} assigned to {}", seat_number, ticket));
+        Ok(())
+    }
+
+    // Waitlist Operations
+
+    /// Queue a passenger for a sold-out class. Returns their position in the queue.
+    pub fn join_waitlist(&mut self, flight_id: Uuid, passenger: Passenger, class: SeatClass) -> Result<usize, String> {
+        let flight = self.database.flights
+            .iter_mut()
+            .find(|f| f.id == flight_id)
+            .ok_or("Flight not found")?;
+
+        if !flight.is_available_for_booking() {
+            return Err("Flight is not available for booking".to_string());
+        }
+        if !passenger.passenger_type.occupies_seat() {
+            return Err("Lap infants don't need a seat and can be booked directly".to_string());
+        }
+        if flight.get_available_seats(&class) > 0 {
+            return Err(format!("{:?} still has seats available - book directly instead", class));
+        }
+        let already_waiting = flight.waitlist.iter().any(|entry| {
+            entry.passenger.email.eq_ignore_ascii_case(&passenger.email)
+                && entry.passenger.full_name().eq_ignore_ascii_case(&passenger.full_name())
+        });
+        if already_waiting {
+            return Err(format!("{} is already on the waitlist for {}", passenger.full_name(), flight.flight_number));
+        }
+
+        let passenger_id = passenger.id;
+        self.messages.say(format!(
+            "⏳ {} added to the {:?} waitlist for {}",
+            passenger.full_name(), class, flight.flight_number
+        ));
+        flight.waitlist.push(WaitlistEntry {
+            passenger,
+            seat_class: class.clone(),
+            joined_at: Utc::now(),
+        });
+
+        let flight = self.get_flight_by_id(flight_id).ok_or("Flight not found")?;
+        let position = self.waitlist_queue(flight, &class)
+            .iter()
+            .position(|entry| entry.passenger.id == passenger_id)
+            .ok_or("Waitlist entry not found")?;
+        Ok(position + 1)
+    }
+
+    /// Waitlist entries for an email on a flight, with each one's class and 1-based position
+    pub fn waitlist_positions(&self, flight_id: Uuid, email: &str) -> Vec<(&WaitlistEntry, usize)> {
+        let Some(flight) = self.get_flight_by_id(flight_id) else {
+            return Vec::new();
+        };
+
+        [SeatClass::FirstClass, SeatClass::Business, SeatClass::Economy]
+            .iter()
+            .flat_map(|class| {
+                self.waitlist_queue(flight, class)
+                    .into_iter()
+                    .enumerate()
+                    .filter(|(_, entry)| entry.passenger.email.eq_ignore_ascii_case(email))
+                    .map(|(i, entry)| (entry, i + 1))
+            })
+            .collect()
+    }
+
+    /// Waitlist for one class in the order freed seats are offered
+    fn waitlist_queue<'a>(&self, flight: &'a Flight, class: &SeatClass) -> Vec<&'a WaitlistEntry> {
+        let mut queue: Vec<&WaitlistEntry> = flight.waitlist
+            .iter()
+            .filter(|entry| &entry.seat_class == class)
+            .collect();
+        queue.sort_by_key(|entry| loyalty::priority_key(self.loyalty_tier(&entry.passenger.email), entry.joined_at));
+        queue
+    }
+
+    /// Book the first passenger waiting for a class into a freed seat
+    fn promote_from_waitlist(&mut self, flight_id: Uuid, class: &SeatClass) -> Option<WaitlistPromotion> {
+        let flight = self.get_flight_by_id(flight_id)?;
+        let next = self.waitlist_queue(flight, class).first()?.passenger.clone();
+
+        let booking_id = self.create_booking(flight_id, next.clone(), class.clone()).ok()?;
+        let flight = self.database.flights.iter_mut().find(|f| f.id == flight_id)?;
+        flight.waitlist.retain(|entry| entry.passenger.id != next.id);
+
+        let ticket_number = self.get_booking_by_id(booking_id)?.ticket_number.clone();
+        self.messages.say(format!("🎟️ {} promoted from the waitlist: {}", next.full_name(), ticket_number));
+        Some(WaitlistPromotion {
+            ticket_number,
+            passenger_name: next.full_name(),
+            seat_class: class.clone(),
+        })
+    }
+
+    /// What cancelling a flight would do - bookings cancelled, refunds and passenger
+    /// notifications - without changing anything. `cancel_flight` applies exactly this plan.
+    pub fn preview_cancel_flight(&self, flight_number: &str, reason: &str) -> Result<FlightCancellation, String> {
+        if !self.admin_panel.is_authenticated() {
+            return Err("Admin authentication required".to_string());
+        }
+
+        let current_admin = self.admin_panel.current_admin.as_ref().unwrap();
+        if !current_admin.can_manage_flights() {
+            return Err("Insufficient permissions to manage flights".to_string());
+        }
+
+        let reason = reason.trim();
+        if reason.is_empty() {
+            return Err("A reason is required to cancel a flight".to_string());
+        }
+
+        let flight = self.get_flight_by_number(flight_number).ok_or("Flight not found")?;
+        self.plan_flight_cancellation(flight, reason)
+    }
+
+    fn plan_flight_cancellation(&self, flight: &Flight, reason: &str) -> Result<FlightCancellation, String> {
+        let mut summary = FlightCancellation {
+            flight_number: flight.flight_number.clone(),
+            reason: reason.to_string(),
+            ..Default::default()
+        };
+
+        match flight.status {
+            FlightStatus::Cancelled => {
+                summary.already_cancelled = true;
+                return Ok(summary);
+            }
+            FlightStatus::Departed | FlightStatus::Arrived => {
+                return Err(format!("Flight {} has already departed and cannot be cancelled", flight.flight_number));
+            }
+            _ => {}
+        }
+
+        let route = format!(
+            "{} {} → {} on {}",
+            flight.flight_number,
+            flight.origin,
+            flight.destination,
+            flight.departure_time.format("%Y-%m-%d %H:%M UTC")
+        );
+
+        // The airline cancelled, so passengers get their full fare back
+        for booking in self.database.bookings.iter().filter(|b| b.flight_id == flight.id && b.can_be_modified()) {
+            let refund = booking.payment.total_amount - booking.payment.refunded_amount;
+            summary.bookings_cancelled += 1;
+            summary.total_refunded += refund;
+            summary.notifications.push(format!(
+                "To {} <{}>: flight {} has been cancelled ({}). Booking {} is cancelled and ${:.2} has been refunded to your {}.",
+                booking.passenger.full_name(),
+                booking.passenger.email,
+                route,
+                reason,
+                booking.ticket_number,
+                refund,
+                booking.payment.payment_method
+            ));
+        }
+
+        Ok(summary)
+    }
+
+    /// Cancel a whole flight for the given reason: every active booking is cancelled and
+    /// fully refunded, the waitlist is dropped and the seat inventory is reset to the
+    /// aircraft's configuration. Cancelling a flight that is already cancelled changes nothing.
+    pub fn cancel_flight(&mut self, flight_number: &str, reason: String) -> Result<FlightCancellation, String> {
+        let summary = self.preview_cancel_flight(flight_number, &reason)?;
+        if summary.already_cancelled {
+            self.messages.say(format!("ℹ️ Flight {} is already cancelled - nothing to do", flight_number));
+            return Ok(summary);
+        }
+        let admin_id = self.admin_panel.current_admin.as_ref().unwrap().id;
+
+        let flight_idx = self.database.flights
+            .iter()
+            .position(|f| f.flight_number == flight_number)
+            .ok_or("Flight not found")?;
+        let flight_id = self.database.flights[flight_idx].id;
+        let old_status = self.database.flights[flight_idx].get_status_display();
+
+        for booking in self.database.bookings.iter_mut().filter(|b| b.flight_id == flight_id && b.can_be_modified()) {
+            booking.cancel()?;
+            booking.payment.refunded_amount = booking.payment.total_amount;
+        }
+
+        let aircraft_seats = self.get_aircraft_by_id(self.database.flights[flight_idx].aircraft_id)
+            .map(|a| [SeatClass::Economy, SeatClass::Business, SeatClass::FirstClass].map(|c| a.get_seats_by_class(&c)));
+
+        self.admin_panel.system_metrics.record_refund(summary.total_refunded);
+
+        let flight = &mut self.database.flights[flight_idx];
+        flight.try_transition(FlightStatus::Cancelled).map_err(|e| e.to_string())?;
+        flight.waitlist.clear();
+        if let Some([economy, business, first_class]) = aircraft_seats {
+            flight.seat_availability.economy = economy;
+            flight.seat_availability.business = business;
+            flight.seat_availability.first_class = first_class;
+        }
+        let new_status = flight.get_status_display();
+
+        self.admin_panel.log_action(
+            admin_id,
+            "CANCEL_FLIGHT".to_string(),
+            format!(
+                "Cancelled flight {}: {} ({} bookings, ${:.2} refunded)",
+                flight_number, summary.reason, summary.bookings_cancelled, summary.total_refunded
+            ),
+            Some(flight_id),
+            Some(old_status),
+            Some(new_status),
+        );
+        self.admin_panel.system_metrics.update_flight_metrics(&self.database.flights);
+
+        self.messages.say(format!("❌ Flight {} cancelled, {} bookings refunded", flight_number, summary.bookings_cancelled));
+        Ok(summary)
+    }
+
+    // Loyalty Program
+
+    /// Enroll a passenger in the frequent-flyer program, returning their member id
+    pub fn enroll_loyalty(&mut self, name: &str, email: &str) -> Result<String, String> {
+        let (name, email) = (name.trim(), email.trim());
+        if name.is_empty() || email.is_empty() {
+            return Err("Name and email are required to enroll".to_string());
+        }
+        if let Some(account) = self.get_loyalty_account(email) {
+            return Err(format!("{} is already enrolled as {}", email, account.member_id));
+        }
+
+        let account = LoyaltyAccount::new(name.to_string(), email.to_string());
+        let member_id = account.member_id.clone();
+        self.loyalty.push(account);
+
+        self.messages.say(format!("⭐ Enrolled {} in the loyalty program ({})", name, member_id));
+        Ok(member_id)
+    }
+
+    pub fn get_loyalty_account(&self, email: &str) -> Option<&LoyaltyAccount> {
+        self.loyalty.iter().find(|account| account.email.eq_ignore_ascii_case(email.trim()))
+    }
+
+    /// Credit a completed booking's miles to the passenger's account, if they have one.
+    /// Returns the miles awarded.
+    fn award_miles(&mut self, ticket_number: &str) -> u64 {
+        let Some(booking) = self.get_booking_by_ticket(ticket_number) else { return 0 };
+        let Some(flight) = self.get_flight_by_id(booking.flight_id) else { return 0 };
+        let (Some(origin), Some(destination)) = (self.get_airport_by_code(&flight.origin), self.get_airport_by_code(&flight.destination)) else {
+            return 0;
+        };
+
+        let distance = crate::utils::calculate_distance(
+            origin.coordinates.latitude,
+            origin.coordinates.longitude,
+            destination.coordinates.latitude,
+            destination.coordinates.longitude,
+        );
+        let miles = loyalty::miles_for_flight(distance, &booking.seat_class);
+        let email = booking.passenger.email.clone();
+
+        let Some(account) = self.loyalty.iter_mut().find(|a| a.email.eq_ignore_ascii_case(&email)) else { return 0 };
+        let old_tier = account.tier;
+        account.add_miles(miles);
+        self.messages.say(format!("⭐ {} miles credited to {}", miles, account.member_id));
+        if account.tier != old_tier {
+            self.messages.say(format!("🎉 {} reached {} status", account.name, account.tier.get_display()));
+        }
+        miles
+    }
+
+    /// Loyalty tier of the account registered to an email; non-members count as `Member`
+    pub fn loyalty_tier(&self, email: &str) -> LoyaltyTier {
+        self.loyalty
+            .iter()
+            .find(|account| account.email.eq_ignore_ascii_case(email))
+            .map_or(LoyaltyTier::Member, |account| account.tier)
+    }
+
+    pub fn set_upgrade_opt_in(&mut self, ticket_number: &str, opt_in: bool) -> Result<(), String> {
+        let booking = self.database.bookings
+            .iter_mut()
+            .find(|b| b.ticket_number == ticket_number)
+            .ok_or("Booking not found")?;
+        if !booking.can_be_modified() {
+            return Err("Booking can no longer be changed".to_string());
+        }
+        booking.upgrade_opt_in = opt_in;
+        Ok(())
+    }
+
+    /// Fill open premium seats with complimentary upgrades for opted-in Silver and Gold
+    /// members, highest tier first and earliest booking breaking ties. First class is
+    /// filled from business before business is filled from economy, so one run can
+    /// cascade. Upgraded passengers lose their seat number and are re-seated at the gate.
+    pub fn process_upgrades(&mut self, flight_number: &str) -> Result<Vec<Upgrade>, String> {
+        if !self.admin_panel.is_authenticated() {
+            return Err("Admin authentication required".to_string());
+        }
+        let current_admin = self.admin_panel.current_admin.as_ref().unwrap();
+        if !current_admin.can_manage_flights() {
+            return Err("Insufficient permissions to manage flights".to_string());
+        }
+        let admin_id = current_admin.id;
+
+        let flight_idx = self.database.flights
+            .iter()
+            .position(|f| f.flight_number == flight_number)
+            .ok_or("Flight not found")?;
+        let flight_id = self.database.flights[flight_idx].id;
+        if !matches!(self.database.flights[flight_idx].status, FlightStatus::OnTime | FlightStatus::Delayed(_) | FlightStatus::Boarding) {
+            return Err(format!("Flight {} is no longer taking upgrades", flight_number));
+        }
+
+        let mut upgrades = Vec::new();
+        for (from, to) in [(SeatClass::Business, SeatClass::FirstClass), (SeatClass::Economy, SeatClass::Business)] {
+            let mut candidates: Vec<(usize, LoyaltyTier)> = self.database.bookings
+                .iter()
+                .enumerate()
+                .filter(|(_, b)| b.flight_id == flight_id && b.seat_class == from && b.can_be_modified())
+                .filter(|(_, b)| b.upgrade_opt_in && b.passenger.passenger_type.occupies_seat())
+                .map(|(idx, b)| (idx, self.loyalty_tier(&b.passenger.email)))
+                .filter(|(_, tier)| *tier >= LoyaltyTier::Silver)
+                .collect();
+            candidates.sort_by_key(|(idx, tier)| loyalty::priority_key(*tier, self.database.bookings[*idx].booking_date));
+
+            for (idx, tier) in candidates {
+                if self.database.flights[flight_idx].move_seat(&from, &to).is_err() {
+                    break; // Cabin is full
+                }
+                let booking = &mut self.database.bookings[idx];
+                booking.seat_class = to.clone();
+                booking.seat_assignment = None;
+                booking.record_event(format!("Complimentary upgrade from {:?} to {:?}", from, to));
+                upgrades.push(Upgrade {
+                    ticket_number: booking.ticket_number.clone(),
+                    passenger_name: booking.passenger.full_name(),
+                    tier,
+                    from: from.clone(),
+                    to: to.clone(),
+                });
+                self.admin_panel.log_action(
+                    admin_id,
+                    "UPGRADE".to_string(),
+                    format!("Complimentary upgrade on {} for {}", flight_number, booking.ticket_number),
+                    Some(booking.id),
+                    Some(format!("{:?}", from)),
+                    Some(format!("{:?}", to)),
+                );
+            }
+        }
+
+        self.messages.say(format!("⬆️ {} upgrades processed on {}", upgrades.len(), flight_number));
+        Ok(upgrades)
+    }
+
+    /// Everyone booked on a flight who hasn't cancelled: seated passengers in seat order
+    /// (row, then letter), followed by the rest by surname
+    pub fn get_manifest(&self, flight_number: &str) -> Vec<&Booking> {
+        let Some(flight) = self.get_flight_by_number(flight_number) else {
+            return Vec::new();
+        };
+
+        let mut manifest: Vec<&Booking> = self.database.bookings
+            .iter()
+            .filter(|b| b.flight_id == flight.id && !matches!(b.status, BookingStatus::Cancelled))
+            .collect();
+        manifest.sort_by_cached_key(|b| {
+            let seat = b.seat_assignment.as_ref().map(|s| {
+                let digits = s.seat_number.chars().take_while(|c| c.is_ascii_digit()).count();
+                (s.seat_number[..digits].parse::<u32>().unwrap_or(0), s.seat_number[digits..].to_string())
+            });
+            (seat.is_none(), seat, b.passenger.last_name.to_lowercase(), b.passenger.first_name.to_lowercase())
+        });
+        manifest
+    }
+
+    /// All special requirements of passengers still travelling on a flight
+    pub fn special_services_report(&self, flight_number: &str) -> Result<SpecialServicesReport, String> {
+        let flight = self.get_flight_by_number(flight_number).ok_or("Flight not found")?;
+
+        let items = self.database.bookings
+            .iter()
+            .filter(|b| b.flight_id == flight.id)
+            .filter(|b| !matches!(b.status, BookingStatus::Cancelled | BookingStatus::NoShow))
+            .flat_map(|b| {
+                b.passenger.special_requirements.iter().map(move |requirement| SpecialServiceItem {
+                    ticket_number: b.ticket_number.clone(),
+                    passenger_name: b.passenger.full_name(),
+                    requirement: requirement.clone(),
+                    category: booking::requirement_category(requirement),
+                    critical: booking::is_critical_requirement(requirement),
+                    fulfilled: b.is_requirement_fulfilled(requirement),
+                })
+            })
+            .collect();
+
+        Ok(SpecialServicesReport {
+            flight_number: flight_number.to_string(),
+            items,
+        })
+    }
+
+    /// Meals needed per class plus special dietary meals, for passengers still travelling.
+    /// Lap infants don't get a meal of their own.
+    pub fn catering_requirements(&self, flight_number: &str) -> Result<CateringRequirements, String> {
+        let flight = self.get_flight_by_number(flight_number).ok_or("Flight not found")?;
+
+        let mut catering = CateringRequirements {
+            flight_number: flight_number.to_string(),
+            meal_service: flight.has_meal_service(),
+            meal_options: flight.meal_options.clone(),
+            ..Default::default()
+        };
+        if !catering.meal_service {
+            return Ok(catering);
+        }
+
+        let passengers = self.database.bookings
+            .iter()
+            .filter(|b| b.flight_id == flight.id)
+            .filter(|b| !matches!(b.status, BookingStatus::Cancelled | BookingStatus::NoShow))
+            .filter(|b| b.passenger.passenger_type.occupies_seat());
+
+        for booking in passengers.filter(|b| b.meal_preference != MealPreference::None) {
+            match booking.seat_class {
+                SeatClass::Economy => catering.economy_meals += 1,
+                SeatClass::Business => catering.business_meals += 1,
+                SeatClass::FirstClass => catering.first_class_meals += 1,
+            }
+
+            // One special meal per passenger: the chosen meal, else the first dietary need listed
+            let dietary = booking.meal_preference.dietary_category().map(str::to_string).or_else(|| {
+                booking.passenger.special_requirements
+                    .iter()
+                    .find(|r| booking::is_dietary_requirement(r))
+                    .map(|r| booking::requirement_category(r))
+            });
+            if let Some(category) = dietary {
+                match catering.special_meals.iter_mut().find(|(c, _)| *c == category) {
+                    Some((_, count)) => *count += 1,
+                    None => catering.special_meals.push((category, 1)),
+                }
+            }
+        }
+        catering.special_meals.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+        Ok(catering)
+    }
+
+    /// Meals of each kind to load for the flight's seated passengers. Passengers who want no
+    /// meal aren't counted, and a flight without meal service (or unknown) needs none.
+    pub fn meal_counts_for_flight(&self, flight_number: &str) -> HashMap<MealPreference, u32> {
+        let mut counts = HashMap::new();
+        let Some(flight) = self.get_flight_by_number(flight_number).filter(|f| f.has_meal_service()) else {
+            return counts;
+        };
+        for booking in self.database.bookings
+            .iter()
+            .filter(|b| b.flight_id == flight.id)
+            .filter(|b| !matches!(b.status, BookingStatus::Cancelled | BookingStatus::NoShow))
+            .filter(|b| b.passenger.passenger_type.occupies_seat())
+            .filter(|b| b.meal_preference != MealPreference::None)
+        {
+            *counts.entry(booking.meal_preference).or_insert(0) += 1;
+        }
+        counts
+    }
+
+    pub fn set_meal_preference(&mut self, ticket_number: &str, meal_preference: MealPreference) -> Result<(), String> {
+        let booking = self.database.bookings
+            .iter_mut()
+            .find(|b| b.ticket_number == ticket_number)
+            .ok_or("Booking not found")?;
+        if !booking.can_be_modified() {
+            return Err("Booking can no longer be changed".to_string());
+        }
+        booking.meal_preference = meal_preference;
+        Ok(())
+    }
+
+    pub fn set_meal_service(&mut self, flight_number: &str, meal_service: Option<bool>) -> Result<(), String> {
+        if !self.admin_panel.is_authenticated() {
+            return Err("Admin authentication required".to_string());
+        }
+
+        let current_admin = self.admin_panel.current_admin.as_ref().unwrap();
+        if !current_admin.can_manage_flights() {
+            return Err("Insufficient permissions to manage flights".to_string());
+        }
+
+        let flight = self.database.flights
+            .iter_mut()
+            .find(|f| f.flight_number == flight_number)
+            .ok_or("Flight not found")?;
+
+        let describe = |flight: &Flight| match flight.meal_service {
+            Some(true) => "Meal service on".to_string(),
+            Some(false) => "Meal service off".to_string(),
+            None => format!("Automatic ({})", if flight.has_meal_service() { "on" } else { "off" }),
+        };
+        let old_value = describe(flight);
+        flight.set_meal_service(meal_service);
+        let new_value = describe(flight);
+
+        self.admin_panel.log_action(
+            current_admin.id,
+            "SET_MEAL_SERVICE".to_string(),
+            format!("Set meal service for flight {}", flight_number),
+            Some(flight.id),
+            Some(old_value),
+            Some(new_value),
+        );
+
+        Ok(())
+    }
+
+    pub fn mark_requirement_fulfilled(&mut self, ticket_number: &str, requirement: &str) -> Result<(), String> {
+        if !self.admin_panel.is_authenticated() {
+            return Err("Admin authentication required".to_string());
+        }
+
+        let current_admin = self.admin_panel.current_admin.as_ref().unwrap();
+        if !current_admin.can_manage_flights() {
+            return Err("Insufficient permissions to manage flights".to_string());
+        }
+
+        let booking = self.database.bookings
+            .iter_mut()
+            .find(|b| b.ticket_number == ticket_number)
+            .ok_or("Booking not found")?;
+
+        booking.mark_requirement_fulfilled(requirement)?;
+
+        self.admin_panel.log_action(
+            current_admin.id,
+            "FULFILL_REQUIREMENT".to_string(),
+            format!("Marked '{}' fulfilled for {}", requirement, ticket_number),
+            Some(booking.id),
+            Some("Pending".to_string()),
+            Some("Fulfilled".to_string()),
+        );
+
+        Ok(())
+    }
+
+    /// Book `n` synthetic passengers onto random available flights, reproducibly for a given seed.
+    /// Full classes fall back to the nearest alternative class, or count as waitlisted.
+    /// Seat counts are checked against the bookings made afterwards.
+    pub fn run_booking_simulation(&mut self, n: u32, seed: u64) -> BookingSimulationStats {
+        const FIRST_NAMES: [&str; 8] = ["Ada", "Grace", "Alan", "Linus", "Margaret", "Dennis", "Barbara", "Ken"];
+        const LAST_NAMES: [&str; 8] = ["Lovelace", "Hopper", "Turing", "Torvalds", "Hamilton", "Ritchie", "Liskov", "Thompson"];
+        const CLASSES: [SeatClass; 3] = [SeatClass::Economy, SeatClass::Business, SeatClass::FirstClass];
+
+        let mut rng = crate::utils::SeededRng::new(seed);
+        let mut stats = BookingSimulationStats::default();
+
+        // Seats per flight and class before the run, to check the books balance afterwards
+        let flight_ids: Vec<Uuid> = self.get_available_flights().iter().map(|f| f.id).collect();
+        let seats_before: Vec<(Uuid, [u32; 3])> = flight_ids
+            .iter()
+            .filter_map(|id| self.get_flight_by_id(*id))
+            .map(|f| (f.id, CLASSES.clone().map(|c| f.get_available_seats(&c))))
+            .collect();
+        let bookings_before = self.database.bookings.len();
+
+        for i in 0..n {
+            stats.attempted += 1;
+            if flight_ids.is_empty() {
+                stats.rejected += 1;
+                continue;
+            }
+
+            let flight_id = flight_ids[rng.below(flight_ids.len())];
+            // Roughly 80% economy, 15% business, 5% first
+            let roll = rng.next_f64();
+            let mut class = if roll < 0.80 { SeatClass::Economy } else if roll < 0.95 { SeatClass::Business } else { SeatClass::FirstClass };
+            let passenger_type = match rng.below(20) {
+                0 => PassengerType::Infant,
+                1..=2 => PassengerType::Child,
+                3..=4 => PassengerType::Senior,
+                _ => PassengerType::Adult,
+            };
+            let first = FIRST_NAMES[rng.below(FIRST_NAMES.len())];
+            let last = LAST_NAMES[rng.below(LAST_NAMES.len())];
+            let passenger = Passenger::new(
+                first.to_string(),
+                last.to_string(),
+                format!("{}.{}{}@example.com", first.to_lowercase(), last.to_lowercase(), i),
+                format!("555{:07}", rng.below(10_000_000)),
+                "1985-06-15".to_string(),
+                passenger_type,
+            );
+
+            if passenger.passenger_type.occupies_seat() {
+                match self.suggest_alternative_class(flight_id, &class) {
+                    Ok(alternatives) => match alternatives.first() {
+                        Some(ClassAlternative::Class { class: alternative, .. }) => class = alternative.clone(),
+                        Some(ClassAlternative::Waitlist) => {
+                            stats.waitlisted += 1;
+                            continue;
+                        }
+                        None => {}
+                    },
+                    Err(_) => {
+                        stats.rejected += 1;
+                        continue;
+                    }
+                }
+            }
+
+            match self.create_booking(flight_id, passenger, class) {
+                Ok(booking_id) => {
+                    stats.booked += 1;
+                    stats.revenue += self.get_booking_by_id(booking_id).map(|b| b.payment.total_amount).unwrap_or(0.0);
+                }
+                Err(_) => stats.rejected += 1,
+            }
+        }
+
+        let new_bookings = &self.database.bookings[bookings_before..];
+        for (flight_id, before) in &seats_before {
+            let Some(flight) = self.get_flight_by_id(*flight_id) else { continue };
+            for (class, seats_before) in CLASSES.iter().zip(before) {
+                let seated = new_bookings
+                    .iter()
+                    .filter(|b| b.flight_id == *flight_id && b.seat_class == *class)
+                    .filter(|b| b.passenger.passenger_type.occupies_seat())
+                    .count() as u32;
+                let available = flight.get_available_seats(class);
+                if available + seated != *seats_before {
+                    stats.invariant_violations.push(format!(
+                        "Flight {} {:?}: {} seats before, {} booked, {} still available",
+                        flight.flight_number, class, seats_before, seated, available
+                    ));
+                }
+            }
+
+            let sold = flight.total_capacity.saturating_sub(
+                CLASSES.iter().map(|c| flight.get_available_seats(c)).sum::<u32>(),
+            );
+            stats.load_factors.push((
+                flight.flight_number.clone(),
+                crate::utils::calculate_load_factor(sold, flight.total_capacity),
+            ));
+        }
+
+        self.messages.say(format!(
+            "🧪 Simulated {} bookings: {} booked, {} waitlisted, {} rejected, ${:.2} revenue",
+            stats.attempted, stats.booked, stats.waitlisted, stats.rejected, stats.revenue
+        ));
+        stats
+    }
+
+    // Aircraft Operations
+    pub fn get_aircraft_by_id(&self, aircraft_id: Uuid) -> Option<&Aircraft> {
+        self.database.aircraft.iter().find(|a| a.id == aircraft_id)
+    }
+
+    pub fn get_available_aircraft(&self) -> Vec<&Aircraft> {
+        self.database.aircraft
+            .iter()
+            .filter(|a| a.is_available_for_flight())
+            .collect()
+    }
+
+    /// Fleet aircraft the route preferences favour for a route, best first.
+    /// Empty when no preference covers the route.
+    pub fn preferred_aircraft_for_route(&self, origin: &str, destination: &str) -> Vec<&Aircraft> {
+        let mut ranked: Vec<((usize, usize), &Aircraft)> = self.database.aircraft
+            .iter()
+            .filter(|a| a.status != AircraftStatus::Retired)
+            .filter_map(|aircraft| {
+                self.admin_panel.route_preferences
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, preference)| preference.applies_to_route(origin, destination))
+                    .find_map(|(idx, preference)| preference.rank(aircraft).map(|rank| ((idx, rank), aircraft)))
+            })
+            .collect();
+        ranked.sort_by_key(|(rank, _)| *rank);
+        ranked.into_iter().map(|(_, aircraft)| aircraft).collect()
+    }
+
+    /// Whether an aircraft is active and not already flying something in the time window
+    fn is_aircraft_free(&self, aircraft: &Aircraft, departure_time: DateTime<Utc>, arrival_time: DateTime<Utc>) -> bool {
+        self.database.is_aircraft_free(aircraft, departure_time, arrival_time)
+    }
+
+    /// Choose an aircraft for a new flight: the best free aircraft the route preferences
+    /// ask for, otherwise the smallest free aircraft with enough range.
+    pub fn auto_assign_aircraft(
+        &self,
+        origin: &str,
+        destination: &str,
+        departure_time: DateTime<Utc>,
+        arrival_time: DateTime<Utc>,
+    ) -> Option<&Aircraft> {
+        let distance = self.route_distance_km(origin, destination).unwrap_or(0.0);
+        let usable = |aircraft: &&Aircraft| {
+            aircraft.performance.range_km as f64 >= distance
+                && self.is_aircraft_free(aircraft, departure_time, arrival_time)
+        };
+
+        self.preferred_aircraft_for_route(origin, destination)
+            .into_iter()
+            .find(usable)
+            .or_else(|| {
+                self.database.aircraft
+                    .iter()
+                    .filter(usable)
+                    .min_by_key(|aircraft| aircraft.total_capacity)
+            })
+    }
+
+    pub fn get_aircraft_for_flight(&self, flight_id: Uuid) -> Option<&Aircraft> {
+        if let Some(flight) = self.get_flight_by_id(flight_id) {
+            self.get_aircraft_by_id(flight.aircraft_id)
+        } else {
+            None
+        }
+    }
+
+    // Airport Operations
+    pub fn get_airport_by_code(&self, code: &str) -> Option<&Airport> {
+        self.database.airports.iter().find(|a| a.code == code)
+    }
+
+    pub fn get_all_airports(&self) -> &Vec<Airport> {
+        &self.database.airports
+    }
+
+    /// The airport closest to the given coordinates, with its distance in km
+    pub fn find_nearest_airport(&self, lat: f64, lon: f64) -> Option<(&Airport, f64)> {
+        self.airports_by_distance(lat, lon).into_iter().next()
+    }
+
+    /// Airports offering a service, matched case-insensitively against their services and
+    /// their terminals' amenities (so "lounge" finds airports with lounges). A blank query
+    /// matches nothing.
+    pub fn airports_with_service(&self, service: &str) -> Vec<&Airport> {
+        let query = service.trim().to_lowercase();
+        if query.is_empty() {
+            return Vec::new();
+        }
+        self.database.airports
+            .iter()
+            .filter(|airport| {
+                airport.services.iter().any(|s| s.to_lowercase().contains(&query)) || airport.has_amenity(&query)
+            })
+            .collect()
+    }
+
+    /// Airports within `radius_km` of the given coordinates, nearest first
+    pub fn airports_within_radius(&self, lat: f64, lon: f64, radius_km: f64) -> Vec<(&Airport, f64)> {
+        self.airports_by_distance(lat, lon)
+            .into_iter()
+            .filter(|(_, distance)| *distance <= radius_km)
+            .collect()
+    }
+
+    fn airports_by_distance(&self, lat: f64, lon: f64) -> Vec<(&Airport, f64)> {
+        let mut airports: Vec<(&Airport, f64)> = self.database.airports
+            .iter()
+            .map(|airport| {
+                let distance = crate::utils::calculate_distance(
+                    lat,
+                    lon,
+                    airport.coordinates.latitude,
+                    airport.coordinates.longitude,
+                );
+                (airport, distance)
+            })
+            .collect();
+        airports.sort_by(|a, b| a.1.total_cmp(&b.1));
+        airports
+    }
+
+    pub fn get_departures_from_airport(&self, airport_code: &str) -> Vec<&Flight> {
+        self.database.flights
+            .iter()
+            .filter(|f| f.origin == airport_code)
+            .collect()
+    }
+
+    pub fn get_arrivals_to_airport(&self, airport_code: &str) -> Vec<&Flight> {
+        self.database.flights
+            .iter()
+            .filter(|f| f.destination == airport_code)
+            .collect()
+    }
+
+    // Admin Operations
+    pub fn authenticate_admin(&mut self, username: &str, password: &str) -> Result<AdminUser, String> {
+        self.admin_panel.authenticate(username, password)
+    }
+
+    pub fn create_admin(
+        &mut self,
+        username: &str,
+        full_name: &str,
+        email: &str,
+        level: AdminLevel,
+        password: &str,
+    ) -> Result<AdminUser, String> {
+        let admin = self.admin_panel.create_admin(username, full_name, email, level, password)?;
+        self.messages.say(format!("🔐 Created admin account {}", admin.username));
+        Ok(admin)
+    }
+
+    pub fn is_admin_authenticated(&self) -> bool {
+        self.admin_panel.is_authenticated()
+    }
+
+    pub fn logout_admin(&mut self) {
+        self.admin_panel.logout();
+    }
+
+    pub fn set_flight_delay(&mut self, flight_number: &str, delay_minutes: i32) -> errors::Result<()> {
+        use crate::config::delays::{MAX_DELAY_MINUTES, MIN_DELAY_MINUTES};
+
+        let admin_id = match self.admin_panel.current_admin.as_ref() {
+            Some(admin) if admin.can_manage_flights() => admin.id,
+            _ => return Err(AirportError::InsufficientPermissions { operation: "set flight delay".to_string() }),
+        };
+
+        if !(MIN_DELAY_MINUTES..=MAX_DELAY_MINUTES).contains(&delay_minutes) {
+            return Err(AirportError::DelayOutOfRange {
+                minutes: delay_minutes,
+                min: MIN_DELAY_MINUTES,
+                max: MAX_DELAY_MINUTES,
+            });
+        }
+
+        let flight = self.database.flights
+            .iter_mut()
+            .find(|f| f.flight_number == flight_number)
+            .ok_or_else(|| AirportError::FlightNumberNotFound { flight_number: flight_number.to_string() })?;
+
+        let old_status = flight.get_status_display();
+        flight.set_delay(delay_minutes)?;
+        let new_status = flight.get_status_display();
+        let auto_cancelled = flight.status == FlightStatus::Cancelled;
+        let total_delay = match flight.status {
+            FlightStatus::Delayed(total) => total,
+            _ => 0,
+        };
+        let flight_id = flight.id;
+
+        // Log the action
+        self.admin_panel.log_action(
+            admin_id,
+            "SET_DELAY".to_string(),
+            format!("Set delay for flight {}", flight_number),
+            Some(flight_id),
+            Some(old_status.clone()),
+            Some(new_status.clone()),
+        );
+
+        if auto_cancelled {
+            // Only the flight changes; its bookings are left for an admin to refund or rebook
+            self.admin_panel.log_action(
+                admin_id,
+                "AUTO_CANCEL_FLIGHT".to_string(),
+                format!(
+                    "Flight {} cancelled automatically - total delay would exceed {} minutes",
+                    flight_number, MAX_DELAY_MINUTES
+                ),
+                Some(flight_id),
+                Some(old_status),
+                Some(new_status),
+            );
+            self.admin_panel.system_metrics.update_flight_metrics(&self.database.flights);
+            self.messages.say(format!("🚫 Flight {} cancelled - delay exceeded {} minutes", flight_number, MAX_DELAY_MINUTES));
+        } else if delay_minutes < 0 {
+            self.messages.say(format!("⏰ Flight {} brought forward {} minutes", flight_number, -delay_minutes));
+        } else {
+            self.messages.say(format!("⏰ Flight {} delay set to {} minutes", flight_number, total_delay));
+            self.cascade_delay(admin_id, flight_id);
+        }
+        Ok(())
+    }
+
+    /// Push back the aircraft's following flights until each has the minimum turnaround
+    /// after the previous one lands. Each knock-on delay is logged on its own.
+    fn cascade_delay(&mut self, admin_id: Uuid, flight_id: Uuid) {
+        use crate::config::delays::{MAX_DELAY_MINUTES, MIN_TURNAROUND_MINUTES};
+
+        let mut inbound = flight_id;
+        while let Some(inbound_flight) = self.get_flight_by_id(inbound) {
+            let (aircraft_id, departure, arrival) = (inbound_flight.aircraft_id, inbound_flight.departure_time, inbound_flight.arrival_time);
+            let inbound_number = inbound_flight.flight_number.clone();
+            let Some(next) = self.database.flights
+                .iter_mut()
+                .filter(|f| f.aircraft_id == aircraft_id && f.id != inbound && f.departure_time > departure)
+                .filter(|f| matches!(f.status, FlightStatus::OnTime | FlightStatus::Delayed(_)))
+                .min_by_key(|f| f.departure_time)
+            else {
+                break;
+            };
+
+            let turnaround = (next.estimated_departure() - arrival).num_minutes();
+            if turnaround >= MIN_TURNAROUND_MINUTES {
+                break;
+            }
+            let old_status = next.get_status_display();
+            if next.set_delay((MIN_TURNAROUND_MINUTES - turnaround) as i32).is_err() {
+                break;
+            }
+            let new_status = next.get_status_display();
+            let (next_id, next_number) = (next.id, next.flight_number.clone());
+            let cancelled = next.status == FlightStatus::Cancelled;
+
+            self.admin_panel.log_action(
+                admin_id,
+                "CASCADE_DELAY".to_string(),
+                format!("Flight {} delayed for turnaround after late inbound {}", next_number, inbound_number),
+                Some(next_id),
+                Some(old_status.clone()),
+                Some(new_status.clone()),
+            );
+            if cancelled {
+                self.admin_panel.log_action(
+                    admin_id,
+                    "AUTO_CANCEL_FLIGHT".to_string(),
+                    format!(
+                        "Flight {} cancelled automatically - total delay would exceed {} minutes",
+                        next_number, MAX_DELAY_MINUTES
+                    ),
+                    Some(next_id),
+                    Some(old_status),
+                    Some(new_status),
+                );
+                self.admin_panel.system_metrics.update_flight_metrics(&self.database.flights);
+                self.messages.say(format!("🚫 Flight {} cancelled - knock-on delay exceeded {} minutes", next_number, MAX_DELAY_MINUTES));
+                break;
+            }
+            self.messages.say(format!("⏰ Flight {} now {} (aircraft arriving late on {})", next_number, new_status, inbound_number));
+            inbound = next_id;
+        }
+    }
+
+    // Aircraft Management
+
+    fn require_aircraft_manager(&self) -> Result<Uuid, String> {
+        let current_admin = self.admin_panel.current_admin.as_ref().ok_or("Admin authentication required")?;
+        if !current_admin.can_manage_aircraft() {
+            return Err("Insufficient permissions to manage aircraft".to_string());
+        }
+        Ok(current_admin.id)
+    }
+
+    pub fn get_aircraft_by_registration(&self, registration: &str) -> Option<&Aircraft> {
+        self.database.aircraft.iter().find(|a| a.registration.eq_ignore_ascii_case(registration))
+    }
+
+    /// Take an aircraft out of service or return it. In-flight status is left to the simulation.
+    pub fn set_aircraft_status(&mut self, registration: &str, status: AircraftStatus) -> Result<(), String> {
+        let admin_id = self.require_aircraft_manager()?;
+        if status == AircraftStatus::InFlight {
+            return Err("In-flight status is set automatically when a flight departs".to_string());
+        }
+        if matches!(status, AircraftStatus::Grounded { .. }) {
+            return Err("Use the grounding option to ground an aircraft".to_string());
+        }
+
+        let aircraft = self.database.aircraft
+            .iter_mut()
+            .find(|a| a.registration.eq_ignore_ascii_case(registration))
+            .ok_or("Aircraft not found")?;
+        if aircraft.status == AircraftStatus::InFlight {
+            return Err(format!("{} is in flight - change its status after it lands", aircraft.registration));
+        }
+        if aircraft.is_grounded() {
+            return Err(format!("{} is grounded - lift the grounding first", aircraft.registration));
+        }
+
+        let old_status = aircraft.get_status_display();
+        aircraft.set_status(status);
+        let new_status = aircraft.get_status_display();
+        let (aircraft_id, registration) = (aircraft.id, aircraft.registration.clone());
+
+        self.admin_panel.log_action(
+            admin_id,
+            "SET_AIRCRAFT_STATUS".to_string(),
+            format!("Set status of aircraft {}", registration),
+            Some(aircraft_id),
+            Some(old_status),
+            Some(new_status.clone()),
+        );
+        self.admin_panel.system_metrics.update_aircraft_metrics(&self.database.aircraft);
+
+        self.messages.say(format!("🛩️ Aircraft {} is now {}", registration, new_status));
+        Ok(())
+    }
+
+    /// Put a safety or regulatory hold on an aircraft. Unlike maintenance it is only
+    /// lifted by an admin, never by the simulation or by recording work.
+    pub fn ground_aircraft(&mut self, registration: &str, reason: String) -> Result<(), String> {
+        let admin_id = self.require_aircraft_manager()?;
+        let reason = reason.trim().to_string();
+        if reason.is_empty() {
+            return Err("A reason is required to ground an aircraft".to_string());
+        }
+
+        let aircraft = self.database.aircraft
+            .iter_mut()
+            .find(|a| a.registration.eq_ignore_ascii_case(registration))
+            .ok_or("Aircraft not found")?;
+        match aircraft.status {
+            AircraftStatus::InFlight => {
+                return Err(format!("{} is in flight - ground it after it lands", aircraft.registration));
+            }
+            AircraftStatus::Retired | AircraftStatus::Grounded { .. } => {
+                return Err(format!("{} is already {}", aircraft.registration, aircraft.get_status_display()));
+            }
+            AircraftStatus::Active | AircraftStatus::Maintenance => {}
+        }
+
+        let old_status = aircraft.get_status_display();
+        aircraft.set_status(AircraftStatus::Grounded { reason: reason.clone(), since: Utc::now() });
+        let (aircraft_id, registration) = (aircraft.id, aircraft.registration.clone());
+
+        self.admin_panel.log_action(
+            admin_id,
+            "GROUND_AIRCRAFT".to_string(),
+            format!("Grounded aircraft {}: {}", registration, reason),
+            Some(aircraft_id),
+            Some(old_status),
+            Some(reason),
+        );
+        self.admin_panel.system_metrics.update_aircraft_metrics(&self.database.aircraft);
+
+        let affected = self.database.flights
+            .iter()
+            .filter(|f| f.aircraft_id == aircraft_id)
+            .filter(|f| matches!(f.status, FlightStatus::OnTime | FlightStatus::Delayed(_) | FlightStatus::Boarding))
+            .count();
+        self.messages.say(format!("⛔ Aircraft {} grounded - {} scheduled flight(s) need another aircraft", registration, affected));
+        Ok(())
+    }
+
+    /// Lift a grounding. The aircraft goes back into service, or into maintenance if a
+    /// check fell due while it was held.
+    pub fn unground_aircraft(&mut self, registration: &str) -> Result<(), String> {
+        let admin_id = self.require_aircraft_manager()?;
+
+        let aircraft = self.database.aircraft
+            .iter_mut()
+            .find(|a| a.registration.eq_ignore_ascii_case(registration))
+            .ok_or("Aircraft not found")?;
+        if !aircraft.is_grounded() {
+            return Err(format!("{} is not grounded", aircraft.registration));
+        }
+
+        let old_status = aircraft.get_status_display();
+        aircraft.set_status(if aircraft.hours_until_maintenance() <= 0.0 {
+            AircraftStatus::Maintenance
+        } else {
+            AircraftStatus::Active
+        });
+        let new_status = aircraft.get_status_display();
+        let (aircraft_id, registration) = (aircraft.id, aircraft.registration.clone());
+
+        self.admin_panel.log_action(
+            admin_id,
+            "UNGROUND_AIRCRAFT".to_string(),
+            format!("Lifted grounding of aircraft {}", registration),
+            Some(aircraft_id),
+            Some(old_status),
+            Some(new_status.clone()),
+        );
+        self.admin_panel.system_metrics.update_aircraft_metrics(&self.database.aircraft);
+
+        self.messages.say(format!("✅ Aircraft {} released from grounding - now {}", registration, new_status));
+        Ok(())
+    }
+
+    /// Aircraft in service within `within_hours` flight hours of their next maintenance check
+    /// (or already overdue), with the hours left, soonest first
+    pub fn maintenance_due(&self, within_hours: f64) -> Vec<(&Aircraft, f64)> {
+        let mut due: Vec<(&Aircraft, f64)> = self.database.aircraft
+            .iter()
+            .filter(|a| !matches!(a.status, AircraftStatus::Retired))
+            .map(|a| (a, a.hours_until_maintenance()))
+            .filter(|(_, remaining)| *remaining <= within_hours)
+            .collect();
+        due.sort_by(|a, b| a.1.total_cmp(&b.1));
+        due
+    }
+
+    /// Record maintenance work on an aircraft; it returns to service once caught up
+    pub fn record_maintenance(&mut self, registration: &str, hours: f64) -> Result<(), String> {
+        let admin_id = self.require_aircraft_manager()?;
+        if !(hours > 0.0 && hours.is_finite()) {
+            return Err("Maintenance hours must be a positive number".to_string());
+        }
+
+        let aircraft = self.database.aircraft
+            .iter_mut()
+            .find(|a| a.registration.eq_ignore_ascii_case(registration))
+            .ok_or("Aircraft not found")?;
+        if matches!(aircraft.status, AircraftStatus::Retired | AircraftStatus::InFlight) {
+            return Err(format!("{} is {} and cannot be maintained", aircraft.registration, aircraft.get_status_display()));
+        }
+
+        let old_hours = aircraft.maintenance_hours;
+        aircraft.perform_maintenance(hours);
+        let (aircraft_id, registration, new_hours) = (aircraft.id, aircraft.registration.clone(), aircraft.maintenance_hours);
+
+        self.admin_panel.log_action(
+            admin_id,
+            "RECORD_MAINTENANCE".to_string(),
+            format!("Recorded {:.1} maintenance hours on aircraft {}", hours, registration),
+            Some(aircraft_id),
+            Some(format!("{:.1}", old_hours)),
+            Some(format!("{:.1}", new_hours)),
+        );
+        self.admin_panel.system_metrics.update_aircraft_metrics(&self.database.aircraft);
+
+        self.messages.say(format!("🔧 Recorded {:.1} maintenance hours on {}", hours, registration));
+        Ok(())
+    }
+
+    /// Move a flight onto another aircraft. The aircraft must be in service, free for the
+    /// flight's times, able to fly the route and big enough for the seats already sold in
+    /// every class. Seats that don't exist on the new aircraft are cleared.
+    pub fn reassign_aircraft(&mut self, flight_number: &str, registration: &str) -> Result<(), String> {
+        let admin_id = self.require_aircraft_manager()?;
+
+        let flight = self.get_flight_by_number(flight_number).ok_or("Flight not found")?;
+        let aircraft = self.get_aircraft_by_registration(registration).ok_or("Aircraft not found")?;
+
+        if !matches!(flight.status, FlightStatus::OnTime | FlightStatus::Delayed(_)) {
+            return Err(format!("Flight {} is {} and can no longer change aircraft", flight.flight_number, flight.get_status_display()));
+        }
+        if aircraft.id == flight.aircraft_id {
+            return Err(format!("{} already operates flight {}", aircraft.registration, flight.flight_number));
+        }
+        if matches!(aircraft.status, AircraftStatus::Maintenance | AircraftStatus::Retired | AircraftStatus::Grounded { .. }) {
+            return Err(format!("{} is {} and cannot be assigned", aircraft.registration, aircraft.get_status_display()));
+        }
+        if !self.is_aircraft_free(aircraft, flight.departure_time, flight.arrival_time) {
+            return Err(format!("{} is already scheduled during this flight", aircraft.registration));
+        }
+
+        let mut reassigned = AirportDatabase::fit_cabins(flight, aircraft)?;
+        reassigned.aircraft_id = aircraft.id;
+        self.validate_flight_range(&reassigned)?;
+
+        let old_registration = self.get_aircraft_by_id(flight.aircraft_id).map(|a| a.registration.clone()).unwrap_or_default();
+        let new_registration = aircraft.registration.clone();
+        let seat_configuration = aircraft.seat_configuration.clone();
+        let flight_id = flight.id;
+
+        self.database.clear_invalid_seat_assignments(flight_id, &seat_configuration);
+        if let Some(slot) = self.database.flights.iter_mut().find(|f| f.id == flight_id) {
+            *slot = reassigned;
+        }
+
+        self.admin_panel.log_action(
+            admin_id,
+            "REASSIGN_AIRCRAFT".to_string(),
+            format!("Reassigned aircraft for flight {}", flight_number),
+            Some(flight_id),
+            Some(old_registration),
+            Some(new_registration.clone()),
+        );
+
+        self.messages.say(format!("🔁 Flight {} now operated by {}", flight_number, new_registration));
+        Ok(())
+    }
+
+    /// Flights the aircraft is scheduled for that haven't started boarding
+    pub fn upcoming_flights_for_aircraft(&self, registration: &str) -> Vec<&Flight> {
+        let Some(aircraft) = self.get_aircraft_by_registration(registration) else {
+            return Vec::new();
+        };
+        self.database.flights
+            .iter()
+            .filter(|f| f.aircraft_id == aircraft.id && matches!(f.status, FlightStatus::OnTime | FlightStatus::Delayed(_)))
+            .collect()
+    }
+
+    /// Re-configure an aircraft's cabins. With `update_flights` its upcoming flights are
+    /// resized to the new cabins; otherwise they keep the seats they were sold with. Returns
+    /// the number of flights updated.
+    pub fn set_seat_configuration(
+        &mut self,
+        registration: &str,
+        seat_configuration: SeatConfiguration,
+        update_flights: bool,
+    ) -> Result<usize, String> {
+        let admin_id = self.require_aircraft_manager()?;
+        let mut aircraft = self.get_aircraft_by_registration(registration).ok_or("Aircraft not found")?.clone();
+        let old_capacity = aircraft.set_seat_configuration(seat_configuration)?;
+
+        // Check every flight fits before changing anything
+        let refitted = if update_flights {
+            self.upcoming_flights_for_aircraft(registration)
+                .into_iter()
+                .map(|flight| AirportDatabase::fit_cabins(flight, &aircraft))
+                .collect::<Result<Vec<Flight>, String>>()?
+        } else {
+            Vec::new()
+        };
+
+        let (aircraft_id, registration, new_capacity) = (aircraft.id, aircraft.registration.clone(), aircraft.total_capacity);
+        for flight in &refitted {
+            self.database.clear_invalid_seat_assignments(flight.id, &aircraft.seat_configuration);
+        }
+        if let Some(slot) = self.database.aircraft.iter_mut().find(|a| a.id == aircraft_id) {
+            *slot = aircraft;
+        }
+        let updated = refitted.len();
+        for flight in refitted {
+            if let Some(slot) = self.database.flights.iter_mut().find(|f| f.id == flight.id) {
+                *slot = flight;
+            }
+        }
+
+        self.admin_panel.log_action(
+            admin_id,
+            "SET_SEAT_CONFIG".to_string(),
+            format!("Re-configured {} cabins ({} upcoming flights updated)", registration, updated),
+            Some(aircraft_id),
+            Some(format!("{} seats", old_capacity)),
+            Some(format!("{} seats", new_capacity)),
+        );
+        self.admin_panel.system_metrics.update_aircraft_metrics(&self.database.aircraft);
+
+        self.messages.say(format!("💺 {} now seats {} (was {})", registration, new_capacity, old_capacity));
+        Ok(updated)
+    }
+
+    pub fn set_dynamic_pricing(&mut self, flight_number: &str, multiplier: f64) -> Result<(), String> {
+        if !self.admin_panel.is_authenticated() {
+            return Err("Admin authentication required".to_string());
+        }
+
+        let current_admin = self.admin_panel.current_admin.as_ref().unwrap();
+        if !current_admin.can_manage_pricing() {
+            return Err("Insufficient permissions to manage pricing".to_string());
+        }
+
+        let flight = self.database.flights
+            .iter_mut()
+            .find(|f| f.flight_number == flight_number)
+            .ok_or("Flight not found")?;
+
+        let old_multiplier = flight.pricing.dynamic_multiplier;
+        flight.pricing.dynamic_multiplier = multiplier;
+        flight.pricing.manual_override = true;
+
+        // Log the action
+        self.admin_panel.log_action(
+            current_admin.id,
+            "SET_PRICING".to_string(),
+            format!("Set pricing multiplier for flight {}", flight_number),
+            Some(flight.id),
+            Some(old_multiplier.to_string()),
+            Some(multiplier.to_string()),
+        );
+
+        self.messages.say(format!("💰 Flight {} pricing multiplier set to {:.2}", flight_number, multiplier));
+        Ok(())
+    }
+
+    /// Override one flight's base fares by class
+    pub fn set_base_prices(&mut self, flight_number: &str, economy: f64, business: f64, first_class: f64) -> Result<(), String> {
+        if !self.admin_panel.is_authenticated() {
+            return Err("Admin authentication required".to_string());
+        }
+
+        let current_admin = self.admin_panel.current_admin.as_ref().unwrap();
+        if !current_admin.can_manage_pricing() {
+            return Err("Insufficient permissions to manage pricing".to_string());
+        }
+
+        let flight = self.database.flights
+            .iter_mut()
+            .find(|f| f.flight_number == flight_number)
+            .ok_or("Flight not found")?;
+
+        let fares = |pricing: &crate::modules::flight::FlightPricing| {
+            format!("{:.2}/{:.2}/{:.2}", pricing.economy, pricing.business, pricing.first_class)
+        };
+        let old_fares = fares(&flight.pricing);
+        flight.set_base_prices(economy, business, first_class)?;
+
+        self.admin_panel.log_action(
+            current_admin.id,
+            "SET_BASE_FARES".to_string(),
+            format!("Set base fares for flight {}", flight_number),
+            Some(flight.id),
+            Some(old_fares),
+            Some(fares(&flight.pricing)),
+        );
+
+        self.messages.say(format!(
+            "💰 Flight {} base fares set to ${:.2} / ${:.2} / ${:.2}",
+            flight_number, economy, business, first_class
+        ));
+        Ok(())
+    }
+
+    /// Switch automatic load-based fare increases on or off for one flight. Switching them
+    /// on drops any manual multiplier so demand sets the fare again.
+    pub fn set_demand_pricing(&mut self, flight_number: &str, enabled: bool) -> Result<(), String> {
+        if !self.admin_panel.is_authenticated() {
+            return Err("Admin authentication required".to_string());
+        }
+
+        let current_admin = self.admin_panel.current_admin.as_ref().unwrap();
+        if !current_admin.can_manage_pricing() {
+            return Err("Insufficient permissions to manage pricing".to_string());
+        }
+
+        let flight = self.database.flights
+            .iter_mut()
+            .find(|f| f.flight_number == flight_number)
+            .ok_or("Flight not found")?;
+
+        let old_setting = flight.pricing.demand_pricing;
+        flight.pricing.demand_pricing = enabled;
+        if enabled {
+            flight.pricing.dynamic_multiplier = 1.0;
+            flight.pricing.manual_override = false;
+        }
+
+        self.admin_panel.log_action(
+            current_admin.id,
+            "SET_DEMAND_PRICING".to_string(),
+            format!("{} demand pricing for flight {}", if enabled { "Enabled" } else { "Disabled" }, flight_number),
+            Some(flight.id),
+            Some(old_setting.to_string()),
+            Some(enabled.to_string()),
+        );
+
+        Ok(())
+    }
+
+    // Real-time Simulation
+    /// Auto-refresh for the interactive menu: steps the simulation to the current time,
+    /// at most once every `SIMULATION_UPDATE_INTERVAL` seconds.
+    pub async fn update_simulation(&mut self) -> Result<SimulationReport, Box<dyn Error>> {
+        let now = Utc::now();
+        let elapsed = now.signed_duration_since(self.last_simulation_update).num_seconds();
+        if elapsed < crate::config::SIMULATION_UPDATE_INTERVAL as i64 {
+            return Ok(SimulationReport::default());
+        }
+
+        Ok(self.tick(now))
+    }
+
+    /// Run one simulation step as of `now`, with no throttling. Flight and aircraft
+    /// statuses move on to match that instant, so tests and demos can drive the
+    /// world forward (or replay it) without waiting on the wall clock.
+    pub fn tick(&mut self, now: DateTime<Utc>) -> SimulationReport {
+        let mut report = SimulationReport::default();
+
+        // A new UTC day starts a fresh revenue count
+        self.admin_panel.system_metrics.roll_revenue_day(now);
+
+        // Update flight statuses based on the given time
+        let mut departed = Vec::new();
+        let mut arrived = Vec::new();
+        for flight in &mut self.database.flights {
+            let time_to_departure = flight.departure_time.signed_duration_since(now);
+            let time_to_arrival = flight.arrival_time.signed_duration_since(now);
+            let previous = flight.status.clone();
+
+            let due = if time_to_arrival <= Duration::minutes(0) {
+                Some(FlightStatus::Arrived)
+            } else if time_to_departure <= Duration::minutes(0) {
+                Some(FlightStatus::Departed)
+            } else if time_to_departure <= Duration::minutes(crate::config::BOARDING_WINDOW_MINUTES) {
+                Some(FlightStatus::Boarding)
+            } else {
+                None
+            };
+
+            // A refused step (e.g. a cancelled flight) leaves the flight as it was
+            if let Some(due) = due {
+                if flight.advance_to(due).is_err() {
+                    continue;
+                }
+            }
+
+            if flight.status != previous {
+                let on_ground = !matches!(previous, FlightStatus::Departed | FlightStatus::Arrived);
+                if on_ground && matches!(flight.status, FlightStatus::Departed | FlightStatus::Arrived) {
+                    departed.push((flight.id, flight.flight_number.clone()));
+                }
+                if flight.status == FlightStatus::Arrived {
+                    arrived.push(flight.id);
+                }
+                match flight.status {
+                    FlightStatus::Boarding => report.notifications.push(format!(
+                        "{} to {} is now boarding{}",
+                        flight.flight_number,
+                        flight.destination,
+                        flight.gate.as_ref().map(|g| format!(" at gate {}", g)).unwrap_or_default()
+                    )),
+                    FlightStatus::Arrived => report.notifications.push(format!(
+                        "{} has arrived in {}", flight.flight_number, flight.destination
+                    )),
+                    _ => {}
+                }
+                report.flight_changes.push(FlightStatusChange {
+                    flight_number: flight.flight_number.clone(),
+                    from: previous,
+                    to: flight.status.clone(),
+                });
+            }
+        }
+
+        // Anyone not on board when the doors closed missed the flight
+        for (flight_id, flight_number) in departed {
+            let no_shows = self.database.bookings
+                .iter_mut()
+                .filter(|b| b.flight_id == flight_id)
+                .filter_map(|b| b.mark_no_show().ok())
+                .count() as u32;
+            if no_shows > 0 {
+                self.admin_panel.system_metrics.no_shows.insert(flight_number.clone(), no_shows);
+                report.notifications.push(format!("{} left with {} no-show(s)", flight_number, no_shows));
+            }
+        }
+
+        // Passengers who flew are done with their booking and earn its miles
+        for flight_id in arrived {
+            let flown: Vec<String> = self.database.bookings
+                .iter_mut()
+                .filter(|b| b.flight_id == flight_id && matches!(b.status, BookingStatus::Boarded))
+                .filter_map(|b| b.complete().ok().map(|_| b.ticket_number.clone()))
+                .collect();
+            for ticket_number in flown {
+                self.award_miles(&ticket_number);
+            }
+        }
+
+        // Update aircraft statuses based on flight status
+        for aircraft in &mut self.database.aircraft {
+            let has_active_flight = self.database.flights
+                .iter()
+                .any(|f| f.aircraft_id == aircraft.id && 
+                         matches!(f.status, FlightStatus::Boarding | FlightStatus::Departed));
+            let previous = aircraft.status.clone();
+
+            match aircraft.status {
+                AircraftStatus::Active if has_active_flight => {
+                    aircraft.status = AircraftStatus::InFlight;
+                }
+                AircraftStatus::InFlight if !has_active_flight => {
+                    aircraft.status = AircraftStatus::Active;
+                }
+                _ => {} // No automatic updates for maintenance or retired aircraft
+            }
+
+            if aircraft.status != previous {
+                report.aircraft_changes.push(AircraftStatusChange {
+                    registration: aircraft.registration.clone(),
+                    from: previous,
+                    to: aircraft.status.clone(),
+                });
+            }
+        }
+
+        if !report.is_empty() {
+            // Update system metrics
+            self.admin_panel.system_metrics.update_flight_metrics(&self.database.flights);
+            self.admin_panel.system_metrics.update_aircraft_metrics(&self.database.aircraft);
+        }
+        // Bookings change occupancy between status changes, so this is always refreshed
+        self.admin_panel.system_metrics.update_load_factor(&self.database.flights);
+        self.update_fuel_metrics();
+
+        self.last_simulation_update = now;
+        report
+    }
+
+    // Data Persistence Operations
+    pub async fn save_all_data(&self) -> Result<(), Box<dyn Error>> {
+        self.persistence.save_all_data(&self.database).await?;
+        self.persistence.save_loyalty_accounts(&self.loyalty).await?;
+        self.persistence.save_admin_credentials(&self.admin_panel.credentials).await?;
+        self.persistence.save_route_preferences(&self.admin_panel.route_preferences).await?;
+        let actions: Vec<AdminAction> = self.admin_panel.all_actions().cloned().collect();
+        self.persistence.save_audit_log(&actions).await?;
+        Ok(())
+    }
+
+    /// Create a backup archive, returning its path and compressed size in bytes
+    pub async fn create_backup(&self) -> Result<(String, u64), Box<dyn Error>> {
+        let backup_path = self.persistence.create_backup().await?;
+        let size = std::fs::metadata(&backup_path)?.len();
+        Ok((backup_path, size))
+    }
+
+    pub fn prune_backups(&self, keep: usize) -> Result<usize, Box<dyn Error>> {
+        self.persistence.prune_backups(keep)
+    }
+
+    pub fn list_backups(&self) -> Vec<String> {
+        self.persistence.list_backups()
+    }
+
+    /// Roll the live data back to the backup taken at `timestamp` and reload it (SuperAdmin
+    /// only). The admin session and this session's audit log are kept.
+    pub async fn restore_backup(&mut self, timestamp: &str) -> Result<(), Box<dyn Error>> {
+        let admin_id = match self.admin_panel.current_admin.as_ref() {
+            Some(admin) if matches!(admin.level, AdminLevel::SuperAdmin) => admin.id,
+            Some(_) => return Err("Only a SuperAdmin can restore a backup".into()),
+            None => return Err("Admin authentication required".into()),
+        };
+
+        self.persistence.restore_backup(timestamp).await?;
+
+        self.reload_database().await?;
+        self.loyalty = self.persistence.load_loyalty_accounts().await?;
+        let credentials = self.persistence.load_admin_credentials().await?;
+        if !credentials.is_empty() {
+            self.admin_panel.credentials = credentials;
+        }
+        let route_preferences = self.persistence.load_route_preferences().await?;
+        if !route_preferences.is_empty() {
+            self.admin_panel.route_preferences = route_preferences;
+        }
+
+        self.admin_panel.log_action(
+            admin_id,
+            "RESTORE_BACKUP".to_string(),
+            format!("Restored data from backup {}", timestamp),
+            None,
+            None,
+            Some(timestamp.to_string()),
+        );
+        self.messages.say(format!("♻️ Reloaded data from backup {}", timestamp));
+        Ok(())
+    }
+
+    /// Run the integrity repair against the current data. A dry run only lists the fixes;
+    /// otherwise they're applied on disk and the data reloaded. SuperAdmin only.
+    pub async fn repair_data_integrity(&mut self, dry_run: bool) -> Result<Vec<String>, Box<dyn Error>> {
+        let admin_id = match self.admin_panel.current_admin.as_ref() {
+            Some(admin) if matches!(admin.level, AdminLevel::SuperAdmin) => admin.id,
+            Some(_) => return Err("Only a SuperAdmin can repair data".into()),
+            None => return Err("Admin authentication required".into()),
+        };
+
+        // The repair works on the saved files, so make sure they match what's in memory
+        self.persistence.save_all_data(&self.database).await?;
+        let actions = self.persistence.repair_data_integrity(dry_run).await?;
+        if dry_run || actions.is_empty() {
+            return Ok(actions);
+        }
+
+        self.reload_database().await?;
+        self.admin_panel.log_action(
+            admin_id,
+            "REPAIR_DATA".to_string(),
+            format!("Applied {} data integrity repairs", actions.len()),
+            None,
+            None,
+            Some(actions.join("; ")),
+        );
+        Ok(actions)
+    }
+
+    /// Replace the in-memory data with what's on disk and refresh everything derived from it
+    async fn reload_database(&mut self) -> Result<(), Box<dyn Error>> {
+        self.database = self.persistence.load_all_data().await?;
+        self.rebuild_flight_index();
+        self.admin_panel.system_metrics.update_flight_metrics(&self.database.flights);
+        self.admin_panel.system_metrics.update_aircraft_metrics(&self.database.aircraft);
+        self.admin_panel.system_metrics.update_load_factor(&self.database.flights);
+        self.admin_panel.system_metrics.total_bookings = self.database.bookings.len() as u32;
+        self.update_fuel_metrics();
+        Ok(())
+    }
+
+    /// Export bookings and flights as timestamped CSV files in the data directory,
+    /// returning their paths
+    pub async fn export_reports_csv(&self) -> Result<Vec<String>, Box<dyn Error>> {
+        let timestamp = Utc::now().format("%Y%m%d_%H%M%S");
+        let data_dir = self.persistence.data_dir();
+        let bookings_path = format!("{}/bookings_{}.csv", data_dir, timestamp);
+        let flights_path = format!("{}/flights_{}.csv", data_dir, timestamp);
+
+        self.persistence.export_bookings_csv(&self.database.bookings, &bookings_path).await?;
+        self.persistence.export_flights_csv(&self.database.flights, &flights_path).await?;
+        Ok(vec![bookings_path, flights_path])
+    }
+
+    /// Write the whole system state to one timestamped JSON snapshot in the data
+    /// directory, returning its path
+    pub async fn export_snapshot(&self) -> Result<String, Box<dyn Error>> {
+        let path = format!("{}/snapshot_{}.json", self.persistence.data_dir(), Utc::now().format("%Y%m%d_%H%M%S"));
+        self.persistence
+            .export_snapshot(&self.database, &self.admin_panel.system_metrics, &self.admin_panel.pricing_rules, &path)
+            .await?;
+        Ok(path)
+    }
+
+    // Diagnostics
+    pub async fn self_check(&self) -> HealthReport {
+        let mut report = HealthReport::default();
+        let data_dir = self.persistence.data_dir();
+
+        // Where data is being kept
+        let storage = self.persistence.storage_status();
+        let storage_health = match storage.mode {
+            StorageMode::Primary => HealthStatus::Pass,
+            StorageMode::Fallback { .. } => HealthStatus::Warn,
+            StorageMode::InMemory { .. } => HealthStatus::Fail,
+        };
+        let storage_detail = match storage_health {
+            HealthStatus::Pass => storage.get_summary(),
+            _ => format!("{} - make {} writable and restart", storage.get_summary(), data_dir),
+        };
+        report.add(HealthCheck::new("Storage", storage_health, storage_detail));
+
+        // Whether saving would work right now
+        report.add(if self.persistence.data_dir_writable() {
+            HealthCheck::new("Data directory", HealthStatus::Pass, format!("{} is writable", data_dir))
+        } else {
+            HealthCheck::new(
+                "Data directory",
+                HealthStatus::Fail,
+                format!("Cannot write to {} - check its permissions and free disk space", data_dir),
+            )
+        });
+
+        // Data file accessibility
+        let mut unreadable = Vec::new();
+        let mut read_only = Vec::new();
+        let mut missing = Vec::new();
+        for file in &DATA_FILES {
+            let path = format!("{}/{}", data_dir, file);
+            match std::fs::metadata(&path) {
+                Ok(meta) => {
+                    if std::fs::File::open(&path).is_err() {
+                        unreadable.push(*file);
+                    } else if meta.permissions().readonly() {
+                        read_only.push(*file);
+                    }
+                }
+                Err(_) => missing.push(*file),
+            }
+        }
+        report.add(if !unreadable.is_empty() {
+            HealthCheck::new("Data files", HealthStatus::Fail, format!("Cannot read: {} - check their permissions", unreadable.join(", ")))
+        } else if !read_only.is_empty() {
+            HealthCheck::new("Data files", HealthStatus::Warn, format!("Read-only, changes won't be saved: {} - make them writable", read_only.join(", ")))
+        } else if !missing.is_empty() {
+            HealthCheck::new("Data files", HealthStatus::Warn, format!("Not created yet: {} - they are written on the next save", missing.join(", ")))
+        } else {
+            HealthCheck::new("Data files", HealthStatus::Pass, format!("All {} files readable in {}", DATA_FILES.len(), data_dir))
+        });
+
+        // Data files that would fail to load
+        let unparseable = self.persistence.unparseable_data_files();
+        report.add(match unparseable.first() {
+            None => HealthCheck::new("Data file contents", HealthStatus::Pass, "Airports, aircraft, flights and bookings parse".to_string()),
+            Some((file, error)) => HealthCheck::new(
+                "Data file contents",
+                HealthStatus::Fail,
+                format!("{} file(s) unreadable, first {}: {} - restore a backup from the admin panel", unparseable.len(), file, error),
+            ),
+        });
+
+        // Integrity issues in the stored data
+        report.add(match self.persistence.validate_data_integrity().await {
+            Ok(issues) if issues.is_empty() => {
+                HealthCheck::new("Data integrity", HealthStatus::Pass, "No issues found".to_string())
+            }
+            Ok(issues) => HealthCheck::new(
+                "Data integrity",
+                HealthStatus::Warn,
+                format!("{} issue(s), first: {} - run Repair Data Integrity from the admin panel", issues.len(), issues[0]),
+            ),
+            Err(e) => HealthCheck::new("Data integrity", HealthStatus::Fail, format!("Validation failed: {} - check the data files parse", e)),
+        });
+
+        // Flights that arrive before they depart
+        let impossible: Vec<&str> = self.database.flights
+            .iter()
+            .filter(|f| f.arrival_time <= f.departure_time)
+            .map(|f| f.flight_number.as_str())
+            .collect();
+        report.add(if impossible.is_empty() {
+            HealthCheck::new("Flight schedules", HealthStatus::Pass, format!("{} flights checked", self.database.flights.len()))
+        } else {
+            HealthCheck::new(
+                "Flight schedules",
+                HealthStatus::Fail,
+                format!("Arrival not after departure: {} - correct or cancel these flights", impossible.join(", ")),
+            )
+        });
+
+        // Something has to be able to fly
+        let active = self.database.aircraft.iter().filter(|a| matches!(a.status, AircraftStatus::Active)).count();
+        report.add(if active > 0 {
+            HealthCheck::new("Active aircraft", HealthStatus::Pass, format!("{} of {} aircraft active", active, self.database.aircraft.len()))
+        } else {
+            HealthCheck::new(
+                "Active aircraft",
+                HealthStatus::Fail,
+                "No aircraft in service - return one to Active from Aircraft Management".to_string(),
+            )
+        });
+
+        // Aircraft still flying past their maintenance interval
+        let overdue: Vec<&str> = self.database.aircraft
+            .iter()
+            .filter(|a| a.hours_until_maintenance() <= 0.0)
+            .filter(|a| !matches!(a.status, AircraftStatus::Maintenance | AircraftStatus::Retired | AircraftStatus::Grounded { .. }))
+            .map(|a| a.registration.as_str())
+            .collect();
+        report.add(if overdue.is_empty() {
+            HealthCheck::new("Aircraft maintenance", HealthStatus::Pass, "No aircraft overdue".to_string())
+        } else {
+            HealthCheck::new(
+                "Aircraft maintenance",
+                HealthStatus::Warn,
+                format!("Overdue for maintenance: {} - record maintenance from Aircraft Management", overdue.join(", ")),
+            )
+        });
+
+        // Bookings pointing at flights that no longer exist
+        let orphaned = self.database.bookings
+            .iter()
+            .filter(|b| self.get_flight_by_id(b.flight_id).is_none())
+            .count();
+        report.add(if orphaned == 0 {
+            HealthCheck::new("Orphaned bookings", HealthStatus::Pass, format!("{} bookings checked", self.database.bookings.len()))
+        } else {
+            HealthCheck::new(
+                "Orphaned bookings",
+                HealthStatus::Warn,
+                format!("{} booking(s) reference missing flights - run Repair Data Integrity from the admin panel", orphaned),
+            )
+        });
+
+        // Metrics the simulation should be keeping current
+        let metrics_age = Utc::now() - self.admin_panel.system_metrics.last_updated;
+        report.add(if metrics_age <= Duration::minutes(crate::config::METRICS_STALE_MINUTES) {
+            HealthCheck::new("System metrics", HealthStatus::Pass, format!("Updated {} ago", crate::utils::format_duration(metrics_age)))
+        } else {
+            HealthCheck::new(
+                "System metrics",
+                HealthStatus::Warn,
+                format!("Last updated {} ago - return to the main menu to run a simulation update", crate::utils::format_duration(metrics_age)),
+            )
+        });
+
+        // Backup storage: writable, and how much the existing backups use
+        let backup_dir = self.persistence.backup_dir();
+        let probe = format!("{}/.self_check", backup_dir);
+        let writable = std::fs::create_dir_all(&backup_dir).is_ok()
+            && std::fs::write(&probe, b"ok").is_ok()
+            && std::fs::remove_file(&probe).is_ok();
+        let (backup_count, backup_bytes) = std::fs::read_dir(&backup_dir)
+            .map(|entries| {
+                entries.flatten().fold((0, 0u64), |(count, bytes), entry| {
+                    let size = if entry.path().is_dir() {
+                        std::fs::read_dir(entry.path())
+                            .map(|files| files.flatten().filter_map(|f| f.metadata().ok()).map(|m| m.len()).sum())
+                            .unwrap_or(0)
+                    } else {
+                        entry.metadata().map(|m| m.len()).unwrap_or(0)
+                    };
+                    (count + 1, bytes + size)
+                })
+            })
+            .unwrap_or((0, 0));
+        report.add(if writable {
+            HealthCheck::new(
+                "Backup storage",
+                HealthStatus::Pass,
+                format!("{} writable, {} backup(s) using {:.1} KB", backup_dir, backup_count, backup_bytes as f64 / 1024.0),
+            )
+        } else {
+            HealthCheck::new("Backup storage", HealthStatus::Fail, format!("Cannot write to {} - check its permissions", backup_dir))
+        });
+
+        report
+    }
+
+    // Statistics and Reporting
+    pub fn get_system_metrics(&self) -> &SystemMetrics {
+        &self.admin_panel.system_metrics
     }
 
     pub fn get_flight_statistics(&self) -> (u32, u32, u32, u32) {
@@ -465,15 +3660,2286 @@ impl DataManager {
         (total, on_time, delayed, cancelled)
     }
 
-    pub fn get_booking_statistics(&self) -> (u32, u32, u32) {
-        let total = self.database.bookings.len() as u32;
-        let confirmed = self.database.bookings.iter()
-            .filter(|b| matches!(b.status, BookingStatus::Confirmed | BookingStatus::CheckedIn))
-            .count() as u32;
-        let cancelled = self.database.bookings.iter()
-            .filter(|b| matches!(b.status, BookingStatus::Cancelled))
-            .count() as u32;
-        
-        (total, confirmed, cancelled)
+    pub fn get_booking_statistics(&self) -> (u32, u32, u32) {
+        let total = self.database.bookings.len() as u32;
+        let confirmed = self.database.bookings.iter()
+            .filter(|b| matches!(b.status, BookingStatus::Confirmed | BookingStatus::CheckedIn))
+            .count() as u32;
+        let cancelled = self.database.bookings.iter()
+            .filter(|b| matches!(b.status, BookingStatus::Cancelled))
+            .count() as u32;
+        
+        (total, confirmed, cancelled)
+    }
+
+    /// Revenue and booking count per "ORIGIN-DEST" route, highest revenue first. Only
+    /// bookings still held or flown count, net of any partial refunds.
+    pub fn revenue_by_route(&self) -> Vec<(String, f64, u32)> {
+        let mut routes: Vec<(String, f64, u32)> = Vec::new();
+        for booking in &self.database.bookings {
+            if !matches!(
+                booking.status,
+                BookingStatus::Confirmed | BookingStatus::CheckedIn | BookingStatus::Boarded | BookingStatus::Completed
+            ) {
+                continue;
+            }
+            let Some(flight) = self.get_flight_by_id(booking.flight_id) else { continue };
+
+            let route = format!("{}-{}", flight.origin, flight.destination);
+            let revenue = booking.payment.total_amount - booking.payment.refunded_amount;
+            match routes.iter_mut().find(|(r, _, _)| *r == route) {
+                Some((_, total, count)) => {
+                    *total += revenue;
+                    *count += 1;
+                }
+                None => routes.push((route, revenue, 1)),
+            }
+        }
+
+        routes.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        routes
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::chaos::{FailureInjector, FailureMode};
+
+    fn test_passenger() -> Passenger {
+        test_passenger_of(PassengerType::Adult)
+    }
+
+    fn test_passenger_of(passenger_type: PassengerType) -> Passenger {
+        Passenger::new(
+            "Grace".to_string(),
+            "Hopper".to_string(),
+            "grace@example.com".to_string(),
+            "5559876543".to_string(),
+            "1985-12-09".to_string(),
+            passenger_type,
+        )
+    }
+
+    fn test_airports() -> Vec<Airport> {
+        vec![
+            Airport::new(
+                "LAX".to_string(),
+                "KLAX".to_string(),
+                "Los Angeles International Airport".to_string(),
+                "Los Angeles".to_string(),
+                "United States".to_string(),
+                "America/Los_Angeles".to_string(),
+                33.9425, -118.4081, 38,
+            ),
+            Airport::new(
+                "JFK".to_string(),
+                "KJFK".to_string(),
+                "John F. Kennedy International Airport".to_string(),
+                "New York".to_string(),
+                "United States".to_string(),
+                "America/New_York".to_string(),
+                40.6413, -73.7781, 4,
+            ),
+        ]
+    }
+
+    /// Tomorrow at `hour` UTC, so scheduling tests don't depend on airport opening hours at run time
+    fn tomorrow_at(hour: u32) -> DateTime<Utc> {
+        (Utc::now() + Duration::days(1)).date_naive().and_hms_opt(hour, 0, 0).unwrap().and_utc()
+    }
+
+    fn test_manager() -> DataManager {
+        test_manager_with(DataPersistence::for_tests("target/never-written"))
+    }
+
+    fn test_manager_with(persistence: DataPersistence) -> DataManager {
+        let aircraft = Aircraft::new("N1TEST".to_string(), "Boeing 737-800".to_string(), "Boeing".to_string(), 2020);
+        let departure = Utc::now() + Duration::hours(6);
+        let flight = Flight::new(
+            "RIA900".to_string(),
+            "Rust International Airways".to_string(),
+            "LAX".to_string(),
+            "JFK".to_string(),
+            departure,
+            departure + Duration::hours(5),
+            &aircraft,
+        );
+
+        let flights = vec![flight];
+        DataManager {
+            flight_index: FlightIndex::build(&flights),
+            database: AirportDatabase {
+                flights,
+                aircraft: vec![aircraft],
+                bookings: Vec::new(),
+                airports: test_airports(),
+            },
+            persistence,
+            admin_panel: AdminPanel::new(),
+            loyalty: Vec::new(),
+            currency: CurrencyConverter::new(),
+            display_currency: crate::config::currency::BASE_CURRENCY.to_string(),
+            messages: MessageSink::Quiet,
+            last_simulation_update: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_search_flights_by_aircraft_model() {
+        let mut manager = test_manager();
+        let a380 = Aircraft::new("N380RA".to_string(), "Airbus A380".to_string(), "Airbus".to_string(), 2015);
+        let mut flight = manager.database.flights[0].clone();
+        flight.id = Uuid::new_v4();
+        flight.flight_number = "RIA380".to_string();
+        flight.aircraft_id = a380.id;
+        manager.database.aircraft.push(a380);
+        manager.database.flights.push(flight);
+
+        let numbers = |flights: Vec<&Flight>| flights.iter().map(|f| f.flight_number.clone()).collect::<Vec<_>>();
+        assert_eq!(numbers(manager.search_flights_by_aircraft_model("a380")), vec!["RIA380"]);
+        assert_eq!(numbers(manager.search_flights_by_aircraft_model("737")), vec!["RIA900"]);
+        assert_eq!(numbers(manager.search_flights_by_aircraft_model("airbus")), vec!["RIA380"]);
+
+        // Known model with nothing scheduled, unknown model, and a blank query
+        manager.database.aircraft.push(Aircraft::new("N777RA".to_string(), "Boeing 777-300".to_string(), "Boeing".to_string(), 2018));
+        assert!(manager.search_flights_by_aircraft_model("777").is_empty());
+        assert!(manager.search_flights_by_aircraft_model("Concorde").is_empty());
+        assert!(manager.search_flights_by_aircraft_model("  ").is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_update_simulation_reports_status_changes() {
+        let mut manager = test_manager();
+        manager.last_simulation_update = Utc::now() - Duration::minutes(2);
+        manager.database.flights[0].departure_time = Utc::now() + Duration::minutes(10);
+        manager.database.flights[0].set_gate("B7".to_string());
+
+        let report = manager.update_simulation().await.unwrap();
+
+        assert_eq!(report.flight_changes, vec![FlightStatusChange {
+            flight_number: "RIA900".to_string(),
+            from: FlightStatus::OnTime,
+            to: FlightStatus::Boarding,
+        }]);
+        assert_eq!(report.aircraft_changes, vec![AircraftStatusChange {
+            registration: "N1TEST".to_string(),
+            from: AircraftStatus::Active,
+            to: AircraftStatus::InFlight,
+        }]);
+        assert_eq!(report.notifications, vec!["RIA900 to JFK is now boarding at gate B7"]);
+
+        // Too soon for another update, and nothing would change anyway
+        assert!(manager.update_simulation().await.unwrap().is_empty());
+        manager.last_simulation_update = Utc::now() - Duration::minutes(2);
+        assert!(manager.update_simulation().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_tick_advances_to_given_instant_without_throttle() {
+        let mut manager = test_manager();
+        let departure = manager.database.flights[0].departure_time;
+        let arrival = manager.database.flights[0].arrival_time;
+
+        // The auto-refresh path is throttled straight after start-up
+        assert!(manager.update_simulation().await.unwrap().is_empty());
+
+        let report = manager.tick(departure - Duration::minutes(5));
+        assert_eq!(manager.database.flights[0].status, FlightStatus::Boarding);
+        assert_eq!(report.flight_changes.len(), 1);
+
+        manager.tick(departure + Duration::minutes(1));
+        assert_eq!(manager.database.flights[0].status, FlightStatus::Departed);
+        assert_eq!(manager.database.aircraft[0].status, AircraftStatus::InFlight);
+
+        let report = manager.tick(arrival);
+        assert_eq!(manager.database.flights[0].status, FlightStatus::Arrived);
+        assert_eq!(manager.database.aircraft[0].status, AircraftStatus::Active);
+        assert_eq!(report.notifications, vec!["RIA900 has arrived in JFK"]);
+    }
+
+    #[test]
+    fn test_suggest_alternative_class_when_economy_full() {
+        let mut manager = test_manager();
+        manager.database.flights[0].seat_availability.economy = 0;
+        let flight_id = manager.database.flights[0].id;
+
+        let alternatives = manager.suggest_alternative_class(flight_id, &SeatClass::Economy).unwrap();
+
+        match &alternatives[0] {
+            ClassAlternative::Class { class, price_difference, .. } => {
+                assert_eq!(*class, SeatClass::Business);
+                assert!(*price_difference > 0.0);
+            }
+            ClassAlternative::Waitlist => panic!("expected a class alternative"),
+        }
+        assert_eq!(alternatives.len(), 2);
+    }
+
+    #[test]
+    fn test_suggest_alternative_class_offers_only_waitlist_when_full() {
+        let mut manager = test_manager();
+        let flight = &mut manager.database.flights[0];
+        flight.seat_availability.economy = 0;
+        flight.seat_availability.business = 0;
+        flight.seat_availability.first_class = 0;
+        let flight_id = flight.id;
+
+        let alternatives = manager.suggest_alternative_class(flight_id, &SeatClass::Business).unwrap();
+
+        assert_eq!(alternatives, vec![ClassAlternative::Waitlist]);
+    }
+
+    #[test]
+    fn test_suggest_alternative_class_offers_downgrade_when_first_full() {
+        let mut manager = test_manager();
+        manager.database.flights[0].seat_availability.first_class = 0;
+        let flight = &manager.database.flights[0];
+        let business_fare = manager.get_fare(flight, &SeatClass::Business);
+
+        let alternatives = manager.suggest_alternative_class(flight.id, &SeatClass::FirstClass).unwrap();
+
+        match &alternatives[0] {
+            ClassAlternative::Class { class, price, price_difference, .. } => {
+                assert_eq!(*class, SeatClass::Business);
+                assert_eq!(*price, business_fare);
+                assert!(*price_difference < 0.0, "a downgrade should be cheaper");
+            }
+            ClassAlternative::Waitlist => panic!("expected a class alternative"),
+        }
+    }
+
+    #[test]
+    fn test_suggest_downgrade_picks_the_nearest_open_lower_class() {
+        let mut manager = test_manager();
+        let flight_id = manager.database.flights[0].id;
+        assert_eq!(manager.suggest_downgrade(flight_id, &SeatClass::FirstClass), None, "first class still has seats");
+
+        manager.database.flights[0].seat_availability.first_class = 0;
+        let fare = |manager: &DataManager, class| manager.get_fare(&manager.database.flights[0], &class);
+        assert_eq!(
+            manager.suggest_downgrade(flight_id, &SeatClass::FirstClass),
+            Some((SeatClass::Business, fare(&manager, SeatClass::Business)))
+        );
+
+        manager.database.flights[0].seat_availability.business = 0;
+        assert_eq!(
+            manager.suggest_downgrade(flight_id, &SeatClass::FirstClass),
+            Some((SeatClass::Economy, fare(&manager, SeatClass::Economy)))
+        );
+        // An upgrade is never offered as a downgrade
+        manager.database.flights[0].seat_availability.first_class = 10;
+        manager.database.flights[0].seat_availability.economy = 0;
+        assert_eq!(manager.suggest_downgrade(flight_id, &SeatClass::Economy), None);
+    }
+
+    #[test]
+    fn test_create_flight_rejects_duplicate_number_with_suggestion() {
+        let mut manager = test_manager();
+        manager.authenticate_admin("admin", "admin123").unwrap();
+        let aircraft_id = manager.database.aircraft[0].id;
+        let departure = tomorrow_at(17);
+
+        let result = manager.create_flight(
+            "RIA900".to_string(),
+            "Rust International Airways".to_string(),
+            "JFK".to_string(),
+            "LAX".to_string(),
+            departure,
+            departure + Duration::hours(6),
+            aircraft_id,
+        );
+
+        let error = result.unwrap_err().to_string();
+        assert!(error.contains("already exists"));
+        assert!(error.contains("RIA901"));
+    }
+
+    #[test]
+    fn test_create_flight_validates_number_and_times() {
+        let mut manager = test_manager();
+        manager.authenticate_admin("admin", "admin123").unwrap();
+        let aircraft_id = manager.database.aircraft[0].id;
+        let departure = tomorrow_at(17);
+
+        let malformed = manager.create_flight(
+            "901".to_string(),
+            "Rust International Airways".to_string(),
+            "JFK".to_string(),
+            "LAX".to_string(),
+            departure,
+            departure + Duration::hours(6),
+            aircraft_id,
+        );
+        assert!(malformed.unwrap_err().to_string().contains("Invalid flight number"));
+
+        let inverted = manager.create_flight(
+            "RIA902".to_string(),
+            "Rust International Airways".to_string(),
+            "JFK".to_string(),
+            "LAX".to_string(),
+            departure,
+            departure - Duration::hours(1),
+            aircraft_id,
+        );
+        assert!(inverted.unwrap_err().to_string().contains("after departure"));
+
+        let flight_id = manager.create_flight(
+            "RIA902".to_string(),
+            "Rust International Airways".to_string(),
+            "JFK".to_string(),
+            "LAX".to_string(),
+            departure,
+            departure + Duration::hours(6),
+            aircraft_id,
+        ).unwrap();
+        assert_eq!(manager.get_flight_by_number("RIA902").map(|f| f.id), Some(flight_id));
+    }
+
+    #[test]
+    fn test_create_flight_respects_airport_operating_hours() {
+        let mut manager = test_manager();
+        manager.authenticate_admin("admin", "admin123").unwrap();
+        let aircraft_id = manager.database.aircraft[0].id;
+        let create = |manager: &mut DataManager, number: &str, departure: DateTime<Utc>| manager.create_flight(
+            number.to_string(),
+            "Rust International Airways".to_string(),
+            "JFK".to_string(),
+            "LAX".to_string(),
+            departure,
+            departure + Duration::hours(6),
+            aircraft_id,
+        );
+
+        // 07:00 UTC is 02:00 or 03:00 in New York
+        let error = create(&mut manager, "RIA910", tomorrow_at(7)).unwrap_err();
+        assert!(
+            matches!(&error, AirportError::ValidationError { message } if message.starts_with("Departure not possible - JFK is closed at 0")),
+            "{:?}", error
+        );
+
+        // With New York open all night, the 10:00 UTC arrival is still the middle of the night in Los Angeles
+        manager.database.airports[1].operating_hours = (0, 23);
+        let error = create(&mut manager, "RIA911", tomorrow_at(4)).unwrap_err();
+        assert!(
+            matches!(&error, AirportError::ValidationError { message } if message.starts_with("Arrival not possible - LAX is closed")),
+            "{:?}", error
+        );
+
+        manager.database.airports[0].operating_hours = (0, 23);
+        assert!(create(&mut manager, "RIA912", tomorrow_at(4)).is_ok());
+    }
+
+    #[test]
+    fn test_migrate_distance_fares_prices_longer_routes_higher() {
+        let mut manager = test_manager();
+        manager.database.airports.push(Airport::new(
+            "BOS".to_string(),
+            "KBOS".to_string(),
+            "Boston Logan International Airport".to_string(),
+            "Boston".to_string(),
+            "United States".to_string(),
+            "America/New_York".to_string(),
+            42.3656, -71.0096, 6,
+        ));
+        let mut shuttle = manager.database.flights[0].clone();
+        shuttle.id = Uuid::new_v4();
+        shuttle.flight_number = "RIA950".to_string();
+        shuttle.origin = "JFK".to_string();
+        shuttle.destination = "BOS".to_string();
+        manager.database.flights.push(shuttle);
+
+        assert_eq!(manager.migrate_distance_fares(), 2);
+        assert_eq!(manager.migrate_distance_fares(), 0);
+
+        let cross_country = manager.get_flight_by_number("RIA900").unwrap();
+        let shuttle = manager.get_flight_by_number("RIA950").unwrap();
+        for class in [SeatClass::Economy, SeatClass::Business, SeatClass::FirstClass] {
+            assert!(cross_country.get_price(&class) > shuttle.get_price(&class) * 2.0);
+        }
+        // About 3,980 km, so close to the old flat economy fare
+        assert!((cross_country.pricing.economy - 299.0).abs() < 5.0, "{}", cross_country.pricing.economy);
+    }
+
+    #[test]
+    fn test_lap_infant_does_not_take_a_seat() {
+        let mut manager = test_manager();
+        let flight_id = manager.database.flights[0].id;
+        let seats_before = manager.database.flights[0].get_available_seats(&SeatClass::Economy);
+
+        let ticket = {
+            let booking_id = manager.create_booking(flight_id, test_passenger_of(PassengerType::Infant), SeatClass::Economy).unwrap();
+            manager.get_booking_by_id(booking_id).unwrap().ticket_number.clone()
+        };
+        assert_eq!(manager.database.flights[0].get_available_seats(&SeatClass::Economy), seats_before);
+
+        manager.cancel_booking(&ticket).unwrap();
+        assert_eq!(manager.database.flights[0].get_available_seats(&SeatClass::Economy), seats_before);
+    }
+
+    #[test]
+    fn test_discounted_fares_are_charged_and_tracked() {
+        let mut manager = test_manager();
+        let flight_id = manager.database.flights[0].id;
+        let adult_fare = manager.get_fare(&manager.database.flights[0], &SeatClass::Economy);
+
+        let booking_id = manager.create_booking(flight_id, test_passenger_of(PassengerType::Child), SeatClass::Economy).unwrap();
+
+        let charged = manager.get_booking_by_id(booking_id).unwrap().payment.total_amount;
+        assert!((charged - adult_fare * 0.75).abs() < 1e-9);
+        assert!((manager.admin_panel.system_metrics.revenue_today - charged).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn test_failed_save_keeps_in_memory_state() {
+        let persistence = DataPersistence::for_tests("target/never-written")
+            .with_failure_injection(FailureInjector::always(FailureMode::DiskFull));
+        let mut manager = test_manager_with(persistence);
+
+        let flight_id = manager.database.flights[0].id;
+        let booking_id = manager.create_booking(flight_id, test_passenger(), SeatClass::Economy).unwrap();
+        let seats_after_booking = manager.database.flights[0].get_available_seats(&SeatClass::Economy);
+
+        let result = manager.save_all_data().await;
+
+        assert!(result.is_err());
+        assert_eq!(manager.database.bookings.len(), 1);
+        assert!(manager.get_booking_by_id(booking_id).is_some());
+        assert_eq!(manager.database.flights[0].get_available_seats(&SeatClass::Economy), seats_after_booking);
+    }
+
+    #[tokio::test]
+    async fn test_self_check_flags_broken_schedule_and_overdue_aircraft() {
+        let dir = std::env::temp_dir().join(format!("ria-self-check-{}", Uuid::new_v4()));
+        let mut manager = test_manager_with(DataPersistence::for_tests(dir.to_str().unwrap()));
+
+        let flight = &mut manager.database.flights[0];
+        flight.arrival_time = flight.departure_time - Duration::hours(1);
+        manager.database.aircraft[0].flight_hours = 150.0;
+        manager.database.aircraft[0].maintenance_hours = 0.0;
+
+        let report = manager.self_check().await;
+        let status_of = |name: &str| report.checks.iter().find(|c| c.name == name).unwrap().status.clone();
+
+        assert!(report.has_failures());
+        assert_eq!(status_of("Flight schedules"), HealthStatus::Fail);
+        assert_eq!(status_of("Aircraft maintenance"), HealthStatus::Warn);
+        assert_eq!(status_of("Backup storage"), HealthStatus::Pass);
+        assert_eq!(status_of("Data directory"), HealthStatus::Pass);
+        assert_eq!(status_of("Active aircraft"), HealthStatus::Pass);
+        assert_eq!(status_of("System metrics"), HealthStatus::Pass);
+
+        // A corrupt data file, no aircraft in service and metrics left to go stale
+        std::fs::write(dir.join("flights.json"), "{ not json").unwrap();
+        manager.database.aircraft[0].set_status(AircraftStatus::Maintenance);
+        manager.admin_panel.system_metrics.last_updated = Utc::now() - Duration::hours(2);
+        let report = manager.self_check().await;
+        let check = |name: &str| report.checks.iter().find(|c| c.name == name).unwrap().clone();
+
+        let contents = check("Data file contents");
+        assert_eq!(contents.status, HealthStatus::Fail);
+        assert!(contents.detail.contains("flights.json") && contents.detail.contains("restore a backup"));
+        assert_eq!(check("Active aircraft").status, HealthStatus::Fail);
+        assert_eq!(check("System metrics").status, HealthStatus::Warn);
+        // Every problem comes with a hint on what to do about it
+        for check in report.checks.iter().filter(|c| c.status != HealthStatus::Pass) {
+            assert!(check.detail.contains(" - "), "no hint for {}: {}", check.name, check.detail);
+        }
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn test_cancel_flight_refunds_bookings_and_is_idempotent() {
+        let mut manager = test_manager();
+        manager.authenticate_admin("admin", "admin123").unwrap();
+        let flight_id = manager.database.flights[0].id;
+        let adult = manager.create_booking(flight_id, test_passenger(), SeatClass::Business).unwrap();
+        let child = manager.create_booking(flight_id, test_passenger_of(PassengerType::Child), SeatClass::Economy).unwrap();
+        let paid: f64 = manager.database.bookings.iter().map(|b| b.payment.total_amount).sum();
+
+        let summary = manager.cancel_flight("RIA900", "Crew shortage".to_string()).unwrap();
+
+        assert!(!summary.already_cancelled);
+        assert_eq!(summary.bookings_cancelled, 2);
+        assert_eq!(summary.notifications.len(), 2);
+        assert!((summary.total_refunded - paid).abs() < 1e-9);
+        for id in [adult, child] {
+            let booking = manager.get_booking_by_id(id).unwrap();
+            assert!(matches!(booking.status, BookingStatus::Cancelled));
+            assert_eq!(booking.payment.refunded_amount, booking.payment.total_amount);
+        }
+
+        let flight = &manager.database.flights[0];
+        let aircraft = &manager.database.aircraft[0];
+        assert!(matches!(flight.status, FlightStatus::Cancelled));
+        assert_eq!(flight.seat_availability.economy, aircraft.get_seats_by_class(&SeatClass::Economy));
+        assert_eq!(flight.seat_availability.business, aircraft.get_seats_by_class(&SeatClass::Business));
+
+        let again = manager.cancel_flight("RIA900", "Crew shortage".to_string()).unwrap();
+        assert!(again.already_cancelled);
+        assert_eq!(again.bookings_cancelled, 0);
+        let logged: Vec<_> = manager.admin_panel.get_recent_actions(10).into_iter().filter(|a| a.action_type == "CANCEL_FLIGHT").collect();
+        assert_eq!(logged.len(), 1);
+        assert!(logged[0].description.contains("Crew shortage"));
+        assert!(summary.notifications[0].contains("(Crew shortage)"));
+        assert_eq!(manager.admin_panel.system_metrics.total_flights, 1);
+        assert_eq!(manager.admin_panel.system_metrics.cancelled_flights, 1);
+    }
+
+    #[test]
+    fn test_cancel_flight_refunds_do_not_make_revenue_negative() {
+        let mut manager = test_manager();
+        manager.authenticate_admin("admin", "admin123").unwrap();
+        let flight_id = manager.database.flights[0].id;
+        manager.create_booking(flight_id, test_passenger(), SeatClass::Business).unwrap();
+        // The bookings were paid for on an earlier accounting day
+        manager.admin_panel.system_metrics.revenue_today = 0.0;
+
+        let summary = manager.cancel_flight("RIA900", "Crew shortage".to_string()).unwrap();
+        assert!(summary.total_refunded > 0.0);
+        assert_eq!(manager.admin_panel.system_metrics.revenue_today, 0.0);
+    }
+
+    #[test]
+    fn test_preview_cancel_flight_changes_nothing_and_matches_outcome() {
+        let mut manager = test_manager();
+        assert!(manager.preview_cancel_flight("RIA900", "Crew shortage").is_err());
+        manager.authenticate_admin("admin", "admin123").unwrap();
+        assert!(manager.preview_cancel_flight("RIA900", "  ").is_err());
+        let flight_id = manager.database.flights[0].id;
+        manager.create_booking(flight_id, test_passenger(), SeatClass::Economy).unwrap();
+        let seats_before = manager.database.flights[0].get_available_seats(&SeatClass::Economy);
+
+        let preview = manager.preview_cancel_flight("RIA900", "Crew shortage").unwrap();
+
+        assert_eq!(preview.bookings_cancelled, 1);
+        assert!(matches!(manager.database.flights[0].status, FlightStatus::OnTime));
+        assert!(matches!(manager.database.bookings[0].status, BookingStatus::Confirmed));
+        assert_eq!(manager.database.flights[0].get_available_seats(&SeatClass::Economy), seats_before);
+        assert!(manager.admin_panel.get_recent_actions(10).iter().all(|a| a.action_type != "CANCEL_FLIGHT"));
+
+        let outcome = manager.cancel_flight("RIA900", "Crew shortage".to_string()).unwrap();
+        assert_eq!(outcome.notifications, preview.notifications);
+        assert_eq!(outcome.total_refunded, preview.total_refunded);
+    }
+
+    #[test]
+    fn test_special_services_report_groups_and_tracks_fulfillment() {
+        let mut manager = test_manager();
+        manager.authenticate_admin("admin", "admin123").unwrap();
+        let flight_id = manager.database.flights[0].id;
+
+        let mut needs = Vec::new();
+        for requirements in [vec!["Wheelchair assistance", "Vegetarian meal"], vec!["Wheelchair"], vec!["vegetarian meal"]] {
+            let mut passenger = test_passenger();
+            for requirement in requirements {
+                passenger.add_special_requirement(requirement.to_string());
+            }
+            let booking_id = manager.create_booking(flight_id, passenger, SeatClass::Economy).unwrap();
+            needs.push(manager.get_booking_by_id(booking_id).unwrap().ticket_number.clone());
+        }
+
+        let report = manager.special_services_report("RIA900").unwrap();
+        assert_eq!(report.get_summary(), "2 vegetarian, 2 wheelchair");
+        assert_eq!(report.unfulfilled_critical().len(), 2);
+
+        manager.mark_requirement_fulfilled(&needs[1], "wheelchair").unwrap();
+        assert!(manager.mark_requirement_fulfilled(&needs[1], "Kosher meal").is_err());
+
+        let report = manager.special_services_report("RIA900").unwrap();
+        let remaining = report.unfulfilled_critical();
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].ticket_number, needs[0]);
+    }
+
+    #[test]
+    fn test_flexible_dates_picks_cheapest_bookable_flight_per_day() {
+        let mut manager = test_manager();
+        let template = manager.database.flights[0].clone();
+        let day = template.departure_time;
+
+        // A cheaper flight the same day that is sold out, and a pricier one two days later
+        let mut sold_out = template.clone();
+        sold_out.id = Uuid::new_v4();
+        sold_out.flight_number = "RIA901".to_string();
+        sold_out.pricing.economy = 99.0;
+        sold_out.seat_availability.economy = 0;
+        let mut later = template.clone();
+        later.id = Uuid::new_v4();
+        later.flight_number = "RIA902".to_string();
+        later.departure_time = day + Duration::days(2);
+        later.arrival_time = later.departure_time + Duration::hours(5);
+        later.pricing.economy = 450.0;
+        manager.database.flights.extend([sold_out, later]);
+
+        let results = manager.search_flexible_dates("LAX", "JFK", day, 3, &SeatClass::Economy);
+
+        assert_eq!(results.len(), 7);
+        assert!(results.windows(2).all(|w| w[0].date < w[1].date));
+        let on = |offset: i64| &results[(3 + offset) as usize];
+        let expected_fare = manager.get_fare(&template, &SeatClass::Economy);
+        assert_eq!(on(0).cheapest.as_ref().map(|(n, _)| n.as_str()), Some("RIA900"));
+        assert!((on(0).cheapest.as_ref().unwrap().1 - expected_fare).abs() < 1e-9);
+        assert_eq!(on(2).cheapest.as_ref().map(|(n, _)| n.as_str()), Some("RIA902"));
+        assert_eq!(on(1).cheapest, None);
+    }
+
+    #[test]
+    fn test_catering_counts_exclude_cancelled_and_lap_infants() {
+        let mut manager = test_manager();
+        let flight_id = manager.database.flights[0].id;
+
+        let mut vegetarian = test_passenger();
+        vegetarian.add_special_requirement("Vegetarian meal".to_string());
+        vegetarian.add_special_requirement("Wheelchair assistance".to_string());
+        manager.create_booking(flight_id, vegetarian, SeatClass::Economy).unwrap();
+        manager.create_booking(flight_id, test_passenger(), SeatClass::Business).unwrap();
+        manager.create_booking(flight_id, test_passenger_of(PassengerType::Infant), SeatClass::Economy).unwrap();
+        let cancelled = manager.create_booking(flight_id, test_passenger(), SeatClass::Economy).unwrap();
+        let ticket = manager.get_booking_by_id(cancelled).unwrap().ticket_number.clone();
+        manager.cancel_booking(&ticket).unwrap();
+
+        let catering = manager.catering_requirements("RIA900").unwrap();
+        assert!(catering.meal_service);
+        assert_eq!((catering.economy_meals, catering.business_meals, catering.first_class_meals), (1, 1, 0));
+        assert_eq!(catering.special_meals, vec![("vegetarian".to_string(), 1)]);
+
+        manager.authenticate_admin("admin", "admin123").unwrap();
+        manager.set_meal_service("RIA900", Some(false)).unwrap();
+        let catering = manager.catering_requirements("RIA900").unwrap();
+        assert!(!catering.meal_service);
+        assert_eq!(catering.total_meals(), 0);
+    }
+
+    #[test]
+    fn test_price_breakdown_components_multiply_to_total() {
+        let mut manager = test_manager();
+        let admin_id = Uuid::new_v4();
+        manager.admin_panel.pricing_rules.push(PricingRule::new("Route surcharge".to_string(), Some("LAX-JFK".to_string()), None, 1.2, admin_id));
+        manager.admin_panel.pricing_rules.push(PricingRule::new("Promo".to_string(), None, None, 0.9, admin_id));
+        manager.database.flights[0].pricing.dynamic_multiplier = 1.5;
+        let flight_id = manager.database.flights[0].id;
+
+        let breakdown = manager.price_breakdown(flight_id, &SeatClass::Business, &PassengerType::Child).unwrap();
+
+        let labels: Vec<&str> = breakdown.components.iter().map(|c| c.label.as_str()).collect();
+        assert_eq!(labels, ["Base fare (Business)", "Dynamic pricing", "Pricing rule: Route surcharge", "Pricing rule: Promo", "Child fare"]);
+
+        let base = breakdown.components[0].running_total;
+        let product = breakdown.components.iter().filter_map(|c| c.multiplier).fold(base, |acc, m| acc * m);
+        assert!((product - breakdown.total).abs() < 1e-9);
+        assert_eq!(breakdown.components.last().unwrap().running_total, breakdown.total);
+        assert!((base * 1.5 * 1.2 * 0.9 * 0.75 - breakdown.total).abs() < 1e-9);
+
+        let flight = &manager.database.flights[0];
+        assert_eq!(manager.get_passenger_fare(flight, &SeatClass::Business, &PassengerType::Child), breakdown.total);
+    }
+
+    #[test]
+    fn test_price_breakdown_itemizes_demand_pricing() {
+        let mut manager = test_manager();
+        manager.database.flights[0].seat_availability.business = 1;
+        let flight_id = manager.database.flights[0].id;
+
+        let breakdown = manager.price_breakdown(flight_id, &SeatClass::Business, &PassengerType::Adult).unwrap();
+        let demand = &breakdown.components[1];
+        assert!(demand.label.starts_with("Demand ("));
+        assert_eq!(demand.multiplier, Some(manager.database.flights[0].demand_multiplier(&SeatClass::Business)));
+
+        manager.authenticate_admin("admin", "admin123").unwrap();
+        manager.set_demand_pricing("RIA900", false).unwrap();
+        let breakdown = manager.price_breakdown(flight_id, &SeatClass::Business, &PassengerType::Adult).unwrap();
+        assert_eq!(breakdown.components.len(), 1);
+    }
+
+    #[test]
+    fn test_manual_multiplier_overrides_demand_pricing_even_at_one() {
+        let mut manager = test_manager();
+        manager.authenticate_admin("admin", "admin123").unwrap();
+        manager.database.flights[0].seat_availability.business = 1;
+        let flight_id = manager.database.flights[0].id;
+        let base = manager.database.flights[0].pricing.business;
+        let fare = |manager: &DataManager| manager.get_flight_by_id(flight_id).unwrap().get_price(&SeatClass::Business);
+        assert!(fare(&manager) > base);
+
+        // Setting 1.0 on purpose keeps the plain fare rather than handing back to demand
+        manager.set_dynamic_pricing("RIA900", 1.0).unwrap();
+        assert!((fare(&manager) - base).abs() < 1e-9);
+        manager.set_dynamic_pricing("RIA900", 1.2).unwrap();
+        assert!((fare(&manager) - base * 1.2).abs() < 1e-9);
+
+        // Switching demand pricing back on drops the manual multiplier
+        manager.set_demand_pricing("RIA900", true).unwrap();
+        assert!(!manager.database.flights[0].pricing.manual_override);
+        assert_eq!(manager.database.flights[0].pricing.dynamic_multiplier, 1.0);
+        assert!(fare(&manager) > base);
+    }
+
+    #[test]
+    fn test_set_flight_delay_enforces_policy_bounds() {
+        use crate::config::delays::{MAX_DELAY_MINUTES, MIN_DELAY_MINUTES};
+
+        let mut manager = test_manager();
+        assert!(matches!(
+            manager.set_flight_delay("RIA900", 30),
+            Err(AirportError::InsufficientPermissions { .. })
+        ));
+
+        manager.authenticate_admin("admin", "admin123").unwrap();
+        assert!(manager.set_flight_delay("RIA900", MIN_DELAY_MINUTES).is_ok());
+        assert!(manager.set_flight_delay("RIA900", MAX_DELAY_MINUTES).is_ok());
+        assert!(matches!(manager.database.flights[0].status, FlightStatus::Delayed(MAX_DELAY_MINUTES)));
+
+        for minutes in [MIN_DELAY_MINUTES - 1, MAX_DELAY_MINUTES + 1] {
+            assert!(matches!(
+                manager.set_flight_delay("RIA900", minutes),
+                Err(AirportError::DelayOutOfRange { minutes: m, .. }) if m == minutes
+            ));
+        }
+        assert!(matches!(
+            manager.set_flight_delay("RIA999", 10),
+            Err(AirportError::FlightNumberNotFound { .. })
+        ));
+
+        // Another hour on top of the maximum cancels the flight and records why
+        manager.set_flight_delay("RIA900", 60).unwrap();
+        assert_eq!(manager.database.flights[0].status, FlightStatus::Cancelled);
+        assert_eq!(manager.admin_panel.get_recent_actions(1)[0].action_type, "AUTO_CANCEL_FLIGHT");
+    }
+
+    #[test]
+    fn test_negative_delay_brings_the_flight_forward_down_to_the_minimum() {
+        use crate::config::delays::MIN_DELAY_MINUTES;
+
+        let mut manager = test_manager();
+        manager.authenticate_admin("admin", "admin123").unwrap();
+        let departure = manager.database.flights[0].departure_time;
+        let arrival = manager.database.flights[0].arrival_time;
+
+        assert!(matches!(
+            manager.set_flight_delay("RIA900", MIN_DELAY_MINUTES - 1),
+            Err(AirportError::DelayOutOfRange { .. })
+        ));
+        assert_eq!(manager.database.flights[0].departure_time, departure);
+
+        manager.set_flight_delay("RIA900", MIN_DELAY_MINUTES).unwrap();
+        let flight = &manager.database.flights[0];
+        assert_eq!(flight.status, FlightStatus::OnTime);
+        assert_eq!(flight.departure_time, departure + Duration::minutes(MIN_DELAY_MINUTES as i64));
+        assert_eq!(flight.arrival_time, arrival + Duration::minutes(MIN_DELAY_MINUTES as i64));
+    }
+
+    #[test]
+    fn test_total_delay_past_the_maximum_cancels_the_flight() {
+        use crate::config::delays::MAX_DELAY_MINUTES;
+
+        let mut manager = test_manager();
+        let (sink, messages) = MessageSink::buffer();
+        manager.messages = sink;
+        manager.authenticate_admin("admin", "admin123").unwrap();
+        let auto_cancels = |manager: &DataManager| {
+            manager.admin_panel.all_actions().filter(|a| a.action_type == "AUTO_CANCEL_FLIGHT").count()
+        };
+
+        // Exactly the maximum in total is still only a delay
+        manager.set_flight_delay("RIA900", 60).unwrap();
+        manager.set_flight_delay("RIA900", MAX_DELAY_MINUTES - 60).unwrap();
+        assert_eq!(manager.database.flights[0].status, FlightStatus::Delayed(MAX_DELAY_MINUTES));
+        assert_eq!(auto_cancels(&manager), 0);
+        // The message reports the flight's total delay, not the minutes just added
+        let last = messages.lock().unwrap().last().cloned().unwrap();
+        assert_eq!(last, format!("⏰ Flight RIA900 delay set to {} minutes", MAX_DELAY_MINUTES));
+
+        // One minute more cancels it
+        manager.set_flight_delay("RIA900", 1).unwrap();
+        assert_eq!(manager.database.flights[0].status, FlightStatus::Cancelled);
+        assert_eq!(auto_cancels(&manager), 1);
+        let action = manager.admin_panel.get_recent_actions(1)[0];
+        assert!(action.description.contains(&MAX_DELAY_MINUTES.to_string()), "{}", action.description);
+        assert!(action.new_value.as_deref().is_some_and(|v| v.starts_with("Cancelled")));
+        assert_eq!(manager.admin_panel.system_metrics.cancelled_flights, 1);
+    }
+
+    #[test]
+    fn test_booking_simulation_is_reproducible_and_consistent() {
+        let run = |seed| {
+            let mut manager = test_manager();
+            manager.database.flights[0].seat_availability.first_class = 2;
+            let stats = manager.run_booking_simulation(300, seed);
+            let seats = manager.database.flights[0].seat_availability.clone();
+            (stats, (seats.economy, seats.business, seats.first_class))
+        };
+
+        let (stats, seats) = run(42);
+        let (again, seats_again) = run(42);
+
+        assert_eq!(stats.attempted, 300);
+        assert_eq!(stats.booked + stats.rejected + stats.waitlisted, 300);
+        assert!(stats.invariant_violations.is_empty(), "{:?}", stats.invariant_violations);
+        assert_eq!(seats.2, 0);
+        assert_eq!((stats.booked, stats.waitlisted, seats), (again.booked, again.waitlisted, seats_again));
+        assert!((stats.revenue - again.revenue).abs() < 1e-6);
+        assert_eq!(stats.load_factors.len(), 1);
+    }
+
+    #[test]
+    fn test_passenger_data_export_and_anonymization() {
+        let mut manager = test_manager();
+        let flight_id = manager.database.flights[0].id;
+        let booking_id = manager.create_booking(flight_id, test_passenger(), SeatClass::Business).unwrap();
+        let ticket = manager.get_booking_by_id(booking_id).unwrap().ticket_number.clone();
+        let revenue_before: f64 = manager.database.bookings.iter().map(|b| b.payment.total_amount).sum();
+
+        let export: serde_json::Value = serde_json::from_str(&manager.export_passenger_data("GRACE@example.com").unwrap()).unwrap();
+        assert_eq!(export["bookings"][0]["ticket_number"], ticket.as_str());
+        assert_eq!(export["profiles"][0]["last_name"], "Hopper");
+        assert!(manager.export_passenger_data("nobody@example.com").is_err());
+
+        manager.authenticate_admin("admin", "admin123").unwrap();
+        let blocked = manager.anonymize_passenger("grace@example.com").unwrap_err();
+        assert!(blocked.contains(&ticket));
+
+        manager.cancel_booking(&ticket).unwrap();
+        assert_eq!(manager.anonymize_passenger("grace@example.com"), Ok(1));
+
+        let booking = manager.get_booking_by_id(booking_id).unwrap();
+        assert!(booking.passenger.is_anonymized());
+        assert!(booking.passenger.phone.is_empty() && booking.passenger.passport_number.is_none());
+        assert_eq!(booking.seat_class, SeatClass::Business);
+        let revenue_after: f64 = manager.database.bookings.iter().map(|b| b.payment.total_amount).sum();
+        assert_eq!(revenue_before, revenue_after);
+        assert!(manager.export_passenger_data("grace@example.com").is_err());
+    }
+
+    #[test]
+    fn test_auto_assign_aircraft_honours_route_preferences() {
+        let mut manager = test_manager();
+        let a380 = Aircraft::new("N380RA".to_string(), "Airbus A380".to_string(), "Airbus".to_string(), 2015);
+        let a320 = Aircraft::new("N320RA".to_string(), "Airbus A320".to_string(), "Airbus".to_string(), 2019);
+        manager.database.aircraft.extend([a380, a320]);
+        manager.admin_panel.route_preferences.push(RoutePreference::new(
+            "Widebody to JFK".to_string(),
+            "*-JFK".to_string(),
+            vec!["Airbus A380".to_string()],
+            None,
+        ));
+
+        let registrations = |aircraft: Vec<&Aircraft>| aircraft.iter().map(|a| a.registration.clone()).collect::<Vec<_>>();
+        assert_eq!(registrations(manager.preferred_aircraft_for_route("LAX", "JFK")), vec!["N380RA"]);
+        assert!(manager.preferred_aircraft_for_route("JFK", "LAX").is_empty());
+
+        let departure = Utc::now() + Duration::days(2);
+        let arrival = departure + Duration::hours(5);
+        let assign = |manager: &DataManager| manager.auto_assign_aircraft("LAX", "JFK", departure, arrival).map(|a| a.registration.clone());
+        assert_eq!(assign(&manager).as_deref(), Some("N380RA"));
+        // No preference the other way: smallest aircraft that can make the trip
+        assert_eq!(
+            manager.auto_assign_aircraft("JFK", "LAX", departure, arrival).map(|a| a.registration.as_str()),
+            Some("N320RA")
+        );
+
+        // Preferred aircraft busy: fall back to the smallest free one
+        manager.database.aircraft[1].set_status(AircraftStatus::Maintenance);
+        assert_eq!(assign(&manager).as_deref(), Some("N320RA"));
+
+        // The 737 is already flying RIA900 in that window
+        manager.database.aircraft[2].set_status(AircraftStatus::Maintenance);
+        let busy_departure = manager.database.flights[0].departure_time + Duration::hours(1);
+        assert!(manager.auto_assign_aircraft("LAX", "JFK", busy_departure, busy_departure + Duration::hours(5)).is_none());
+    }
+
+    #[test]
+    fn test_process_upgrades_orders_by_tier_then_booking_date() {
+        let mut manager = test_manager();
+        let flight_id = manager.database.flights[0].id;
+        let book = |manager: &mut DataManager, email: &str, tier: Option<LoyaltyTier>, opt_in: bool, hours_ago: i64| {
+            let mut passenger = test_passenger();
+            passenger.email = email.to_string();
+            let id = manager.create_booking(flight_id, passenger, SeatClass::Economy).unwrap();
+            if let Some(tier) = tier {
+                let mut account = LoyaltyAccount::new("Member".to_string(), email.to_string());
+                account.tier = tier;
+                manager.loyalty.push(account);
+            }
+            let booking = manager.database.bookings.iter_mut().find(|b| b.id == id).unwrap();
+            booking.upgrade_opt_in = opt_in;
+            booking.booking_date = Utc::now() - Duration::hours(hours_ago);
+            booking.ticket_number.clone()
+        };
+
+        let silver_late = book(&mut manager, "silver.late@example.com", Some(LoyaltyTier::Silver), true, 1);
+        let gold = book(&mut manager, "gold@example.com", Some(LoyaltyTier::Gold), true, 2);
+        let silver_early = book(&mut manager, "silver.early@example.com", Some(LoyaltyTier::Silver), true, 5);
+        book(&mut manager, "member@example.com", None, true, 10);
+        book(&mut manager, "gold.stays@example.com", Some(LoyaltyTier::Gold), false, 10);
+
+        let flight = &mut manager.database.flights[0];
+        flight.seat_availability.business = 2;
+        flight.seat_availability.first_class = 0;
+        let economy_before = flight.seat_availability.economy;
+
+        assert!(manager.process_upgrades("RIA900").is_err()); // Admins only
+        manager.authenticate_admin("admin", "admin123").unwrap();
+        let upgrades = manager.process_upgrades("RIA900").unwrap();
+
+        let tickets: Vec<&str> = upgrades.iter().map(|u| u.ticket_number.as_str()).collect();
+        assert_eq!(tickets, vec![gold.as_str(), silver_early.as_str()]);
+        assert_eq!(manager.get_booking_by_ticket(&gold).unwrap().seat_class, SeatClass::Business);
+        assert_eq!(manager.get_booking_by_ticket(&silver_late).unwrap().seat_class, SeatClass::Economy);
+        let flight = &manager.database.flights[0];
+        assert_eq!(flight.seat_availability.business, 0);
+        assert_eq!(flight.seat_availability.economy, economy_before + 2);
+        assert_eq!(manager.admin_panel.get_recent_actions(2).iter().filter(|a| a.action_type == "UPGRADE").count(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_quiet_mode_captures_all_status_output() {
+        let dir = std::env::temp_dir().join(format!("ria-messages-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let (sink, messages) = MessageSink::buffer();
+        let mut manager = test_manager_with(DataPersistence::for_tests(dir.to_str().unwrap()).with_messages(sink.clone()));
+        manager.messages = sink;
+
+        let flight_id = manager.database.flights[0].id;
+        let booking_id = manager.create_booking(flight_id, test_passenger(), SeatClass::Economy).unwrap();
+        let ticket = manager.get_booking_by_id(booking_id).unwrap().ticket_number.clone();
+        manager.cancel_booking(&ticket).unwrap();
+        manager.save_all_data().await.unwrap();
+
+        // Everything the data layer had to say went to the sink, not stdout
+        let captured = messages.lock().unwrap().clone();
+        assert!(captured.iter().any(|m| m.starts_with("🎫 Booking created")));
+        assert!(captured.iter().any(|m| m.starts_with(&format!("❌ Booking cancelled: {}", ticket))));
+        assert!(captured.iter().any(|m| m == "💾 Saved complete airport database"));
+
+        assert!(MessageSink::Quiet.is_quiet());
+        std::fs::remove_dir_all(dir).ok();
+    }
+
+    #[test]
+    fn test_find_connecting_flights() {
+        let mut manager = test_manager();
+        let first_leg = manager.database.flights[0].clone();
+        let leg = |number: &str, origin: &str, destination: &str, departs_after_arrival: i64| {
+            let mut flight = first_leg.clone();
+            flight.id = Uuid::new_v4();
+            flight.flight_number = number.to_string();
+            flight.origin = origin.to_string();
+            flight.destination = destination.to_string();
+            flight.departure_time = first_leg.arrival_time + Duration::minutes(departs_after_arrival);
+            flight.arrival_time = flight.departure_time + Duration::hours(7);
+            flight
+        };
+        // JFK is a hub, so needs 90 minutes to connect
+        manager.database.flights.extend([
+            leg("RIA901", "JFK", "CDG", 100),
+            leg("RIA902", "JFK", "CDG", 45),  // Too tight to make
+            leg("RIA903", "JFK", "LAX", 100), // Back where we started
+            leg("RIA904", "JFK", "BOS", 100),
+            leg("RIA905", "BOS", "CDG", 600),
+        ]);
+
+        let routes = |itineraries: Vec<Vec<&Flight>>| {
+            itineraries
+                .iter()
+                .map(|legs| legs.iter().map(|f| f.flight_number.as_str()).collect::<Vec<_>>().join("+"))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(routes(manager.find_connecting_flights("LAX", "CDG", 1)), vec!["RIA900+RIA901"]);
+        assert_eq!(
+            routes(manager.find_connecting_flights("LAX", "CDG", 2)),
+            vec!["RIA900+RIA901", "RIA900+RIA904+RIA905"]
+        );
+        assert!(manager.find_connecting_flights("LAX", "CDG", 0).is_empty());
+        assert_eq!(routes(manager.find_connecting_flights("LAX", "JFK", 2)), vec!["RIA900"]);
+    }
+
+    #[test]
+    fn test_group_booking_is_all_or_nothing_and_cancels_together() {
+        let mut manager = test_manager();
+        let flight_id = manager.database.flights[0].id;
+        manager.database.flights[0].seat_availability.economy = 2;
+        let party = || vec![test_passenger(), test_passenger(), test_passenger_of(PassengerType::Infant)];
+
+        // A lap infant needs no seat, so two seats fit the party of three
+        let group_id = manager.create_group_booking(flight_id, party(), SeatClass::Economy).unwrap();
+        let group = manager.get_group_bookings(group_id);
+        assert_eq!(group.len(), 3);
+        assert!(group.iter().all(|b| b.group_id == Some(group_id)));
+        assert_eq!(manager.database.flights[0].seat_availability.economy, 0);
+
+        assert_eq!(manager.cancel_group_booking(group_id).unwrap().len(), 3);
+        assert_eq!(manager.database.flights[0].seat_availability.economy, 2);
+        assert!(manager.cancel_group_booking(group_id).is_err());
+
+        // One boarded passenger stops the whole group from being cancelled
+        let group_id = manager.create_group_booking(flight_id, party(), SeatClass::Economy).unwrap();
+        let boarded = manager.get_group_bookings(group_id)[0].ticket_number.clone();
+        manager.database.bookings.iter_mut().find(|b| b.ticket_number == boarded).unwrap().status = BookingStatus::Boarded;
+        let err = manager.cancel_group_booking(group_id).unwrap_err();
+        assert!(matches!(&err, AirportError::ValidationError { message } if message.contains(&boarded)), "{}", err);
+        assert!(manager.get_group_bookings(group_id).iter().skip(1).all(|b| matches!(b.status, BookingStatus::Confirmed)));
+        assert_eq!(manager.database.flights[0].seat_availability.economy, 0);
+        manager.database.bookings.retain(|b| b.group_id != Some(group_id));
+        manager.database.flights[0].seat_availability.economy = 2;
+
+        // Not enough seats for everyone: nobody is booked
+        manager.database.flights[0].seat_availability.economy = 1;
+        let bookings_before = manager.database.bookings.len();
+        let revenue_before = manager.admin_panel.system_metrics.revenue_today;
+        assert!(manager.create_group_booking(flight_id, party(), SeatClass::Economy).is_err());
+        assert_eq!(manager.database.bookings.len(), bookings_before);
+        assert_eq!(manager.database.flights[0].seat_availability.economy, 1);
+
+        // A failure part-way through undoes the passengers already booked
+        let first = manager.create_booking(flight_id, test_passenger(), SeatClass::Economy).unwrap();
+        manager.roll_back_bookings(&[first]);
+        assert_eq!(manager.database.bookings.len(), bookings_before);
+        assert_eq!(manager.database.flights[0].seat_availability.economy, 1);
+        assert!((manager.admin_panel.system_metrics.revenue_today - revenue_before).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_round_trip_links_discounted_legs() {
+        let mut manager = test_manager();
+        let outbound = manager.database.flights[0].clone();
+        let mut inbound = outbound.clone();
+        inbound.id = Uuid::new_v4();
+        inbound.flight_number = "RIA901".to_string();
+        inbound.origin = "JFK".to_string();
+        inbound.destination = "LAX".to_string();
+        inbound.departure_time = outbound.arrival_time + Duration::days(3);
+        inbound.arrival_time = inbound.departure_time + Duration::hours(6);
+        manager.database.flights.push(inbound.clone());
+
+        // Wrong direction: the outbound flight is no return for itself
+        assert!(manager.create_round_trip(outbound.id, outbound.id, test_passenger(), SeatClass::Economy).is_err());
+        assert_eq!(manager.get_return_flights(outbound.id).len(), 1);
+
+        // Sold-out return leg: the outbound leg is not booked either
+        manager.database.flights[1].seat_availability.economy = 0;
+        assert!(manager.create_round_trip(outbound.id, inbound.id, test_passenger(), SeatClass::Economy).is_err());
+        assert!(manager.database.bookings.is_empty());
+        manager.database.flights[1].seat_availability.economy = inbound.seat_availability.economy;
+
+        let separate_total = manager.get_passenger_fare(&outbound, &SeatClass::Economy, &PassengerType::Adult)
+            + manager.get_passenger_fare(&inbound, &SeatClass::Economy, &PassengerType::Adult);
+        let trip_id = manager.create_round_trip(outbound.id, inbound.id, test_passenger(), SeatClass::Economy).unwrap();
+        let legs = manager.get_trip_bookings(trip_id);
+        assert_eq!(legs.len(), 2);
+        let paid: f64 = legs.iter().map(|b| b.payment.total_amount).sum();
+        let expected = separate_total * (1.0 - crate::config::pricing::ROUND_TRIP_DISCOUNT);
+        assert!((paid - expected).abs() < 0.01);
+
+        let (outbound_ticket, return_ticket) = (legs[0].ticket_number.clone(), legs[1].ticket_number.clone());
+        assert_eq!(manager.cancel_booking(&outbound_ticket).unwrap().companion_ticket, Some(return_ticket.clone()));
+        assert_eq!(manager.cancel_booking(&return_ticket).unwrap().companion_ticket, None);
+    }
+
+    #[test]
+    fn test_cancellation_promotes_waitlist_by_tier_then_joining_order() {
+        let mut manager = test_manager();
+        let flight_id = manager.database.flights[0].id;
+        manager.database.flights[0].seat_availability.economy = 1;
+        let passenger = |first: &str, email: &str| {
+            let mut p = test_passenger();
+            p.first_name = first.to_string();
+            p.email = email.to_string();
+            p
+        };
+
+        assert!(manager.join_waitlist(flight_id, test_passenger(), SeatClass::Economy).is_err());
+        let booking_id = manager.create_booking(flight_id, test_passenger(), SeatClass::Economy).unwrap();
+        let ticket = manager.get_booking_by_id(booking_id).unwrap().ticket_number.clone();
+
+        let mut gold = LoyaltyAccount::new("Ada Lovelace".to_string(), "ada@example.com".to_string());
+        gold.tier = LoyaltyTier::Gold;
+        manager.loyalty.push(gold);
+
+        assert_eq!(manager.join_waitlist(flight_id, passenger("Alan", "alan@example.com"), SeatClass::Economy), Ok(1));
+        assert!(manager.join_waitlist(flight_id, passenger("Alan", "alan@example.com"), SeatClass::Economy).is_err());
+        assert_eq!(manager.join_waitlist(flight_id, passenger("Ada", "ada@example.com"), SeatClass::Economy), Ok(1));
+        assert_eq!(manager.waitlist_positions(flight_id, "alan@example.com")[0].1, 2);
+
+        let promoted = manager.cancel_booking(&ticket).unwrap().promoted.unwrap();
+        assert_eq!(promoted.passenger_name, "Ada Hopper");
+        assert_eq!(manager.get_booking_by_ticket(&promoted.ticket_number).unwrap().passenger.email, "ada@example.com");
+        assert_eq!(manager.database.flights[0].seat_availability.economy, 0);
+        assert_eq!(manager.waitlist_positions(flight_id, "alan@example.com")[0].1, 1);
+    }
+
+    #[test]
+    fn test_assign_specific_seat_checks_class_and_availability() {
+        let mut manager = test_manager();
+        let flight_id = manager.database.flights[0].id;
+        let config = manager.database.aircraft[0].seat_configuration.clone();
+        let first_economy = flight::cabin_seat_blocks(&config, &SeatClass::Economy)[0][0].clone();
+        let first_business = flight::cabin_seat_blocks(&config, &SeatClass::Business)[0][0].clone();
+
+        let seat_map = manager.get_seat_map(flight_id);
+        assert_eq!(seat_map.len() as u32, manager.database.aircraft[0].total_capacity);
+        assert!(seat_map.iter().all(|(_, taken)| !taken));
+
+        let ticket = |manager: &mut DataManager| {
+            let id = manager.create_booking(flight_id, test_passenger(), SeatClass::Economy).unwrap();
+            manager.get_booking_by_id(id).unwrap().ticket_number.clone()
+        };
+        let (first, second) = (ticket(&mut manager), ticket(&mut manager));
+
+        assert!(manager.assign_specific_seat(&first, "99Z").is_err());
+        assert!(manager.assign_specific_seat(&first, &first_business).is_err());
+        manager.assign_specific_seat(&first, &first_economy.to_lowercase()).unwrap();
+        assert!(manager.get_booking_by_ticket(&first).unwrap().has_fixed_seat());
+        assert!(manager.get_seat_map(flight_id).contains(&(first_economy.clone(), true)));
+
+        assert!(manager.assign_specific_seat(&second, &first_economy).is_err());
+        manager.cancel_booking(&first).unwrap();
+        manager.assign_specific_seat(&second, &first_economy).unwrap();
+    }
+
+    #[test]
+    fn test_refund_depends_on_notice_and_class() {
+        let mut manager = test_manager();
+        let flight_id = manager.database.flights[0].id;
+        let book = |manager: &mut DataManager, class: SeatClass| {
+            let id = manager.create_booking(flight_id, test_passenger(), class).unwrap();
+            manager.get_booking_by_id(id).unwrap().clone()
+        };
+
+        let economy = book(&mut manager, SeatClass::Economy);
+        let first = book(&mut manager, SeatClass::FirstClass);
+        let fare = economy.payment.total_amount;
+        let refund_with_notice = |manager: &mut DataManager, hours: i64| {
+            manager.database.flights[0].departure_time = Utc::now() + Duration::hours(hours);
+            manager.calculate_refund(&economy, &manager.database.flights[0])
+        };
+        assert_eq!(refund_with_notice(&mut manager, 100), fare);
+        assert!((refund_with_notice(&mut manager, 48) - fare * 0.5).abs() < 1e-9);
+        assert_eq!(refund_with_notice(&mut manager, 6), 0.0);
+        assert_eq!(manager.calculate_refund(&first, &manager.database.flights[0]), first.payment.total_amount);
+
+        // Cancelling records the refund and takes it out of revenue
+        manager.database.flights[0].departure_time = Utc::now() + Duration::hours(48);
+        let revenue_before = manager.admin_panel.system_metrics.revenue_today;
+        let cancellation = manager.cancel_booking(&economy.ticket_number).unwrap();
+        assert!((cancellation.refund - fare * 0.5).abs() < 1e-9);
+        assert_eq!(manager.get_booking_by_ticket(&economy.ticket_number).unwrap().payment.refunded_amount, cancellation.refund);
+        assert!((revenue_before - manager.admin_panel.system_metrics.revenue_today - cancellation.refund).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_cancelling_a_booking_on_a_cancelled_flight_refunds_in_full() {
+        use crate::config::delays::MAX_DELAY_MINUTES;
+
+        let mut manager = test_manager();
+        let flight_id = manager.database.flights[0].id;
+        let booking_id = manager.create_booking(flight_id, test_passenger(), SeatClass::Economy).unwrap();
+        let booking = manager.get_booking_by_id(booking_id).unwrap().clone();
+        // Six hours out an economy fare is normally not refundable at all
+        assert_eq!(manager.calculate_refund(&booking, &manager.database.flights[0]), 0.0);
+
+        // Delayed past the maximum, the flight is cancelled automatically
+        manager.authenticate_admin("admin", "admin123").unwrap();
+        manager.set_flight_delay("RIA900", MAX_DELAY_MINUTES).unwrap();
+        manager.set_flight_delay("RIA900", 1).unwrap();
+        assert_eq!(manager.database.flights[0].status, FlightStatus::Cancelled);
+
+        let cancellation = manager.cancel_booking(&booking.ticket_number).unwrap();
+        assert_eq!(cancellation.refund, booking.payment.total_amount);
+        assert_eq!(manager.get_booking_by_ticket(&booking.ticket_number).unwrap().payment.refunded_amount, booking.payment.total_amount);
+    }
+
+    #[test]
+    fn test_create_flight_refuses_routes_beyond_aircraft_range() {
+        let mut manager = test_manager();
+        manager.authenticate_admin("admin", "admin123").unwrap();
+        manager.database.airports.push(Airport::new(
+            "NRT".to_string(),
+            "RJAA".to_string(),
+            "Narita International Airport".to_string(),
+            "Tokyo".to_string(),
+            "Japan".to_string(),
+            "Asia/Tokyo".to_string(),
+            35.7720, 140.3929, 41,
+        ));
+        let aircraft_id = manager.database.aircraft[0].id; // 737-800, 5665 km
+        assert!(manager.validate_flight_range(&manager.database.flights[0]).is_ok());
+
+        let departure = tomorrow_at(17);
+        let result = manager.create_flight(
+            "RIA950".to_string(),
+            "Rust International Airways".to_string(),
+            "LAX".to_string(),
+            "NRT".to_string(),
+            departure,
+            departure + Duration::hours(11),
+            aircraft_id,
+        );
+        assert!(result.unwrap_err().to_string().contains("beyond the 5665 km range"));
+        assert!(manager.get_flight_by_number("RIA950").is_none());
+    }
+
+    #[test]
+    fn test_aircraft_management_guards_status_and_reassignment() {
+        let mut manager = test_manager();
+        let mut spare = Aircraft::new("N2TEST".to_string(), "Boeing 737-800".to_string(), "Boeing".to_string(), 2021);
+        spare.set_status(AircraftStatus::Maintenance);
+        manager.database.aircraft.push(spare);
+
+        assert!(manager.set_aircraft_status("N2TEST", AircraftStatus::Active).is_err());
+        manager.authenticate_admin("admin", "admin123").unwrap();
+
+        assert!(manager.set_aircraft_status("N2TEST", AircraftStatus::InFlight).is_err());
+        assert!(manager.record_maintenance("N2TEST", -2.0).is_err());
+        let err = manager.reassign_aircraft("RIA900", "N2TEST").unwrap_err();
+        assert!(err.contains("Maintenance"), "{}", err);
+
+        manager.set_aircraft_status("n2test", AircraftStatus::Active).unwrap();
+        manager.reassign_aircraft("RIA900", "N2TEST").unwrap();
+
+        let spare_id = manager.get_aircraft_by_registration("N2TEST").unwrap().id;
+        assert_eq!(manager.get_flight_by_number("RIA900").unwrap().aircraft_id, spare_id);
+        let last = manager.admin_panel.get_recent_actions(1)[0];
+        assert_eq!(last.action_type, "REASSIGN_AIRCRAFT");
+        assert_eq!(last.old_value.as_deref(), Some("N1TEST"));
+        assert_eq!(last.new_value.as_deref(), Some("N2TEST"));
+    }
+
+    #[test]
+    fn test_boarding_pass_requires_check_in_and_exports() {
+        let mut manager = test_manager();
+        let flight_id = manager.get_flight_by_number("RIA900").unwrap().id;
+        let booking_id = manager.create_booking(flight_id, test_passenger(), SeatClass::Economy).unwrap();
+        let ticket = manager.database.bookings.iter().find(|b| b.id == booking_id).unwrap().ticket_number.clone();
+        let path = std::env::temp_dir().join(format!("ria_boarding_pass_{}.txt", ticket));
+
+        assert!(manager.export_boarding_pass(&ticket, &path).is_err());
+        assert!(!path.exists());
+
+        manager.database.bookings.iter_mut().find(|b| b.id == booking_id).unwrap().check_in().unwrap();
+        manager.export_boarding_pass(&ticket, &path).unwrap();
+
+        let pass = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert!(pass.contains("BOARDING PASS"));
+        assert!(pass.contains(&ticket));
+        assert!(pass.contains("GRACE HOPPER"));
+        assert!(pass.contains("LAX -> JFK"));
+        assert!(pass.contains("Gate      TBD"));
+        let width = pass.lines().next().unwrap().chars().count();
+        assert!(pass.lines().all(|line| line.chars().count() == width));
+    }
+
+    #[test]
+    fn test_check_in_window_and_boarding_follow_flight_status() {
+        let mut manager = test_manager();
+        let flight_id = manager.get_flight_by_number("RIA900").unwrap().id;
+        let booking_id = manager.create_booking(flight_id, test_passenger(), SeatClass::Economy).unwrap();
+        let ticket = manager.get_booking_by_id(booking_id).unwrap().ticket_number.clone();
+
+        manager.database.flights[0].departure_time = Utc::now() + Duration::hours(30);
+        assert!(manager.check_in_booking(&ticket).unwrap_err().contains("opens"));
+
+        manager.database.flights[0].departure_time = Utc::now() + Duration::hours(6);
+        assert!(manager.board_booking(&ticket).is_err());
+        manager.check_in_booking(&ticket).unwrap();
+        assert!(manager.check_in_booking(&ticket).is_err());
+        assert!(manager.get_booking_by_id(booking_id).unwrap().check_in_time.is_some());
+
+        assert!(manager.board_booking(&ticket).unwrap_err().contains("not boarding"));
+        manager.database.flights[0].advance_to(FlightStatus::Boarding).unwrap();
+        manager.board_booking(&ticket).unwrap();
+
+        let booking = manager.get_booking_by_id(booking_id).unwrap();
+        assert!(matches!(booking.status, BookingStatus::Boarded));
+        assert!(booking.boarding_time.is_some());
+    }
+
+    #[test]
+    fn test_cancelling_after_departure_does_not_restore_seats() {
+        let mut manager = test_manager();
+        let flight_id = manager.get_flight_by_number("RIA900").unwrap().id;
+        let booking_id = manager.create_booking(flight_id, test_passenger(), SeatClass::Economy).unwrap();
+        let ticket = manager.get_booking_by_id(booking_id).unwrap().ticket_number.clone();
+        manager.check_in_booking(&ticket).unwrap();
+        manager.database.flights[0].advance_to(FlightStatus::Departed).unwrap();
+        let seats_after_departure = manager.database.flights[0].get_available_seats(&SeatClass::Economy);
+
+        let err = manager.cancel_booking(&ticket).unwrap_err();
+        assert!(matches!(err, AirportError::FlightAlreadyDeparted { ref flight_number } if flight_number == "RIA900"), "{}", err);
+        assert_eq!(manager.database.flights[0].get_available_seats(&SeatClass::Economy), seats_after_departure);
+
+        manager.complete_booking(&ticket).unwrap();
+        assert!(matches!(manager.get_booking_by_id(booking_id).unwrap().status, BookingStatus::Completed));
+        assert_eq!(manager.database.flights[0].get_available_seats(&SeatClass::Economy), seats_after_departure);
+    }
+
+    #[test]
+    fn test_flight_lookups_use_the_index() {
+        let mut manager = test_manager();
+        let template = manager.database.flights[0].clone();
+        manager.database.flights = (0..50_000)
+            .map(|i| {
+                let mut flight = template.clone();
+                flight.id = Uuid::new_v4();
+                flight.flight_number = format!("RIA{:05}", i);
+                if i % 2 == 1 {
+                    (flight.origin, flight.destination) = (flight.destination, flight.origin);
+                }
+                flight
+            })
+            .collect();
+        manager.rebuild_flight_index();
+
+        let last = manager.database.flights[49_999].clone();
+        assert_eq!(manager.get_flight_by_number("RIA49999").unwrap().id, last.id);
+        assert_eq!(manager.get_flight_by_id(last.id).unwrap().flight_number, "RIA49999");
+        assert_eq!(manager.search_flights(Some("JFK"), Some("LAX"), None).len(), 25_000);
+
+        // A scan would stop at the first match; the index goes straight to the indexed position
+        let mut decoy = last.clone();
+        decoy.id = Uuid::new_v4();
+        manager.database.flights[0] = decoy;
+        assert_eq!(manager.get_flight_by_number("RIA49999").unwrap().id, last.id);
+
+        // Flights added behind the index's back are still found
+        let mut extra = template;
+        extra.flight_number = "RIA50000".to_string();
+        manager.database.flights.push(extra);
+        assert!(manager.get_flight_by_number("RIA50000").is_some());
+    }
+
+    #[test]
+    fn test_flight_index_checks_hits_against_the_flights() {
+        let mut manager = test_manager();
+        let mut twin = manager.database.flights[0].clone();
+        twin.id = Uuid::new_v4();
+        manager.database.flights.push(twin.clone());
+        manager.rebuild_flight_index();
+        let first_id = manager.database.flights[0].id;
+
+        // Duplicate numbers: the first flight wins until it stops carrying the number
+        assert_eq!(manager.get_flight_by_number("RIA900").unwrap().id, first_id);
+        manager.database.flights[0].flight_number = "RIA901".to_string();
+        assert_eq!(manager.get_flight_by_number("RIA900").unwrap().id, twin.id);
+        assert_eq!(manager.get_flight_by_number("RIA901").unwrap().id, first_id);
+
+        // A flight moved off the route is no longer listed on it
+        manager.database.flights[1].destination = "SFO".to_string();
+        let on_route: Vec<Uuid> = manager.search_flights(Some("LAX"), Some("JFK"), None).iter().map(|f| f.id).collect();
+        assert_eq!(on_route, vec![first_id]);
+
+        // Swapped positions are found by id either way
+        manager.database.flights.swap(0, 1);
+        assert_eq!(manager.get_flight_by_id(first_id).unwrap().flight_number, "RIA901");
+        assert_eq!(manager.get_flight_by_id(twin.id).unwrap().destination, "SFO");
+    }
+
+    #[test]
+    fn test_completed_bookings_earn_miles_by_distance_and_class() {
+        let mut manager = test_manager();
+        let flight_id = manager.get_flight_by_number("RIA900").unwrap().id;
+        let member_id = manager.enroll_loyalty("Grace Hopper", "grace@example.com").unwrap();
+        assert!(manager.enroll_loyalty("Grace Hopper", "GRACE@example.com").is_err());
+
+        let booking_id = manager.create_booking(flight_id, test_passenger(), SeatClass::Business).unwrap();
+        let ticket = manager.get_booking_by_id(booking_id).unwrap().ticket_number.clone();
+        manager.check_in_booking(&ticket).unwrap();
+        manager.database.flights[0].advance_to(FlightStatus::Arrived).unwrap();
+        manager.complete_booking(&ticket).unwrap();
+
+        let distance = manager.route_distance_km("LAX", "JFK").unwrap();
+        let account = manager.get_loyalty_account("grace@example.com").unwrap();
+        assert_eq!(account.member_id, member_id);
+        assert_eq!(account.miles, (distance * 1.5).round() as u64);
+        assert_eq!(account.tier, LoyaltyTier::Member);
+
+        assert!(manager.complete_booking(&ticket).is_err());
+        assert_eq!(manager.get_loyalty_account("grace@example.com").unwrap().miles, (distance * 1.5).round() as u64);
+    }
+
+    #[test]
+    fn test_assign_gate_keeps_overlapping_flights_apart() {
+        let mut manager = test_manager();
+        let mut second = manager.database.flights[0].clone();
+        second.id = Uuid::new_v4();
+        second.flight_number = "RIA901".to_string();
+        second.departure_time += Duration::minutes(30);
+        second.arrival_time += Duration::minutes(30);
+        manager.database.flights.push(second);
+        manager.rebuild_flight_index();
+
+        let first_gate = manager.assign_gate("RIA900").unwrap();
+        let second_gate = manager.assign_gate("RIA901").unwrap();
+        assert_ne!(first_gate, second_gate);
+        assert_eq!(manager.assign_gate("RIA900").unwrap(), first_gate);
+        assert_eq!(manager.get_flight_by_number("RIA901").unwrap().gate.as_deref(), Some(second_gate.as_str()));
+
+        // With every gate taken the conflict is reported
+        let gates = manager.get_airport_by_code("LAX").unwrap().get_all_gates();
+        for (i, gate) in gates.iter().enumerate() {
+            let mut busy = manager.database.flights[0].clone();
+            busy.id = Uuid::new_v4();
+            busy.flight_number = format!("RIA{}", 100 + i);
+            busy.gate = Some(gate.clone());
+            manager.database.flights.push(busy);
+        }
+        manager.rebuild_flight_index();
+        manager.database.flights[1].gate = None;
+        let err = manager.assign_gate("RIA901").unwrap_err();
+        assert!(err.starts_with("No free gate at LAX"), "{}", err);
+        assert!(err.contains("RIA100"), "{}", err);
+    }
+
+    #[test]
+    fn test_search_with_class_skips_sold_out_cabins() {
+        let mut manager = test_manager();
+        let mut sold_out = manager.database.flights[0].clone();
+        sold_out.id = Uuid::new_v4();
+        sold_out.flight_number = "RIA901".to_string();
+        sold_out.seat_availability.first_class = 0;
+        manager.database.flights.push(sold_out);
+        manager.rebuild_flight_index();
+
+        assert_eq!(manager.search_flights_with_class(Some("LAX"), Some("JFK"), None, None).len(), 2);
+        assert_eq!(manager.search_flights_with_class(Some("LAX"), Some("JFK"), None, Some(&SeatClass::Economy)).len(), 2);
+        let first = manager.search_flights_with_class(Some("LAX"), Some("JFK"), None, Some(&SeatClass::FirstClass));
+        assert_eq!(first.len(), 1);
+        assert_eq!(first[0].flight_number, "RIA900");
+    }
+
+    #[test]
+    fn test_price_search_uses_the_effective_fare() {
+        let mut manager = test_manager();
+        let mut pricier = manager.database.flights[0].clone();
+        pricier.id = Uuid::new_v4();
+        pricier.flight_number = "RIA901".to_string();
+        pricier.pricing.dynamic_multiplier = 2.0;
+        manager.database.flights.push(pricier);
+        manager.rebuild_flight_index();
+
+        let cap = manager.get_fare(&manager.database.flights[0], &SeatClass::Economy);
+        let cheap = manager.search_flights_by_price(Some("LAX"), Some("JFK"), None, &SeatClass::Economy, cap);
+        assert_eq!(cheap.len(), 1);
+        assert_eq!(cheap[0].flight_number, "RIA900");
+        assert_eq!(manager.search_flights_by_price(None, None, None, &SeatClass::Economy, cap * 2.0).len(), 2);
+        assert!(manager.search_flights_by_price(None, None, None, &SeatClass::Economy, cap - 0.01).is_empty());
+    }
+
+    #[test]
+    fn test_price_search_applies_pricing_rules() {
+        let mut manager = test_manager();
+        let cap = manager.get_fare(&manager.database.flights[0], &SeatClass::Economy);
+        assert_eq!(manager.search_flights_by_price(Some("LAX"), Some("JFK"), None, &SeatClass::Economy, cap).len(), 1);
+
+        // The base price still fits under the cap, but the route surcharge pushes the fare over it
+        manager.admin_panel.pricing_rules.push(PricingRule::new("Route surcharge".to_string(), Some("LAX-JFK".to_string()), None, 1.2, Uuid::new_v4()));
+        assert!(manager.database.flights[0].get_price(&SeatClass::Economy) <= cap);
+        assert!(manager.search_flights_by_price(Some("LAX"), Some("JFK"), None, &SeatClass::Economy, cap).is_empty());
+        assert_eq!(manager.search_flights_by_price(Some("LAX"), Some("JFK"), None, &SeatClass::Economy, cap * 1.2).len(), 1);
+    }
+
+    #[test]
+    fn test_sort_flights_by_each_key_with_flight_number_tiebreak() {
+        let mut manager = test_manager();
+        let base = manager.database.flights[0].clone();
+        let variant = |number: &str, depart_offset: i64, hours: i64, multiplier: f64, sold: u32| {
+            let mut flight = base.clone();
+            flight.id = Uuid::new_v4();
+            flight.flight_number = number.to_string();
+            flight.departure_time = base.departure_time + Duration::hours(depart_offset);
+            flight.arrival_time = flight.departure_time + Duration::hours(hours);
+            flight.pricing.dynamic_multiplier = multiplier;
+            flight.seat_availability.economy -= sold;
+            flight
+        };
+        manager.database.flights = vec![
+            variant("RIA300", 2, 5, 1.0, 30),
+            variant("RIA100", 0, 6, 1.5, 10),
+            variant("RIA200", 1, 4, 0.8, 20),
+            variant("RIA050", 2, 5, 1.0, 30),
+        ];
+        manager.rebuild_flight_index();
+
+        let order = |key: SortKey, ascending: bool| {
+            let mut flights: Vec<&Flight> = manager.database.flights.iter().collect();
+            manager.sort_flights(&mut flights, key, ascending);
+            flights.iter().map(|f| f.flight_number.as_str()).collect::<Vec<_>>()
+        };
+
+        assert_eq!(order(SortKey::DepartureTime, true), ["RIA100", "RIA200", "RIA050", "RIA300"]);
+        assert_eq!(order(SortKey::DepartureTime, false), ["RIA050", "RIA300", "RIA200", "RIA100"]);
+        assert_eq!(order(SortKey::Duration, true), ["RIA200", "RIA050", "RIA300", "RIA100"]);
+        assert_eq!(order(SortKey::PriceEconomy, true), ["RIA200", "RIA050", "RIA300", "RIA100"]);
+        assert_eq!(order(SortKey::LoadFactor, false), ["RIA050", "RIA300", "RIA200", "RIA100"]);
+
+        // A peak-hour rule on RIA200's departure hour makes it the dearest fare despite its low multiplier
+        let peak_hour = manager.database.flights[2].departure_time.hour() as u8;
+        manager.admin_panel.pricing_rules.push(PricingRule::new("Peak hour".to_string(), None, Some((peak_hour, peak_hour)), 2.0, Uuid::new_v4()));
+        let order = |key: SortKey, ascending: bool| {
+            let mut flights: Vec<&Flight> = manager.database.flights.iter().collect();
+            manager.sort_flights(&mut flights, key, ascending);
+            flights.iter().map(|f| f.flight_number.as_str()).collect::<Vec<_>>()
+        };
+        assert_eq!(order(SortKey::PriceEconomy, true), ["RIA050", "RIA300", "RIA100", "RIA200"]);
+    }
+
+    #[test]
+    fn test_nearest_airport_and_radius_search() {
+        let manager = test_manager();
+
+        // Santa Monica is about 13 km from LAX
+        let (nearest, distance) = manager.find_nearest_airport(34.0195, -118.4912).unwrap();
+        assert_eq!(nearest.code, "LAX");
+        assert!(distance < 20.0);
+
+        // Newark is about 33 km from JFK and 3,900 km from LAX
+        let nearby = manager.airports_within_radius(40.6895, -74.1745, 100.0);
+        assert_eq!(nearby.len(), 1);
+        assert_eq!(nearby[0].0.code, "JFK");
+
+        let both = manager.airports_within_radius(40.6895, -74.1745, 5000.0);
+        assert_eq!(both.iter().map(|(a, _)| a.code.as_str()).collect::<Vec<_>>(), vec!["JFK", "LAX"]);
+        assert!(both[0].1 < both[1].1);
+        assert!(manager.airports_within_radius(0.0, 0.0, 100.0).is_empty());
+    }
+
+    #[test]
+    fn test_projected_fuel_cost_covers_scheduled_flights() {
+        let mut manager = test_manager();
+        let distance = manager.route_distance_km("LAX", "JFK").unwrap();
+        let expected = distance / 100.0 * 3.2 * crate::config::FUEL_PRICE_PER_LITER;
+        assert!((manager.projected_fuel_cost() - expected).abs() < 1e-6);
+
+        manager.database.flights[0].status = FlightStatus::Cancelled;
+        assert_eq!(manager.projected_fuel_cost(), 0.0);
+    }
+
+    #[test]
+    fn test_grounded_aircraft_stays_on_the_ground() {
+        let mut manager = test_manager();
+        assert!(manager.ground_aircraft("N1TEST", "Safety directive".to_string()).is_err());
+        manager.authenticate_admin("admin", "admin123").unwrap();
+
+        assert!(manager.ground_aircraft("N1TEST", "  ".to_string()).is_err());
+        manager.ground_aircraft("N1TEST", "Safety directive".to_string()).unwrap();
+        let aircraft = manager.get_aircraft_by_registration("N1TEST").unwrap();
+        assert!(aircraft.is_grounded());
+        assert!(!aircraft.is_available_for_flight());
+        assert!(aircraft.get_status_display().contains("Safety directive"));
+        assert!(manager.set_aircraft_status("N1TEST", AircraftStatus::Active).is_err());
+
+        // The simulation leaves it grounded even once its flight departs
+        manager.database.flights[0].departure_time = Utc::now() - Duration::minutes(5);
+        manager.tick(Utc::now());
+        assert!(manager.get_aircraft_by_registration("N1TEST").unwrap().is_grounded());
+
+        manager.record_maintenance("N1TEST", 5.0).unwrap();
+        assert!(manager.get_aircraft_by_registration("N1TEST").unwrap().is_grounded());
+
+        manager.unground_aircraft("N1TEST").unwrap();
+        assert_eq!(manager.get_aircraft_by_registration("N1TEST").unwrap().status, AircraftStatus::Active);
+        assert!(manager.unground_aircraft("N1TEST").is_err());
+        assert_eq!(manager.admin_panel.get_recent_actions(2).len(), 2);
+    }
+
+    #[test]
+    fn test_connection_time_depends_on_airport() {
+        let mut manager = test_manager();
+        let lhr = Airport::new("LHR".to_string(), "EGLL".to_string(), "London Heathrow Airport".to_string(),
+            "London".to_string(), "United Kingdom".to_string(), "Europe/London".to_string(), 51.4700, -0.4543, 25);
+        let cdg = Airport::new("CDG".to_string(), "LFPG".to_string(), "Paris Charles de Gaulle Airport".to_string(),
+            "Paris".to_string(), "France".to_string(), "Europe/Paris".to_string(), 49.0097, 2.5479, 119);
+        manager.database.airports.extend([lhr, cdg]);
+
+        let inbound = manager.database.flights[0].clone();
+        let mut outbound = inbound.clone();
+        outbound.origin = "JFK".to_string();
+        outbound.destination = "LHR".to_string();
+
+        // Domestic into the JFK hub, international out: just the hub's 90 minutes
+        assert_eq!(manager.connection_minutes(&inbound, &outbound), 90);
+
+        // Paris to London via JFK is international both ways, so customs adds a buffer
+        let mut from_paris = inbound.clone();
+        from_paris.origin = "CDG".to_string();
+        assert_eq!(
+            manager.connection_minutes(&from_paris, &outbound),
+            90 + crate::config::INTERNATIONAL_CONNECTION_BUFFER_MINUTES
+        );
+    }
+
+    #[test]
+    fn test_exit_rows_are_off_limits_to_parties_with_infants() {
+        let mut manager = test_manager();
+        let flight_id = manager.database.flights[0].id;
+        let config = manager.database.aircraft[0].seat_configuration.clone();
+        let economy: Vec<String> = flight::cabin_seat_blocks(&config, &SeatClass::Economy).into_iter().flatten().collect();
+        let is_exit = |seat: &String| booking::SeatAssignment::new(seat.clone(), SeatClass::Economy).is_emergency_exit;
+        let exit_seat = economy.iter().find(|s| is_exit(s)).unwrap().clone();
+        let regular_seat = economy.iter().find(|s| !is_exit(s)).unwrap().clone();
+
+        let group_id = manager
+            .create_group_booking(flight_id, vec![test_passenger(), test_passenger_of(PassengerType::Infant)], SeatClass::Economy)
+            .unwrap();
+        let parent = manager.get_group_bookings(group_id)
+            .into_iter()
+            .find(|b| matches!(b.passenger.passenger_type, PassengerType::Adult))
+            .unwrap()
+            .ticket_number
+            .clone();
+
+        let err = manager.assign_specific_seat(&parent, &exit_seat).unwrap_err();
+        assert!(err.contains("emergency exit"), "{}", err);
+        assert!(!manager.is_seat_taken(flight_id, &exit_seat));
+        manager.assign_specific_seat(&parent, &regular_seat).unwrap();
+        assert!(manager.is_seat_taken(flight_id, &regular_seat.to_lowercase()));
+
+        // A passenger without an infant can take the exit row
+        let solo = manager.create_booking(flight_id, test_passenger(), SeatClass::Economy).unwrap();
+        let solo = manager.get_booking_by_id(solo).unwrap().ticket_number.clone();
+        manager.assign_specific_seat(&solo, &exit_seat).unwrap();
+    }
+
+    #[test]
+    fn test_revenue_by_route_aggregates_bookings() {
+        let mut manager = test_manager();
+        let mut return_leg = manager.database.flights[0].clone();
+        return_leg.id = Uuid::new_v4();
+        return_leg.flight_number = "RIA901".to_string();
+        return_leg.origin = "JFK".to_string();
+        return_leg.destination = "LAX".to_string();
+        manager.database.flights.push(return_leg);
+        manager.rebuild_flight_index();
+        let (outbound, inbound) = (manager.database.flights[0].id, manager.database.flights[1].id);
+
+        let mut book = |flight_id, class| {
+            let id = manager.create_booking(flight_id, test_passenger(), class).unwrap();
+            manager.get_booking_by_id(id).unwrap().payment.total_amount
+        };
+        let lax_jfk = book(outbound, SeatClass::FirstClass) + book(outbound, SeatClass::Economy);
+        let jfk_lax = book(inbound, SeatClass::Economy);
+        let cancelled = manager.create_booking(inbound, test_passenger(), SeatClass::Business).unwrap();
+        let ticket = manager.get_booking_by_id(cancelled).unwrap().ticket_number.clone();
+        manager.cancel_booking(&ticket).unwrap();
+
+        let report = manager.revenue_by_route();
+        assert_eq!(report.len(), 2);
+        assert_eq!((report[0].0.as_str(), report[0].2), ("LAX-JFK", 2));
+        assert!((report[0].1 - lax_jfk).abs() < 1e-9);
+        assert_eq!((report[1].0.as_str(), report[1].2), ("JFK-LAX", 1));
+        assert!((report[1].1 - jfk_lax).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_maintenance_due_boundary() {
+        let mut manager = test_manager();
+        manager.database.aircraft[0].add_flight_hours(90.0);
+
+        let due = manager.maintenance_due(10.0);
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].0.registration, "N1TEST");
+        assert_eq!(due[0].1, 10.0);
+        assert!(manager.maintenance_due(9.9).is_empty());
+
+        manager.database.aircraft[0].set_status(AircraftStatus::Retired);
+        assert!(manager.maintenance_due(10.0).is_empty());
+    }
+
+    #[test]
+    fn test_change_seat_class_charges_and_refunds_difference() {
+        let mut manager = test_manager();
+        let flight_id = manager.database.flights[0].id;
+        let booking_id = manager.create_booking(flight_id, test_passenger(), SeatClass::Economy).unwrap();
+        let ticket = manager.get_booking_by_id(booking_id).unwrap().ticket_number.clone();
+        let paid = manager.get_booking_by_id(booking_id).unwrap().payment.total_amount;
+        let (economy, business) = {
+            let flight = &manager.database.flights[0];
+            (flight.get_available_seats(&SeatClass::Economy), flight.get_available_seats(&SeatClass::Business))
+        };
+
+        let charge = manager.change_seat_class(&ticket, SeatClass::Business).unwrap();
+        assert!(charge > 0.0);
+        let booking = manager.get_booking_by_id(booking_id).unwrap();
+        assert_eq!(booking.seat_class, SeatClass::Business);
+        assert!((booking.payment.total_amount - (paid + charge)).abs() < 1e-9);
+        let flight = &manager.database.flights[0];
+        assert_eq!(flight.get_available_seats(&SeatClass::Economy), economy + 1);
+        assert_eq!(flight.get_available_seats(&SeatClass::Business), business - 1);
+
+        let refund = manager.change_seat_class(&ticket, SeatClass::Economy).unwrap();
+        assert!((refund + charge).abs() < 1e-9);
+        assert!((manager.get_booking_by_id(booking_id).unwrap().payment.total_amount - paid).abs() < 1e-9);
+        assert!(manager.change_seat_class(&ticket, SeatClass::Economy).is_err());
+
+        manager.database.flights[0].status = FlightStatus::Departed;
+        let error = manager.change_seat_class(&ticket, SeatClass::Business).unwrap_err();
+        assert!(error.contains("already departed"));
+    }
+
+    #[test]
+    fn test_manifest_sorted_by_seat_then_name() {
+        let mut manager = test_manager();
+        let flight_id = manager.database.flights[0].id;
+        let mut book = |first: &str, last: &str, seat: Option<&str>| {
+            let mut passenger = test_passenger();
+            passenger.first_name = first.to_string();
+            passenger.last_name = last.to_string();
+            let id = manager.create_booking(flight_id, passenger, SeatClass::Economy).unwrap();
+            let booking = manager.database.bookings.iter_mut().find(|b| b.id == id).unwrap();
+            if let Some(seat) = seat {
+                booking.assign_seat(seat.to_string());
+            }
+            booking.ticket_number.clone()
+        };
+        book("Zed", "Young", None);
+        book("Amy", "Brown", Some("12A"));
+        let cancelled = book("Cat", "Cole", Some("3F"));
+        book("Bob", "Adams", None);
+        book("Dan", "Diaz", Some("9C"));
+        manager.cancel_booking(&cancelled).unwrap();
+
+        let names: Vec<String> = manager.get_manifest("RIA900").iter().map(|b| b.passenger.last_name.clone()).collect();
+        assert_eq!(names, ["Diaz", "Brown", "Adams", "Young"]);
+        assert!(manager.get_manifest("RIA999").is_empty());
+    }
+
+    #[test]
+    fn test_delay_cascades_to_next_flight_on_aircraft() {
+        let mut manager = test_manager();
+        let mut second_leg = manager.database.flights[0].clone();
+        second_leg.id = Uuid::new_v4();
+        second_leg.flight_number = "RIA901".to_string();
+        second_leg.origin = "JFK".to_string();
+        second_leg.destination = "LAX".to_string();
+        second_leg.departure_time = manager.database.flights[0].arrival_time + Duration::minutes(60);
+        second_leg.arrival_time = second_leg.departure_time + Duration::hours(6);
+        manager.database.flights.push(second_leg);
+        manager.rebuild_flight_index();
+        manager.authenticate_admin("admin", "admin123").unwrap();
+
+        // 50 minutes on the ground is still enough
+        manager.set_flight_delay("RIA900", 10).unwrap();
+        assert_eq!(manager.database.flights[1].status, FlightStatus::OnTime);
+
+        manager.set_flight_delay("RIA900", 20).unwrap();
+        let second_leg = &manager.database.flights[1];
+        assert_eq!(second_leg.status, FlightStatus::Delayed(15));
+        assert_eq!((second_leg.estimated_departure() - manager.database.flights[0].arrival_time).num_minutes(), 45);
+
+        let last = manager.admin_panel.get_recent_actions(1)[0];
+        assert_eq!(last.action_type, "CASCADE_DELAY");
+        assert_eq!(last.affected_entity_id, Some(second_leg.id));
+    }
+
+    #[test]
+    fn test_booking_in_display_currency_is_charged_in_usd() {
+        let mut manager = test_manager();
+        let flight_id = manager.database.flights[0].id;
+        assert!(manager.set_display_currency("XYZ").is_err());
+        assert_eq!(manager.display_currency(), "USD");
+
+        let usd_booking = manager.create_booking(flight_id, test_passenger(), SeatClass::Economy).unwrap();
+        assert_eq!(manager.get_booking_by_id(usd_booking).unwrap().payment.quote, None);
+
+        manager.set_display_currency("eur").unwrap();
+        let fare = manager.get_passenger_fare(&manager.database.flights[0], &SeatClass::Economy, &PassengerType::Adult);
+        let booking_id = manager.create_booking(flight_id, test_passenger(), SeatClass::Economy).unwrap();
+        let payment = &manager.get_booking_by_id(booking_id).unwrap().payment;
+        assert_eq!(payment.currency, "USD");
+        assert!((payment.total_amount - fare).abs() < 1e-9);
+        let quote = payment.quote.as_ref().unwrap();
+        assert_eq!((quote.currency.as_str(), quote.rate), ("EUR", manager.currency.rate("EUR").unwrap()));
+    }
+
+    #[test]
+    fn test_generate_from_template_creates_flights_on_template_days() {
+        let mut manager = test_manager();
+        manager.authenticate_admin("admin", "admin123").unwrap();
+        let template = FlightTemplate {
+            flight_number: "RIA500".to_string(),
+            airline: "Rust International Airways".to_string(),
+            origin: "LAX".to_string(),
+            destination: "JFK".to_string(),
+            aircraft_id: manager.database.aircraft[0].id,
+            departure_time: chrono::NaiveTime::from_hms_opt(17, 0, 0).unwrap(),
+            duration_minutes: 300,
+            days: vec![chrono::Weekday::Mon, chrono::Weekday::Wed, chrono::Weekday::Fri],
+        };
+        let start = tomorrow_at(0).date_naive();
+        let end = start + Duration::days(13);
+
+        let created = manager.generate_from_template(&template, start, end).unwrap();
+
+        // Two full weeks hold each weekday exactly twice
+        assert_eq!(created.len(), 6);
+        let flights: Vec<&Flight> = created.iter().map(|id| manager.get_flight_by_id(*id).unwrap()).collect();
+        assert_eq!(flights[0].flight_number, "RIA500");
+        assert_eq!(flights.iter().map(|f| f.flight_number.as_str()).collect::<HashSet<_>>().len(), 6);
+        for flight in &flights {
+            assert!(template.runs_on(flight.departure_time.date_naive()));
+            assert_eq!(flight.departure_time.hour(), 17);
+            assert_eq!(flight.arrival_time - flight.departure_time, Duration::minutes(300));
+        }
+
+        assert!(manager.generate_from_template(&template, end, start).is_err());
+    }
+
+    #[test]
+    fn test_rebook_moves_seat_to_a_later_flight_on_the_same_route() {
+        let mut manager = test_manager();
+        let mut later = manager.database.flights[0].clone();
+        later.id = Uuid::new_v4();
+        later.flight_number = "RIA902".to_string();
+        later.departure_time += Duration::hours(3);
+        later.arrival_time += Duration::hours(3);
+        let mut earlier = later.clone();
+        earlier.id = Uuid::new_v4();
+        earlier.flight_number = "RIA898".to_string();
+        earlier.departure_time -= Duration::hours(4);
+        earlier.arrival_time -= Duration::hours(4);
+        manager.database.flights.extend([later, earlier]);
+        manager.rebuild_flight_index();
+
+        let flight_id = manager.database.flights[0].id;
+        let booking_id = manager.create_booking(flight_id, test_passenger(), SeatClass::Business).unwrap();
+        let ticket = manager.get_booking_by_id(booking_id).unwrap().ticket_number.clone();
+        let original_seats = manager.get_flight_by_number("RIA900").unwrap().seat_availability.business;
+        let later_seats = manager.get_flight_by_number("RIA902").unwrap().seat_availability.business;
+
+        let options: Vec<&str> = manager.rebooking_options("RIA900").iter().map(|f| f.flight_number.as_str()).collect();
+        assert_eq!(options, vec!["RIA902"]);
+        assert!(manager.rebook(&ticket, "RIA898").unwrap_err().contains("departs before"));
+
+        manager.rebook(&ticket, "RIA902").unwrap();
+
+        let booking = manager.get_booking_by_ticket(&ticket).unwrap();
+        assert_eq!(manager.get_flight_by_id(booking.flight_id).unwrap().flight_number, "RIA902");
+        assert!(matches!(booking.seat_class, SeatClass::Business));
+        assert_eq!(manager.get_flight_by_number("RIA900").unwrap().seat_availability.business, original_seats + 1);
+        assert_eq!(manager.get_flight_by_number("RIA902").unwrap().seat_availability.business, later_seats - 1);
+
+        // A later flight with no seats left in the booked class is refused
+        let mut full = manager.get_flight_by_number("RIA902").unwrap().clone();
+        full.id = Uuid::new_v4();
+        full.flight_number = "RIA904".to_string();
+        full.departure_time += Duration::hours(2);
+        full.seat_availability.business = 0;
+        manager.database.flights.push(full);
+        manager.rebuild_flight_index();
+        assert!(manager.rebook(&ticket, "RIA904").unwrap_err().contains("No business seats"));
+        assert_eq!(manager.get_booking_by_ticket(&ticket).map(|b| b.flight_id), manager.get_flight_by_number("RIA902").map(|f| f.id));
+    }
+
+    #[test]
+    fn test_rebook_refuses_a_flight_whose_hold_is_nearly_full() {
+        let mut manager = test_manager();
+        let mut later = manager.database.flights[0].clone();
+        later.id = Uuid::new_v4();
+        later.flight_number = "RIA902".to_string();
+        later.departure_time += Duration::hours(3);
+        later.arrival_time += Duration::hours(3);
+        manager.database.flights.push(later);
+        manager.rebuild_flight_index();
+
+        let book = |manager: &mut DataManager, flight_number: &str, bags: u32| {
+            let flight_id = manager.get_flight_by_number(flight_number).unwrap().id;
+            let booking_id = manager.create_booking(flight_id, test_passenger(), SeatClass::Economy).unwrap();
+            let ticket = manager.get_booking_by_id(booking_id).unwrap().ticket_number.clone();
+            manager.add_checked_bags(&ticket, bags).unwrap();
+            ticket
+        };
+        let ticket = book(&mut manager, "RIA900", 2);
+        book(&mut manager, "RIA902", 1);
+        let moving = manager.get_booking_by_ticket(&ticket).unwrap().checked_baggage_weight_kg();
+        let seats = manager.get_flight_by_number("RIA902").unwrap().seat_availability.economy;
+
+        // One kg short of room for the bags being moved
+        let room = manager.baggage_room_remaining("RIA902").unwrap();
+        manager.database.aircraft[0].baggage_capacity_kg -= room - moving + 1;
+        assert!(manager.rebook(&ticket, "RIA902").unwrap_err().contains("Not enough baggage capacity"));
+        assert_eq!(manager.get_booking_by_ticket(&ticket).map(|b| b.flight_id), manager.get_flight_by_number("RIA900").map(|f| f.id));
+        assert_eq!(manager.get_flight_by_number("RIA902").unwrap().seat_availability.economy, seats);
+
+        manager.database.aircraft[0].baggage_capacity_kg += 1;
+        manager.rebook(&ticket, "RIA902").unwrap();
+        assert_eq!(manager.baggage_room_remaining("RIA902"), Some(0));
+    }
+
+    #[test]
+    fn test_rebook_refuses_a_flight_that_is_not_open_for_booking() {
+        let mut manager = test_manager();
+        let mut later = manager.database.flights[0].clone();
+        later.id = Uuid::new_v4();
+        later.flight_number = "RIA902".to_string();
+        later.departure_time += Duration::hours(3);
+        later.arrival_time += Duration::hours(3);
+        later.status = FlightStatus::Boarding;
+        manager.database.flights.push(later);
+        manager.rebuild_flight_index();
+
+        let flight_id = manager.database.flights[0].id;
+        for passenger_type in [PassengerType::Adult, PassengerType::Infant] {
+            let booking_id = manager.create_booking(flight_id, test_passenger_of(passenger_type), SeatClass::Economy).unwrap();
+            let ticket = manager.get_booking_by_id(booking_id).unwrap().ticket_number.clone();
+            let err = manager.rebook(&ticket, "RIA902").unwrap_err();
+            assert!(err.contains("not open for booking"), "{}", err);
+            assert_eq!(manager.get_booking_by_ticket(&ticket).unwrap().flight_id, flight_id);
+        }
+    }
+
+    #[test]
+    fn test_set_seat_configuration_resizes_upcoming_flights() {
+        let mut manager = test_manager();
+        manager.authenticate_admin("admin", "admin123").unwrap();
+        let flight_id = manager.database.flights[0].id;
+        let old_capacity = manager.database.aircraft[0].total_capacity;
+        manager.create_booking(flight_id, test_passenger(), SeatClass::Business).unwrap();
+        let config = |business_rows| SeatConfiguration {
+            economy_rows: 30,
+            economy_seats_per_row: 6,
+            business_rows,
+            business_seats_per_row: 4,
+            first_class_rows: 0,
+            first_class_seats_per_row: 0,
+        };
+
+        let oversized = SeatConfiguration { economy_rows: 200, ..config(1) };
+        assert!(manager.set_seat_configuration("N1TEST", oversized, true).unwrap_err().contains("maximum"));
+        // Business is sold, so a cabin without it can't be applied to the flight
+        assert!(manager.set_seat_configuration("N1TEST", config(0), true).unwrap_err().contains("already sold"));
+        assert_eq!(manager.database.aircraft[0].total_capacity, old_capacity);
+
+        assert_eq!(manager.set_seat_configuration("N1TEST", config(1), true), Ok(1));
+
+        assert_eq!(manager.database.aircraft[0].total_capacity, 184);
+        let flight = manager.get_flight_by_id(flight_id).unwrap();
+        assert_eq!(flight.total_capacity, 184);
+        assert_eq!(flight.seat_availability.business, 3);
+        assert_eq!(flight.seat_availability.economy, 180);
+        assert_eq!(flight.seat_availability.first_class, 0);
+        let action = manager.admin_panel.all_actions().find(|a| a.action_type == "SET_SEAT_CONFIG").unwrap();
+        assert_eq!(action.old_value, Some(format!("{} seats", old_capacity)));
+        assert_eq!(action.new_value.as_deref(), Some("184 seats"));
+    }
+
+    #[test]
+    fn test_find_bookings_by_passenger_matches_name_or_email() {
+        let mut manager = test_manager();
+        let flight_id = manager.database.flights[0].id;
+        let passenger = test_passenger();
+        let (first_name, email) = (passenger.first_name.clone(), passenger.email.clone());
+        let booking_id = manager.create_booking(flight_id, passenger, SeatClass::Economy).unwrap();
+
+        let ids = |bookings: Vec<&Booking>| bookings.iter().map(|b| b.id).collect::<Vec<_>>();
+        assert_eq!(ids(manager.find_bookings_by_passenger(&first_name.to_uppercase())), vec![booking_id]);
+        assert_eq!(ids(manager.find_bookings_by_passenger(&email[1..email.len() - 1])), vec![booking_id]);
+        assert!(manager.find_bookings_by_passenger("nobody-by-this-name").is_empty());
+        assert!(manager.find_bookings_by_passenger("   ").is_empty());
+    }
+
+    #[test]
+    fn test_meal_counts_for_flight_by_preference() {
+        let mut manager = test_manager();
+        let flight_id = manager.database.flights[0].id;
+        let mut tickets = Vec::new();
+        for passenger_type in [PassengerType::Adult, PassengerType::Adult, PassengerType::Adult, PassengerType::Infant] {
+            let booking_id = manager.create_booking(flight_id, test_passenger_of(passenger_type), SeatClass::Economy).unwrap();
+            tickets.push(manager.get_booking_by_id(booking_id).unwrap().ticket_number.clone());
+        }
+        manager.set_meal_preference(&tickets[1], MealPreference::Vegan).unwrap();
+        manager.set_meal_preference(&tickets[2], MealPreference::None).unwrap();
+
+        let counts = manager.meal_counts_for_flight("RIA900");
+        assert_eq!(counts.get(&MealPreference::Standard), Some(&1));
+        assert_eq!(counts.get(&MealPreference::Vegan), Some(&1));
+        // No meal wanted, and infants don't get one
+        assert_eq!(counts.len(), 2);
+
+        let catering = manager.catering_requirements("RIA900").unwrap();
+        assert_eq!(catering.economy_meals, 2);
+        assert_eq!(catering.special_meals, vec![("vegan".to_string(), 1)]);
+
+        // Bookings saved before meal preferences existed get a standard meal
+        let mut json = serde_json::to_value(manager.get_booking_by_ticket(&tickets[1]).unwrap()).unwrap();
+        json.as_object_mut().unwrap().remove("meal_preference");
+        let old: Booking = serde_json::from_value(json).unwrap();
+        assert_eq!(old.meal_preference, MealPreference::Standard);
+    }
+
+    #[test]
+    fn test_flight_path_runs_between_the_flights_airports() {
+        let manager = test_manager();
+        let lax = manager.get_airport_by_code("LAX").unwrap().coordinates.clone();
+        let jfk = manager.get_airport_by_code("JFK").unwrap().coordinates.clone();
+
+        let path = manager.flight_path("RIA900", 8).unwrap();
+
+        assert_eq!(path.len(), 9);
+        assert_eq!(path.first(), Some(&(lax.latitude, lax.longitude)));
+        assert_eq!(path.last(), Some(&(jfk.latitude, jfk.longitude)));
+        assert!(manager.flight_path("RIA000", 8).is_none());
+    }
+
+    #[test]
+    fn test_departure_marks_passengers_not_on_board_as_no_shows() {
+        let mut manager = test_manager();
+        let flight_id = manager.database.flights[0].id;
+        let departure = manager.database.flights[0].departure_time;
+        let missed = manager.create_booking(flight_id, test_passenger(), SeatClass::Economy).unwrap();
+        let boarded = manager.create_booking(flight_id, test_passenger(), SeatClass::Economy).unwrap();
+        let ticket = manager.get_booking_by_id(boarded).unwrap().ticket_number.clone();
+        manager.check_in_booking(&ticket).unwrap();
+        manager.database.flights[0].advance_to(FlightStatus::Boarding).unwrap();
+        manager.board_booking(&ticket).unwrap();
+        let seats_before = manager.database.flights[0].get_available_seats(&SeatClass::Economy);
+
+        manager.tick(departure + Duration::minutes(1));
+
+        assert!(matches!(manager.database.flights[0].status, FlightStatus::Departed));
+        assert!(matches!(manager.get_booking_by_id(missed).unwrap().status, BookingStatus::NoShow));
+        assert!(matches!(manager.get_booking_by_id(boarded).unwrap().status, BookingStatus::Boarded));
+        let no_shows = manager.database.bookings.iter().filter(|b| matches!(b.status, BookingStatus::NoShow)).count();
+        assert_eq!(no_shows, 1);
+        assert_eq!(manager.get_system_metrics().no_shows.get("RIA900"), Some(&1));
+        // The missed seat is not resold
+        assert_eq!(manager.database.flights[0].get_available_seats(&SeatClass::Economy), seats_before);
+
+        // Later ticks don't count the flight again
+        manager.tick(departure + Duration::minutes(10));
+        assert_eq!(manager.get_system_metrics().no_shows.get("RIA900"), Some(&1));
+        assert!(manager.complete_booking(&manager.get_booking_by_id(missed).unwrap().ticket_number.clone()).unwrap_err().contains("missed"));
+    }
+
+    #[test]
+    fn test_airports_with_service_searches_services_and_amenities() {
+        let mut manager = test_manager();
+        manager.database.airports.push(Airport::new(
+            "SBP".to_string(),
+            "KSBP".to_string(),
+            "San Luis Obispo County Regional Airport".to_string(),
+            "San Luis Obispo".to_string(),
+            "United States".to_string(),
+            "America/Los_Angeles".to_string(),
+            35.2368, -120.6424, 64,
+        ));
+        let codes = |airports: Vec<&Airport>| airports.iter().map(|a| a.code.clone()).collect::<Vec<_>>();
+
+        // Only the big airports have lounges
+        assert!(manager.get_airport_by_code("LAX").unwrap().has_amenity("LOUNGE"));
+        assert!(!manager.get_airport_by_code("SBP").unwrap().has_amenity("lounge"));
+        assert_eq!(codes(manager.airports_with_service("Lounges")), vec!["LAX", "JFK"]);
+        // Every airport has parking
+        assert_eq!(codes(manager.airports_with_service("parking")), vec!["LAX", "JFK", "SBP"]);
+
+        assert!(manager.airports_with_service("Spa").is_empty());
+        assert!(manager.airports_with_service(" ").is_empty());
+    }
+
+    #[test]
+    fn test_checked_baggage_is_limited_by_hold_capacity() {
+        let mut manager = test_manager();
+        // A small aircraft with room for five average bags
+        manager.database.aircraft[0].baggage_capacity_kg = 100;
+        let flight_id = manager.database.flights[0].id;
+        let tickets: Vec<String> = (0..3)
+            .map(|_| {
+                let id = manager.create_booking(flight_id, test_passenger(), SeatClass::Economy).unwrap();
+                manager.get_booking_by_id(id).unwrap().ticket_number.clone()
+            })
+            .collect();
+        // Each booking includes one checked bag
+        assert_eq!(manager.flight_baggage_weight("RIA900"), 60);
+
+        assert_eq!(manager.add_checked_bags(&tickets[0], 2), Ok(0));
+        assert_eq!(manager.flight_baggage_weight("RIA900"), 100);
+
+        let err = manager.add_checked_bags(&tickets[1], 1).unwrap_err();
+        assert!(err.contains("only 0 kg"), "{}", err);
+        assert!(manager.declare_checked_baggage(&tickets[1], 30).is_err());
+        assert_eq!(manager.flight_baggage_weight("RIA900"), 100);
+
+        // Declaring a lighter bag than the estimate frees room
+        manager.declare_checked_baggage(&tickets[2], 12).unwrap();
+        assert_eq!(manager.baggage_room_remaining("RIA900"), Some(8));
+        assert!(manager.add_checked_bags(&tickets[1], 1).is_err());
+    }
+
+    #[test]
+    fn test_bags_added_after_declaration_count_on_top_and_full_hold_refuses_bookings() {
+        let mut manager = test_manager();
+        manager.database.aircraft[0].baggage_capacity_kg = 60;
+        let flight_id = manager.database.flights[0].id;
+        let id = manager.create_booking(flight_id, test_passenger(), SeatClass::Economy).unwrap();
+        let ticket = manager.get_booking_by_id(id).unwrap().ticket_number.clone();
+
+        manager.declare_checked_baggage(&ticket, 15).unwrap();
+        assert_eq!(manager.add_checked_bags(&ticket, 1), Ok(25));
+        assert_eq!(manager.flight_baggage_weight("RIA900"), 35);
+
+        // The included bag of a new booking needs room as well
+        assert_eq!(manager.add_checked_bags(&ticket, 1), Ok(5));
+        let err = manager.create_booking(flight_id, test_passenger(), SeatClass::Economy).unwrap_err();
+        assert!(matches!(err, AirportError::ValidationError { .. }), "{:?}", err);
+        assert_eq!(manager.database.bookings.len(), 1);
+    }
+
+    #[test]
+    fn test_admin_overrides_base_fares() {
+        let mut manager = test_manager();
+        assert!(manager.set_base_prices("RIA900", 199.0, 599.0, 1299.0).is_err());
+
+        manager.authenticate_admin("admin", "admin123").unwrap();
+        manager.set_base_prices("RIA900", 199.0, 599.0, 1299.0).unwrap();
+        let flight = manager.get_flight_by_number("RIA900").unwrap();
+        assert_eq!((flight.pricing.economy, flight.pricing.business, flight.pricing.first_class), (199.0, 599.0, 1299.0));
+        let action = manager.admin_panel.all_actions().find(|a| a.action_type == "SET_BASE_FARES").unwrap();
+        assert_eq!(action.new_value.as_deref(), Some("199.00/599.00/1299.00"));
+
+        assert!(manager.set_base_prices("RIA900", -1.0, 599.0, 1299.0).is_err());
+        assert!(manager.set_base_prices("NOPE1", 199.0, 599.0, 1299.0).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_manager_keeps_its_data_in_the_given_directory() {
+        let dir = std::env::temp_dir().join(format!("ria-data-dir-{}", Uuid::new_v4()));
+        let dir = dir.to_str().unwrap();
+
+        let mut manager = DataManager::new_with_dir(Some(dir), MessageSink::Quiet).await.unwrap();
+        assert_eq!(manager.persistence.data_dir(), dir);
+        assert!(!manager.database.flights.is_empty());
+        let flight_id = manager.database.flights[0].id;
+        manager.create_booking(flight_id, test_passenger(), SeatClass::Economy).unwrap();
+        manager.save_all_data().await.unwrap();
+
+        let reopened = DataManager::new_with_dir(Some(dir), MessageSink::Quiet).await.unwrap();
+        assert_eq!(reopened.database.bookings.len(), manager.database.bookings.len());
+        assert!(std::path::Path::new(&format!("{}/flights.json", dir)).exists());
+        std::fs::remove_dir_all(dir).ok();
+    }
+
+    #[tokio::test]
+    async fn test_route_preferences_are_saved_with_the_data_files() {
+        let dir = std::env::temp_dir().join(format!("ria-route-prefs-{}", Uuid::new_v4()));
+        let dir = dir.to_str().unwrap();
+
+        let mut manager = DataManager::new_with_dir(Some(dir), MessageSink::Quiet).await.unwrap();
+        assert!(std::path::Path::new(&format!("{}/route_preferences.json", dir)).exists());
+        assert_eq!(manager.admin_panel.route_preferences.len(), 3);
+        manager.admin_panel.route_preferences.truncate(1);
+        manager.admin_panel.route_preferences[0].preferred_models = vec!["Airbus A380".to_string()];
+        manager.save_all_data().await.unwrap();
+
+        let reopened = DataManager::new_with_dir(Some(dir), MessageSink::Quiet).await.unwrap();
+        let names: Vec<&str> = reopened.admin_panel.route_preferences.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["London long-haul"]);
+        assert_eq!(reopened.admin_panel.route_preferences[0].preferred_models, vec!["Airbus A380"]);
+        std::fs::remove_dir_all(dir).ok();
+    }
+
+    #[test]
+    fn test_arrival_completes_boarded_bookings_and_awards_miles() {
+        let mut manager = test_manager();
+        let flight_id = manager.database.flights[0].id;
+        manager.enroll_loyalty("Grace Hopper", "grace@example.com").unwrap();
+        let booking_id = manager.create_booking(flight_id, test_passenger(), SeatClass::Economy).unwrap();
+        let ticket = manager.get_booking_by_id(booking_id).unwrap().ticket_number.clone();
+        manager.check_in_booking(&ticket).unwrap();
+
+        let departure = manager.database.flights[0].departure_time;
+        manager.tick(departure - Duration::minutes(10));
+        manager.board_booking(&ticket).unwrap();
+        manager.tick(departure + Duration::minutes(1));
+        assert_eq!(manager.get_loyalty_account("grace@example.com").unwrap().miles, 0);
+
+        let arrival = manager.database.flights[0].arrival_time;
+        manager.tick(arrival + Duration::minutes(1));
+
+        assert!(matches!(manager.get_booking_by_id(booking_id).unwrap().status, BookingStatus::Completed));
+        assert!(manager.get_loyalty_account("grace@example.com").unwrap().miles > 0);
+    }
+
+    #[test]
+    fn test_passenger_data_export_includes_loyalty_account() {
+        let mut manager = test_manager();
+        let member_id = manager.enroll_loyalty("Grace Hopper", "grace@example.com").unwrap();
+
+        // A member who has not booked yet still has data to export
+        let export: serde_json::Value = serde_json::from_str(&manager.export_passenger_data("grace@example.com").unwrap()).unwrap();
+        assert_eq!(export["loyalty"]["member_id"], member_id.as_str());
+        assert_eq!(export["loyalty"]["name"], "Grace Hopper");
+
+        let flight_id = manager.database.flights[0].id;
+        manager.create_booking(flight_id, test_passenger(), SeatClass::Economy).unwrap();
+        let export: serde_json::Value = serde_json::from_str(&manager.export_passenger_data("grace@example.com").unwrap()).unwrap();
+        assert_eq!(export["loyalty"]["email"], "grace@example.com");
+        assert_eq!(export["bookings"].as_array().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_anonymize_passenger_redacts_loyalty_account() {
+        let mut manager = test_manager();
+        let member_id = manager.enroll_loyalty("Grace Hopper", "grace@example.com").unwrap();
+        let flight_id = manager.database.flights[0].id;
+        let booking_id = manager.create_booking(flight_id, test_passenger(), SeatClass::Economy).unwrap();
+        let ticket = manager.get_booking_by_id(booking_id).unwrap().ticket_number.clone();
+        manager.cancel_booking(&ticket).unwrap();
+        manager.loyalty[0].add_miles(1200);
+
+        manager.authenticate_admin("admin", "admin123").unwrap();
+        assert_eq!(manager.anonymize_passenger("grace@example.com"), Ok(1));
+
+        let account = manager.loyalty.iter().find(|a| a.member_id == member_id).unwrap();
+        assert_eq!(account.name, crate::modules::booking::REDACTED);
+        assert!(account.email.is_empty());
+        assert_eq!(account.miles, 1200);
+        assert!(manager.get_loyalty_account("grace@example.com").is_none());
+        assert!(manager.export_passenger_data("grace@example.com").is_err());
+    }
+
+    #[test]
+    fn test_confirmation_message_itemizes_the_fare() {
+        let mut manager = test_manager();
+        manager.database.flights[0].pricing.dynamic_multiplier = 1.5;
+        let flight_id = manager.database.flights[0].id;
+        let booking_id = manager.create_booking(flight_id, test_passenger_of(PassengerType::Child), SeatClass::Economy).unwrap();
+        let ticket = manager.get_booking_by_id(booking_id).unwrap().ticket_number.clone();
+
+        let message = manager.confirmation_message(&ticket).unwrap();
+        let breakdown = manager.price_breakdown(flight_id, &SeatClass::Economy, &PassengerType::Child).unwrap();
+        assert!(breakdown.components.len() >= 3);
+        for component in &breakdown.components {
+            assert!(message.contains(&component.label), "{} missing from\n{}", component.label, message);
+        }
+        assert!(message.contains("Dynamic pricing x1.50"));
+        assert!(message.contains(&format!("Child fare x{:.2}", PassengerType::Child.fare_rate())));
+    }
+
+    #[test]
+    fn test_group_and_round_trip_bookings_return_typed_errors() {
+        let mut manager = test_manager();
+        let flight_id = manager.database.flights[0].id;
+        let missing = Uuid::new_v4();
+
+        assert!(matches!(
+            manager.create_group_booking(missing, vec![test_passenger()], SeatClass::Economy),
+            Err(AirportError::FlightNotFound { flight_id }) if flight_id == missing
+        ));
+        assert!(matches!(
+            manager.create_group_booking(flight_id, Vec::new(), SeatClass::Economy),
+            Err(AirportError::ValidationError { .. })
+        ));
+        assert!(matches!(
+            manager.create_round_trip(flight_id, missing, test_passenger(), SeatClass::Economy),
+            Err(AirportError::FlightNotFound { flight_id }) if flight_id == missing
+        ));
+        assert!(matches!(manager.cancel_group_booking(missing), Err(AirportError::ValidationError { .. })));
+
+        // Errors from create_booking come through unchanged
+        manager.database.flights[0].status = FlightStatus::Boarding;
+        assert!(matches!(
+            manager.create_group_booking(flight_id, vec![test_passenger()], SeatClass::Economy),
+            Err(AirportError::FlightNotAvailable { .. })
+        ));
     }
-}
\ No newline at end of file
+}
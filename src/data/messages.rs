@@ -0,0 +1,33 @@
+use std::fmt::Display;
+use std::sync::{Arc, Mutex};
+
+/// Where the data layer's status messages ("💾 Saved 12 flights", "🎫 Booking created ...")
+/// go. The terminal app prints them; library users and servers can silence them or
+/// collect them as events. Results are always returned by the methods themselves.
+#[derive(Debug, Clone, Default)]
+pub enum MessageSink {
+    #[default]
+    Stdout,
+    Quiet,
+    Buffer(Arc<Mutex<Vec<String>>>),
+}
+
+impl MessageSink {
+    /// A sink that keeps messages in memory, plus a handle for reading them back
+    pub fn buffer() -> (Self, Arc<Mutex<Vec<String>>>) {
+        let messages = Arc::new(Mutex::new(Vec::new()));
+        (MessageSink::Buffer(Arc::clone(&messages)), messages)
+    }
+
+    pub fn say(&self, message: impl Display) {
+        match self {
+            MessageSink::Stdout => println!("{}", message),
+            MessageSink::Quiet => {}
+            MessageSink::Buffer(messages) => messages.lock().unwrap().push(message.to_string()),
+        }
+    }
+
+    pub fn is_quiet(&self) -> bool {
+        matches!(self, MessageSink::Quiet)
+    }
+}
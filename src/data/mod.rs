@@ -1,2 +0,0 @@
-pub mod manager;
-pub mod persistence;
\ No newline at end of file
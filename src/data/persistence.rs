@@ -1,15 +1,21 @@
 use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
 use std::fs;
+use std::io::{Read, Write};
 use std::path::Path;
-use uuid::Uuid;
+use std::sync::Mutex;
 use chrono::{DateTime, Utc, Duration};
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
 use crate::modules::{
-    flight::{Flight, FlightStatus, SeatClass},
-    aircraft::{Aircraft, AircraftStatus},
-    booking::{Booking, Passenger, PassengerType},
+    flight::{self, Flight, FlightStatus, SeatAvailability, SeatClass},
+    aircraft::{Aircraft, SeatConfiguration},
+    booking::Booking,
     airport::Airport,
-    admin::{AdminPanel, PricingRule, AdminUser, AdminLevel},
+    loyalty::LoyaltyAccount,
+    admin::{AdminAction, AdminCredential, PricingRule, RoutePreference, SystemMetrics},
 };
+use crate::data::chaos::FailureInjector;
+use crate::data::messages::MessageSink;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AirportDatabase {
@@ -19,31 +25,450 @@ pub struct AirportDatabase {
     pub airports: Vec<Airport>,
 }
 
+impl AirportDatabase {
+    /// Check the flight's assigned aircraft can fly the great-circle distance of its route
+    pub fn validate_flight_range(&self, flight: &Flight) -> Result<(), String> {
+        let airport = |code: &str| {
+            self.airports
+                .iter()
+                .find(|a| a.code == code)
+                .ok_or_else(|| format!("Flight {} uses unknown airport {}", flight.flight_number, code))
+        };
+        let (origin, destination) = (airport(&flight.origin)?, airport(&flight.destination)?);
+        let aircraft = self.aircraft
+            .iter()
+            .find(|a| a.id == flight.aircraft_id)
+            .ok_or_else(|| format!("Flight {} references non-existent aircraft {}", flight.flight_number, flight.aircraft_id))?;
+
+        let distance = crate::utils::calculate_distance(
+            origin.coordinates.latitude,
+            origin.coordinates.longitude,
+            destination.coordinates.latitude,
+            destination.coordinates.longitude,
+        );
+        if distance > aircraft.performance.range_km as f64 {
+            return Err(format!(
+                "Flight {} ({} → {}, {:.0} km) is beyond the {:.0} km range of {} ({})",
+                flight.flight_number, flight.origin, flight.destination, distance,
+                aircraft.performance.range_km, aircraft.registration, aircraft.model
+            ));
+        }
+        Ok(())
+    }
+
+    /// Whether an aircraft is active and not already flying something in the time window
+    pub fn is_aircraft_free(&self, aircraft: &Aircraft, departure_time: DateTime<Utc>, arrival_time: DateTime<Utc>) -> bool {
+        aircraft.is_available_for_flight()
+            && !self.flights.iter().any(|f| {
+                f.aircraft_id == aircraft.id
+                    && !matches!(f.status, FlightStatus::Cancelled | FlightStatus::Arrived)
+                    && f.departure_time < arrival_time
+                    && departure_time < f.arrival_time
+            })
+    }
+
+    /// `flight` with its cabins sized for `aircraft`'s seat configuration, keeping the seats
+    /// already sold. Fails if a cabin would have fewer seats than it has passengers.
+    pub fn fit_cabins(flight: &Flight, aircraft: &Aircraft) -> Result<Flight, String> {
+        let mut refitted = flight.clone();
+        refitted.total_capacity = aircraft.total_capacity;
+        refitted.cabin_capacity = Some(SeatAvailability::for_aircraft(aircraft));
+
+        for class in [SeatClass::Economy, SeatClass::Business, SeatClass::FirstClass] {
+            let sold = flight.class_capacity(&class).saturating_sub(flight.get_available_seats(&class));
+            let seats = refitted.class_capacity(&class);
+            if sold > seats {
+                return Err(format!("{} has only {} {:?} seats but {} are already sold on {}", aircraft.registration, seats, class, sold, flight.flight_number));
+            }
+            match class {
+                SeatClass::Economy => refitted.seat_availability.economy = seats - sold,
+                SeatClass::Business => refitted.seat_availability.business = seats - sold,
+                SeatClass::FirstClass => refitted.seat_availability.first_class = seats - sold,
+            }
+        }
+        Ok(refitted)
+    }
+
+    /// Drop seat assignments on the flight that don't exist in `seat_configuration`
+    pub fn clear_invalid_seat_assignments(&mut self, flight_id: uuid::Uuid, seat_configuration: &SeatConfiguration) {
+        let valid_seats: HashSet<String> = [SeatClass::FirstClass, SeatClass::Business, SeatClass::Economy]
+            .iter()
+            .flat_map(|class| flight::cabin_seat_blocks(seat_configuration, class))
+            .flatten()
+            .collect();
+        for booking in self.bookings.iter_mut().filter(|b| b.flight_id == flight_id) {
+            if booking.seat_assignment.as_ref().is_some_and(|s| !valid_seats.contains(&s.seat_number)) {
+                booking.seat_assignment = None;
+            }
+        }
+    }
+}
+
+/// One CSV line; fields with commas, quotes or line breaks are quoted
+fn csv_row(fields: &[String]) -> String {
+    let quoted: Vec<String> = fields
+        .iter()
+        .map(|field| {
+            if field.contains([',', '"', '\n', '\r']) {
+                format!("\"{}\"", field.replace('"', "\"\""))
+            } else {
+                field.clone()
+            }
+        })
+        .collect();
+    format!("{}\n", quoted.join(","))
+}
+
+/// JSON files that make up the airport database
+pub const DATA_FILES: [&str; 8] = [
+    "airports.json", "aircraft.json", "flights.json", "bookings.json", "loyalty.json", "admins.json", "audit_log.json",
+    "route_preferences.json",
+];
+
+/// Files a backup must contain to be restored; the rest of DATA_FILES are restored when present
+pub const REQUIRED_BACKUP_FILES: [&str; 4] = ["airports.json", "aircraft.json", "flights.json", "bookings.json"];
+
+/// File extension of a backup archive
+const BACKUP_EXTENSION: &str = ".tar.gz";
+
+/// Suffix of the scratch file a save goes to before it replaces the real file
+const TEMP_FILE_SUFFIX: &str = ".tmp";
+
+/// Version of the snapshot layout; bump it whenever the document shape changes
+pub const SNAPSHOT_SCHEMA_VERSION: u32 = 1;
+
+/// Single-document dump of the whole system, written by `export_snapshot`
+#[derive(Serialize)]
+struct SnapshotOut<'a> {
+    schema_version: u32,
+    created_at: DateTime<Utc>,
+    database: &'a AirportDatabase,
+    metrics: &'a SystemMetrics,
+    pricing_rules: &'a [PricingRule],
+}
+
+/// The part of a snapshot `import_snapshot` reads back
+#[derive(Deserialize)]
+struct SnapshotIn {
+    database: AirportDatabase,
+}
+
+/// Environment variable selecting how data files are written ("compact" or "pretty")
+pub const JSON_FORMAT_ENV: &str = "AIRPORT_JSON_FORMAT";
+
+/// Layout used when writing data files. Loading accepts either.
+///
+/// Compact output is roughly 20% smaller for bookings (about 1.9 MB vs 2.5 MB
+/// for 2,000 bookings), so it is the better choice for production data sets.
+/// Pretty output is easier to read and diff while debugging.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum JsonFormat {
+    #[default]
+    Pretty,
+    Compact,
+}
+
+impl JsonFormat {
+    /// Format requested through `AIRPORT_JSON_FORMAT`, defaulting to pretty
+    pub fn from_env() -> Self {
+        match std::env::var(JSON_FORMAT_ENV) {
+            Ok(value) if value.eq_ignore_ascii_case("compact") => JsonFormat::Compact,
+            _ => JsonFormat::Pretty,
+        }
+    }
+}
+
+/// Where the database is actually being kept
+#[derive(Debug, Clone, PartialEq)]
+pub enum StorageMode {
+    Primary,                       // The configured data directory
+    Fallback { original: String }, // Configured directory was not writable
+    InMemory { original: String }, // Nothing writable; changes are lost on exit
+}
+
+#[derive(Debug, Clone)]
+pub struct StorageStatus {
+    pub data_dir: String,
+    pub writable: bool,
+    pub mode: StorageMode,
+}
+
+impl StorageStatus {
+    pub fn get_summary(&self) -> String {
+        match &self.mode {
+            StorageMode::Primary => format!("{} (writable)", self.data_dir),
+            StorageMode::Fallback { original } => {
+                format!("{} (fallback - {} is not writable)", self.data_dir, original)
+            }
+            StorageMode::InMemory { original } => {
+                format!("in memory only - {} is not writable, changes will not be saved", original)
+            }
+        }
+    }
+}
+
 pub struct DataPersistence {
     data_dir: String,
+    json_format: JsonFormat,
+    failure_injector: Option<FailureInjector>,
+    fallback_dirs: Vec<String>,
+    mode: StorageMode,
+    memory: Mutex<HashMap<String, String>>, // File contents when running in memory
+    messages: MessageSink,
+}
+
+impl Default for DataPersistence {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl DataPersistence {
+    /// Storage in `config::DATA_DIR`
     pub fn new() -> Self {
+        Self::with_dir(crate::config::DATA_DIR)
+    }
+
+    /// Storage in `dir`, e.g. to run a second instance or point at fixture data
+    pub fn with_dir(dir: impl Into<String>) -> Self {
         Self {
-            data_dir: "data".to_string(),
+            data_dir: dir.into(),
+            json_format: JsonFormat::from_env(),
+            failure_injector: None,
+            fallback_dirs: Self::default_fallback_dirs(),
+            mode: StorageMode::Primary,
+            memory: Mutex::new(HashMap::new()),
+            messages: MessageSink::default(),
+        }
+    }
+
+    #[cfg(test)]
+    pub(crate) fn for_tests(data_dir: &str) -> Self {
+        Self {
+            data_dir: data_dir.to_string(),
+            json_format: JsonFormat::Pretty,
+            failure_injector: None,
+            fallback_dirs: Vec::new(),
+            mode: StorageMode::Primary,
+            memory: Mutex::new(HashMap::new()),
+            messages: MessageSink::Quiet,
+        }
+    }
+
+    pub fn data_dir(&self) -> &str {
+        &self.data_dir
+    }
+
+    /// Directories tried, in order, when the data directory is not writable
+    fn default_fallback_dirs() -> Vec<String> {
+        let mut dirs = Vec::new();
+        if let Ok(home) = std::env::var("HOME") {
+            dirs.push(format!("{}/.rust_international_airport", home));
+        }
+        dirs.push(std::env::temp_dir().join("rust_international_airport").to_string_lossy().to_string());
+        dirs
+    }
+
+    pub fn with_fallback_dirs(mut self, fallback_dirs: Vec<String>) -> Self {
+        self.fallback_dirs = fallback_dirs;
+        self
+    }
+
+    pub fn storage_status(&self) -> StorageStatus {
+        StorageStatus {
+            data_dir: self.data_dir.clone(),
+            writable: !matches!(self.mode, StorageMode::InMemory { .. }),
+            mode: self.mode.clone(),
         }
     }
 
-    pub async fn initialize(&self) -> Result<(), Box<dyn std::error::Error>> {
+    fn is_in_memory(&self) -> bool {
+        matches!(self.mode, StorageMode::InMemory { .. })
+    }
+
+    fn is_writable(dir: &str) -> bool {
+        let probe = format!("{}/.write_test", dir);
+        fs::create_dir_all(dir).is_ok()
+            && fs::write(&probe, b"ok").is_ok()
+            && fs::remove_file(&probe).is_ok()
+    }
+
+    /// Whether the data directory accepts writes right now
+    pub fn data_dir_writable(&self) -> bool {
+        Self::is_writable(&self.data_dir)
+    }
+
+    /// Core data files that exist but don't parse as what they should hold, with the
+    /// parser's error. Missing files are not reported; they load as empty.
+    pub fn unparseable_data_files(&self) -> Vec<(&'static str, String)> {
+        type Parser = fn(&str) -> Result<(), String>;
+        fn parse<T: serde::de::DeserializeOwned>(content: &str) -> Result<(), String> {
+            serde_json::from_str::<Vec<T>>(content).map(|_| ()).map_err(|e| e.to_string())
+        }
+        let files: [(&'static str, Parser); 4] = [
+            ("airports.json", parse::<Airport>),
+            ("aircraft.json", parse::<Aircraft>),
+            ("flights.json", parse::<Flight>),
+            ("bookings.json", parse::<Booking>),
+        ];
+
+        files
+            .into_iter()
+            .filter_map(|(file, parse)| {
+                let path = format!("{}/{}", self.data_dir, file);
+                if !self.file_exists(&path) {
+                    return None;
+                }
+                let result = self.read_file(&path).map_err(|e| e.to_string()).and_then(|content| parse(&content));
+                result.err().map(|e| (file, e))
+            })
+            .collect()
+    }
+
+    /// Pick where to keep data: the configured directory if writable, otherwise the first
+    /// writable fallback (seeded with any existing data), otherwise memory only.
+    fn select_storage(&mut self) {
+        if Self::is_writable(&self.data_dir) {
+            self.mode = StorageMode::Primary;
+            return;
+        }
+
+        let original = self.data_dir.clone();
+        if let Some(fallback) = self.fallback_dirs.iter().find(|dir| Self::is_writable(dir)).cloned() {
+            for file in &DATA_FILES {
+                let source = format!("{}/{}", original, file);
+                let destination = format!("{}/{}", fallback, file);
+                if Path::new(&source).exists() && !Path::new(&destination).exists() {
+                    fs::copy(&source, &destination).ok();
+                }
+            }
+            self.messages.say(format!("⚠️ Data directory '{}' is not writable - using '{}' instead", original, fallback));
+            self.data_dir = fallback;
+            self.mode = StorageMode::Fallback { original };
+            return;
+        }
+
+        let mut memory = self.memory.lock().unwrap();
+        for file in &DATA_FILES {
+            let path = format!("{}/{}", original, file);
+            if let Ok(content) = fs::read_to_string(&path) {
+                memory.insert(path, content);
+            }
+        }
+        self.messages.say("⚠️ No writable storage found - running in memory, changes will not be saved");
+        self.mode = StorageMode::InMemory { original };
+    }
+
+    fn file_exists(&self, file_path: &str) -> bool {
+        if self.is_in_memory() {
+            self.memory.lock().unwrap().contains_key(file_path)
+        } else {
+            Path::new(file_path).exists()
+        }
+    }
+
+    pub fn backup_dir(&self) -> String {
+        format!("{}/backups", self.data_dir)
+    }
+
+    /// Where passenger-facing exports such as confirmation messages are written
+    pub fn export_dir(&self) -> String {
+        format!("{}/exports", self.data_dir)
+    }
+
+    pub fn with_json_format(mut self, json_format: JsonFormat) -> Self {
+        self.json_format = json_format;
+        self
+    }
+
+    pub fn json_format(&self) -> JsonFormat {
+        self.json_format
+    }
+
+    fn to_json<T: Serialize + ?Sized>(&self, value: &T) -> serde_json::Result<String> {
+        match self.json_format {
+            JsonFormat::Pretty => serde_json::to_string_pretty(value),
+            JsonFormat::Compact => serde_json::to_string(value),
+        }
+    }
+
+    /// Send status messages ("💾 Saved ...") somewhere other than stdout
+    pub fn with_messages(mut self, messages: MessageSink) -> Self {
+        self.messages = messages;
+        self
+    }
+
+    /// Route all file I/O through a failure injector (test/dev only)
+    pub fn with_failure_injection(mut self, injector: FailureInjector) -> Self {
+        self.failure_injector = Some(injector);
+        self
+    }
+
+    fn read_file(&self, file_path: &str) -> Result<String, Box<dyn std::error::Error>> {
+        let content = if self.is_in_memory() {
+            self.memory.lock().unwrap().get(file_path).cloned().ok_or("File not found in memory")?
+        } else {
+            fs::read_to_string(file_path)?
+        };
+        Ok(match &self.failure_injector {
+            Some(injector) => injector.on_read(content),
+            None => content,
+        })
+    }
+
+    fn write_file(&self, file_path: &str, content: &str) -> Result<(), Box<dyn std::error::Error>> {
+        if self.is_in_memory() {
+            self.memory.lock().unwrap().insert(file_path.to_string(), content.to_string());
+            return Ok(());
+        }
+
+        // Write a sibling temp file, then rename it over the target. Renames within a
+        // directory are atomic, so a failed or interrupted save leaves the previous file intact.
+        let temp_path = format!("{}{}", file_path, TEMP_FILE_SUFFIX);
+        if let Err(e) = self.write_temp_file(file_path, &temp_path, content) {
+            fs::remove_file(&temp_path).ok();
+            return Err(e);
+        }
+        fs::rename(&temp_path, file_path)?;
+        Ok(())
+    }
+
+    fn write_temp_file(&self, file_path: &str, temp_path: &str, content: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let (written, error) = match &self.failure_injector {
+            Some(injector) => injector.on_write(file_path, content),
+            None => (Some(content), None),
+        };
+
+        if let Some(written) = written {
+            let mut file = fs::File::create(temp_path)?;
+            file.write_all(written.as_bytes())?;
+            file.sync_all()?; // On disk before the rename makes it visible
+        }
+        match error {
+            Some(e) => Err(e.into()),
+            None => Ok(()),
+        }
+    }
+
+    pub async fn initialize(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        // Fall back to another location if the data directory can't be written
+        self.select_storage();
+
         // Ensure data directories exist
-        self.ensure_directories()?;
+        if !self.is_in_memory() {
+            self.ensure_directories()?;
+        }
         
         // Create sample data files if they don't exist
-        if !Path::new(&format!("{}/airports.json", self.data_dir)).exists() {
+        if !self.file_exists(&format!("{}/airports.json", self.data_dir)) {
             self.create_sample_airports().await?;
         }
         
-        if !Path::new(&format!("{}/aircraft.json", self.data_dir)).exists() {
+        if !self.file_exists(&format!("{}/aircraft.json", self.data_dir)) {
             self.create_sample_aircraft().await?;
         }
         
-        if !Path::new(&format!("{}/flights.json", self.data_dir)).exists() {
+        if !self.file_exists(&format!("{}/flights.json", self.data_dir)) {
             self.create_sample_flights().await?;
         }
 
@@ -61,7 +486,7 @@ impl DataPersistence {
         for dir in &directories {
             if !Path::new(dir).exists() {
                 fs::create_dir_all(dir)?;
-                println!("📁 Created directory: {}", dir);
+                self.messages.say(format!("📁 Created directory: {}", dir));
             }
         }
 
@@ -72,23 +497,27 @@ impl DataPersistence {
     pub async fn load_airports(&self) -> Result<Vec<Airport>, Box<dyn std::error::Error>> {
         let file_path = format!("{}/airports.json", self.data_dir);
         
-        if !Path::new(&file_path).exists() {
+        if !self.file_exists(&file_path) {
             return Ok(Vec::new());
         }
 
-        let content = fs::read_to_string(&file_path)?;
-        let airports: Vec<Airport> = serde_json::from_str(&content)?;
+        let content = self.read_file(&file_path)?;
+        let mut airports: Vec<Airport> = serde_json::from_str(&content)?;
+        // Airports saved before connection times existed get the default for their size
+        for airport in airports.iter_mut().filter(|a| a.min_connection_minutes == 0) {
+            airport.min_connection_minutes = Airport::default_connection_minutes(&airport.airport_size);
+        }
         
-        println!("✈️ Loaded {} airports", airports.len());
+        self.messages.say(format!("✈️ Loaded {} airports", airports.len()));
         Ok(airports)
     }
 
     pub async fn save_airports(&self, airports: &[Airport]) -> Result<(), Box<dyn std::error::Error>> {
         let file_path = format!("{}/airports.json", self.data_dir);
-        let content = serde_json::to_string_pretty(airports)?;
-        fs::write(&file_path, content)?;
+        let content = self.to_json(airports)?;
+        self.write_file(&file_path, &content)?;
         
-        println!("💾 Saved {} airports", airports.len());
+        self.messages.say(format!("💾 Saved {} airports", airports.len()));
         Ok(())
     }
 
@@ -96,23 +525,23 @@ impl DataPersistence {
     pub async fn load_aircraft(&self) -> Result<Vec<Aircraft>, Box<dyn std::error::Error>> {
         let file_path = format!("{}/aircraft.json", self.data_dir);
         
-        if !Path::new(&file_path).exists() {
+        if !self.file_exists(&file_path) {
             return Ok(Vec::new());
         }
 
-        let content = fs::read_to_string(&file_path)?;
+        let content = self.read_file(&file_path)?;
         let aircraft: Vec<Aircraft> = serde_json::from_str(&content)?;
         
-        println!("🛩️ Loaded {} aircraft", aircraft.len());
+        self.messages.say(format!("🛩️ Loaded {} aircraft", aircraft.len()));
         Ok(aircraft)
     }
 
     pub async fn save_aircraft(&self, aircraft: &[Aircraft]) -> Result<(), Box<dyn std::error::Error>> {
         let file_path = format!("{}/aircraft.json", self.data_dir);
-        let content = serde_json::to_string_pretty(aircraft)?;
-        fs::write(&file_path, content)?;
+        let content = self.to_json(aircraft)?;
+        self.write_file(&file_path, &content)?;
         
-        println!("💾 Saved {} aircraft", aircraft.len());
+        self.messages.say(format!("💾 Saved {} aircraft", aircraft.len()));
         Ok(())
     }
 
@@ -120,23 +549,23 @@ impl DataPersistence {
     pub async fn load_flights(&self) -> Result<Vec<Flight>, Box<dyn std::error::Error>> {
         let file_path = format!("{}/flights.json", self.data_dir);
         
-        if !Path::new(&file_path).exists() {
+        if !self.file_exists(&file_path) {
             return Ok(Vec::new());
         }
 
-        let content = fs::read_to_string(&file_path)?;
+        let content = self.read_file(&file_path)?;
         let flights: Vec<Flight> = serde_json::from_str(&content)?;
         
-        println!("🛫 Loaded {} flights", flights.len());
+        self.messages.say(format!("🛫 Loaded {} flights", flights.len()));
         Ok(flights)
     }
 
     pub async fn save_flights(&self, flights: &[Flight]) -> Result<(), Box<dyn std::error::Error>> {
         let file_path = format!("{}/flights.json", self.data_dir);
-        let content = serde_json::to_string_pretty(flights)?;
-        fs::write(&file_path, content)?;
+        let content = self.to_json(flights)?;
+        self.write_file(&file_path, &content)?;
         
-        println!("💾 Saved {} flights", flights.len());
+        self.messages.say(format!("💾 Saved {} flights", flights.len()));
         Ok(())
     }
 
@@ -144,23 +573,121 @@ impl DataPersistence {
     pub async fn load_bookings(&self) -> Result<Vec<Booking>, Box<dyn std::error::Error>> {
         let file_path = format!("{}/bookings.json", self.data_dir);
         
-        if !Path::new(&file_path).exists() {
+        if !self.file_exists(&file_path) {
             return Ok(Vec::new());
         }
 
-        let content = fs::read_to_string(&file_path)?;
+        let content = self.read_file(&file_path)?;
         let bookings: Vec<Booking> = serde_json::from_str(&content)?;
         
-        println!("🎫 Loaded {} bookings", bookings.len());
+        self.messages.say(format!("🎫 Loaded {} bookings", bookings.len()));
         Ok(bookings)
     }
 
     pub async fn save_bookings(&self, bookings: &[Booking]) -> Result<(), Box<dyn std::error::Error>> {
         let file_path = format!("{}/bookings.json", self.data_dir);
-        let content = serde_json::to_string_pretty(bookings)?;
-        fs::write(&file_path, content)?;
+        let content = self.to_json(bookings)?;
+        self.write_file(&file_path, &content)?;
         
-        println!("💾 Saved {} bookings", bookings.len());
+        self.messages.say(format!("💾 Saved {} bookings", bookings.len()));
+        Ok(())
+    }
+
+    // Loyalty Account Management
+    pub async fn load_loyalty_accounts(&self) -> Result<Vec<LoyaltyAccount>, Box<dyn std::error::Error>> {
+        let file_path = format!("{}/loyalty.json", self.data_dir);
+
+        if !self.file_exists(&file_path) {
+            return Ok(Vec::new());
+        }
+
+        let content = self.read_file(&file_path)?;
+        let accounts: Vec<LoyaltyAccount> = serde_json::from_str(&content)?;
+
+        self.messages.say(format!("⭐ Loaded {} loyalty accounts", accounts.len()));
+        Ok(accounts)
+    }
+
+    pub async fn save_loyalty_accounts(&self, accounts: &[LoyaltyAccount]) -> Result<(), Box<dyn std::error::Error>> {
+        let file_path = format!("{}/loyalty.json", self.data_dir);
+        let content = self.to_json(accounts)?;
+        self.write_file(&file_path, &content)?;
+
+        self.messages.say(format!("💾 Saved {} loyalty accounts", accounts.len()));
+        Ok(())
+    }
+
+    // Admin Account Management
+    pub async fn load_admin_credentials(&self) -> Result<Vec<AdminCredential>, Box<dyn std::error::Error>> {
+        let file_path = format!("{}/admins.json", self.data_dir);
+
+        if !self.file_exists(&file_path) {
+            return Ok(Vec::new());
+        }
+
+        let content = self.read_file(&file_path)?;
+        let credentials: Vec<AdminCredential> = serde_json::from_str(&content)?;
+
+        self.messages.say(format!("🔐 Loaded {} admin accounts", credentials.len()));
+        Ok(credentials)
+    }
+
+    pub async fn save_admin_credentials(&self, credentials: &[AdminCredential]) -> Result<(), Box<dyn std::error::Error>> {
+        let file_path = format!("{}/admins.json", self.data_dir);
+        let content = self.to_json(credentials)?;
+        self.write_file(&file_path, &content)?;
+
+        self.messages.say(format!("💾 Saved {} admin accounts", credentials.len()));
+        Ok(())
+    }
+
+    // Route Preference Management
+    pub async fn load_route_preferences(&self) -> Result<Vec<RoutePreference>, Box<dyn std::error::Error>> {
+        let file_path = format!("{}/route_preferences.json", self.data_dir);
+
+        if !self.file_exists(&file_path) {
+            return Ok(Vec::new());
+        }
+
+        let content = self.read_file(&file_path)?;
+        let preferences: Vec<RoutePreference> = serde_json::from_str(&content)?;
+
+        self.messages.say(format!("🛫 Loaded {} route preferences", preferences.len()));
+        Ok(preferences)
+    }
+
+    pub async fn save_route_preferences(&self, preferences: &[RoutePreference]) -> Result<(), Box<dyn std::error::Error>> {
+        let file_path = format!("{}/route_preferences.json", self.data_dir);
+        let content = self.to_json(preferences)?;
+        self.write_file(&file_path, &content)?;
+
+        self.messages.say(format!("💾 Saved {} route preferences", preferences.len()));
+        Ok(())
+    }
+
+    // Audit Log Management
+    pub async fn load_audit_log(&self) -> Result<Vec<AdminAction>, Box<dyn std::error::Error>> {
+        let file_path = format!("{}/audit_log.json", self.data_dir);
+
+        if !self.file_exists(&file_path) {
+            return Ok(Vec::new());
+        }
+
+        let content = self.read_file(&file_path)?;
+        let actions: Vec<AdminAction> = serde_json::from_str(&content)?;
+
+        self.messages.say(format!("📜 Loaded {} audit log entries", actions.len()));
+        Ok(actions)
+    }
+
+    /// Save actions (oldest first), keeping only the newest `MAX_AUDIT_LOG_FILE_ENTRIES`
+    pub async fn save_audit_log(&self, actions: &[AdminAction]) -> Result<(), Box<dyn std::error::Error>> {
+        let file_path = format!("{}/audit_log.json", self.data_dir);
+        let kept = &actions[actions.len().saturating_sub(crate::config::MAX_AUDIT_LOG_FILE_ENTRIES)..];
+        let content = self.to_json(kept)?;
+        self.write_file(&file_path, &content)?;
+
+        self.messages.say(format!("💾 Saved {} audit log entries", kept.len()));
         Ok(())
     }
 
@@ -224,7 +751,7 @@ impl DataPersistence {
         ];
 
         self.save_airports(&airports).await?;
-        println!("🌍 Created sample airports database");
+        self.messages.say("🌍 Created sample airports database");
         Ok(())
     }
 
@@ -269,7 +796,7 @@ impl DataPersistence {
         ];
 
         self.save_aircraft(&aircraft).await?;
-        println!("🛩️ Created sample aircraft database");
+        self.messages.say("🛩️ Created sample aircraft database");
         Ok(())
     }
 
@@ -297,10 +824,24 @@ impl DataPersistence {
             ("CDG", "LAX", "RIA001", "Rust International Airways"),
         ];
 
+        let airports = self.load_airports().await?;
+        let distance = |origin: &str, destination: &str| {
+            let coordinates = |code: &str| airports.iter().find(|a| a.code == code).map(|a| &a.coordinates);
+            match (coordinates(origin), coordinates(destination)) {
+                (Some(from), Some(to)) => crate::utils::calculate_distance(from.latitude, from.longitude, to.latitude, to.longitude),
+                _ => 0.0,
+            }
+        };
+
         let mut flights = Vec::new();
 
         for (i, (origin, destination, flight_num, airline)) in sample_routes.iter().enumerate() {
-            let aircraft_id = aircraft[i % aircraft.len()].id;
+            // Rotate through the fleet, skipping aircraft that can't fly the route
+            let route_km = distance(origin, destination);
+            let assigned = (0..aircraft.len())
+                .map(|offset| &aircraft[(i + offset) % aircraft.len()])
+                .find(|a| a.performance.range_km as f64 >= route_km)
+                .unwrap_or(&aircraft[i % aircraft.len()]);
             let departure_time = base_time + Duration::hours(i as i64 * 3);
             let flight_duration = Duration::hours(8 + (i as i64 % 4)); // 8-11 hour flights
             let arrival_time = departure_time + flight_duration;
@@ -312,16 +853,14 @@ impl DataPersistence {
                 destination.to_string(),
                 departure_time,
                 arrival_time,
-                aircraft_id,
-                aircraft[i % aircraft.len()].total_capacity,
+                assigned,
             );
 
             // Add some variety to flight statuses
             match i % 4 {
-                0 => flight.status = FlightStatus::OnTime,
-                1 => flight.set_delay(15),
-                2 => flight.status = FlightStatus::Boarding,
-                3 => flight.set_delay(30),
+                1 => flight.set_delay(15)?,
+                2 => flight.try_transition(FlightStatus::Boarding)?,
+                3 => flight.set_delay(30)?,
                 _ => {}
             }
 
@@ -333,7 +872,7 @@ impl DataPersistence {
         }
 
         self.save_flights(&flights).await?;
-        println!("🛫 Created sample flights database");
+        self.messages.say("🛫 Created sample flights database");
         Ok(())
     }
 
@@ -358,31 +897,242 @@ impl DataPersistence {
         self.save_bookings(&database.bookings).await?;
         self.save_airports(&database.airports).await?;
         
-        println!("💾 Saved complete airport database");
+        self.messages.say("💾 Saved complete airport database");
         Ok(())
     }
 
     // Backup operations
+    /// Pack the data files into `<timestamp>.tar.gz` under the backup directory, returning
+    /// the archive's path
     pub async fn create_backup(&self) -> Result<String, Box<dyn std::error::Error>> {
-        let timestamp = Utc::now().format("%Y%m%d_%H%M%S");
-        let backup_dir = format!("{}/backups/{}", self.data_dir, timestamp);
-        
-        fs::create_dir_all(&backup_dir)?;
-        
-        // Copy all data files to backup directory
-        let files = ["airports.json", "aircraft.json", "flights.json", "bookings.json"];
-        
-        for file in &files {
+        let timestamp = Utc::now().format("%Y%m%d_%H%M%S").to_string();
+        fs::create_dir_all(self.backup_dir())?;
+        let mut name = timestamp.clone();
+        // Never overwrite a backup taken earlier in the same second, and keep the new one
+        // sorting after it
+        let existing = self.list_backups();
+        let mut suffix = 1;
+        while existing.iter().any(|backup| backup.starts_with(&timestamp) && *backup >= name) {
+            name = format!("{}_{}", timestamp, suffix);
+            suffix += 1;
+        }
+        let archive_path = self.backup_archive_path(&name);
+
+        let encoder = GzEncoder::new(fs::File::create(&archive_path)?, Compression::default());
+        let mut archive = tar::Builder::new(encoder);
+        for file in &DATA_FILES {
             let source = format!("{}/{}", self.data_dir, file);
-            let destination = format!("{}/{}", backup_dir, file);
-            
-            if Path::new(&source).exists() {
-                fs::copy(&source, &destination)?;
+            if self.file_exists(&source) {
+                let content = self.read_file(&source)?;
+                let mut header = tar::Header::new_gnu();
+                header.set_size(content.len() as u64);
+                header.set_mode(0o644);
+                header.set_mtime(Utc::now().timestamp().max(0) as u64);
+                header.set_cksum();
+                archive.append_data(&mut header, file, content.as_bytes())?;
             }
         }
-        
-        println!("📋 Created backup: {}", backup_dir);
-        Ok(backup_dir)
+        archive.into_inner()?.finish()?;
+
+        let size = fs::metadata(&archive_path)?.len();
+        self.messages.say(format!("📋 Created backup: {} ({:.1} KB)", archive_path, size as f64 / 1024.0));
+        Ok(archive_path)
+    }
+
+    fn backup_archive_path(&self, timestamp: &str) -> String {
+        format!("{}/{}{}", self.backup_dir(), timestamp, BACKUP_EXTENSION)
+    }
+
+    /// Timestamps of the backups on disk, oldest first. Directories left by older versions,
+    /// which copied the files instead of archiving them, are listed too.
+    pub fn list_backups(&self) -> Vec<String> {
+        let mut backups: Vec<String> = fs::read_dir(self.backup_dir())
+            .map(|entries| {
+                entries
+                    .flatten()
+                    .filter_map(|entry| {
+                        let name = entry.file_name().to_string_lossy().to_string();
+                        if entry.path().is_dir() {
+                            Some(name)
+                        } else {
+                            name.strip_suffix(BACKUP_EXTENSION).map(str::to_string)
+                        }
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        backups.sort();
+        backups
+    }
+
+    /// Delete all but the newest `keep` backup archives, returning how many were removed
+    pub fn prune_backups(&self, keep: usize) -> Result<usize, Box<dyn std::error::Error>> {
+        let archives: Vec<String> = self
+            .list_backups()
+            .into_iter()
+            .map(|timestamp| self.backup_archive_path(&timestamp))
+            .filter(|path| Path::new(path).is_file())
+            .collect();
+        let excess = archives.len().saturating_sub(keep);
+        for path in &archives[..excess] {
+            fs::remove_file(path)?;
+        }
+        if excess > 0 {
+            self.messages.say(format!("🧹 Pruned {} old backup(s)", excess));
+        }
+        Ok(excess)
+    }
+
+    /// The data files stored in a backup, by file name
+    fn read_backup(&self, timestamp: &str) -> Result<HashMap<String, String>, Box<dyn std::error::Error>> {
+        let mut contents = HashMap::new();
+        let archive_path = self.backup_archive_path(timestamp);
+        let legacy_dir = format!("{}/{}", self.backup_dir(), timestamp);
+
+        if Path::new(&archive_path).is_file() {
+            let mut archive = tar::Archive::new(GzDecoder::new(fs::File::open(&archive_path)?));
+            for entry in archive.entries()? {
+                let mut entry = entry?;
+                let name = entry.path()?.to_string_lossy().to_string();
+                if DATA_FILES.contains(&name.as_str()) {
+                    let mut content = String::new();
+                    entry.read_to_string(&mut content)?;
+                    contents.insert(name, content);
+                }
+            }
+        } else if Path::new(&legacy_dir).is_dir() {
+            for file in &DATA_FILES {
+                let source = format!("{}/{}", legacy_dir, file);
+                if Path::new(&source).exists() {
+                    contents.insert(file.to_string(), fs::read_to_string(&source)?);
+                }
+            }
+        } else {
+            return Err(format!("Backup '{}' not found", timestamp).into());
+        }
+        Ok(contents)
+    }
+
+    /// Copy the backup taken at `timestamp` over the live data files. The current files are
+    /// backed up first, so a restore can itself be rolled back.
+    pub async fn restore_backup(&self, timestamp: &str) -> Result<(), Box<dyn std::error::Error>> {
+        if timestamp.is_empty() || timestamp.contains(['/', '\\']) || timestamp.contains("..") {
+            return Err(format!("Invalid backup name '{}'", timestamp).into());
+        }
+
+        // Read everything up front so a bad backup leaves the live data untouched
+        let contents = self.read_backup(timestamp)?;
+        let missing: Vec<&str> = REQUIRED_BACKUP_FILES
+            .iter()
+            .copied()
+            .filter(|file| !contents.contains_key(*file))
+            .collect();
+        if !missing.is_empty() {
+            return Err(format!("Backup '{}' is incomplete, missing: {}", timestamp, missing.join(", ")).into());
+        }
+
+        self.create_backup().await?;
+
+        for file in &DATA_FILES {
+            if let Some(content) = contents.get(*file) {
+                self.write_file(&format!("{}/{}", self.data_dir, file), content)?;
+            }
+        }
+
+        self.messages.say(format!("♻️ Restored backup: {}", timestamp));
+        Ok(())
+    }
+
+    // Export operations
+    /// Save a text export under the exports directory, returning its path
+    pub async fn save_export(&self, file_name: &str, content: &str) -> Result<String, Box<dyn std::error::Error>> {
+        let export_dir = self.export_dir();
+        if !self.is_in_memory() {
+            fs::create_dir_all(&export_dir)?;
+        }
+
+        let path = format!("{}/{}", export_dir, file_name);
+        self.write_file(&path, content)?;
+        self.messages.say(format!("📄 Saved export: {}", path));
+        Ok(path)
+    }
+
+    /// Write bookings as CSV: ticket, passenger, flight_id, class, status, amount
+    pub async fn export_bookings_csv(&self, bookings: &[Booking], path: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let mut csv = String::from("ticket,passenger,flight_id,class,status,amount\n");
+        for booking in bookings {
+            let row = [
+                booking.ticket_number.clone(),
+                booking.passenger.full_name(),
+                booking.flight_id.to_string(),
+                format!("{:?}", booking.seat_class),
+                format!("{:?}", booking.status),
+                format!("{:.2}", booking.payment.total_amount),
+            ];
+            csv.push_str(&csv_row(&row));
+        }
+
+        self.write_file(path, &csv)?;
+        self.messages.say(format!("📄 Exported {} bookings to {}", bookings.len(), path));
+        Ok(())
+    }
+
+    /// Write flights as CSV: flight_number, origin, destination, departure, arrival, status
+    pub async fn export_flights_csv(&self, flights: &[Flight], path: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let mut csv = String::from("flight_number,origin,destination,departure,arrival,status\n");
+        for flight in flights {
+            let row = [
+                flight.flight_number.clone(),
+                flight.origin.clone(),
+                flight.destination.clone(),
+                flight.departure_time.to_rfc3339(),
+                flight.arrival_time.to_rfc3339(),
+                format!("{:?}", flight.status),
+            ];
+            csv.push_str(&csv_row(&row));
+        }
+
+        self.write_file(path, &csv)?;
+        self.messages.say(format!("📄 Exported {} flights to {}", flights.len(), path));
+        Ok(())
+    }
+
+    /// Write the database, metrics and pricing rules as one pretty JSON document, handy
+    /// for attaching to a bug report
+    pub async fn export_snapshot(
+        &self,
+        database: &AirportDatabase,
+        metrics: &SystemMetrics,
+        pricing_rules: &[PricingRule],
+        path: &str,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let snapshot = SnapshotOut {
+            schema_version: SNAPSHOT_SCHEMA_VERSION,
+            created_at: Utc::now(),
+            database,
+            metrics,
+            pricing_rules,
+        };
+        self.write_file(path, &serde_json::to_string_pretty(&snapshot)?)?;
+        self.messages.say(format!("📸 Wrote system snapshot to {}", path));
+        Ok(())
+    }
+
+    /// Read the database back out of a snapshot written by `export_snapshot`
+    pub async fn import_snapshot(&self, path: &str) -> Result<AirportDatabase, Box<dyn std::error::Error>> {
+        let content = self.read_file(path)?;
+        let document: serde_json::Value = serde_json::from_str(&content)?;
+        match document.get("schema_version").and_then(|v| v.as_u64()) {
+            Some(version) if version == SNAPSHOT_SCHEMA_VERSION as u64 => {}
+            Some(version) => return Err(format!(
+                "Snapshot {} uses schema version {}; only version {} is supported",
+                path, version, SNAPSHOT_SCHEMA_VERSION
+            ).into()),
+            None => return Err(format!("{} is not a system snapshot (no schema version)", path).into()),
+        }
+
+        let snapshot: SnapshotIn = serde_json::from_value(document)?;
+        Ok(snapshot.database)
     }
 
     // Data validation
@@ -407,6 +1157,14 @@ impl DataPersistence {
             }
         }
         
+        // Validate flight number uniqueness
+        let mut seen_numbers = std::collections::HashSet::new();
+        for flight in &database.flights {
+            if !seen_numbers.insert(&flight.flight_number) {
+                issues.push(format!("Duplicate flight number: {}", flight.flight_number));
+            }
+        }
+        
         // Validate airport codes in flights
         let airport_codes: Vec<&String> = database.airports.iter().map(|a| &a.code).collect();
         for flight in &database.flights {
@@ -419,13 +1177,509 @@ impl DataPersistence {
                     flight.flight_number, flight.destination));
             }
         }
+
+        // Validate gates against the origin airport's terminals
+        for flight in &database.flights {
+            if let Some(gate) = &flight.gate {
+                if !database.airports.iter().any(|a| a.code == flight.origin && a.get_all_gates().contains(gate)) {
+                    issues.push(format!("Flight {} is assigned to invalid gate {} at {}", 
+                        flight.flight_number, gate, flight.origin));
+                }
+            }
+        }
+
+        // Flag flights whose aircraft can't reach the destination. Missing airports and
+        // aircraft are reported above, so only range problems are added here.
+        for flight in &database.flights {
+            let known = airport_codes.contains(&&flight.origin)
+                && airport_codes.contains(&&flight.destination)
+                && database.aircraft.iter().any(|a| a.id == flight.aircraft_id);
+            if !known {
+                continue;
+            }
+            if let Err(issue) = database.validate_flight_range(flight) {
+                issues.push(issue);
+            }
+        }
+
+        // Flag departures and arrivals scheduled while the airport is closed
+        for flight in &database.flights {
+            let movements = [(&flight.origin, flight.departure_time, "departs"), (&flight.destination, flight.arrival_time, "arrives")];
+            for (code, time, movement) in movements {
+                let closed = database.airports.iter().find(|a| &a.code == code).and_then(|a| a.closed_at(time));
+                if let Some(reason) = closed {
+                    issues.push(format!("Flight {} {} during closed hours: {}", flight.flight_number, movement, reason));
+                }
+            }
+        }
         
         if issues.is_empty() {
-            println!("✅ Data integrity validation passed");
+            self.messages.say("✅ Data integrity validation passed");
         } else {
-            println!("⚠️ Found {} data integrity issues", issues.len());
+            self.messages.say(format!("⚠️ Found {} data integrity issues", issues.len()));
         }
         
         Ok(issues)
     }
-}
\ No newline at end of file
+
+    /// Propose a fix for each repairable integrity issue: flights on a missing aircraft move
+    /// to an active aircraft with the range and seats for them, gates the origin airport
+    /// doesn't have are cleared, and bookings for missing flights are removed. Unless
+    /// `dry_run` is set the fixes are applied and saved. Returns the actions either way.
+    pub async fn repair_data_integrity(&self, dry_run: bool) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+        let mut database = self.load_all_data().await?;
+        let mut actions = Vec::new();
+
+        for i in 0..database.flights.len() {
+            let flight = &database.flights[i];
+            if database.aircraft.iter().any(|a| a.id == flight.aircraft_id) {
+                continue;
+            }
+            // Same rules as reassigning by hand: free for the flight's times, every cabin
+            // big enough for the seats sold, and in range of the route
+            let fallback = database.aircraft
+                .iter()
+                .filter(|a| database.is_aircraft_free(a, flight.departure_time, flight.arrival_time))
+                .find_map(|a| {
+                    let mut refitted = AirportDatabase::fit_cabins(flight, a).ok()?;
+                    refitted.aircraft_id = a.id;
+                    database.validate_flight_range(&refitted).ok()?;
+                    Some((refitted, a.registration.clone(), a.seat_configuration.clone()))
+                });
+
+            match fallback {
+                Some((refitted, registration, seat_configuration)) => {
+                    actions.push(format!("Reassign flight {} from missing aircraft {} to {}", 
+                        flight.flight_number, flight.aircraft_id, registration));
+                    let flight_id = flight.id;
+                    database.flights[i] = refitted;
+                    database.clear_invalid_seat_assignments(flight_id, &seat_configuration);
+                }
+                None => actions.push(format!("No active aircraft can take over flight {}; left on missing aircraft {}", 
+                    flight.flight_number, flight.aircraft_id)),
+            }
+        }
+
+        for flight in &mut database.flights {
+            let Some(gate) = &flight.gate else { continue };
+            if !database.airports.iter().any(|a| a.code == flight.origin && a.get_all_gates().contains(gate)) {
+                actions.push(format!("Clear invalid gate {} from flight {} at {}", gate, flight.flight_number, flight.origin));
+                flight.gate = None;
+            }
+        }
+
+        let flights = &database.flights;
+        database.bookings.retain(|booking| {
+            let exists = flights.iter().any(|f| f.id == booking.flight_id);
+            if !exists {
+                actions.push(format!("Remove booking {} for non-existent flight {}", booking.ticket_number, booking.flight_id));
+            }
+            exists
+        });
+
+        if dry_run || actions.is_empty() {
+            self.messages.say(format!("🔎 {} repair action(s) proposed", actions.len()));
+        } else {
+            self.save_all_data(&database).await?;
+            self.messages.say(format!("🔧 Applied {} repair action(s)", actions.len()));
+        }
+        Ok(actions)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::chaos::{FailureInjector, FailureMode};
+    use crate::modules::booking::{Passenger, PassengerType};
+    use crate::modules::flight::SeatClass;
+
+    fn temp_dir() -> String {
+        let dir = std::env::temp_dir().join(format!("ria-test-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        dir.to_string_lossy().to_string()
+    }
+
+    fn sample_database() -> AirportDatabase {
+        let aircraft = Aircraft::new("N1TEST".to_string(), "Boeing 737-800".to_string(), "Boeing".to_string(), 2020);
+        let departure = Utc::now() + Duration::hours(4);
+        let flight = Flight::new(
+            "RIA900".to_string(),
+            "Rust International Airways".to_string(),
+            "LAX".to_string(),
+            "JFK".to_string(),
+            departure,
+            departure + Duration::hours(5),
+            &aircraft,
+        );
+        let passenger = Passenger::new(
+            "Ada".to_string(),
+            "Lovelace".to_string(),
+            "ada@example.com".to_string(),
+            "5551234567".to_string(),
+            "1990-01-01".to_string(),
+            PassengerType::Adult,
+        );
+        let booking = Booking::new(flight.id, passenger, SeatClass::Economy, 299.99, "Credit Card".to_string());
+
+        AirportDatabase {
+            flights: vec![flight],
+            aircraft: vec![aircraft],
+            bookings: vec![booking],
+            airports: Vec::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_integrity_check_flags_flights_in_closed_hours() {
+        use chrono::TimeZone;
+
+        let dir = temp_dir();
+        let persistence = DataPersistence::for_tests(&dir);
+        let mut database = sample_database();
+        database.airports = vec![
+            Airport::new("LAX".to_string(), "KLAX".to_string(), "Los Angeles International Airport".to_string(),
+                "Los Angeles".to_string(), "United States".to_string(), "America/Los_Angeles".to_string(), 33.9425, -118.4081, 38),
+            Airport::new("JFK".to_string(), "KJFK".to_string(), "John F. Kennedy International Airport".to_string(),
+                "New York".to_string(), "United States".to_string(), "America/New_York".to_string(), 40.6413, -73.7781, 4),
+        ];
+        // Leaves LAX at 02:00 local and lands at JFK at 10:00 local
+        database.flights[0].departure_time = Utc.with_ymd_and_hms(2025, 7, 1, 9, 0, 0).unwrap();
+        database.flights[0].arrival_time = Utc.with_ymd_and_hms(2025, 7, 1, 14, 0, 0).unwrap();
+        persistence.save_all_data(&database).await.unwrap();
+
+        let issues = persistence.validate_data_integrity().await.unwrap();
+
+        assert_eq!(issues, vec![
+            "Flight RIA900 departs during closed hours: LAX is closed at 02:00 local time (open 05:00-23:59 local)".to_string(),
+        ]);
+        fs::remove_dir_all(dir).ok();
+    }
+
+    #[tokio::test]
+    async fn test_disk_full_surfaces_error() {
+        let dir = temp_dir();
+        let persistence = DataPersistence::for_tests(&dir)
+            .with_failure_injection(FailureInjector::always(FailureMode::DiskFull));
+
+        let result = persistence.save_all_data(&sample_database()).await;
+
+        let error = result.expect_err("save should fail when the disk is full");
+        assert!(error.to_string().contains("No space left"));
+        assert!(!Path::new(&format!("{}/flights.json", dir)).exists());
+        fs::remove_dir_all(dir).ok();
+    }
+
+    #[tokio::test]
+    async fn test_partial_write_surfaces_error() {
+        let dir = temp_dir();
+        let database = sample_database();
+        DataPersistence::for_tests(&dir).save_flights(&database.flights).await.unwrap();
+        let flights_path = format!("{}/flights.json", dir);
+        let before = fs::read_to_string(&flights_path).unwrap();
+        let persistence = DataPersistence::for_tests(&dir)
+            .with_failure_injection(FailureInjector::always(FailureMode::PartialWrite));
+
+        assert!(persistence.save_flights(&database.flights).await.is_err());
+        // The truncated content never reaches the real file
+        assert_eq!(fs::read_to_string(&flights_path).unwrap(), before);
+        fs::remove_dir_all(dir).ok();
+    }
+
+    #[tokio::test]
+    async fn test_interrupted_write_keeps_previous_file() {
+        let dir = temp_dir();
+        let database = sample_database();
+        DataPersistence::for_tests(&dir).save_all_data(&database).await.unwrap();
+        let flights_path = format!("{}/flights.json", dir);
+        let before = fs::read_to_string(&flights_path).unwrap();
+
+        let mut changed = database.flights.clone();
+        changed[0].flight_number = "RIA999".to_string();
+        let failing = DataPersistence::for_tests(&dir)
+            .with_failure_injection(FailureInjector::always(FailureMode::PartialWrite));
+        assert!(failing.save_flights(&changed).await.is_err());
+
+        // The half-written content never replaced the good file, and no scratch file is left
+        assert_eq!(fs::read_to_string(&flights_path).unwrap(), before);
+        assert!(!Path::new(&format!("{}{}", flights_path, TEMP_FILE_SUFFIX)).exists());
+
+        // A crash between writing and renaming leaves only a stray temp file behind
+        fs::write(format!("{}{}", flights_path, TEMP_FILE_SUFFIX), "{\"trunc").unwrap();
+        let persistence = DataPersistence::for_tests(&dir);
+        assert_eq!(persistence.load_flights().await.unwrap()[0].flight_number, database.flights[0].flight_number);
+        persistence.save_flights(&changed).await.unwrap();
+        assert_eq!(persistence.load_flights().await.unwrap()[0].flight_number, "RIA999");
+        fs::remove_dir_all(dir).ok();
+    }
+
+    #[tokio::test]
+    async fn test_corrupt_read_is_an_error_not_a_panic() {
+        let dir = temp_dir();
+        let database = sample_database();
+        DataPersistence::for_tests(&dir).save_all_data(&database).await.unwrap();
+
+        let persistence = DataPersistence::for_tests(&dir)
+            .with_failure_injection(FailureInjector::always(FailureMode::CorruptRead));
+
+        assert!(persistence.load_all_data().await.is_err());
+        fs::remove_dir_all(dir).ok();
+    }
+
+    #[tokio::test]
+    async fn test_zero_rate_never_fails() {
+        let dir = temp_dir();
+        let database = sample_database();
+        let injector = FailureInjector::new(
+            vec![FailureMode::DiskFull, FailureMode::PartialWrite, FailureMode::CorruptRead],
+            0.0,
+            42,
+        );
+        let persistence = DataPersistence::for_tests(&dir).with_failure_injection(injector);
+
+        persistence.save_all_data(&database).await.unwrap();
+        let loaded = persistence.load_all_data().await.unwrap();
+
+        assert_eq!(loaded.flights.len(), 1);
+        assert_eq!(loaded.bookings.len(), 1);
+        fs::remove_dir_all(dir).ok();
+    }
+
+    #[tokio::test]
+    async fn test_compact_and_pretty_files_both_load() {
+        let mut database = sample_database();
+        let template = database.bookings[0].clone();
+        database.bookings = (0..2000).map(|_| template.clone()).collect();
+
+        let mut sizes = Vec::new();
+        for format in [JsonFormat::Pretty, JsonFormat::Compact] {
+            let dir = temp_dir();
+            let persistence = DataPersistence::for_tests(&dir).with_json_format(format);
+            persistence.save_bookings(&database.bookings).await.unwrap();
+
+            let loaded = persistence.load_bookings().await.unwrap();
+            assert_eq!(loaded.len(), 2000);
+            sizes.push(fs::metadata(format!("{}/bookings.json", dir)).unwrap().len());
+            fs::remove_dir_all(dir).ok();
+        }
+
+        assert!(sizes[1] * 10 < sizes[0] * 9, "compact output should be at least 10% smaller");
+    }
+
+    #[tokio::test]
+    async fn test_unwritable_data_dir_falls_back() {
+        let blocker = temp_dir();
+        fs::write(format!("{}/file", blocker), "not a directory").unwrap();
+        let unwritable = format!("{}/file/data", blocker);
+        let fallback_root = temp_dir();
+        let fallback = format!("{}/fallback", fallback_root);
+
+        let mut persistence = DataPersistence::for_tests(&unwritable).with_fallback_dirs(vec![fallback.clone()]);
+        persistence.initialize().await.unwrap();
+
+        let status = persistence.storage_status();
+        assert_eq!(status.data_dir, fallback);
+        assert!(status.writable);
+        assert_eq!(status.mode, StorageMode::Fallback { original: unwritable });
+        assert!(Path::new(&format!("{}/flights.json", fallback)).exists());
+        fs::remove_dir_all(blocker).ok();
+        fs::remove_dir_all(fallback_root).ok();
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_mode_when_nothing_is_writable() {
+        let blocker = temp_dir();
+        fs::write(format!("{}/file", blocker), "not a directory").unwrap();
+
+        let mut persistence = DataPersistence::for_tests(&format!("{}/file/data", blocker));
+        persistence.initialize().await.unwrap();
+        assert!(!persistence.storage_status().writable);
+
+        let mut database = persistence.load_all_data().await.unwrap();
+        assert!(!database.flights.is_empty());
+        database.bookings = sample_database().bookings;
+        persistence.save_all_data(&database).await.unwrap();
+
+        assert_eq!(persistence.load_bookings().await.unwrap().len(), 1);
+        fs::remove_dir_all(blocker).ok();
+    }
+
+    #[tokio::test]
+    async fn test_csv_exports_quote_fields_with_commas() {
+        let mut database = sample_database();
+        database.bookings[0].passenger.last_name = "Lovelace, Countess".to_string();
+        database.bookings[0].passenger.first_name = "Ada \"Augusta\"".to_string();
+        let dir = temp_dir();
+        let persistence = DataPersistence::for_tests(&dir);
+        let bookings_path = format!("{}/bookings.csv", dir);
+        let flights_path = format!("{}/flights.csv", dir);
+
+        persistence.export_bookings_csv(&database.bookings, &bookings_path).await.unwrap();
+        persistence.export_flights_csv(&database.flights, &flights_path).await.unwrap();
+
+        let bookings = fs::read_to_string(&bookings_path).unwrap();
+        let mut lines = bookings.lines();
+        assert_eq!(lines.next(), Some("ticket,passenger,flight_id,class,status,amount"));
+        let row = lines.next().unwrap();
+        assert!(row.contains(",\"Ada \"\"Augusta\"\" Lovelace, Countess\","), "{}", row);
+        assert!(row.ends_with(",Economy,Confirmed,299.99"), "{}", row);
+
+        let flights = fs::read_to_string(&flights_path).unwrap();
+        assert_eq!(flights.lines().next(), Some("flight_number,origin,destination,departure,arrival,status"));
+        assert!(flights.lines().nth(1).unwrap().starts_with("RIA900,LAX,JFK,"));
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[tokio::test]
+    async fn test_audit_log_round_trips_and_rotates() {
+        let dir = temp_dir();
+        let persistence = DataPersistence::for_tests(&dir);
+        let admin_id = uuid::Uuid::new_v4();
+        let actions: Vec<AdminAction> = (0..crate::config::MAX_AUDIT_LOG_FILE_ENTRIES + 3)
+            .map(|i| AdminAction::new(admin_id, "TEST".to_string(), format!("action {}", i), None, None, Some(i.to_string())))
+            .collect();
+
+        persistence.save_audit_log(&actions[..2]).await.unwrap();
+        let loaded = persistence.load_audit_log().await.unwrap();
+        assert_eq!(loaded.len(), 2);
+        assert_eq!(loaded[1].id, actions[1].id);
+        assert_eq!(loaded[1].description, "action 1");
+        assert_eq!(loaded[1].new_value.as_deref(), Some("1"));
+
+        persistence.save_audit_log(&actions).await.unwrap();
+        let loaded = persistence.load_audit_log().await.unwrap();
+        assert_eq!(loaded.len(), crate::config::MAX_AUDIT_LOG_FILE_ENTRIES);
+        assert_eq!(loaded[0].description, "action 3");
+        assert_eq!(loaded.last().unwrap().id, actions.last().unwrap().id);
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[tokio::test]
+    async fn test_backup_archives_restore_and_prune() {
+        let dir = temp_dir();
+        let persistence = DataPersistence::for_tests(&dir);
+        let mut database = sample_database();
+        persistence.save_all_data(&database).await.unwrap();
+        let backup = persistence.create_backup().await.unwrap();
+        assert!(backup.ends_with(".tar.gz"));
+        let timestamp = persistence.list_backups()[0].clone();
+
+        database.bookings.clear();
+        persistence.save_all_data(&database).await.unwrap();
+        persistence.restore_backup(&timestamp).await.unwrap();
+
+        assert_eq!(persistence.load_bookings().await.unwrap().len(), 1);
+        let backups = persistence.list_backups();
+        assert_eq!(backups.len(), 2);
+        assert_eq!(backups[0], timestamp);
+
+        assert_eq!(persistence.prune_backups(1).unwrap(), 1);
+        assert_eq!(persistence.list_backups().len(), 1);
+        assert!(persistence.restore_backup(&timestamp).await.is_err());
+
+        // A backup missing one of the core files is refused
+        fs::remove_file(format!("{}/bookings.json", dir)).unwrap();
+        persistence.create_backup().await.unwrap();
+        let incomplete = persistence.list_backups().pop().unwrap();
+        let error = persistence.restore_backup(&incomplete).await.expect_err("incomplete backup should be refused");
+        assert!(error.to_string().contains("bookings.json"));
+        assert!(persistence.restore_backup("../nope").await.is_err());
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[tokio::test]
+    async fn test_repair_data_integrity_dry_run_then_apply() {
+        let dir = temp_dir();
+        let persistence = DataPersistence::for_tests(&dir);
+        let mut database = sample_database();
+        database.airports = vec![
+            Airport::new("LAX".to_string(), "KLAX".to_string(), "Los Angeles International Airport".to_string(),
+                "Los Angeles".to_string(), "United States".to_string(), "America/Los_Angeles".to_string(), 33.9425, -118.4081, 38),
+            Airport::new("JFK".to_string(), "KJFK".to_string(), "John F. Kennedy International Airport".to_string(),
+                "New York".to_string(), "United States".to_string(), "America/New_York".to_string(), 40.6413, -73.7781, 4),
+        ];
+        let spare = Aircraft::new("N2SPARE".to_string(), "Boeing 777-300ER".to_string(), "Boeing".to_string(), 2021);
+        let spare_id = spare.id;
+        database.aircraft = vec![spare];
+        database.flights[0].gate = Some("Z99".to_string());
+        let orphan = Booking::new(uuid::Uuid::new_v4(), database.bookings[0].passenger.clone(), SeatClass::Economy, 99.0, "Cash".to_string());
+        let orphan_ticket = orphan.ticket_number.clone();
+        database.bookings.push(orphan);
+        persistence.save_all_data(&database).await.unwrap();
+
+        let proposed = persistence.repair_data_integrity(true).await.unwrap();
+        assert_eq!(proposed.len(), 3);
+        assert!(proposed[0].starts_with("Reassign flight RIA900") && proposed[0].ends_with("N2SPARE"));
+        assert_eq!(proposed[1], "Clear invalid gate Z99 from flight RIA900 at LAX");
+        assert!(proposed[2].contains(&orphan_ticket));
+        assert_eq!(persistence.load_bookings().await.unwrap().len(), 2);
+
+        assert_eq!(persistence.repair_data_integrity(false).await.unwrap(), proposed);
+        let repaired = persistence.load_all_data().await.unwrap();
+        assert_eq!(repaired.flights[0].aircraft_id, spare_id);
+        assert_eq!(repaired.flights[0].gate, None);
+        assert_eq!(repaired.bookings.len(), 1);
+        let remaining = persistence.validate_data_integrity().await.unwrap();
+        assert!(!remaining.iter().any(|issue| issue.contains("non-existent") || issue.contains("gate")));
+        fs::remove_dir_all(dir).ok();
+    }
+
+    #[tokio::test]
+    async fn test_repair_refits_replacement_aircraft_and_avoids_double_booking() {
+        let dir = temp_dir();
+        let persistence = DataPersistence::for_tests(&dir);
+        let mut database = sample_database();
+        database.airports = vec![
+            Airport::new("LAX".to_string(), "KLAX".to_string(), "Los Angeles International Airport".to_string(),
+                "Los Angeles".to_string(), "United States".to_string(), "America/Los_Angeles".to_string(), 33.9425, -118.4081, 38),
+            Airport::new("JFK".to_string(), "KJFK".to_string(), "John F. Kennedy International Airport".to_string(),
+                "New York".to_string(), "United States".to_string(), "America/New_York".to_string(), 40.6413, -73.7781, 4),
+        ];
+        database.flights[0].book_seat(&SeatClass::Economy).unwrap();
+        database.bookings[0].assign_seat("32A".to_string()); // Exists on the 737, not on an A320
+        // A second flight at the same time, also on the missing 737
+        let mut overlapping = database.flights[0].clone();
+        overlapping.id = uuid::Uuid::new_v4();
+        overlapping.flight_number = "RIA901".to_string();
+        database.flights.push(overlapping);
+        let spare = Aircraft::new("N3SPARE".to_string(), "Airbus A320".to_string(), "Airbus".to_string(), 2019);
+        let spare_economy = spare.get_seats_by_class(&SeatClass::Economy);
+        database.aircraft = vec![spare.clone()];
+        persistence.save_all_data(&database).await.unwrap();
+
+        let actions = persistence.repair_data_integrity(false).await.unwrap();
+
+        assert!(actions[0].starts_with("Reassign flight RIA900") && actions[0].ends_with("N3SPARE"));
+        assert!(actions[1].starts_with("No active aircraft can take over flight RIA901"));
+        let repaired = persistence.load_all_data().await.unwrap();
+        let flight = &repaired.flights[0];
+        assert_eq!(flight.aircraft_id, spare.id);
+        assert_eq!(flight.total_capacity, spare.total_capacity);
+        assert_eq!(flight.get_available_seats(&SeatClass::Economy), spare_economy - 1);
+        assert!(repaired.bookings[0].seat_assignment.is_none());
+        assert_ne!(repaired.flights[1].aircraft_id, spare.id);
+        fs::remove_dir_all(dir).ok();
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_round_trip_and_version_check() {
+        let dir = temp_dir();
+        let persistence = DataPersistence::for_tests(&dir);
+        let database = sample_database();
+        let path = format!("{}/snapshot.json", dir);
+
+        persistence.export_snapshot(&database, &SystemMetrics::new(), &[], &path).await.unwrap();
+        let content = fs::read_to_string(&path).unwrap();
+        assert!(content.contains("\n  \"schema_version\": 1,"));
+        assert!(content.contains("\"metrics\""));
+
+        let imported = persistence.import_snapshot(&path).await.unwrap();
+        assert_eq!(imported.flights[0].id, database.flights[0].id);
+        assert_eq!(imported.bookings[0].ticket_number, database.bookings[0].ticket_number);
+
+        fs::write(&path, content.replace("\"schema_version\": 1", "\"schema_version\": 99")).unwrap();
+        let error = persistence.import_snapshot(&path).await.unwrap_err();
+        assert!(error.to_string().contains("schema version 99"));
+        fs::remove_dir_all(dir).ok();
+    }
+}
@@ -24,7 +24,7 @@
 //! 
 //! ## Usage
 //! 
-//! ```rust
+//! ```rust,no_run
 //! use rust_international_airport::data::manager::DataManager;
 //! use rust_international_airport::ui::menu::MainMenu;
 //! 
@@ -48,6 +48,8 @@ pub mod modules {
     pub mod booking;
     pub mod airport;
     pub mod admin;
+    pub mod loyalty;
+    pub mod currency;
 }
 
 pub mod data {
@@ -58,6 +60,10 @@ pub mod data {
     
     pub mod manager;
     pub mod persistence;
+    pub mod chaos;
+    pub mod health;
+    pub mod messages;
+    pub mod index;
 }
 
 pub mod ui {
@@ -82,7 +88,8 @@ pub use modules::{
 
 pub use data::{
     manager::DataManager,
-    persistence::{DataPersistence, AirportDatabase},
+    messages::MessageSink,
+    persistence::{DataPersistence, AirportDatabase, JsonFormat, StorageMode, StorageStatus},
 };
 
 pub use ui::{
@@ -107,12 +114,11 @@ pub const DEFAULT_HUB_CODE: &str = "RIA";
 pub const MAX_PASSENGERS_PER_FLIGHT: u32 = 853; // Airbus A380 capacity
 
 /// Maximum delay time in minutes before automatic cancellation
-pub const MAX_DELAY_MINUTES: i32 = 480; // 8 hours
+pub use config::delays::MAX_DELAY_MINUTES;
 
 /// Default currency for pricing
 pub const DEFAULT_CURRENCY: &str = "USD";
 
-/// System configuration and constants
 pub mod config {
     //! System configuration constants and default values.
     
@@ -125,9 +131,67 @@ pub mod config {
     /// Simulation update interval in seconds
     pub const SIMULATION_UPDATE_INTERVAL: u64 = 60;
     
-    /// Maximum number of recent admin actions to display
+    /// Shortest layover offered between connecting flights at an airport the system doesn't know
+    pub const MIN_CONNECTION_MINUTES: i64 = 45;
+    
+    /// Extra connection time for an international-to-international transfer through customs
+    pub const INTERNATIONAL_CONNECTION_BUFFER_MINUTES: i64 = 30;
+
+    /// Most passengers that can be booked together under one reservation
+    pub const MAX_GROUP_SIZE: u32 = 9;
+
+    /// Online check-in opens this many hours before departure
+    pub const CHECK_IN_WINDOW_HOURS: i64 = 24;
+
+    /// A gate is held for this many minutes either side of a flight's departure
+    pub const GATE_BUFFER_MINUTES: i64 = 90;
+
+    /// Boarding opens this many minutes before departure
+    pub const BOARDING_WINDOW_MINUTES: i64 = 30;
+    
+    /// Flights longer than this many hours serve a meal unless overridden
+    pub const MEAL_SERVICE_MIN_HOURS: i64 = 3;
+    
+    /// Rows shown per page in long tables
+    pub const TABLE_PAGE_SIZE: usize = 20;
+    
+    /// Attempts allowed to type the confirmation phrase for a destructive admin action
+    pub const DESTRUCTIVE_CONFIRMATION_ATTEMPTS: u32 = 3;
+    
+    /// Shortest password accepted for a new admin account
+    pub const MIN_ADMIN_PASSWORD_LENGTH: usize = 8;
+    
+    /// Actions kept in the live admin log; older ones move to the archive
     pub const MAX_ADMIN_LOG_ENTRIES: usize = 100;
     
+    /// Actions kept in audit_log.json; the oldest are dropped beyond this
+    pub const MAX_AUDIT_LOG_FILE_ENTRIES: usize = 1000;
+    
+    /// Backup archives the admin panel offers to keep when pruning old ones
+    pub const BACKUPS_TO_KEEP: usize = 10;
+    
+    /// System metrics not refreshed for this many minutes are reported as stale
+    pub const METRICS_STALE_MINUTES: i64 = 15;
+    
+    /// Jet fuel price (USD per liter) used for operating cost estimates
+    pub const FUEL_PRICE_PER_LITER: f64 = 0.85;
+    
+    /// Display currencies. Everything is priced and charged in USD.
+    pub mod currency {
+        pub const BASE_CURRENCY: &str = "USD";
+        /// Exchange rates file in the data directory; overrides the defaults below
+        pub const RATES_FILE: &str = "exchange_rates.json";
+        /// Units per USD used when no rates file provides one
+        pub const DEFAULT_RATES: [(&str, f64); 6] = [
+            ("USD", 1.0),
+            ("EUR", 0.92),
+            ("GBP", 0.79),
+            ("CAD", 1.37),
+            ("AUD", 1.52),
+            ("JPY", 151.0),
+        ];
+    }
+    
     /// Default seat distribution percentages
     pub mod seats {
         pub const ECONOMY_PERCENTAGE: f32 = 0.70;
@@ -141,6 +205,61 @@ pub mod config {
         pub const BASE_BUSINESS_PRICE: f64 = 899.99;
         pub const BASE_FIRST_CLASS_PRICE: f64 = 1999.99;
         pub const DEFAULT_MULTIPLIER: f64 = 1.0;
+        
+        // Share of the full fare paid by each passenger type
+        pub const CHILD_FARE_RATE: f64 = 0.75;
+        pub const INFANT_FARE_RATE: f64 = 0.10; // Lap infant, no seat of their own
+        pub const SENIOR_FARE_RATE: f64 = 0.90;
+
+        // Distance-based base fares: a per-km rate with a minimum, by class.
+        // Tuned so a ~4,000 km route like LAX-JFK lands near the flat defaults above.
+        pub const ECONOMY_FARE_PER_KM: f64 = 0.075;
+        pub const BUSINESS_FARE_PER_KM: f64 = 0.225;
+        pub const FIRST_CLASS_FARE_PER_KM: f64 = 0.50;
+        pub const MIN_ECONOMY_FARE: f64 = 89.0;
+        pub const MIN_BUSINESS_FARE: f64 = 249.0;
+        pub const MIN_FIRST_CLASS_FARE: f64 = 499.0;
+
+        // Demand pricing: fares rise by DEMAND_INCREMENT for every DEMAND_STEP of load
+        // beyond DEMAND_THRESHOLD (+5% per 10% over 60% full, so +20% when sold out)
+        pub const DEMAND_THRESHOLD: f64 = 0.60;
+        pub const DEMAND_STEP: f64 = 0.10;
+        pub const DEMAND_INCREMENT: f64 = 0.05;
+
+        // Share taken off both legs when outbound and return are booked together
+        pub const ROUND_TRIP_DISCOUNT: f64 = 0.05;
+    }
+    
+    /// Frequent-flyer programme
+    pub mod loyalty {
+        /// Miles needed for each tier
+        pub const SILVER_MILES: u64 = 25_000;
+        pub const GOLD_MILES: u64 = 50_000;
+        
+        /// Miles earned per km flown in each class
+        pub const ECONOMY_MILES_MULTIPLIER: f64 = 1.0;
+        pub const BUSINESS_MILES_MULTIPLIER: f64 = 1.5;
+        pub const FIRST_CLASS_MILES_MULTIPLIER: f64 = 2.0;
+    }
+    
+    /// Refunds when a passenger cancels their own booking
+    pub mod refunds {
+        /// Cancelling earlier than this before departure refunds the full fare
+        pub const FULL_REFUND_HOURS: i64 = 72;
+        /// Between this and FULL_REFUND_HOURS, PARTIAL_REFUND_RATE of the fare is refunded;
+        /// closer to departure nothing is
+        pub const PARTIAL_REFUND_HOURS: i64 = 24;
+        pub const PARTIAL_REFUND_RATE: f64 = 0.5;
+    }
+    
+    /// Flight delay policy (in minutes)
+    pub mod delays {
+        /// Furthest a flight may be brought forward
+        pub const MIN_DELAY_MINUTES: i32 = -60;
+        /// Longest delay accepted; anything beyond this means the flight should be cancelled
+        pub const MAX_DELAY_MINUTES: i32 = 480; // 8 hours
+        /// Shortest time an aircraft needs on the ground between two flights
+        pub const MIN_TURNAROUND_MINUTES: i64 = 45;
     }
     
     /// Baggage allowances by seat class (in kg)
@@ -148,14 +267,27 @@ pub mod config {
         pub const ECONOMY_ALLOWANCE: u32 = 23;
         pub const BUSINESS_ALLOWANCE: u32 = 32;
         pub const FIRST_CLASS_ALLOWANCE: u32 = 46;
+        
+        // Bags included in the fare; extra bags are charged per piece
+        pub const CABIN_BAGS_INCLUDED: u32 = 1;
+        pub const ECONOMY_CHECKED_BAGS_INCLUDED: u32 = 1;
+        pub const BUSINESS_CHECKED_BAGS_INCLUDED: u32 = 2;
+        pub const FIRST_CLASS_CHECKED_BAGS_INCLUDED: u32 = 3;
+        pub const EXTRA_CABIN_BAG_FEE: f64 = 35.0;
+        pub const EXTRA_CHECKED_BAG_FEE: f64 = 60.0;
+        /// Charged per kg of checked baggage over the class weight allowance
+        pub const OVERWEIGHT_FEE_PER_KG: f64 = 15.0;
+        
+        // Planning weights used for load estimates (in kg)
+        pub const AVERAGE_CABIN_BAG_WEIGHT: u32 = 8;
+        pub const AVERAGE_CHECKED_BAG_WEIGHT: u32 = 20;
     }
 }
 
-/// Utility functions for the airport system
 pub mod utils {
     //! Utility functions and helpers for common operations.
     
-    use chrono::{DateTime, Utc, Duration};
+    use chrono::{DateTime, Duration, Utc};
     
     /// Calculate the distance between two geographical points using the Haversine formula
     pub fn calculate_distance(lat1: f64, lon1: f64, lat2: f64, lon2: f64) -> f64 {
@@ -173,6 +305,39 @@ pub mod utils {
         r * c
     }
     
+    /// `segments + 1` evenly spaced (latitude, longitude) points along the great circle
+    /// from the first coordinate to the second, endpoints included, by spherical linear
+    /// interpolation. Points too close (or exactly opposite) to define a single great
+    /// circle are interpolated in latitude and longitude instead.
+    pub fn great_circle_path(lat1: f64, lon1: f64, lat2: f64, lon2: f64, segments: usize) -> Vec<(f64, f64)> {
+        let segments = segments.max(1);
+        let to_vector = |lat: f64, lon: f64| {
+            let (lat, lon) = (lat.to_radians(), lon.to_radians());
+            [lat.cos() * lon.cos(), lat.cos() * lon.sin(), lat.sin()]
+        };
+        let start = to_vector(lat1, lon1);
+        let end = to_vector(lat2, lon2);
+        let dot: f64 = start.iter().zip(&end).map(|(a, b)| a * b).sum();
+        let angle = dot.clamp(-1.0, 1.0).acos();
+
+        let mut path: Vec<(f64, f64)> = (0..=segments)
+            .map(|i| {
+                let t = i as f64 / segments as f64;
+                if angle.sin().abs() < 1e-9 {
+                    return (lat1 + (lat2 - lat1) * t, lon1 + (lon2 - lon1) * t);
+                }
+                let a = ((1.0 - t) * angle).sin() / angle.sin();
+                let b = (t * angle).sin() / angle.sin();
+                let [x, y, z] = [0, 1, 2].map(|k| a * start[k] + b * end[k]);
+                (z.atan2((x * x + y * y).sqrt()).to_degrees(), y.atan2(x).to_degrees())
+            })
+            .collect();
+        // Exact endpoints rather than round-tripped ones
+        path[0] = (lat1, lon1);
+        path[segments] = (lat2, lon2);
+        path
+    }
+    
     /// Estimate flight duration based on distance
     pub fn estimate_flight_duration(distance_km: f64) -> Duration {
         // Average commercial aircraft speed: 850 km/h
@@ -187,6 +352,70 @@ pub mod utils {
         format!("{}h {}m", hours, minutes)
     }
     
+    /// Describe `target` relative to `now` from a departing passenger's point of view,
+    /// e.g. "in 2h 15m", "boarding now" or "departed 35m ago". Flights a day or more
+    /// out show their date instead.
+    pub fn format_relative_time(target: DateTime<Utc>, now: DateTime<Utc>) -> String {
+        let until = target - now;
+        
+        if until < Duration::zero() {
+            let since = -until;
+            return if since < Duration::minutes(1) {
+                "departed just now".to_string()
+            } else if since < Duration::days(1) {
+                format!("departed {} ago", format_short_duration(since))
+            } else {
+                format!("departed {}", target.format("%b %d"))
+            };
+        }
+        
+        if until <= Duration::minutes(crate::config::BOARDING_WINDOW_MINUTES) {
+            "boarding now".to_string()
+        } else if until < Duration::days(1) {
+            format!("in {}", format_short_duration(until))
+        } else {
+            format!("on {}", target.format("%b %d"))
+        }
+    }
+    
+    // "2h 15m", dropping the hours when there are none
+    fn format_short_duration(duration: Duration) -> String {
+        match duration.num_hours() {
+            0 => format!("{}m", duration.num_minutes()),
+            hours => format!("{}h {}m", hours, duration.num_minutes() % 60),
+        }
+    }
+    
+    /// Small deterministic random number generator (xorshift64) for reproducible simulations
+    #[derive(Debug, Clone)]
+    pub struct SeededRng {
+        state: u64,
+    }
+    
+    impl SeededRng {
+        pub fn new(seed: u64) -> Self {
+            // xorshift must never be seeded with zero
+            Self { state: seed.max(1) }
+        }
+        
+        pub fn next_u64(&mut self) -> u64 {
+            self.state ^= self.state << 13;
+            self.state ^= self.state >> 7;
+            self.state ^= self.state << 17;
+            self.state
+        }
+        
+        /// Uniform value in [0, 1)
+        pub fn next_f64(&mut self) -> f64 {
+            (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+        }
+        
+        /// Uniform index in [0, bound); `bound` must be non-zero
+        pub fn below(&mut self, bound: usize) -> usize {
+            (self.next_u64() % bound as u64) as usize
+        }
+    }
+    
     /// Generate a random seat number for a given row and seat count
     pub fn generate_seat_number(row: u32, max_seats_per_row: u32) -> String {
         let seat_letters = ['A', 'B', 'C', 'D', 'E', 'F', 'G', 'H', 'J', 'K'];
@@ -199,9 +428,21 @@ pub mod utils {
         code.len() == 3 && code.chars().all(|c| c.is_ascii_uppercase())
     }
     
+    /// Validate a flight number (2-3 letter airline prefix followed by 1-4 digits, e.g. "RIA101")
+    pub fn validate_flight_number(flight_number: &str) -> bool {
+        let prefix_len = flight_number.chars().take_while(|c| c.is_ascii_uppercase()).count();
+        let digits = &flight_number[prefix_len..];
+        (2..=3).contains(&prefix_len)
+            && (1..=4).contains(&digits.len())
+            && digits.chars().all(|c| c.is_ascii_digit())
+    }
+    
     /// Validate an email address (basic validation)
     pub fn validate_email(email: &str) -> bool {
-        email.contains('@') && email.contains('.') && email.len() > 5
+        match email.split_once('@') {
+            Some((local, domain)) => !local.is_empty() && domain.contains('.') && email.len() > 5,
+            None => false,
+        }
     }
     
     /// Format currency amount
@@ -224,7 +465,6 @@ pub mod utils {
     }
 }
 
-/// Error types specific to the airport system
 pub mod errors {
     //! Custom error types for the airport management system.
     
@@ -248,9 +488,25 @@ pub mod errors {
         #[error("No seats available in {class:?}")]
         NoSeatsAvailable { class: crate::SeatClass },
         
+        #[error("Flight not found: {flight_number}")]
+        FlightNumberNotFound { flight_number: String },
+        
+        #[error("Delay of {minutes} minutes is outside the allowed range ({min} to {max} minutes)")]
+        DelayOutOfRange { minutes: i32, min: i32, max: i32 },
+        
         #[error("Flight {flight_number} is not available for booking")]
         FlightNotAvailable { flight_number: String },
         
+        #[error("Flight {flight_number} has already departed - the booking can be completed but not cancelled")]
+        FlightAlreadyDeparted { flight_number: String },
+        
+        #[error("Flight {flight_number} cannot change from {from:?} to {to:?}")]
+        InvalidStatusTransition {
+            flight_number: String,
+            from: crate::modules::flight::FlightStatus,
+            to: crate::modules::flight::FlightStatus,
+        },
+        
         #[error("Insufficient permissions for operation: {operation}")]
         InsufficientPermissions { operation: String },
         
@@ -271,9 +527,28 @@ pub mod errors {
 mod tests {
     //! Unit tests for the airport system.
     
-    use super::*;
     use crate::utils::*;
     
+    #[test]
+    fn test_great_circle_path() {
+        let path = great_circle_path(33.9425, -118.4081, 40.6413, -73.7781, 10);
+        assert_eq!(path.len(), 11);
+        assert_eq!(path[0], (33.9425, -118.4081));
+        assert_eq!(path[10], (40.6413, -73.7781));
+        // Evenly spaced along the route
+        let total = calculate_distance(33.9425, -118.4081, 40.6413, -73.7781);
+        for pair in path.windows(2) {
+            let leg = calculate_distance(pair[0].0, pair[0].1, pair[1].0, pair[1].1);
+            assert!((leg - total / 10.0).abs() < 0.01);
+        }
+
+        // An east-west route along the equator stays on it
+        let equator = great_circle_path(0.0, 10.0, 0.0, 50.0, 4);
+        let (lat, lon) = equator[2];
+        assert!(lat.abs() < 1e-9);
+        assert!((lon - 30.0).abs() < 1e-9);
+    }
+
     #[test]
     fn test_distance_calculation() {
         // Distance between LAX and JFK (approximately 3944 km)
@@ -290,6 +565,32 @@ mod tests {
         assert!(!validate_airport_code("LA"));
     }
     
+    #[test]
+    fn test_flight_number_validation() {
+        assert!(validate_flight_number("RIA101"));
+        assert!(validate_flight_number("BA9"));
+        assert!(!validate_flight_number("RIA"));
+        assert!(!validate_flight_number("ria101"));
+        assert!(!validate_flight_number("RIA10A"));
+        assert!(!validate_flight_number("R101"));
+        assert!(!validate_flight_number("RIA12345"));
+    }
+    
+    #[test]
+    fn test_relative_time_formatting() {
+        use chrono::{Duration, TimeZone, Utc};
+        
+        let now = Utc.with_ymd_and_hms(2025, 3, 14, 12, 0, 0).unwrap();
+        assert_eq!(format_relative_time(now + Duration::minutes(135), now), "in 2h 15m");
+        assert_eq!(format_relative_time(now + Duration::minutes(45), now), "in 45m");
+        assert_eq!(format_relative_time(now + Duration::minutes(10), now), "boarding now");
+        assert_eq!(format_relative_time(now, now), "boarding now");
+        assert_eq!(format_relative_time(now - Duration::seconds(20), now), "departed just now");
+        assert_eq!(format_relative_time(now - Duration::minutes(35), now), "departed 35m ago");
+        assert_eq!(format_relative_time(now - Duration::days(2), now), "departed Mar 12");
+        assert_eq!(format_relative_time(now + Duration::days(3), now), "on Mar 17");
+    }
+    
     #[test]
     fn test_email_validation() {
         assert!(validate_email("user@example.com"));
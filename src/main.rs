@@ -5,50 +5,222 @@ use crossterm::{
     style::{Color, Print, ResetColor, SetForegroundColor},
 };
 use std::io::{self, Write};
+use clap::{Parser, Subcommand, ValueEnum};
 use colored::*;
 
-mod modules;
-mod ui;
-mod data;
+use rust_international_airport::{DataManager, MainMenu, DisplayManager, MessageSink, StorageMode, StorageStatus};
+use rust_international_airport::modules::booking::{Passenger, PassengerType};
+use rust_international_airport::modules::flight::SeatClass;
+use rust_international_airport::ui::display::init_color;
+use rust_international_airport::utils;
 
-use ui::menu::MainMenu;
-use data::manager::DataManager;
+/// Rust International Airport. Starts the interactive menus unless a subcommand is given.
+#[derive(Parser)]
+#[command(name = "airport", version)]
+struct Cli {
+    /// Plain output without colors or screen clearing
+    #[arg(long, global = true)]
+    no_color: bool,
+
+    /// Directory holding the data files (default: data)
+    #[arg(long, global = true, value_name = "DIR")]
+    data_dir: Option<String>,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run the health checks; exits 1 if any fail
+    SelfCheck,
+    /// List flights, optionally by route, date and class with seats left
+    Search {
+        #[arg(long)]
+        origin: Option<String>,
+        #[arg(long)]
+        dest: Option<String>,
+        /// Departure date, YYYY-MM-DD
+        #[arg(long, value_parser = parse_date)]
+        date: Option<chrono::DateTime<chrono::Utc>>,
+        #[arg(long, value_enum)]
+        class: Option<CliSeatClass>,
+    },
+    /// Book one passenger on a flight and print the ticket number
+    Book {
+        #[arg(long)]
+        flight: String,
+        #[arg(long, value_enum, default_value = "economy")]
+        class: CliSeatClass,
+        /// Passenger's full name, e.g. "Ada Lovelace"
+        #[arg(long)]
+        name: String,
+        #[arg(long)]
+        email: String,
+        #[arg(long, default_value = "")]
+        phone: String,
+        /// Date of birth, YYYY-MM-DD
+        #[arg(long, default_value = "")]
+        dob: String,
+        #[arg(long, value_enum, default_value = "adult")]
+        passenger_type: CliPassengerType,
+    },
+    /// Print the system metrics dashboard
+    Metrics,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum CliSeatClass {
+    Economy,
+    Business,
+    First,
+}
+
+impl From<CliSeatClass> for SeatClass {
+    fn from(class: CliSeatClass) -> Self {
+        match class {
+            CliSeatClass::Economy => SeatClass::Economy,
+            CliSeatClass::Business => SeatClass::Business,
+            CliSeatClass::First => SeatClass::FirstClass,
+        }
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum CliPassengerType {
+    Adult,
+    Child,
+    Infant,
+    Senior,
+}
+
+impl From<CliPassengerType> for PassengerType {
+    fn from(passenger_type: CliPassengerType) -> Self {
+        match passenger_type {
+            CliPassengerType::Adult => PassengerType::Adult,
+            CliPassengerType::Child => PassengerType::Child,
+            CliPassengerType::Infant => PassengerType::Infant,
+            CliPassengerType::Senior => PassengerType::Senior,
+        }
+    }
+}
+
+fn parse_date(value: &str) -> Result<chrono::DateTime<chrono::Utc>, String> {
+    chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .map(|date| date.and_hms_opt(0, 0, 0).unwrap().and_utc())
+        .map_err(|_| format!("{} is not a YYYY-MM-DD date", value))
+}
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let cli = Cli::parse();
+
+    // Color is off for pipes, NO_COLOR and --no-color; everything below honours the decision
+    let color = init_color(cli.no_color);
+
+    if let Some(command) = cli.command {
+        let code = run_command(command, cli.data_dir.as_deref(), color).await?;
+        std::process::exit(code);
+    }
+
     // Initialize the terminal
     let mut stdout = io::stdout();
     
-    // Clear screen and show welcome
-    execute!(
-        stdout,
-        Clear(ClearType::All),
-        cursor::MoveTo(0, 0)
-    )?;
-
-    // Display welcome banner
-    display_welcome_banner()?;
-    
     // Initialize data manager
-    let mut data_manager = DataManager::new().await?;
+    let data_manager = DataManager::new_with_dir(cli.data_dir.as_deref(), MessageSink::Stdout).await?;
+
+    // Clear screen and show welcome, including where data is stored
+    if color {
+        execute!(
+            stdout,
+            Clear(ClearType::All),
+            cursor::MoveTo(0, 0)
+        )?;
+    }
+    display_welcome_banner(&data_manager.persistence.storage_status(), color)?;
     
     // Create and run main menu
     let mut main_menu = MainMenu::new(data_manager);
     main_menu.run().await?;
 
     // Clean exit
-    execute!(stdout, ResetColor)?;
+    if color {
+        execute!(stdout, ResetColor)?;
+    }
     println!("\n{}", "Thank you for using Rust International Airport! ✈️".bright_cyan());
     
     Ok(())
 }
 
-fn display_welcome_banner() -> Result<(), Box<dyn std::error::Error>> {
+/// Run one subcommand without the menus and return the process exit code
+async fn run_command(command: Command, data_dir: Option<&str>, color: bool) -> Result<i32, Box<dyn std::error::Error>> {
+    let display = DisplayManager::new_with_color(color);
+
+    match command {
+        Command::SelfCheck => {
+            let data_manager = DataManager::new_with_dir(data_dir, MessageSink::Stdout).await?;
+            let report = data_manager.self_check().await;
+            display.display_health_report(&report)?;
+            Ok(if report.has_failures() { 1 } else { 0 })
+        }
+        Command::Search { origin, dest, date, class } => {
+            let data_manager = DataManager::new_with_dir(data_dir, MessageSink::Quiet).await?;
+            let origin = origin.map(|code| code.to_uppercase());
+            let dest = dest.map(|code| code.to_uppercase());
+            let class = class.map(SeatClass::from);
+            let flights = data_manager.search_flights_with_class(origin.as_deref(), dest.as_deref(), date, class.as_ref());
+            display.display_flights_table(&flights, &data_manager.database.aircraft, &data_manager.database.airports)?;
+            Ok(0)
+        }
+        Command::Book { flight, class, name, email, phone, dob, passenger_type } => {
+            let mut data_manager = DataManager::new_with_dir(data_dir, MessageSink::Quiet).await?;
+            let Some(flight_id) = data_manager.get_flight_by_number(&flight.to_uppercase()).map(|f| f.id) else {
+                eprintln!("Flight {} not found", flight);
+                return Ok(1);
+            };
+            if !utils::validate_email(&email) {
+                eprintln!("{} is not a valid email address", email);
+                return Ok(1);
+            }
+            let (first_name, last_name) = name.trim().split_once(' ').unwrap_or((name.trim(), ""));
+            let passenger = Passenger::new(
+                first_name.to_string(),
+                last_name.trim().to_string(),
+                email,
+                phone,
+                dob,
+                passenger_type.into(),
+            );
+
+            match data_manager.create_booking(flight_id, passenger, class.into()) {
+                Ok(booking_id) => {
+                    data_manager.save_all_data().await?;
+                    let booking = data_manager.get_booking_by_id(booking_id).ok_or("Booking vanished after creation")?;
+                    println!("{} ${:.2}", booking.ticket_number, booking.payment.total_amount);
+                    Ok(0)
+                }
+                Err(e) => {
+                    eprintln!("Booking failed: {}", e);
+                    Ok(1)
+                }
+            }
+        }
+        Command::Metrics => {
+            let data_manager = DataManager::new_with_dir(data_dir, MessageSink::Quiet).await?;
+            display.display_system_metrics(data_manager.get_system_metrics())?;
+            Ok(0)
+        }
+    }
+}
+
+fn display_welcome_banner(storage: &StorageStatus, color: bool) -> Result<(), Box<dyn std::error::Error>> {
     let mut stdout = io::stdout();
-    
+
+    if color {
+        execute!(stdout, SetForegroundColor(Color::Cyan))?;
+    }
     execute!(
         stdout,
-        SetForegroundColor(Color::Cyan),
         Print("╔══════════════════════════════════════════════════════════════╗\n"),
         Print("║                                                              ║\n"),
         Print("║            🛫  RUST INTERNATIONAL AIRPORT  🛬               ║\n"),
@@ -57,9 +229,17 @@ fn display_welcome_banner() -> Result<(), Box<dyn std::error::Error>> {
         Print("║                        Version 1.0.0                        ║\n"),
         Print("║                                                              ║\n"),
         Print("╚══════════════════════════════════════════════════════════════╝\n"),
-        ResetColor,
-        Print("\n")
     )?;
+    if color {
+        execute!(stdout, ResetColor)?;
+    }
+    println!();
+
+    let location = format!("📂 Data: {}", storage.get_summary());
+    match storage.mode {
+        StorageMode::Primary => println!("{}\n", location.bright_blue()),
+        _ => println!("{}\n", location.bright_yellow().bold()),
+    }
     
     stdout.flush()?;
     std::thread::sleep(std::time::Duration::from_millis(1500));
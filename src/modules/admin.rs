@@ -1,9 +1,10 @@
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use chrono::{DateTime, Utc};
 use uuid::Uuid;
+use sha2::{Digest, Sha256};
 use crate::modules::flight::{Flight, FlightStatus};
 use crate::modules::aircraft::{Aircraft, AircraftStatus};
-use std::collections::HashMap;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum AdminLevel {
@@ -26,6 +27,15 @@ pub struct AdminUser {
     pub is_active: bool,
 }
 
+/// An admin account as stored in `admins.json`: the user plus a salted SHA-256 hash of
+/// their password. The password itself is never kept.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AdminCredential {
+    pub user: AdminUser,
+    pub salt: String,
+    pub password_hash: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AdminAction {
     pub id: Uuid,
@@ -49,9 +59,17 @@ pub struct SystemMetrics {
     pub aircraft_in_maintenance: u32,
     pub total_bookings: u32,
     pub revenue_today: f64,
+    #[serde(default)]
+    pub revenue_yesterday: f64,   // Previous UTC day's takings, for comparison
     pub revenue_month: f64,
     pub average_load_factor: f64, // Percentage of seats filled
     pub last_updated: DateTime<Utc>,
+    #[serde(default = "Utc::now")]
+    pub last_revenue_reset: DateTime<Utc>, // Start of the accounting day revenue_today covers
+    #[serde(default)]
+    pub projected_fuel_cost: f64, // Estimated fuel spend of flights yet to depart
+    #[serde(default)]
+    pub no_shows: HashMap<String, u32>, // Passengers who missed each departed flight, by flight number
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -66,12 +84,79 @@ pub struct PricingRule {
     pub created_date: DateTime<Utc>,
 }
 
+/// Aircraft a route should preferably be flown with, e.g. widebodies on long-haul
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RoutePreference {
+    pub id: Uuid,
+    pub name: String,
+    pub route_pattern: String,         // Same syntax as PricingRule, e.g. "*-LHR"
+    pub preferred_models: Vec<String>, // Most preferred first; empty accepts any model
+    pub min_capacity: Option<u32>,
+    pub is_active: bool,
+}
+
 #[derive(Debug)]
 pub struct AdminPanel {
     pub current_admin: Option<AdminUser>,
-    pub audit_log: Vec<AdminAction>,
+    pub audit_log: Vec<AdminAction>,     // Most recent actions, capped at MAX_ADMIN_LOG_ENTRIES
+    pub archived_log: Vec<AdminAction>,  // Older actions moved out of the live log, oldest first
     pub pricing_rules: Vec<PricingRule>,
+    pub route_preferences: Vec<RoutePreference>,
     pub system_metrics: SystemMetrics,
+    pub credentials: Vec<AdminCredential>,
+}
+
+impl AdminCredential {
+    pub fn new(user: AdminUser, password: &str) -> Self {
+        let salt = Uuid::new_v4().simple().to_string();
+        let password_hash = hash_password(&salt, password);
+        Self { user, salt, password_hash }
+    }
+
+    pub fn verify(&self, password: &str) -> bool {
+        hash_password(&self.salt, password) == self.password_hash
+    }
+
+    /// The demo accounts every fresh install starts with
+    pub fn demo_accounts() -> Vec<Self> {
+        vec![
+            Self::new(
+                AdminUser::new(
+                    "admin".to_string(),
+                    "System Administrator".to_string(),
+                    "admin@rust-airport.com".to_string(),
+                    AdminLevel::SuperAdmin,
+                ),
+                "admin123",
+            ),
+            Self::new(
+                AdminUser::new(
+                    "flight_mgr".to_string(),
+                    "Flight Manager".to_string(),
+                    "flights@rust-airport.com".to_string(),
+                    AdminLevel::FlightManager,
+                ),
+                "flight123",
+            ),
+            Self::new(
+                AdminUser::new(
+                    "aircraft_mgr".to_string(),
+                    "Aircraft Manager".to_string(),
+                    "aircraft@rust-airport.com".to_string(),
+                    AdminLevel::AircraftManager,
+                ),
+                "aircraft123",
+            ),
+        ]
+    }
+}
+
+/// Hex SHA-256 of the salt followed by the password
+fn hash_password(salt: &str, password: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(salt.as_bytes());
+    hasher.update(password.as_bytes());
+    hasher.finalize().iter().map(|byte| format!("{:02x}", byte)).collect()
 }
 
 impl AdminUser {
@@ -173,6 +258,31 @@ impl AdminAction {
     }
 }
 
+/// Match a route against a pattern such as "LAX-JFK", "LAX-*", "*-JFK" or "*-LH*".
+/// Shared by pricing rules and route preferences so patterns behave the same everywhere.
+pub fn route_matches(pattern: &str, origin: &str, destination: &str) -> bool {
+    let route = format!("{}-{}", origin, destination);
+    if pattern.contains('*') {
+        // Wildcard matching
+        if pattern.starts_with('*') && pattern.ends_with('*') {
+            // *-pattern-* (contains)
+            let middle = pattern.trim_start_matches('*').trim_end_matches('*');
+            route.contains(middle)
+        } else if pattern.starts_with('*') {
+            // *-destination
+            route.ends_with(pattern.trim_start_matches('*'))
+        } else if let Some(prefix) = pattern.strip_suffix('*') {
+            // origin-*
+            route.starts_with(prefix)
+        } else {
+            false
+        }
+    } else {
+        // Exact match
+        route == pattern
+    }
+}
+
 impl PricingRule {
     pub fn new(
         rule_name: String,
@@ -195,28 +305,7 @@ impl PricingRule {
 
     pub fn applies_to_route(&self, origin: &str, destination: &str) -> bool {
         match &self.route_pattern {
-            Some(pattern) => {
-                let route = format!("{}-{}", origin, destination);
-                if pattern.contains('*') {
-                    // Wildcard matching
-                    if pattern.starts_with('*') && pattern.ends_with('*') {
-                        // *-pattern-* (contains)
-                        let middle = pattern.trim_start_matches('*').trim_end_matches('*');
-                        route.contains(middle)
-                    } else if pattern.starts_with('*') {
-                        // *-destination
-                        route.ends_with(&pattern[1..])
-                    } else if pattern.ends_with('*') {
-                        // origin-*
-                        route.starts_with(&pattern[..pattern.len()-1])
-                    } else {
-                        false
-                    }
-                } else {
-                    // Exact match
-                    route == *pattern
-                }
-            }
+            Some(pattern) => route_matches(pattern, origin, destination),
             None => true, // Apply to all routes if no pattern specified
         }
     }
@@ -229,6 +318,42 @@ impl PricingRule {
     }
 }
 
+impl RoutePreference {
+    pub fn new(name: String, route_pattern: String, preferred_models: Vec<String>, min_capacity: Option<u32>) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            name,
+            route_pattern,
+            preferred_models,
+            min_capacity,
+            is_active: true,
+        }
+    }
+
+    pub fn applies_to_route(&self, origin: &str, destination: &str) -> bool {
+        self.is_active && route_matches(&self.route_pattern, origin, destination)
+    }
+
+    /// Position of the aircraft in this preference's ranking, or None when it does not qualify
+    pub fn rank(&self, aircraft: &Aircraft) -> Option<usize> {
+        if self.min_capacity.is_some_and(|min| aircraft.total_capacity < min) {
+            return None;
+        }
+        if self.preferred_models.is_empty() {
+            return Some(0);
+        }
+        self.preferred_models
+            .iter()
+            .position(|model| model.eq_ignore_ascii_case(&aircraft.model))
+    }
+}
+
+impl Default for SystemMetrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl SystemMetrics {
     pub fn new() -> Self {
         Self {
@@ -241,9 +366,13 @@ impl SystemMetrics {
             aircraft_in_maintenance: 0,
             total_bookings: 0,
             revenue_today: 0.0,
+            revenue_yesterday: 0.0,
             revenue_month: 0.0,
             average_load_factor: 0.0,
             last_updated: Utc::now(),
+            last_revenue_reset: Utc::now(),
+            projected_fuel_cost: 0.0,
+            no_shows: HashMap::new(),
         }
     }
 
@@ -265,6 +394,52 @@ impl SystemMetrics {
         self.last_updated = Utc::now();
     }
 
+    /// Start a new accounting day once `now` has passed UTC midnight: today's revenue
+    /// becomes yesterday's and the count starts again. Returns whether it rolled over.
+    pub fn roll_revenue_day(&mut self, now: DateTime<Utc>) -> bool {
+        let today = now.date_naive();
+        let accounting_day = self.last_revenue_reset.date_naive();
+        if today <= accounting_day {
+            return false;
+        }
+
+        // Nothing was taken on any skipped days in between
+        self.revenue_yesterday = if today == accounting_day.succ_opt().unwrap_or(today) {
+            self.revenue_today
+        } else {
+            0.0
+        };
+        self.revenue_today = 0.0;
+        self.last_revenue_reset = now;
+        true
+    }
+
+    /// Take money paid back to passengers off the revenue figures. A refund of an earlier
+    /// day's takings can't push them below zero.
+    pub fn record_refund(&mut self, amount: f64) {
+        self.revenue_today = (self.revenue_today - amount).max(0.0);
+        self.revenue_month = (self.revenue_month - amount).max(0.0);
+    }
+
+    /// Average share of seats sold across flights that are still operating (cancelled ones excluded)
+    pub fn update_load_factor(&mut self, flights: &[Flight]) {
+        let load_factors: Vec<f64> = flights
+            .iter()
+            .filter(|f| !matches!(f.status, FlightStatus::Cancelled))
+            .map(|f| {
+                let available = f.seat_availability.economy + f.seat_availability.business + f.seat_availability.first_class;
+                crate::utils::calculate_load_factor(f.total_capacity.saturating_sub(available), f.total_capacity)
+            })
+            .collect();
+
+        self.average_load_factor = if load_factors.is_empty() {
+            0.0
+        } else {
+            load_factors.iter().sum::<f64>() / load_factors.len() as f64
+        };
+        self.last_updated = Utc::now();
+    }
+
     pub fn update_aircraft_metrics(&mut self, aircraft: &[Aircraft]) {
         self.total_aircraft = aircraft.len() as u32;
         self.active_aircraft = aircraft
@@ -291,43 +466,34 @@ impl SystemMetrics {
     }
 }
 
+impl Default for AdminPanel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl AdminPanel {
     pub fn new() -> Self {
         Self {
             current_admin: None,
             audit_log: Vec::new(),
+            archived_log: Vec::new(),
             pricing_rules: Vec::new(),
+            route_preferences: Vec::new(),
             system_metrics: SystemMetrics::new(),
+            credentials: AdminCredential::demo_accounts(),
         }
     }
 
     pub fn authenticate(&mut self, username: &str, password: &str) -> Result<AdminUser, String> {
-        // In a real system, this would check against a database
-        // For demo purposes, we'll create default admin users
-        let default_admin = match username {
-            "admin" if password == "admin123" => AdminUser::new(
-                "admin".to_string(),
-                "System Administrator".to_string(),
-                "admin@rust-airport.com".to_string(),
-                AdminLevel::SuperAdmin,
-            ),
-            "flight_mgr" if password == "flight123" => AdminUser::new(
-                "flight_mgr".to_string(),
-                "Flight Manager".to_string(),
-                "flights@rust-airport.com".to_string(),
-                AdminLevel::FlightManager,
-            ),
-            "aircraft_mgr" if password == "aircraft123" => AdminUser::new(
-                "aircraft_mgr".to_string(),
-                "Aircraft Manager".to_string(),
-                "aircraft@rust-airport.com".to_string(),
-                AdminLevel::AircraftManager,
-            ),
-            _ => return Err("Invalid username or password".to_string()),
-        };
-
-        let mut admin = default_admin;
-        admin.login();
+        let credential = self.credentials
+            .iter_mut()
+            .find(|c| c.user.username == username && c.user.is_active)
+            .filter(|c| c.verify(password))
+            .ok_or("Invalid username or password")?;
+
+        credential.user.login();
+        let admin = credential.user.clone();
         self.current_admin = Some(admin.clone());
         
         self.log_action(
@@ -342,6 +508,46 @@ impl AdminPanel {
         Ok(admin)
     }
 
+    /// Add an admin account. Only a SuperAdmin can create accounts.
+    pub fn create_admin(
+        &mut self,
+        username: &str,
+        full_name: &str,
+        email: &str,
+        level: AdminLevel,
+        password: &str,
+    ) -> Result<AdminUser, String> {
+        let creator = self.current_admin.as_ref().ok_or("Admin authentication required")?;
+        if !matches!(creator.level, AdminLevel::SuperAdmin) {
+            return Err("Only a SuperAdmin can create admin accounts".to_string());
+        }
+        let creator_id = creator.id;
+
+        let username = username.trim();
+        if username.is_empty() {
+            return Err("Username is required".to_string());
+        }
+        if self.credentials.iter().any(|c| c.user.username.eq_ignore_ascii_case(username)) {
+            return Err(format!("Username {} is already taken", username));
+        }
+        if password.chars().count() < crate::config::MIN_ADMIN_PASSWORD_LENGTH {
+            return Err(format!("Password must be at least {} characters", crate::config::MIN_ADMIN_PASSWORD_LENGTH));
+        }
+
+        let user = AdminUser::new(username.to_string(), full_name.trim().to_string(), email.trim().to_string(), level);
+        self.credentials.push(AdminCredential::new(user.clone(), password));
+
+        self.log_action(
+            creator_id,
+            "CREATE_ADMIN".to_string(),
+            format!("Created admin account {} ({:?})", user.username, user.level),
+            Some(user.id),
+            None,
+            Some(user.username.clone()),
+        );
+        Ok(user)
+    }
+
     pub fn logout(&mut self) {
         if let Some(admin) = &self.current_admin {
             self.log_action(
@@ -374,6 +580,29 @@ impl AdminPanel {
             new_value,
         );
         self.audit_log.push(action);
+        self.trim_log();
+    }
+
+    /// Keep the live log within `config::MAX_ADMIN_LOG_ENTRIES` by moving the oldest entries
+    /// to the archive. Both stay in chronological order; returns how many were archived.
+    pub fn trim_log(&mut self) -> usize {
+        let excess = self.audit_log.len().saturating_sub(crate::config::MAX_ADMIN_LOG_ENTRIES);
+        self.archived_log.extend(self.audit_log.drain(..excess));
+        excess
+    }
+
+    /// Put actions saved by an earlier session (oldest first) ahead of this session's
+    pub fn restore_log(&mut self, history: Vec<AdminAction>) {
+        let mut actions = history;
+        actions.append(&mut self.archived_log);
+        actions.append(&mut self.audit_log);
+        self.audit_log = actions;
+        self.trim_log();
+    }
+
+    /// Every recorded action, archived ones included, oldest first
+    pub fn all_actions(&self) -> impl Iterator<Item = &AdminAction> {
+        self.archived_log.iter().chain(self.audit_log.iter())
     }
 
     pub fn add_pricing_rule(&mut self, rule: PricingRule) -> Result<(), String> {
@@ -398,12 +627,18 @@ impl AdminPanel {
         }
     }
 
-    pub fn get_applicable_multiplier(&self, origin: &str, destination: &str, hour: u8) -> f64 {
+    pub fn get_applicable_rules(&self, origin: &str, destination: &str, hour: u8) -> Vec<&PricingRule> {
         self.pricing_rules
             .iter()
             .filter(|rule| rule.is_active)
             .filter(|rule| rule.applies_to_route(origin, destination))
             .filter(|rule| rule.applies_to_time(hour))
+            .collect()
+    }
+
+    pub fn get_applicable_multiplier(&self, origin: &str, destination: &str, hour: u8) -> f64 {
+        self.get_applicable_rules(origin, destination, hour)
+            .iter()
             .map(|rule| rule.multiplier)
             .fold(1.0, |acc, multiplier| acc * multiplier)
     }
@@ -416,6 +651,18 @@ impl AdminPanel {
             .collect()
     }
 
+    /// Logged actions, archived ones included, matching every filter given, newest first.
+    /// The action type is matched case-insensitively; `since` is inclusive.
+    pub fn filter_actions(&self, action_type: Option<&str>, admin_id: Option<Uuid>, since: Option<DateTime<Utc>>) -> Vec<&AdminAction> {
+        let mut actions: Vec<&AdminAction> = self.all_actions()
+            .filter(|a| action_type.is_none_or(|t| a.action_type.eq_ignore_ascii_case(t)))
+            .filter(|a| admin_id.is_none_or(|id| a.admin_id == id))
+            .filter(|a| since.is_none_or(|since| a.timestamp >= since))
+            .collect();
+        actions.reverse();
+        actions
+    }
+
     pub fn is_authenticated(&self) -> bool {
         self.current_admin.is_some()
     }
@@ -426,4 +673,164 @@ impl AdminPanel {
             .map(|admin| admin.full_name.clone())
             .unwrap_or_else(|| "Not logged in".to_string())
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::MAX_ADMIN_LOG_ENTRIES;
+
+    #[test]
+    fn test_log_is_capped_and_archive_keeps_order() {
+        let mut panel = AdminPanel::new();
+        let admin_id = Uuid::new_v4();
+        let total = MAX_ADMIN_LOG_ENTRIES + 5;
+        for i in 0..total {
+            panel.log_action(admin_id, "TEST".to_string(), format!("action {}", i), None, None, None);
+        }
+
+        assert_eq!(panel.audit_log.len(), MAX_ADMIN_LOG_ENTRIES);
+        assert_eq!(panel.archived_log.len(), 5);
+        assert_eq!(panel.audit_log[0].description, "action 5");
+        assert_eq!(panel.get_recent_actions(1)[0].description, format!("action {}", total - 1));
+
+        let descriptions: Vec<String> = panel.all_actions().map(|a| a.description.clone()).collect();
+        let expected: Vec<String> = (0..total).map(|i| format!("action {}", i)).collect();
+        assert_eq!(descriptions, expected);
+
+        // Already within the cap
+        assert_eq!(panel.trim_log(), 0);
+    }
+
+    #[test]
+    fn test_route_patterns_match_like_pricing_rules() {
+        assert!(route_matches("LAX-JFK", "LAX", "JFK"));
+        assert!(!route_matches("LAX-JFK", "JFK", "LAX"));
+        assert!(route_matches("LAX-*", "LAX", "LHR"));
+        assert!(route_matches("*-LHR", "JFK", "LHR"));
+        assert!(route_matches("*-LH*", "JFK", "LHR"));
+        assert!(!route_matches("L*X", "LAX", "JFK"));
+
+        let rule = PricingRule::new("Test".to_string(), Some("*-LHR".to_string()), None, 1.2, Uuid::new_v4());
+        let preference = RoutePreference::new("Test".to_string(), "*-LHR".to_string(), Vec::new(), Some(300));
+        for (origin, destination) in [("JFK", "LHR"), ("LHR", "JFK")] {
+            assert_eq!(rule.applies_to_route(origin, destination), preference.applies_to_route(origin, destination));
+        }
+
+        let narrowbody = Aircraft::new("N1TEST".to_string(), "Boeing 737-800".to_string(), "Boeing".to_string(), 2020);
+        assert_eq!(preference.rank(&narrowbody), None); // Too small
+    }
+
+    #[test]
+    fn test_load_factor_averages_operating_flights() {
+        let flight = |economy_left: u32, status: FlightStatus| {
+            let mut flight = Flight::new(
+                "RIA900".to_string(),
+                "Rust International Airways".to_string(),
+                "LAX".to_string(),
+                "JFK".to_string(),
+                Utc::now(),
+                Utc::now() + chrono::Duration::hours(5),
+                &Aircraft::new("N1TEST".to_string(), "Boeing 737-800".to_string(), "Boeing".to_string(), 2020),
+            );
+            flight.total_capacity = 100;
+            flight.seat_availability.first_class = 0;
+            flight.seat_availability.business = 0;
+            flight.seat_availability.economy = economy_left;
+            flight.status = status;
+            flight
+        };
+
+        let mut metrics = SystemMetrics::new();
+        metrics.update_load_factor(&[
+            flight(50, FlightStatus::OnTime),      // 50% full
+            flight(0, FlightStatus::Delayed(15)),  // Sold out
+            flight(100, FlightStatus::Cancelled),  // Ignored
+        ]);
+        assert_eq!(metrics.average_load_factor, 75.0);
+
+        metrics.update_load_factor(&[]);
+        assert_eq!(metrics.average_load_factor, 0.0);
+    }
+
+    #[test]
+    fn test_revenue_today_resets_at_utc_midnight() {
+        use chrono::TimeZone;
+
+        let mut metrics = SystemMetrics::new();
+        metrics.last_revenue_reset = Utc.with_ymd_and_hms(2025, 7, 1, 8, 0, 0).unwrap();
+        metrics.revenue_today = 1200.0;
+        metrics.revenue_month = 5000.0;
+
+        assert!(!metrics.roll_revenue_day(Utc.with_ymd_and_hms(2025, 7, 1, 23, 59, 59).unwrap()));
+        assert_eq!(metrics.revenue_today, 1200.0);
+
+        assert!(metrics.roll_revenue_day(Utc.with_ymd_and_hms(2025, 7, 2, 0, 0, 1).unwrap()));
+        assert_eq!(metrics.revenue_today, 0.0);
+        assert_eq!(metrics.revenue_yesterday, 1200.0);
+        assert_eq!(metrics.revenue_month, 5000.0);
+
+        // A gap of several days leaves nothing for "yesterday"
+        metrics.revenue_today = 300.0;
+        assert!(metrics.roll_revenue_day(Utc.with_ymd_and_hms(2025, 7, 5, 9, 0, 0).unwrap()));
+        assert_eq!(metrics.revenue_yesterday, 0.0);
+        assert_eq!(metrics.revenue_month, 5000.0);
+    }
+
+    #[test]
+    fn test_passwords_are_hashed_and_only_super_admins_create_accounts() {
+        let mut panel = AdminPanel::new();
+        assert!(panel.credentials.iter().all(|c| c.password_hash.len() == 64 && !c.password_hash.contains("123")));
+        assert_ne!(panel.credentials[0].salt, panel.credentials[1].salt);
+        assert!(panel.authenticate("admin", "wrong").is_err());
+
+        panel.authenticate("flight_mgr", "flight123").unwrap();
+        assert!(panel.create_admin("ops", "Ops Lead", "ops@rust-airport.com", AdminLevel::Viewer, "opspassword").is_err());
+
+        let admin_id = panel.authenticate("admin", "admin123").unwrap().id;
+        assert_eq!(panel.authenticate("admin", "admin123").unwrap().id, admin_id);
+        assert!(panel.create_admin("ops", "Ops Lead", "ops@rust-airport.com", AdminLevel::Viewer, "short").is_err());
+        panel.create_admin("ops", "Ops Lead", "ops@rust-airport.com", AdminLevel::Viewer, "opspassword").unwrap();
+        assert!(panel.create_admin("OPS", "Ops Again", "ops2@rust-airport.com", AdminLevel::Viewer, "opspassword").is_err());
+
+        let ops = panel.authenticate("ops", "opspassword").unwrap();
+        assert!(matches!(ops.level, AdminLevel::Viewer));
+    }
+
+    #[test]
+    fn test_filter_actions_combines_filters() {
+        let mut panel = AdminPanel::new();
+        let (alice, bob) = (Uuid::new_v4(), Uuid::new_v4());
+        panel.log_action(alice, "SET_PRICING".to_string(), "old pricing".to_string(), None, None, None);
+        panel.log_action(alice, "SET_PRICING".to_string(), "alice pricing".to_string(), None, None, None);
+        panel.log_action(bob, "SET_PRICING".to_string(), "bob pricing".to_string(), None, None, None);
+        panel.log_action(alice, "DELAY_FLIGHT".to_string(), "alice delay".to_string(), None, None, None);
+        panel.audit_log[0].timestamp = Utc::now() - chrono::Duration::hours(30);
+        let day_ago = Utc::now() - chrono::Duration::hours(24);
+        let descriptions = |actions: Vec<&AdminAction>| actions.iter().map(|a| a.description.clone()).collect::<Vec<_>>();
+
+        assert_eq!(descriptions(panel.filter_actions(None, None, None)),
+            ["alice delay", "bob pricing", "alice pricing", "old pricing"]);
+        assert_eq!(descriptions(panel.filter_actions(Some("set_pricing"), None, None)),
+            ["bob pricing", "alice pricing", "old pricing"]);
+        assert_eq!(descriptions(panel.filter_actions(None, Some(alice), None)),
+            ["alice delay", "alice pricing", "old pricing"]);
+        assert_eq!(descriptions(panel.filter_actions(None, None, Some(day_ago))),
+            ["alice delay", "bob pricing", "alice pricing"]);
+        assert_eq!(descriptions(panel.filter_actions(Some("SET_PRICING"), Some(alice), Some(day_ago))), ["alice pricing"]);
+        assert!(panel.filter_actions(Some("DELAY_FLIGHT"), Some(bob), None).is_empty());
+    }
+
+    #[test]
+    fn test_refunds_do_not_take_revenue_below_zero() {
+        let mut metrics = SystemMetrics::new();
+        metrics.revenue_today = 100.0;
+        metrics.revenue_month = 500.0;
+        metrics.record_refund(40.0);
+        assert_eq!((metrics.revenue_today, metrics.revenue_month), (60.0, 460.0));
+
+        // Refunding yesterday's booking after the day rolled over
+        metrics.record_refund(250.0);
+        assert_eq!((metrics.revenue_today, metrics.revenue_month), (0.0, 210.0));
+    }
+}
@@ -1,14 +1,20 @@
 use serde::{Deserialize, Serialize};
+use chrono::{DateTime, Utc};
 use uuid::Uuid;
 use std::collections::HashMap;
+use std::ops::RangeInclusive;
 use crate::modules::flight::SeatClass;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Flight hours allowed between maintenance checks
+pub const MAINTENANCE_INTERVAL_HOURS: f64 = 100.0;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum AircraftStatus {
     Active,
     Maintenance,
     Retired,
     InFlight,
+    Grounded { reason: String, since: DateTime<Utc> }, // Regulatory or safety hold, lifted by an admin
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -21,6 +27,84 @@ pub struct SeatConfiguration {
     pub first_class_seats_per_row: u32,
 }
 
+impl SeatConfiguration {
+    /// Row numbers and seats per row for a cabin. Rows are numbered from the front:
+    /// first class, then business, then economy.
+    pub fn cabin_rows(&self, class: &SeatClass) -> (RangeInclusive<u32>, u32) {
+        let business_start = 1 + self.first_class_rows;
+        let economy_start = business_start + self.business_rows;
+        let (start, rows, seats_per_row) = match class {
+            SeatClass::FirstClass => (1, self.first_class_rows, self.first_class_seats_per_row),
+            SeatClass::Business => (business_start, self.business_rows, self.business_seats_per_row),
+            SeatClass::Economy => (economy_start, self.economy_rows, self.economy_seats_per_row),
+        };
+        (start..=start + rows - 1, seats_per_row)
+    }
+}
+
+/// Comfort and amenities in one cabin, for passengers comparing flights
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CabinDetails {
+    pub seat_pitch_cm: u32,
+    pub seat_width_cm: u32,
+    pub power_outlets: bool,
+    pub wifi: bool,
+    pub entertainment: bool, // Seatback in-flight entertainment
+}
+
+impl CabinDetails {
+    /// Typical cabin fit-out for a model; unknown models get a basic regional layout
+    pub fn default_for(model: &str, class: &SeatClass) -> Self {
+        let (seat_pitch_cm, seat_width_cm, power_outlets, wifi, entertainment) = match (model, class) {
+            ("Boeing 737-800", SeatClass::Economy) => (79, 43, false, true, false),
+            ("Boeing 737-800", SeatClass::Business) => (97, 53, true, true, false),
+            ("Boeing 737-800", SeatClass::FirstClass) => (104, 56, true, true, true),
+            ("Airbus A320", SeatClass::Economy) => (76, 46, false, false, false),
+            ("Airbus A320", SeatClass::Business) => (94, 53, true, false, false),
+            ("Airbus A320", SeatClass::FirstClass) => (102, 56, true, false, true),
+            ("Boeing 777-300", SeatClass::Economy) => (81, 44, true, true, true),
+            ("Boeing 777-300", SeatClass::Business) => (152, 51, true, true, true),
+            ("Boeing 777-300", SeatClass::FirstClass) => (203, 58, true, true, true),
+            ("Airbus A380", SeatClass::Economy) => (81, 46, true, true, true),
+            ("Airbus A380", SeatClass::Business) => (152, 56, true, true, true),
+            ("Airbus A380", SeatClass::FirstClass) => (208, 64, true, true, true),
+            (_, SeatClass::Economy) => (76, 43, false, false, false),
+            (_, SeatClass::Business) => (94, 50, true, false, false),
+            (_, SeatClass::FirstClass) => (102, 53, true, false, true),
+        };
+
+        Self {
+            seat_pitch_cm,
+            seat_width_cm,
+            power_outlets,
+            wifi,
+            entertainment,
+        }
+    }
+
+    pub fn amenities(&self) -> Vec<&'static str> {
+        [
+            (self.power_outlets, "Power"),
+            (self.wifi, "Wi-Fi"),
+            (self.entertainment, "Entertainment"),
+        ]
+        .into_iter()
+        .filter(|(available, _)| *available)
+        .map(|(_, name)| name)
+        .collect()
+    }
+
+    pub fn get_summary(&self) -> String {
+        let amenities = self.amenities();
+        format!(
+            "{} cm pitch, {} cm wide | {}",
+            self.seat_pitch_cm,
+            self.seat_width_cm,
+            if amenities.is_empty() { "No amenities".to_string() } else { amenities.join(", ") }
+        )
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PerformanceSpecs {
     pub max_speed_kmh: u32,
@@ -45,6 +129,8 @@ pub struct Aircraft {
     pub performance: PerformanceSpecs,
     pub maintenance_hours: f64,
     pub flight_hours: f64,
+    #[serde(default)]
+    pub cabins: HashMap<SeatClass, CabinDetails>, // Missing classes fall back to the model defaults
 }
 
 impl Aircraft {
@@ -100,6 +186,14 @@ impl Aircraft {
 
         let total_capacity = Self::calculate_total_capacity(&seat_config);
 
+        let cabins = [SeatClass::Economy, SeatClass::Business, SeatClass::FirstClass]
+            .into_iter()
+            .map(|class| {
+                let details = CabinDetails::default_for(&model, &class);
+                (class, details)
+            })
+            .collect();
+
         let performance = match model.as_str() {
             "Boeing 737-800" => PerformanceSpecs {
                 max_speed_kmh: 876,
@@ -152,7 +246,28 @@ impl Aircraft {
             performance,
             maintenance_hours: 0.0,
             flight_hours: 0.0,
+            cabins,
+        }
+    }
+
+    /// Refit the cabins, returning the previous total capacity
+    pub fn set_seat_configuration(&mut self, config: SeatConfiguration) -> Result<u32, String> {
+        let capacity = Self::calculate_total_capacity(&config);
+        if capacity == 0 {
+            return Err("Seat configuration has no seats".to_string());
+        }
+        if capacity > crate::MAX_PASSENGERS_PER_FLIGHT {
+            return Err(format!(
+                "{} seats exceeds the maximum of {} passengers per flight",
+                capacity,
+                crate::MAX_PASSENGERS_PER_FLIGHT
+            ));
         }
+
+        let old_capacity = self.total_capacity;
+        self.seat_configuration = config;
+        self.total_capacity = capacity;
+        Ok(old_capacity)
     }
 
     fn calculate_total_capacity(config: &SeatConfiguration) -> u32 {
@@ -175,6 +290,20 @@ impl Aircraft {
         }
     }
 
+    pub fn cabin_details(&self, class: &SeatClass) -> CabinDetails {
+        self.cabins
+            .get(class)
+            .cloned()
+            .unwrap_or_else(|| CabinDetails::default_for(&self.model, class))
+    }
+
+    /// Wi-Fi in at least one cabin
+    pub fn has_wifi(&self) -> bool {
+        [SeatClass::Economy, SeatClass::Business, SeatClass::FirstClass]
+            .iter()
+            .any(|class| self.cabin_details(class).wifi)
+    }
+
     pub fn is_available_for_flight(&self) -> bool {
         matches!(self.status, AircraftStatus::Active)
     }
@@ -186,14 +315,20 @@ impl Aircraft {
     pub fn add_flight_hours(&mut self, hours: f64) {
         self.flight_hours += hours;
         // Every 100 flight hours requires 10 hours of maintenance
-        if self.flight_hours - self.maintenance_hours >= 100.0 {
+        if self.hours_until_maintenance() <= 0.0 && matches!(self.status, AircraftStatus::Active | AircraftStatus::InFlight) {
             self.status = AircraftStatus::Maintenance;
         }
     }
 
+    /// Flight hours left before the next maintenance check is due (negative when overdue)
+    pub fn hours_until_maintenance(&self) -> f64 {
+        MAINTENANCE_INTERVAL_HOURS - (self.flight_hours - self.maintenance_hours)
+    }
+
     pub fn perform_maintenance(&mut self, hours: f64) {
         self.maintenance_hours += hours;
-        if self.maintenance_hours >= self.flight_hours {
+        // A grounding stays in place until it is lifted, whatever work is done
+        if self.maintenance_hours >= self.flight_hours && self.status == AircraftStatus::Maintenance {
             self.status = AircraftStatus::Active;
         }
     }
@@ -203,14 +338,21 @@ impl Aircraft {
     }
 
     pub fn get_status_display(&self) -> String {
-        match self.status {
+        match &self.status {
             AircraftStatus::Active => "Active ✅".to_string(),
             AircraftStatus::Maintenance => "Maintenance 🔧".to_string(),
             AircraftStatus::Retired => "Retired 🚫".to_string(),
             AircraftStatus::InFlight => "In Flight ✈️".to_string(),
+            AircraftStatus::Grounded { reason, since } => {
+                format!("Grounded ⛔ since {} ({})", since.format("%Y-%m-%d"), reason)
+            }
         }
     }
 
+    pub fn is_grounded(&self) -> bool {
+        matches!(self.status, AircraftStatus::Grounded { .. })
+    }
+
     pub fn get_baggage_allowance(&self) -> HashMap<SeatClass, u32> {
         let mut allowance = HashMap::new();
         
@@ -245,4 +387,26 @@ impl std::fmt::Display for Aircraft {
             self.get_status_display()
         )
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cabin_details_by_model_and_fallback() {
+        let mut aircraft = Aircraft::new("N1TEST".to_string(), "Boeing 777-300".to_string(), "Boeing".to_string(), 2015);
+        let economy = aircraft.cabin_details(&SeatClass::Economy);
+        assert_eq!(economy.seat_pitch_cm, 81);
+        assert_eq!(economy.amenities(), vec!["Power", "Wi-Fi", "Entertainment"]);
+        assert!(aircraft.cabin_details(&SeatClass::FirstClass).seat_pitch_cm > economy.seat_pitch_cm);
+        assert!(aircraft.has_wifi());
+
+        // Aircraft saved before cabins were recorded use the defaults for their model
+        aircraft.cabins.clear();
+        assert_eq!(aircraft.cabin_details(&SeatClass::Economy), economy);
+
+        let regional = Aircraft::new("N2TEST".to_string(), "Embraer E175".to_string(), "Embraer".to_string(), 2018);
+        assert!(!regional.has_wifi());
+        assert_eq!(regional.cabin_details(&SeatClass::Economy).get_summary(), "76 cm pitch, 43 cm wide | No amenities");
+    }
+}
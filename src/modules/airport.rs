@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
-use std::collections::HashMap;
+use chrono::{DateTime, NaiveDateTime, Timelike, Utc};
+use chrono_tz::Tz;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum AirportSize {
@@ -50,13 +51,16 @@ pub struct Airport {
     pub runways: Vec<Runway>,
     pub annual_passengers: u64,
     pub cargo_capacity_tonnes: u32,
-    pub operating_hours: (u8, u8), // (start_hour, end_hour) in 24h format
+    pub operating_hours: (u8, u8), // (start_hour, end_hour) in 24h local time, both inclusive
     pub services: Vec<String>,     // Available services
     pub is_international: bool,
     pub customs_available: bool,
+    #[serde(default)]
+    pub min_connection_minutes: u32, // Shortest transfer between flights; 0 in files saved before it existed
 }
 
 impl Airport {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         code: String,
         icao_code: String,
@@ -72,7 +76,7 @@ impl Airport {
         let airport_size = Self::determine_size(&code);
         
         // Generate default terminals and runways based on size
-        let (terminals, runways) = Self::generate_infrastructure(&airport_size, &code);
+        let (terminals, runways) = Self::generate_infrastructure(&airport_size);
         
         // Estimate annual passengers based on size
         let annual_passengers = match airport_size {
@@ -104,7 +108,6 @@ impl Airport {
             timezone,
             coordinates: Coordinates { latitude, longitude },
             elevation_meters,
-            airport_size,
             terminals,
             runways,
             annual_passengers,
@@ -113,9 +116,33 @@ impl Airport {
             services,
             is_international: true, // Most airports in our system are international
             customs_available: true,
+            min_connection_minutes: Self::default_connection_minutes(&airport_size),
+            airport_size,
+        }
+    }
+
+    /// Typical minimum connection time for an airport of this size: bigger airports mean
+    /// longer walks between gates
+    pub fn default_connection_minutes(size: &AirportSize) -> u32 {
+        match size {
+            AirportSize::Small => 30,
+            AirportSize::Medium => 45,
+            AirportSize::Large => 60,
+            AirportSize::Hub => 90,
         }
     }
 
+    /// Minutes a passenger needs to change flights here. International-to-international
+    /// transfers through customs take an extra buffer.
+    pub fn connection_minutes(&self, international_transfer: bool) -> i64 {
+        let buffer = if international_transfer && self.customs_available {
+            crate::config::INTERNATIONAL_CONNECTION_BUFFER_MINUTES
+        } else {
+            0
+        };
+        self.min_connection_minutes as i64 + buffer
+    }
+
     fn determine_size(code: &str) -> AirportSize {
         // Classify based on well-known airport codes
         match code {
@@ -126,7 +153,7 @@ impl Airport {
         }
     }
 
-    fn generate_infrastructure(size: &AirportSize, code: &str) -> (Vec<Terminal>, Vec<Runway>) {
+    fn generate_infrastructure(size: &AirportSize) -> (Vec<Terminal>, Vec<Runway>) {
         let terminals = match size {
             AirportSize::Hub => vec![
                 Terminal {
@@ -258,17 +285,61 @@ impl Airport {
             .collect()
     }
 
-    pub fn find_available_gate(&self) -> Option<String> {
-        // Simple gate assignment - return first gate
-        // In real system, this would check current usage
+    /// First gate, terminal by terminal, that isn't in `occupied`
+    pub fn find_available_gate(&self, occupied: &[&str]) -> Option<String> {
         self.terminals
-            .first()
-            .and_then(|terminal| terminal.gates.first())
+            .iter()
+            .flat_map(|terminal| &terminal.gates)
+            .find(|gate| !occupied.contains(&gate.as_str()))
             .cloned()
     }
 
+    /// Whether the airport is open during local `hour`. Both ends are inclusive, so (5, 23)
+    /// means 05:00-23:59; a start later than the end wraps past midnight, e.g. (22, 6).
     pub fn is_operating(&self, hour: u8) -> bool {
-        hour >= self.operating_hours.0 && hour <= self.operating_hours.1
+        let (open, close) = self.operating_hours;
+        if open <= close {
+            hour >= open && hour <= close
+        } else {
+            hour >= open || hour <= close
+        }
+    }
+
+    pub fn is_open_24_hours(&self) -> bool {
+        (0..24).all(|hour| self.is_operating(hour))
+    }
+
+    /// `time` on the airport's local clock; an unrecognised time zone is treated as UTC
+    pub fn local_time(&self, time: DateTime<Utc>) -> NaiveDateTime {
+        match self.timezone.parse::<Tz>() {
+            Ok(tz) => time.with_timezone(&tz).naive_local(),
+            Err(_) => time.naive_utc(),
+        }
+    }
+
+    pub fn is_operating_at(&self, time: DateTime<Utc>) -> bool {
+        self.is_open_24_hours() || self.is_operating(self.local_time(time).hour() as u8)
+    }
+
+    pub fn get_operating_hours_display(&self) -> String {
+        if self.is_open_24_hours() {
+            "Open 24 hours".to_string()
+        } else {
+            format!("{:02}:00-{:02}:59 local", self.operating_hours.0, self.operating_hours.1)
+        }
+    }
+
+    /// Explain why the airport cannot handle a movement at `time`, or `None` if it is open
+    pub fn closed_at(&self, time: DateTime<Utc>) -> Option<String> {
+        if self.is_operating_at(time) {
+            return None;
+        }
+        Some(format!(
+            "{} is closed at {} local time (open {})",
+            self.code,
+            self.local_time(time).format("%H:%M"),
+            self.get_operating_hours_display()
+        ))
     }
 
     pub fn can_handle_aircraft(&self, aircraft_length: u32) -> bool {
@@ -291,6 +362,16 @@ impl Airport {
         6371.0 * c // Earth's radius in kilometers
     }
 
+    /// Whether any terminal lists an amenity containing `amenity`, ignoring case
+    pub fn has_amenity(&self, amenity: &str) -> bool {
+        let amenity = amenity.trim().to_lowercase();
+        !amenity.is_empty()
+            && self.terminals
+                .iter()
+                .flat_map(|terminal| &terminal.amenities)
+                .any(|a| a.to_lowercase().contains(&amenity))
+    }
+
     pub fn get_terminal_info(&self) -> String {
         let terminal_count = self.terminals.len();
         let gate_count: usize = self.terminals.iter().map(|t| t.gates.len()).sum();
@@ -324,4 +405,71 @@ impl std::fmt::Display for Airport {
             self.get_size_display()
         )
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn test_airport(code: &str, timezone: &str) -> Airport {
+        Airport::new(
+            code.to_string(),
+            format!("K{}", code),
+            format!("{} Airport", code),
+            "Test City".to_string(),
+            "United States".to_string(),
+            timezone.to_string(),
+            0.0,
+            0.0,
+            0,
+        )
+    }
+
+    #[test]
+    fn test_operating_hours_use_local_time() {
+        let lax = test_airport("LAX", "America/Los_Angeles");
+        assert_eq!(lax.operating_hours, (5, 23));
+
+        // 10:30 UTC is 03:30 in Los Angeles in summer (UTC-7) - closed
+        let summer_night = Utc.with_ymd_and_hms(2025, 7, 1, 10, 30, 0).unwrap();
+        assert!(!lax.is_operating_at(summer_night));
+        assert_eq!(lax.closed_at(summer_night).unwrap(), "LAX is closed at 03:30 local time (open 05:00-23:59 local)");
+
+        // Same UTC time in winter (UTC-8) is 02:30, still closed; 13:00 UTC is 05:00 - open
+        assert!(!lax.is_operating_at(Utc.with_ymd_and_hms(2025, 1, 15, 10, 30, 0).unwrap()));
+        assert!(lax.is_operating_at(Utc.with_ymd_and_hms(2025, 1, 15, 13, 0, 0).unwrap()));
+
+        // 23:59 local is the last open minute; unknown zones fall back to UTC
+        assert!(lax.is_operating_at(Utc.with_ymd_and_hms(2025, 1, 16, 7, 59, 0).unwrap()));
+        let unknown = test_airport("XXX", "Mars/Olympus_Mons");
+        assert!(!unknown.is_operating_at(Utc.with_ymd_and_hms(2025, 1, 15, 2, 0, 0).unwrap()));
+    }
+
+    #[test]
+    fn test_overnight_and_round_the_clock_hours() {
+        let mut airport = test_airport("NRT", "Asia/Tokyo");
+        airport.operating_hours = (0, 23);
+        assert!(airport.is_open_24_hours());
+        assert!(airport.closed_at(Utc.with_ymd_and_hms(2025, 3, 1, 18, 0, 0).unwrap()).is_none());
+        assert_eq!(airport.get_operating_hours_display(), "Open 24 hours");
+
+        // A night-shift airport open 22:00-06:59
+        airport.operating_hours = (22, 6);
+        assert!(!airport.is_open_24_hours());
+        assert!(airport.is_operating(23) && airport.is_operating(0) && airport.is_operating(6));
+        assert!(!airport.is_operating(7) && !airport.is_operating(21));
+    }
+
+    #[test]
+    fn test_connection_time_defaults_by_size() {
+        assert_eq!(test_airport("JFK", "America/New_York").min_connection_minutes, 90);
+        assert_eq!(test_airport("SFO", "America/Los_Angeles").min_connection_minutes, 60);
+        assert_eq!(test_airport("XYZ", "America/Chicago").min_connection_minutes, 30);
+
+        let mut hub = test_airport("JFK", "America/New_York");
+        assert_eq!(hub.connection_minutes(true), 90 + crate::config::INTERNATIONAL_CONNECTION_BUFFER_MINUTES);
+        hub.customs_available = false;
+        assert_eq!(hub.connection_minutes(true), 90);
+    }
+}
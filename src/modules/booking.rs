@@ -1,7 +1,10 @@
 use serde::{Deserialize, Serialize};
 use chrono::{DateTime, Utc};
 use uuid::Uuid;
-use crate::modules::flight::SeatClass;
+use crate::modules::flight::{Flight, PriceBreakdown, SeatClass};
+use crate::modules::airport::Airport;
+use crate::utils::{format_currency, format_duration};
+use crate::modules::currency::ExchangeQuote;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum BookingStatus {
@@ -21,6 +24,116 @@ pub enum PassengerType {
     Senior,
 }
 
+impl PassengerType {
+    /// Share of the full fare this passenger type pays
+    pub fn fare_rate(&self) -> f64 {
+        use crate::config::pricing;
+        match self {
+            PassengerType::Adult => 1.0,
+            PassengerType::Child => pricing::CHILD_FARE_RATE,
+            PassengerType::Infant => pricing::INFANT_FARE_RATE,
+            PassengerType::Senior => pricing::SENIOR_FARE_RATE,
+        }
+    }
+
+    /// Infants travel on an adult's lap and don't take a seat
+    pub fn occupies_seat(&self) -> bool {
+        !matches!(self, PassengerType::Infant)
+    }
+}
+
+// Keyword -> report category for free-text special requirements
+const REQUIREMENT_CATEGORIES: [(&str, &str); 11] = [
+    ("wheelchair", "wheelchair"),
+    ("medical", "medical equipment"),
+    ("oxygen", "medical equipment"),
+    ("vegetarian", "vegetarian"),
+    ("vegan", "vegan"),
+    ("kosher", "kosher"),
+    ("halal", "halal"),
+    ("gluten", "gluten free"),
+    ("legroom", "extra legroom"),
+    ("pet", "pet travel"),
+    ("unaccompanied", "unaccompanied minor"),
+];
+
+// Categories that ground staff must handle before the flight can board
+const CRITICAL_CATEGORIES: [&str; 3] = ["wheelchair", "medical equipment", "unaccompanied minor"];
+
+// Categories that need a special meal from catering
+const DIETARY_CATEGORIES: [&str; 5] = ["vegetarian", "vegan", "kosher", "halal", "gluten free"];
+
+/// Category used to group a free-text requirement in reports, e.g. "Vegetarian meal" -> "vegetarian"
+pub fn requirement_category(requirement: &str) -> String {
+    let lower = requirement.trim().to_lowercase();
+    lower
+        .split(|c: char| !c.is_alphanumeric())
+        .find_map(|word| REQUIREMENT_CATEGORIES.iter().find(|(keyword, _)| *keyword == word))
+        .map(|(_, category)| category.to_string())
+        .unwrap_or(lower)
+}
+
+pub fn is_critical_requirement(requirement: &str) -> bool {
+    CRITICAL_CATEGORIES.contains(&requirement_category(requirement).as_str())
+}
+
+pub fn is_dietary_requirement(requirement: &str) -> bool {
+    DIETARY_CATEGORIES.contains(&requirement_category(requirement).as_str())
+}
+
+/// Meal the passenger wants served. Bookings made before this was asked get Standard.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum MealPreference {
+    #[default]
+    Standard,
+    Vegetarian,
+    Vegan,
+    Kosher,
+    Halal,
+    GlutenFree,
+    None, // No meal wanted
+}
+
+impl MealPreference {
+    pub const ALL: [MealPreference; 7] = [
+        MealPreference::Standard,
+        MealPreference::Vegetarian,
+        MealPreference::Vegan,
+        MealPreference::Kosher,
+        MealPreference::Halal,
+        MealPreference::GlutenFree,
+        MealPreference::None,
+    ];
+
+    /// Dietary category catering reports the meal under, matching the free-text
+    /// requirement categories. None for a standard meal or no meal.
+    pub fn dietary_category(&self) -> Option<&'static str> {
+        match self {
+            MealPreference::Vegetarian => Some("vegetarian"),
+            MealPreference::Vegan => Some("vegan"),
+            MealPreference::Kosher => Some("kosher"),
+            MealPreference::Halal => Some("halal"),
+            MealPreference::GlutenFree => Some("gluten free"),
+            MealPreference::Standard | MealPreference::None => None,
+        }
+    }
+
+    pub fn get_display(&self) -> &'static str {
+        match self {
+            MealPreference::Standard => "Standard",
+            MealPreference::Vegetarian => "Vegetarian",
+            MealPreference::Vegan => "Vegan",
+            MealPreference::Kosher => "Kosher",
+            MealPreference::Halal => "Halal",
+            MealPreference::GlutenFree => "Gluten free",
+            MealPreference::None => "No meal",
+        }
+    }
+}
+
+/// Stand-in for names scrubbed from anonymized bookings
+pub const REDACTED: &str = "REDACTED";
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Passenger {
     pub id: Uuid,
@@ -41,6 +154,8 @@ pub struct SeatAssignment {
     pub is_window: bool,
     pub is_aisle: bool,
     pub is_emergency_exit: bool,
+    #[serde(default)]
+    pub is_fixed: bool,        // Paid seat selection - never moved when seats are reshuffled
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -50,6 +165,17 @@ pub struct BookingPayment {
     pub payment_method: String, // e.g., "Credit Card", "PayPal"
     pub transaction_id: String,
     pub payment_date: DateTime<Utc>,
+    #[serde(default)]
+    pub refunded_amount: f64,
+    #[serde(default)]
+    pub quote: Option<ExchangeQuote>, // Rate shown to the passenger when booked in another currency
+}
+
+/// One change in a booking's life, e.g. check-in or a move to another flight
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BookingEvent {
+    pub timestamp: DateTime<Utc>,
+    pub description: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -63,10 +189,29 @@ pub struct Booking {
     pub booking_date: DateTime<Utc>,
     pub status: BookingStatus,
     pub payment: BookingPayment,
-    pub baggage_count: u32,
+    #[serde(default = "default_cabin_bags")]
+    pub cabin_bags: u32,
+    #[serde(alias = "baggage_count", default = "default_checked_bags")] // Older bookings only stored a total
+    pub checked_bags: u32,
     pub special_services: Vec<String>, // e.g., "Extra legroom", "Priority boarding"
     pub check_in_time: Option<DateTime<Utc>>,
     pub boarding_time: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub fulfilled_requirements: Vec<String>, // Passenger requirements crew have marked as done
+    #[serde(default)]
+    pub group_id: Option<Uuid>,      // Bookings made together share an id and are seated together
+    #[serde(default)]
+    pub trip_id: Option<Uuid>,       // Outbound and return legs of a round trip share an id
+    #[serde(default)]
+    pub upgrade_opt_in: bool,        // Wants to be considered for complimentary upgrades
+    #[serde(default)]
+    pub checked_baggage_kg: u32,     // Declared total weight of checked bags
+    #[serde(default)]
+    pub weighed_bags: u32,           // Checked bags the declared weight covers; 0 on older bookings means all
+    #[serde(default)]
+    pub meal_preference: MealPreference,
+    #[serde(default)]
+    pub history: Vec<BookingEvent>, // Oldest first
 }
 
 impl Passenger {
@@ -104,6 +249,21 @@ impl Passenger {
     pub fn set_passport(&mut self, passport_number: String) {
         self.passport_number = Some(passport_number);
     }
+
+    /// Strip personal details. The passenger type stays so fare statistics still add up.
+    pub fn anonymize(&mut self) {
+        self.first_name = REDACTED.to_string();
+        self.last_name = REDACTED.to_string();
+        self.email.clear();
+        self.phone.clear();
+        self.passport_number = None;
+        self.date_of_birth.clear();
+        self.special_requirements.clear(); // May reveal health or religion
+    }
+
+    pub fn is_anonymized(&self) -> bool {
+        self.first_name == REDACTED && self.email.is_empty()
+    }
 }
 
 impl SeatAssignment {
@@ -116,7 +276,7 @@ impl SeatAssignment {
             .collect::<String>()
             .parse()
             .unwrap_or(1);
-        let is_emergency_exit = row_number >= 12 && row_number <= 15; // Typical emergency exit rows
+        let is_emergency_exit = (12..=15).contains(&row_number); // Typical emergency exit rows
 
         Self {
             seat_number,
@@ -124,6 +284,7 @@ impl SeatAssignment {
             is_window,
             is_aisle,
             is_emergency_exit,
+            is_fixed: false,
         }
     }
 
@@ -155,7 +316,7 @@ impl Booking {
         payment_method: String,
     ) -> Self {
         let booking_id = Uuid::new_v4();
-        let ticket_number = Self::generate_ticket_number();
+        let ticket_number = Self::random_ticket_number();
         
         let payment = BookingPayment {
             total_amount,
@@ -163,6 +324,8 @@ impl Booking {
             payment_method,
             transaction_id: Uuid::new_v4().to_string(),
             payment_date: Utc::now(),
+            refunded_amount: 0.0,
+            quote: None,
         };
 
         Self {
@@ -175,17 +338,37 @@ impl Booking {
             booking_date: Utc::now(),
             status: BookingStatus::Confirmed,
             payment,
-            baggage_count: 1, // Default one bag
+            cabin_bags: default_cabin_bags(),
+            checked_bags: default_checked_bags(),
             special_services: Vec::new(),
             check_in_time: None,
             boarding_time: None,
+            fulfilled_requirements: Vec::new(),
+            group_id: None,
+            trip_id: None,
+            upgrade_opt_in: false,
+            checked_baggage_kg: 0,
+            weighed_bags: 0,
+            meal_preference: MealPreference::default(),
+            history: Vec::new(),
         }
     }
 
-    fn generate_ticket_number() -> String {
+    /// A ticket number not used by any of `existing`, drawing again on a collision.
+    /// `new` can't see other bookings, so anything stored alongside them should use this.
+    pub fn generate_ticket_number(existing: &[Booking]) -> String {
+        loop {
+            let candidate = Self::random_ticket_number();
+            if !existing.iter().any(|b| b.ticket_number == candidate) {
+                return candidate;
+            }
+        }
+    }
+
+    fn random_ticket_number() -> String {
         // Generate a human-readable ticket number (airline code + 6 digits)
         let airline_code = "RIA"; // Rust International Airport
-        let number = rand::random::<u32>() % 1000000;
+        let number = rand::random() % 1000000;
         format!("{}{:06}", airline_code, number)
     }
 
@@ -193,11 +376,29 @@ impl Booking {
         self.seat_assignment = Some(SeatAssignment::new(seat_number, self.seat_class.clone()));
     }
 
+    /// Assign a seat the passenger paid to choose; it stays put when the layout is optimized
+    pub fn select_seat(&mut self, seat_number: String) {
+        let mut seat = SeatAssignment::new(seat_number, self.seat_class.clone());
+        seat.is_fixed = true;
+        self.record_event(format!("Seat {} selected", seat.seat_number));
+        self.seat_assignment = Some(seat);
+    }
+
+    /// Append to the booking's history, timestamped now
+    pub fn record_event(&mut self, description: String) {
+        self.history.push(BookingEvent { timestamp: Utc::now(), description });
+    }
+
+    pub fn has_fixed_seat(&self) -> bool {
+        self.seat_assignment.as_ref().is_some_and(|seat| seat.is_fixed)
+    }
+
     pub fn check_in(&mut self) -> Result<(), String> {
         match self.status {
             BookingStatus::Confirmed => {
                 self.status = BookingStatus::CheckedIn;
                 self.check_in_time = Some(Utc::now());
+                self.record_event("Checked in".to_string());
                 Ok(())
             }
             _ => Err("Cannot check in - booking not in confirmed status".to_string()),
@@ -209,16 +410,42 @@ impl Booking {
             BookingStatus::CheckedIn => {
                 self.status = BookingStatus::Boarded;
                 self.boarding_time = Some(Utc::now());
+                self.record_event("Boarded".to_string());
                 Ok(())
             }
             _ => Err("Cannot board - must be checked in first".to_string()),
         }
     }
 
+    /// Close out a booking whose flight has left
+    pub fn complete(&mut self) -> Result<(), String> {
+        match self.status {
+            BookingStatus::CheckedIn | BookingStatus::Boarded => {
+                self.status = BookingStatus::Completed;
+                Ok(())
+            }
+            BookingStatus::NoShow => Err("Cannot complete - passenger missed the flight".to_string()),
+            _ => Err("Cannot complete - passenger never checked in".to_string()),
+        }
+    }
+
+    /// The flight left without a passenger who hadn't boarded. Their seat stays sold.
+    pub fn mark_no_show(&mut self) -> Result<(), String> {
+        match self.status {
+            BookingStatus::Confirmed | BookingStatus::CheckedIn => {
+                self.status = BookingStatus::NoShow;
+                self.record_event("Marked as a no-show".to_string());
+                Ok(())
+            }
+            _ => Err(format!("Cannot mark a {:?} booking as a no-show", self.status)),
+        }
+    }
+
     pub fn cancel(&mut self) -> Result<(), String> {
         match self.status {
             BookingStatus::Confirmed | BookingStatus::CheckedIn => {
                 self.status = BookingStatus::Cancelled;
+                self.record_event("Cancelled".to_string());
                 Ok(())
             }
             BookingStatus::Boarded | BookingStatus::Completed => {
@@ -228,8 +455,68 @@ impl Booking {
         }
     }
 
-    pub fn add_baggage(&mut self, count: u32) {
-        self.baggage_count += count;
+    pub fn add_cabin_bags(&mut self, count: u32) {
+        self.cabin_bags += count;
+    }
+
+    pub fn add_checked_bags(&mut self, count: u32) {
+        self.checked_bags += count;
+    }
+
+    /// Checked bags included in the fare for this booking's class
+    pub fn checked_bag_allowance(&self) -> u32 {
+        use crate::config::baggage;
+        match self.seat_class {
+            SeatClass::Economy => baggage::ECONOMY_CHECKED_BAGS_INCLUDED,
+            SeatClass::Business => baggage::BUSINESS_CHECKED_BAGS_INCLUDED,
+            SeatClass::FirstClass => baggage::FIRST_CLASS_CHECKED_BAGS_INCLUDED,
+        }
+    }
+
+    /// Fee for bags beyond the cabin and checked allowances
+    pub fn excess_bag_fee(&self) -> f64 {
+        use crate::config::baggage;
+        let extra_cabin = self.cabin_bags.saturating_sub(baggage::CABIN_BAGS_INCLUDED);
+        let extra_checked = self.checked_bags.saturating_sub(self.checked_bag_allowance());
+        extra_cabin as f64 * baggage::EXTRA_CABIN_BAG_FEE + extra_checked as f64 * baggage::EXTRA_CHECKED_BAG_FEE
+    }
+
+    /// Checked baggage weight included in the fare for this booking's class, in kg
+    pub fn baggage_weight_allowance(&self) -> u32 {
+        use crate::config::baggage;
+        match self.seat_class {
+            SeatClass::Economy => baggage::ECONOMY_ALLOWANCE,
+            SeatClass::Business => baggage::BUSINESS_ALLOWANCE,
+            SeatClass::FirstClass => baggage::FIRST_CLASS_ALLOWANCE,
+        }
+    }
+
+    /// Overweight charge for `total_weight_kg` of checked baggage
+    pub fn calculate_baggage_fee(&self, total_weight_kg: u32) -> f64 {
+        let overage = total_weight_kg.saturating_sub(self.baggage_weight_allowance());
+        overage as f64 * crate::config::baggage::OVERWEIGHT_FEE_PER_KG
+    }
+
+    /// Estimated weight carried in the cabin, in kg
+    pub fn cabin_baggage_weight_kg(&self) -> u32 {
+        self.cabin_bags * crate::config::baggage::AVERAGE_CABIN_BAG_WEIGHT
+    }
+
+    /// Weight loaded into the hold, in kg: the declared weight of the bags that were
+    /// weighed plus an estimate for any checked since
+    pub fn checked_baggage_weight_kg(&self) -> u32 {
+        let average = crate::config::baggage::AVERAGE_CHECKED_BAG_WEIGHT;
+        if self.checked_baggage_kg == 0 {
+            return self.checked_bags * average;
+        }
+        let weighed = if self.weighed_bags == 0 { self.checked_bags } else { self.weighed_bags };
+        self.checked_baggage_kg + self.checked_bags.saturating_sub(weighed) * average
+    }
+
+    /// Record the weighed total of the bags checked so far
+    pub fn declare_checked_baggage(&mut self, total_weight_kg: u32) {
+        self.checked_baggage_kg = total_weight_kg;
+        self.weighed_bags = self.checked_bags;
     }
 
     pub fn add_special_service(&mut self, service: String) {
@@ -238,6 +525,37 @@ impl Booking {
         }
     }
 
+    pub fn is_requirement_fulfilled(&self, requirement: &str) -> bool {
+        self.fulfilled_requirements.iter().any(|r| r.eq_ignore_ascii_case(requirement))
+    }
+
+    pub fn mark_requirement_fulfilled(&mut self, requirement: &str) -> Result<(), String> {
+        let requirement = self.passenger.special_requirements
+            .iter()
+            .find(|r| r.eq_ignore_ascii_case(requirement.trim()))
+            .cloned()
+            .ok_or_else(|| format!("Passenger has no requirement '{}'", requirement))?;
+
+        if !self.is_requirement_fulfilled(&requirement) {
+            self.fulfilled_requirements.push(requirement);
+        }
+        Ok(())
+    }
+
+    /// Scrub the passenger's personal data. Fare, class and status are kept for reporting.
+    pub fn anonymize(&mut self) {
+        self.passenger.anonymize();
+        self.fulfilled_requirements.clear();
+    }
+
+    /// Bookings that still need the passenger's details: travel has not finished yet
+    pub fn is_active(&self) -> bool {
+        matches!(
+            self.status,
+            BookingStatus::Confirmed | BookingStatus::CheckedIn | BookingStatus::Boarded
+        )
+    }
+
     pub fn get_status_display(&self) -> String {
         match self.status {
             BookingStatus::Confirmed => "Confirmed ✅".to_string(),
@@ -249,6 +567,143 @@ impl Booking {
         }
     }
 
+    /// Plain-text confirmation email for this booking, free of terminal colors so it can be
+    /// saved or handed to a mailer. Missing details (seat, gate, passport) are explained
+    /// rather than left blank. `fare` itemizes the fare as the flight prices it.
+    pub fn render_confirmation_message(&self, flight: &Flight, airports: &[Airport], fare: &PriceBreakdown) -> String {
+        use crate::config::{baggage, refunds, BOARDING_WINDOW_MINUTES};
+
+        let airport_line = |code: &str| match airports.iter().find(|a| a.code == code) {
+            Some(airport) => format!("{} - {} ({}, {})", airport.code, airport.name, airport.city, airport.country),
+            None => code.to_string(),
+        };
+        let money = |amount: f64| format_currency(amount, &self.payment.currency);
+        let time = |t: DateTime<Utc>| t.format("%a %d %b %Y, %H:%M UTC").to_string();
+
+        let mut lines = vec![
+            format!("Subject: Booking confirmation {} - {} {} to {}", self.ticket_number, flight.flight_number, flight.origin, flight.destination),
+            String::new(),
+            format!("Dear {},", self.passenger.full_name()),
+            String::new(),
+            format!("Thank you for flying with {}. Here are the details of your booking.", flight.airline),
+            String::new(),
+            "BOOKING".to_string(),
+            format!("  Ticket number:  {}", self.ticket_number),
+            format!("  Status:         {:?}", self.status),
+            format!("  Booked on:      {}", time(self.booking_date)),
+            String::new(),
+            "ITINERARY".to_string(),
+            format!("  Flight:         {} ({})", flight.flight_number, flight.airline),
+            format!("  From:           {}", airport_line(&flight.origin)),
+            format!("  To:             {}", airport_line(&flight.destination)),
+            format!("  Departs:        {}", time(flight.departure_time)),
+            format!("  Arrives:        {}", time(flight.arrival_time)),
+            format!("  Duration:       {}", format_duration(flight.duration())),
+            format!("  Gate:           {}", flight.gate.as_deref().unwrap_or("Shown at the airport on the day of travel")),
+            format!("  Class:          {:?}", self.seat_class),
+            format!("  Seat:           {}", match &self.seat_assignment {
+                Some(seat) => format!("{} ({})", seat.seat_number, seat.get_seat_type()),
+                None => "Assigned at check-in".to_string(),
+            }),
+            String::new(),
+            "PASSENGER".to_string(),
+            format!("  Name:           {}", self.passenger.full_name()),
+            format!("  Passenger type: {:?}", self.passenger.passenger_type),
+            format!("  Passport:       {}", self.passenger.passport_number.as_deref()
+                .unwrap_or("Not on file - please bring a valid passport or ID")),
+        ];
+        if !self.passenger.special_requirements.is_empty() {
+            lines.push(format!("  Requirements:   {}", self.passenger.special_requirements.join(", ")));
+        }
+
+        lines.push(String::new());
+        lines.push("FARE".to_string());
+        for component in &fare.components {
+            let label = match component.multiplier {
+                Some(multiplier) => format!("{} x{:.2}", component.label, multiplier),
+                None => component.label.clone(),
+            };
+            lines.push(format!("  {:<28}{:>12}", label, money(component.running_total)));
+        }
+        lines.push(format!("  {:<28}{:>12}", format!("Fare ({:?}, {:?})", self.seat_class, self.passenger.passenger_type), money(self.payment.total_amount)));
+        let bag_fee = self.excess_bag_fee();
+        if bag_fee > 0.0 {
+            lines.push(format!("  {:<28}{:>12}", "Extra baggage", money(bag_fee)));
+        }
+        lines.push(format!("  {:<28}{:>12}", "Total", money(self.payment.total_amount + bag_fee)));
+        if self.payment.refunded_amount > 0.0 {
+            lines.push(format!("  {:<28}{:>12}", "Refunded", money(self.payment.refunded_amount)));
+        }
+        lines.push(format!("  Paid by {} (transaction {})", self.payment.payment_method, self.payment.transaction_id));
+
+        let bag_weight = flight.baggage_allowance.get(&self.seat_class).copied().unwrap_or(baggage::ECONOMY_ALLOWANCE);
+        lines.extend([
+            String::new(),
+            "BAGGAGE".to_string(),
+            format!("  Cabin:          {} bag included, {} booked", baggage::CABIN_BAGS_INCLUDED, self.cabin_bags),
+            format!("  Checked:        {} bag(s) up to {} kg each included, {} booked", self.checked_bag_allowance(), bag_weight, self.checked_bags),
+            format!("  Weight:         {}", match self.checked_baggage_kg {
+                0 => "Not declared".to_string(),
+                kg => format!("{} kg declared ({} kg included)", kg, self.baggage_weight_allowance()),
+            }),
+            String::new(),
+            "CHECK-IN".to_string(),
+            "  Check in online from 24 hours before departure, or at the airport desk.".to_string(),
+            "  Please arrive at least 2 hours before departure (3 hours for international flights).".to_string(),
+            format!("  Boarding starts {} minutes before departure and the gate closes at departure time.", BOARDING_WINDOW_MINUTES),
+            String::new(),
+            "CANCELLATION POLICY".to_string(),
+            "  You can cancel this booking under Manage Bookings until you board.".to_string(),
+            format!("  Cancel more than {} hours before departure for a full refund, or {}-{} hours before for {:.0}%.",
+                refunds::FULL_REFUND_HOURS, refunds::PARTIAL_REFUND_HOURS, refunds::FULL_REFUND_HOURS, refunds::PARTIAL_REFUND_RATE * 100.0),
+            format!("  Tickets cancelled within {} hours of departure are non-refundable; First Class is always fully refundable.",
+                refunds::PARTIAL_REFUND_HOURS),
+            "  If we cancel your flight, the full fare is refunded to the original payment method.".to_string(),
+            String::new(),
+            "We look forward to welcoming you on board.".to_string(),
+            crate::SYSTEM_NAME.to_string(),
+        ]);
+
+        let mut message = lines.join("\n");
+        message.push('\n');
+        message
+    }
+
+    /// Printable ASCII boarding pass. Values too long for the card are cut to fit.
+    pub fn generate_boarding_pass(&self, flight: &Flight) -> String {
+        use crate::config::BOARDING_WINDOW_MINUTES;
+        const WIDTH: usize = 46;
+
+        let border = format!("+{}+", "-".repeat(WIDTH + 2));
+        let row = |label: &str, value: &str| {
+            let text = format!("{:<10}{}", label, value);
+            format!("| {:<width$} |", text.chars().take(WIDTH).collect::<String>(), width = WIDTH)
+        };
+        let boarding_time = flight.departure_time - chrono::Duration::minutes(BOARDING_WINDOW_MINUTES);
+
+        let lines = [
+            border.clone(),
+            format!("| {:^width$} |", "BOARDING PASS", width = WIDTH),
+            format!("| {:^width$} |", flight.airline.chars().take(WIDTH).collect::<String>(), width = WIDTH),
+            border.clone(),
+            row("Name", &self.passenger.full_name().to_uppercase()),
+            row("Ticket", &self.ticket_number),
+            row("Flight", &flight.flight_number),
+            row("Route", &format!("{} -> {}", flight.origin, flight.destination)),
+            row("Date", &flight.departure_time.format("%d %b %Y").to_string()),
+            row("Boarding", &boarding_time.format("%H:%M UTC").to_string()),
+            row("Departs", &flight.departure_time.format("%H:%M UTC").to_string()),
+            row("Gate", flight.gate.as_deref().unwrap_or("TBD")),
+            row("Seat", self.seat_assignment.as_ref().map_or("TBD", |seat| seat.seat_number.as_str())),
+            row("Class", &format!("{:?}", self.seat_class)),
+            border,
+        ];
+
+        let mut pass = lines.join("\n");
+        pass.push('\n');
+        pass
+    }
+
     pub fn get_ticket_summary(&self) -> String {
         let seat_info = match &self.seat_assignment {
             Some(seat) => format!("Seat: {} ({})", seat.seat_number, seat.get_seat_type()),
@@ -271,6 +726,14 @@ impl Booking {
     }
 }
 
+fn default_cabin_bags() -> u32 {
+    1
+}
+
+fn default_checked_bags() -> u32 {
+    1
+}
+
 impl std::fmt::Display for Booking {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
@@ -285,9 +748,13 @@ impl std::fmt::Display for Booking {
 mod rand {
     use std::collections::hash_map::DefaultHasher;
     use std::hash::{Hash, Hasher};
+    use std::sync::atomic::{AtomicU64, Ordering};
     use std::time::{SystemTime, UNIX_EPOCH};
 
-    pub fn random<T: Hash>() -> u64 {
+    // Calls within the same clock tick would otherwise hash to the same value
+    static CALLS: AtomicU64 = AtomicU64::new(0);
+
+    pub fn random() -> u64 {
         let mut hasher = DefaultHasher::new();
         
         // Use current time as seed
@@ -297,6 +764,166 @@ mod rand {
             .as_nanos();
         
         time.hash(&mut hasher);
+        CALLS.fetch_add(1, Ordering::Relaxed).hash(&mut hasher);
         hasher.finish()
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_requirement_categories() {
+        assert_eq!(requirement_category("Wheelchair assistance"), "wheelchair");
+        assert_eq!(requirement_category("Vegetarian meal"), "vegetarian");
+        assert_eq!(requirement_category("Portable oxygen"), "medical equipment");
+        assert_eq!(requirement_category("Carpet cleaning"), "carpet cleaning");
+        assert!(is_critical_requirement("Medical equipment"));
+        assert!(!is_critical_requirement("Kosher meal"));
+    }
+
+    fn test_booking(seat_class: SeatClass) -> Booking {
+        let passenger = Passenger::new(
+            "Ada".to_string(),
+            "Lovelace".to_string(),
+            "ada@example.com".to_string(),
+            "5551234567".to_string(),
+            "1990-01-01".to_string(),
+            PassengerType::Adult,
+        );
+        Booking::new(Uuid::new_v4(), passenger, seat_class, 299.99, "Credit Card".to_string())
+    }
+
+    #[test]
+    fn test_excess_bag_fees_by_class() {
+        let mut booking = test_booking(SeatClass::Economy);
+        assert_eq!((booking.cabin_bags, booking.checked_bags), (1, 1));
+        assert_eq!(booking.excess_bag_fee(), 0.0);
+
+        booking.add_cabin_bags(1);
+        booking.add_checked_bags(2);
+        assert_eq!(booking.excess_bag_fee(), 35.0 + 2.0 * 60.0);
+        assert_eq!(booking.checked_baggage_weight_kg(), 60);
+
+        let mut business = test_booking(SeatClass::Business);
+        business.add_checked_bags(1);
+        assert_eq!(business.excess_bag_fee(), 0.0);
+    }
+
+    #[test]
+    fn test_legacy_baggage_count_migrates_to_checked_bags() {
+        let mut json = serde_json::to_value(test_booking(SeatClass::Economy)).unwrap();
+        let fields = json.as_object_mut().unwrap();
+        fields.remove("cabin_bags");
+        fields.remove("checked_bags");
+        fields.insert("baggage_count".to_string(), serde_json::json!(3));
+
+        let booking: Booking = serde_json::from_value(json).unwrap();
+
+        assert_eq!(booking.checked_bags, 3);
+        assert_eq!(booking.cabin_bags, 1);
+    }
+
+    #[test]
+    fn test_confirmation_message_is_plain_and_handles_missing_details() {
+        let airport = Airport::new(
+            "LAX".to_string(),
+            "KLAX".to_string(),
+            "Los Angeles International Airport".to_string(),
+            "Los Angeles".to_string(),
+            "United States".to_string(),
+            "America/Los_Angeles".to_string(),
+            33.9425,
+            -118.4081,
+            38,
+        );
+        let departure = Utc::now() + chrono::Duration::hours(6);
+        let flight = Flight::new(
+            "RIA900".to_string(),
+            "Rust International Airways".to_string(),
+            "LAX".to_string(),
+            "JFK".to_string(),
+            departure,
+            departure + chrono::Duration::hours(5),
+            &crate::modules::aircraft::Aircraft::new("N1TEST".to_string(), "Boeing 737-800".to_string(), "Boeing".to_string(), 2020),
+        );
+        let mut booking = test_booking(SeatClass::Economy);
+        let mut fare = PriceBreakdown::start("Base fare (Economy)".to_string(), 249.99);
+        fare.apply("Dynamic pricing".to_string(), 1.2);
+
+        let message = booking.render_confirmation_message(&flight, std::slice::from_ref(&airport), &fare);
+        assert!(!message.contains('\x1b'));
+        assert!(message.starts_with(&format!("Subject: Booking confirmation {}", booking.ticket_number)));
+        assert!(message.contains("Dear Ada Lovelace,"));
+        assert!(message.contains("From:           LAX - Los Angeles International Airport (Los Angeles, United States)"));
+        // JFK is not in the airport list, so only the code is shown
+        assert!(message.contains("To:             JFK\n"));
+        assert!(message.contains("Seat:           Assigned at check-in"));
+        assert!(message.contains("Passport:       Not on file"));
+        assert!(message.contains("Gate:           Shown at the airport"));
+        assert!(message.contains("  Base fare (Economy)              $249.99\n"));
+        assert!(message.contains("  Dynamic pricing x1.20            $299.99\n"));
+        assert!(!message.contains("Extra baggage"));
+        assert!(message.contains("CANCELLATION POLICY"));
+
+        booking.assign_seat("14A".to_string());
+        booking.passenger.passport_number = Some("X1234567".to_string());
+        booking.add_checked_bags(1);
+        let message = booking.render_confirmation_message(&flight, &[airport], &fare);
+        assert!(message.contains("Seat:           14A (Window + Emergency Exit)"));
+        assert!(message.contains("Passport:       X1234567"));
+        assert!(message.contains("Extra baggage"));
+        assert!(message.contains("Total                            $359.99"));
+    }
+
+    #[test]
+    fn test_ticket_numbers_are_unique_across_many_bookings() {
+        let mut bookings: Vec<Booking> = Vec::with_capacity(10_000);
+        for _ in 0..10_000 {
+            let mut booking = test_booking(SeatClass::Economy);
+            booking.ticket_number = Booking::generate_ticket_number(&bookings);
+            bookings.push(booking);
+        }
+
+        let distinct: std::collections::HashSet<&str> = bookings.iter().map(|b| b.ticket_number.as_str()).collect();
+        assert_eq!(distinct.len(), 10_000);
+        assert!(bookings.iter().all(|b| b.ticket_number.len() == 9 && b.ticket_number.starts_with("RIA")));
+    }
+
+    #[test]
+    fn test_baggage_fee_charges_weight_over_class_allowance() {
+        let passenger = Passenger::new(
+            "Ada".to_string(),
+            "Lovelace".to_string(),
+            "ada@example.com".to_string(),
+            "5551234567".to_string(),
+            "1990-01-01".to_string(),
+            PassengerType::Adult,
+        );
+        let economy = Booking::new(Uuid::new_v4(), passenger.clone(), SeatClass::Economy, 100.0, "Credit Card".to_string());
+        let business = Booking::new(Uuid::new_v4(), passenger, SeatClass::Business, 100.0, "Credit Card".to_string());
+
+        assert_eq!(economy.calculate_baggage_fee(18), 0.0);
+        assert_eq!(economy.calculate_baggage_fee(23), 0.0);
+        assert_eq!(economy.calculate_baggage_fee(33), 150.0);
+        assert_eq!(business.calculate_baggage_fee(33), 15.0);
+    }
+
+    #[test]
+    fn test_history_records_check_in_then_cancel_in_order() {
+        // Bookings saved before history was kept load with an empty one
+        let mut json = serde_json::to_value(test_booking(SeatClass::Economy)).unwrap();
+        json.as_object_mut().unwrap().remove("history");
+        let mut booking: Booking = serde_json::from_value(json).unwrap();
+        assert!(booking.history.is_empty());
+
+        booking.check_in().unwrap();
+        booking.cancel().unwrap();
+        // Failed transitions leave no trace
+        assert!(booking.board().is_err());
+
+        let events: Vec<&str> = booking.history.iter().map(|e| e.description.as_str()).collect();
+        assert_eq!(events, ["Checked in", "Cancelled"]);
+        assert!(booking.history[0].timestamp <= booking.history[1].timestamp);
+    }
+}
@@ -0,0 +1,97 @@
+use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use crate::config::currency::{BASE_CURRENCY, DEFAULT_RATES};
+use crate::utils::format_currency;
+
+/// Exchange rate in force when a booking was priced for a passenger viewing another
+/// currency. The booking itself is always charged in USD.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExchangeQuote {
+    pub currency: String,
+    pub rate: f64, // Units of `currency` per USD
+}
+
+/// Converts USD amounts for display. Rates are units of each currency per USD.
+#[derive(Debug, Clone)]
+pub struct CurrencyConverter {
+    pub rates: HashMap<String, f64>,
+}
+
+impl Default for CurrencyConverter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CurrencyConverter {
+    /// The built-in rates from `config::currency::DEFAULT_RATES`
+    pub fn new() -> Self {
+        Self {
+            rates: DEFAULT_RATES.iter().map(|(code, rate)| (code.to_string(), *rate)).collect(),
+        }
+    }
+
+    /// Rates from a JSON object such as `{"EUR": 0.92, "GBP": 0.79}`, on top of the
+    /// built-in ones so currencies missing from the file still convert
+    pub fn from_json(json: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let loaded: HashMap<String, f64> = serde_json::from_str(json)?;
+        if let Some((code, rate)) = loaded.iter().find(|(_, rate)| !rate.is_finite() || **rate <= 0.0) {
+            return Err(format!("Exchange rate for {} must be a positive number, got {}", code, rate).into());
+        }
+
+        let mut converter = Self::new();
+        converter.rates.extend(loaded.into_iter().map(|(code, rate)| (code.to_uppercase(), rate)));
+        converter.rates.insert(BASE_CURRENCY.to_string(), 1.0);
+        Ok(converter)
+    }
+
+    pub fn load_from_file(path: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        Self::from_json(&std::fs::read_to_string(path)?)
+    }
+
+    pub fn rate(&self, currency: &str) -> Option<f64> {
+        self.rates.get(&currency.to_uppercase()).copied()
+    }
+
+    /// `amount` USD in `to`. An unknown currency falls back to the USD amount.
+    pub fn convert(&self, amount: f64, to: &str) -> f64 {
+        amount * self.rate(to).unwrap_or(1.0)
+    }
+
+    /// `amount` USD converted and formatted in `to`, or in USD when `to` is unknown
+    pub fn format(&self, amount: f64, to: &str) -> String {
+        match self.rate(to) {
+            Some(rate) => format_currency(amount * rate, &to.to_uppercase()),
+            None => format_currency(amount, BASE_CURRENCY),
+        }
+    }
+
+    /// Currency codes with a rate, alphabetically
+    pub fn currencies(&self) -> Vec<&str> {
+        let mut codes: Vec<&str> = self.rates.keys().map(String::as_str).collect();
+        codes.sort_unstable();
+        codes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_convert_and_fall_back_for_unknown_currency() {
+        let converter = CurrencyConverter::from_json(r#"{"eur": 0.5, "XTS": 2.0}"#).unwrap();
+
+        assert_eq!(converter.convert(100.0, "EUR"), 50.0);
+        assert_eq!(converter.convert(100.0, "xts"), 200.0);
+        assert_eq!(converter.format(100.0, "EUR"), "€50.00");
+        // GBP wasn't in the file, so the built-in rate applies
+        assert_eq!(converter.rate("GBP"), DEFAULT_RATES.iter().find(|(c, _)| *c == "GBP").map(|(_, r)| *r));
+        // Unknown currencies show the USD amount
+        assert_eq!(converter.convert(100.0, "ZZZ"), 100.0);
+        assert_eq!(converter.format(100.0, "ZZZ"), "$100.00");
+
+        assert!(CurrencyConverter::from_json(r#"{"EUR": -1.0}"#).is_err());
+        assert!(CurrencyConverter::from_json("not json").is_err());
+    }
+}
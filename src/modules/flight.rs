@@ -1,9 +1,13 @@
 use serde::{Deserialize, Serialize};
-use chrono::{DateTime, Utc, Duration};
+use chrono::{DateTime, Datelike, NaiveDate, NaiveTime, Utc, Duration, Weekday};
 use uuid::Uuid;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::ops::RangeInclusive;
+use crate::modules::aircraft::{Aircraft, SeatConfiguration};
+use crate::modules::booking::{Booking, BookingStatus, Passenger, PassengerType};
+use crate::errors::{self, AirportError};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum FlightStatus {
     OnTime,
     Delayed(i32), // minutes delayed
@@ -13,6 +17,23 @@ pub enum FlightStatus {
     Cancelled,
 }
 
+impl FlightStatus {
+    /// The flight lifecycle: OnTime/Delayed → Boarding → Departed → Arrived. Delays can be
+    /// set, changed or cleared until the aircraft leaves, and a flight can be cancelled at
+    /// any point before departure. Arrived and Cancelled are final.
+    pub fn can_transition_to(&self, next: &FlightStatus) -> bool {
+        use FlightStatus::*;
+
+        match (self, next) {
+            (OnTime, Delayed(_) | Boarding | Cancelled) => true,
+            (Delayed(_), OnTime | Delayed(_) | Boarding | Cancelled) => true,
+            (Boarding, Delayed(_) | Departed | Cancelled) => true, // Held at the gate
+            (Departed, Arrived) => true,
+            (current, next) => current == next,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub enum SeatClass {
     Economy,
@@ -27,12 +48,70 @@ pub struct SeatAvailability {
     pub first_class: u32,
 }
 
+impl SeatAvailability {
+    /// Every seat in each cabin of the aircraft's seat configuration
+    pub fn for_aircraft(aircraft: &Aircraft) -> Self {
+        Self {
+            economy: aircraft.get_seats_by_class(&SeatClass::Economy),
+            business: aircraft.get_seats_by_class(&SeatClass::Business),
+            first_class: aircraft.get_seats_by_class(&SeatClass::FirstClass),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FlightPricing {
     pub economy: f64,
     pub business: f64,
     pub first_class: f64,
     pub dynamic_multiplier: f64, // For admin dynamic pricing
+    #[serde(default)]
+    pub manual_override: bool,   // An admin set the multiplier by hand; it replaces demand pricing
+    #[serde(default = "default_demand_pricing")]
+    pub demand_pricing: bool,    // Raise fares automatically as each class fills
+    #[serde(default)]
+    pub distance_based: bool,    // Base fares set for this route (from its length or by hand) rather than flat defaults
+}
+
+fn default_demand_pricing() -> bool {
+    true
+}
+
+/// One step of a fare calculation
+#[derive(Debug, Clone, PartialEq)]
+pub struct PriceComponent {
+    pub label: String,
+    pub multiplier: Option<f64>, // None for the base fare
+    pub running_total: f64,
+}
+
+/// Itemized fare: base fare followed by every multiplier applied to it
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PriceBreakdown {
+    pub components: Vec<PriceComponent>,
+    pub total: f64,
+}
+
+impl PriceBreakdown {
+    pub(crate) fn start(label: String, base: f64) -> Self {
+        Self {
+            components: vec![PriceComponent { label, multiplier: None, running_total: base }],
+            total: base,
+        }
+    }
+
+    pub(crate) fn apply(&mut self, label: String, multiplier: f64) {
+        self.total *= multiplier;
+        self.components.push(PriceComponent { label, multiplier: Some(multiplier), running_total: self.total });
+    }
+}
+
+/// A passenger waiting for a seat in a sold-out class
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WaitlistEntry {
+    pub passenger: Passenger,
+    pub seat_class: SeatClass,
+    pub joined_at: DateTime<Utc>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -50,10 +129,102 @@ pub struct Flight {
     pub seat_availability: SeatAvailability,
     pub pricing: FlightPricing,
     pub total_capacity: u32,
+    #[serde(default)]
+    pub cabin_capacity: Option<SeatAvailability>, // Seats per class on the aircraft; None = 70/25/5 split of total_capacity
     pub baggage_allowance: HashMap<SeatClass, u32>, // kg per class
+    #[serde(default)]
+    pub meal_service: Option<bool>, // None = decided by flight duration
+    #[serde(default)]
+    pub meal_options: Vec<String>,  // Main course choices, e.g. "Chicken", "Pasta"
+    #[serde(default)]
+    pub waitlist: Vec<WaitlistEntry>, // In joining order; promoted by loyalty tier first
+}
+
+/// A seat reassignment made by `Flight::optimize_seat_layout`
+#[derive(Debug, Clone, PartialEq)]
+pub struct SeatChange {
+    pub booking_id: Uuid,
+    pub ticket_number: String,
+    pub from: Option<String>, // None when the passenger had no seat yet
+    pub to: String,
+}
+
+/// Seat letters across a row; "I" is skipped because it reads like a 1
+const SEAT_LETTERS: [char; 10] = ['A', 'B', 'C', 'D', 'E', 'F', 'G', 'H', 'J', 'K'];
+
+/// Seats between aisles for a row width, e.g. 3-3 on a narrow-body
+fn row_blocks(seats_per_row: u32) -> Vec<u32> {
+    match seats_per_row {
+        0..=3 => vec![seats_per_row],
+        4 => vec![2, 2],
+        5 => vec![2, 3],
+        6 => vec![3, 3],
+        7 => vec![2, 3, 2],
+        8 => vec![2, 4, 2],
+        9 => vec![3, 3, 3],
+        n => vec![3, n - 6, 3],
+    }
+}
+
+/// Seat numbers of a cabin split into aisle-to-aisle blocks, front row first
+fn cabin_blocks(rows: RangeInclusive<u32>, seats_per_row: u32) -> Vec<Vec<String>> {
+    let seats_per_row = seats_per_row.min(SEAT_LETTERS.len() as u32);
+    let mut blocks = Vec::new();
+    for row in rows {
+        let mut letters = SEAT_LETTERS.iter();
+        for width in row_blocks(seats_per_row) {
+            blocks.push(letters.by_ref().take(width as usize).map(|l| format!("{}{}", row, l)).collect());
+        }
+    }
+    blocks
+}
+
+/// Seat numbers of one cabin of an aircraft, split into aisle-to-aisle blocks, front row first
+pub fn cabin_seat_blocks(seat_config: &SeatConfiguration, class: &SeatClass) -> Vec<Vec<String>> {
+    let (rows, seats_per_row) = seat_config.cabin_rows(class);
+    cabin_blocks(rows, seats_per_row)
+}
+
+/// Maximal runs of free seats within each block as (block, start, length)
+fn free_runs(blocks: &[Vec<String>], taken: &HashSet<String>) -> Vec<(usize, usize, usize)> {
+    let mut runs = Vec::new();
+    for (index, block) in blocks.iter().enumerate() {
+        let mut start = None;
+        for (position, seat) in block.iter().enumerate() {
+            match (taken.contains(seat), start) {
+                (false, None) => start = Some(position),
+                (true, Some(from)) => {
+                    runs.push((index, from, position - from));
+                    start = None;
+                }
+                _ => {}
+            }
+        }
+        if let Some(from) = start {
+            runs.push((index, from, block.len() - from));
+        }
+    }
+    runs
+}
+
+/// Pick seats for a party: the smallest run that fits all of them (so a lone passenger
+/// fills an isolated seat first), otherwise the largest runs available in turn
+fn pick_seats(blocks: &[Vec<String>], taken: &HashSet<String>, party_size: usize) -> Vec<String> {
+    let mut runs = free_runs(blocks, taken);
+    if let Some(&(block, start, _)) = runs.iter().filter(|r| r.2 >= party_size).min_by_key(|r| r.2) {
+        return blocks[block][start..start + party_size].to_vec();
+    }
+
+    // No single run is big enough: split across as few runs as possible
+    runs.sort_by_key(|r| std::cmp::Reverse(r.2));
+    runs.iter()
+        .flat_map(|&(block, start, len)| blocks[block][start..start + len].iter().cloned())
+        .take(party_size)
+        .collect()
 }
 
 impl Flight {
+    /// A new on-time flight with every seat of the aircraft's cabins for sale
     pub fn new(
         flight_number: String,
         airline: String,
@@ -61,12 +232,9 @@ impl Flight {
         destination: String,
         departure_time: DateTime<Utc>,
         arrival_time: DateTime<Utc>,
-        aircraft_id: Uuid,
-        total_capacity: u32,
+        aircraft: &Aircraft,
     ) -> Self {
-        let economy_seats = (total_capacity as f32 * 0.7) as u32;
-        let business_seats = (total_capacity as f32 * 0.25) as u32;
-        let first_class_seats = total_capacity - economy_seats - business_seats;
+        let cabin_capacity = SeatAvailability::for_aircraft(aircraft);
 
         let mut baggage_allowance = HashMap::new();
         baggage_allowance.insert(SeatClass::Economy, 23);
@@ -82,28 +250,120 @@ impl Flight {
             departure_time,
             arrival_time,
             status: FlightStatus::OnTime,
-            aircraft_id,
+            aircraft_id: aircraft.id,
             gate: None,
-            seat_availability: SeatAvailability {
-                economy: economy_seats,
-                business: business_seats,
-                first_class: first_class_seats,
-            },
+            seat_availability: cabin_capacity.clone(),
             pricing: FlightPricing {
                 economy: 299.99,
                 business: 899.99,
                 first_class: 1999.99,
                 dynamic_multiplier: 1.0,
+                manual_override: false,
+                demand_pricing: true,
+                distance_based: false,
             },
-            total_capacity,
+            total_capacity: aircraft.total_capacity,
+            cabin_capacity: Some(cabin_capacity),
             baggage_allowance,
+            meal_service: None,
+            meal_options: Vec::new(),
+            waitlist: Vec::new(),
         }
     }
 
+    /// Set base fares from the great-circle route length using the per-km rates and
+    /// minimum fares in `config::pricing`
+    pub fn recompute_base_fares(&mut self, distance_km: f64) {
+        use crate::config::pricing::*;
+
+        let fare = |rate: f64, minimum: f64| ((rate * distance_km).max(minimum) * 100.0).round() / 100.0;
+        self.pricing.economy = fare(ECONOMY_FARE_PER_KM, MIN_ECONOMY_FARE);
+        self.pricing.business = fare(BUSINESS_FARE_PER_KM, MIN_BUSINESS_FARE);
+        self.pricing.first_class = fare(FIRST_CLASS_FARE_PER_KM, MIN_FIRST_CLASS_FARE);
+        self.pricing.distance_based = true;
+    }
+
+    /// Override the base fare of each class, e.g. for a route priced differently from
+    /// its distance. Dynamic and demand multipliers still apply on top.
+    pub fn set_base_prices(&mut self, economy: f64, business: f64, first_class: f64) -> Result<(), String> {
+        if [economy, business, first_class].iter().any(|fare| !fare.is_finite() || *fare <= 0.0) {
+            return Err("Base fares must be positive".to_string());
+        }
+        self.pricing.economy = economy;
+        self.pricing.business = business;
+        self.pricing.first_class = first_class;
+        self.pricing.distance_based = true; // Keep the load-time migration from recomputing them
+        Ok(())
+    }
+
+    /// Economy, business and first class seats for a cabin of `total_capacity`, for flights
+    /// saved before per-class capacity was recorded
+    fn class_split(total_capacity: u32) -> (u32, u32, u32) {
+        let economy_seats = (total_capacity as f32 * 0.7) as u32;
+        let business_seats = (total_capacity as f32 * 0.25) as u32;
+        (economy_seats, business_seats, total_capacity - economy_seats - business_seats)
+    }
+
+    /// Seats sold in a class at the start of sales
+    pub fn class_capacity(&self, class: &SeatClass) -> u32 {
+        let (economy, business, first_class) = match &self.cabin_capacity {
+            Some(cabin) => (cabin.economy, cabin.business, cabin.first_class),
+            None => Self::class_split(self.total_capacity),
+        };
+        match class {
+            SeatClass::Economy => economy,
+            SeatClass::Business => business,
+            SeatClass::FirstClass => first_class,
+        }
+    }
+
+    /// Share of a class already sold, from 0.0 (empty) to 1.0 (full)
+    pub fn load_factor(&self, class: &SeatClass) -> f64 {
+        let capacity = self.class_capacity(class);
+        if capacity == 0 {
+            return 0.0;
+        }
+        let sold = capacity.saturating_sub(self.get_available_seats(class));
+        sold as f64 / capacity as f64
+    }
+
+    /// Automatic fare increase for a class as it fills, see `config::pricing::DEMAND_*`.
+    /// Always 1.0 when demand pricing is switched off for this flight or an admin has set
+    /// the multiplier by hand, even to 1.0.
+    pub fn demand_multiplier(&self, class: &SeatClass) -> f64 {
+        use crate::config::pricing::{DEMAND_INCREMENT, DEMAND_STEP, DEMAND_THRESHOLD};
+
+        if !self.pricing.demand_pricing || self.pricing.manual_override {
+            return 1.0;
+        }
+        let excess_load = (self.load_factor(class) - DEMAND_THRESHOLD).max(0.0);
+        1.0 + excess_load / DEMAND_STEP * DEMAND_INCREMENT
+    }
+
     pub fn duration(&self) -> Duration {
         self.arrival_time - self.departure_time
     }
 
+    /// Fuel needed to fly `distance_km` at the aircraft's rated burn
+    pub fn estimated_fuel_liters(&self, distance_km: f64, aircraft: &Aircraft) -> f64 {
+        distance_km / 100.0 * aircraft.performance.fuel_efficiency_l_per_100km
+    }
+
+    pub fn fuel_cost(&self, distance_km: f64, aircraft: &Aircraft, price_per_liter: f64) -> f64 {
+        self.estimated_fuel_liters(distance_km, aircraft) * price_per_liter
+    }
+
+    /// Whether a meal is served: long flights get one automatically unless overridden
+    pub fn has_meal_service(&self) -> bool {
+        self.meal_service
+            .unwrap_or_else(|| self.duration() > Duration::hours(crate::config::MEAL_SERVICE_MIN_HOURS))
+    }
+
+    /// Force meal service on or off, or pass `None` to go back to the duration-based default
+    pub fn set_meal_service(&mut self, meal_service: Option<bool>) {
+        self.meal_service = meal_service;
+    }
+
     pub fn is_available_for_booking(&self) -> bool {
         matches!(self.status, FlightStatus::OnTime | FlightStatus::Delayed(_))
             && self.departure_time > Utc::now()
@@ -123,7 +383,31 @@ impl Flight {
             SeatClass::Business => self.pricing.business,
             SeatClass::FirstClass => self.pricing.first_class,
         };
-        base_price * self.pricing.dynamic_multiplier
+        base_price * self.pricing.dynamic_multiplier * self.demand_multiplier(class)
+    }
+
+    /// Price for a class after the passenger-type discount
+    pub fn get_price_for(&self, class: &SeatClass, passenger_type: &PassengerType) -> f64 {
+        self.get_price(class) * passenger_type.fare_rate()
+    }
+
+    /// Move a booked seat from one cabin to another, e.g. for an upgrade. Unlike
+    /// `book_seat` this still works while boarding.
+    pub fn move_seat(&mut self, from: &SeatClass, to: &SeatClass) -> Result<(), String> {
+        if self.get_available_seats(to) == 0 {
+            return Err(format!("No {:?} seats available", to));
+        }
+        *self.seats_mut(to) -= 1;
+        *self.seats_mut(from) += 1;
+        Ok(())
+    }
+
+    fn seats_mut(&mut self, class: &SeatClass) -> &mut u32 {
+        match class {
+            SeatClass::Economy => &mut self.seat_availability.economy,
+            SeatClass::Business => &mut self.seat_availability.business,
+            SeatClass::FirstClass => &mut self.seat_availability.first_class,
+        }
     }
 
     pub fn book_seat(&mut self, class: &SeatClass) -> Result<(), String> {
@@ -159,20 +443,150 @@ impl Flight {
         }
     }
 
-    pub fn set_delay(&mut self, minutes: i32) {
+    /// Move to a new status, refusing jumps the lifecycle does not allow
+    /// (see `FlightStatus::can_transition_to`). Setting the current status again is a no-op.
+    pub fn try_transition(&mut self, new_status: FlightStatus) -> errors::Result<()> {
+        if !self.status.can_transition_to(&new_status) {
+            return Err(AirportError::InvalidStatusTransition {
+                flight_number: self.flight_number.clone(),
+                from: self.status.clone(),
+                to: new_status,
+            });
+        }
+        self.status = new_status;
+        Ok(())
+    }
+
+    /// Step forward through the lifecycle until `target` is reached, so a simulation that
+    /// missed a window (e.g. boarding while the app was closed) still takes every legal step.
+    /// Targets behind the current stage are ignored.
+    pub fn advance_to(&mut self, target: FlightStatus) -> errors::Result<()> {
+        let stages = [FlightStatus::Boarding, FlightStatus::Departed, FlightStatus::Arrived];
+        let Some(target_stage) = stages.iter().position(|s| *s == target) else {
+            return self.try_transition(target);
+        };
+        let current_stage = stages.iter().position(|s| *s == self.status);
+
+        // Walk a copy first so a refused step leaves the flight untouched
+        let mut walked = self.clone();
+        let first = current_stage.map_or(0, |stage| stage + 1);
+        for stage in stages.into_iter().take(target_stage + 1).skip(first) {
+            walked.try_transition(stage)?;
+        }
+        self.status = walked.status;
+        Ok(())
+    }
+
+    /// Add `minutes` of delay on top of any delay already announced. A flight whose total
+    /// delay goes past `config::delays::MAX_DELAY_MINUTES` is cancelled instead. Zero puts
+    /// the flight back on time; a negative value also brings departure and arrival forward.
+    pub fn set_delay(&mut self, minutes: i32) -> errors::Result<()> {
         if minutes > 0 {
-            self.status = FlightStatus::Delayed(minutes);
+            let total = match self.status {
+                FlightStatus::Delayed(previous) => previous + minutes,
+                _ => minutes,
+            };
+            if total > crate::config::delays::MAX_DELAY_MINUTES {
+                return self.try_transition(FlightStatus::Cancelled);
+            }
+            self.try_transition(FlightStatus::Delayed(total))?;
             // Update arrival time accordingly
-            self.arrival_time = self.arrival_time + Duration::minutes(minutes as i64);
+            self.arrival_time += Duration::minutes(minutes as i64);
         } else {
-            self.status = FlightStatus::OnTime;
+            self.try_transition(FlightStatus::OnTime)?;
+            let early = Duration::minutes(minutes as i64);
+            self.departure_time += early;
+            self.arrival_time += early;
+        }
+        Ok(())
+    }
+
+    /// Reshuffle seats before departure so groups sit together and empty seats form
+    /// contiguous blocks rather than isolated middle seats. Paid seat selections are never
+    /// moved; everyone else is repacked cabin by cabin with a greedy best-fit pass, largest
+    /// groups first. Returns the seats that changed.
+    pub fn optimize_seat_layout<'a>(
+        &self,
+        seat_config: &SeatConfiguration,
+        bookings: impl IntoIterator<Item = &'a mut Booking>,
+    ) -> Vec<SeatChange> {
+        if !matches!(self.status, FlightStatus::OnTime | FlightStatus::Delayed(_)) {
+            return Vec::new();
+        }
+
+        let mut bookings: Vec<&mut Booking> = bookings
+            .into_iter()
+            .filter(|b| b.flight_id == self.id)
+            .filter(|b| matches!(b.status, BookingStatus::Confirmed | BookingStatus::CheckedIn))
+            .filter(|b| b.passenger.passenger_type.occupies_seat())
+            .collect();
+
+        let mut changes = Vec::new();
+        for class in [SeatClass::FirstClass, SeatClass::Business, SeatClass::Economy] {
+            let blocks = cabin_seat_blocks(seat_config, &class);
+            let seat_order: HashMap<&String, usize> = blocks.iter().flatten().enumerate().map(|(i, s)| (s, i)).collect();
+
+            let cabin: Vec<usize> = (0..bookings.len()).filter(|&i| bookings[i].seat_class == class).collect();
+            let mut taken: HashSet<String> = cabin
+                .iter()
+                .filter(|&&i| bookings[i].has_fixed_seat())
+                .filter_map(|&i| bookings[i].seat_assignment.as_ref().map(|s| s.seat_number.clone()))
+                .collect();
+
+            // Current seat position keeps the result stable for passengers who are already well placed
+            let position = |booking: &Booking| {
+                let seat = booking.seat_assignment.as_ref().and_then(|s| seat_order.get(&s.seat_number));
+                (seat.copied().unwrap_or(usize::MAX), booking.booking_date)
+            };
+
+            let mut groups: HashMap<Uuid, Vec<usize>> = HashMap::new();
+            let mut parties: Vec<Vec<usize>> = Vec::new();
+            for &i in cabin.iter().filter(|&&i| !bookings[i].has_fixed_seat()) {
+                match bookings[i].group_id {
+                    Some(group_id) => groups.entry(group_id).or_default().push(i),
+                    None => parties.push(vec![i]),
+                }
+            }
+            parties.extend(groups.into_values());
+            for party in parties.iter_mut() {
+                party.sort_by_key(|&i| position(bookings[i]));
+            }
+            parties.sort_by_key(|party| (std::cmp::Reverse(party.len()), position(bookings[party[0]])));
+
+            for party in parties {
+                let seats = pick_seats(&blocks, &taken, party.len());
+                for (&i, seat) in party.iter().zip(seats) {
+                    taken.insert(seat.clone());
+                    let booking = &mut bookings[i];
+                    let from = booking.seat_assignment.as_ref().map(|s| s.seat_number.clone());
+                    if from.as_ref() != Some(&seat) {
+                        booking.assign_seat(seat.clone());
+                        changes.push(SeatChange {
+                            booking_id: booking.id,
+                            ticket_number: booking.ticket_number.clone(),
+                            from,
+                            to: seat,
+                        });
+                    }
+                }
+            }
         }
+
+        changes
     }
 
     pub fn set_gate(&mut self, gate: String) {
         self.gate = Some(gate);
     }
 
+    /// Scheduled departure pushed back by any delay
+    pub fn estimated_departure(&self) -> DateTime<Utc> {
+        match self.status {
+            FlightStatus::Delayed(minutes) => self.departure_time + Duration::minutes(minutes as i64),
+            _ => self.departure_time,
+        }
+    }
+
     pub fn get_status_display(&self) -> String {
         match &self.status {
             FlightStatus::OnTime => "On Time ✅".to_string(),
@@ -197,4 +611,395 @@ impl std::fmt::Display for Flight {
             self.get_status_display()
         )
     }
-}
\ No newline at end of file
+}
+
+/// A flight that repeats on fixed weekdays. Times are UTC, like every other time in
+/// the schedule.
+#[derive(Debug, Clone)]
+pub struct FlightTemplate {
+    pub flight_number: String, // The first generated flight gets this number if it's free
+    pub airline: String,
+    pub origin: String,
+    pub destination: String,
+    pub aircraft_id: Uuid,
+    pub departure_time: NaiveTime,
+    pub duration_minutes: i64,
+    pub days: Vec<Weekday>,
+}
+
+impl FlightTemplate {
+    pub fn runs_on(&self, date: NaiveDate) -> bool {
+        self.days.contains(&date.weekday())
+    }
+
+    /// Departure and arrival of the flight on `date`
+    pub fn times_on(&self, date: NaiveDate) -> (DateTime<Utc>, DateTime<Utc>) {
+        let departure = date.and_time(self.departure_time).and_utc();
+        (departure, departure + Duration::minutes(self.duration_minutes))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::booking::Passenger;
+
+    fn test_flight() -> Flight {
+        let departure = Utc::now() + Duration::hours(6);
+        Flight::new(
+            "RIA900".to_string(),
+            "Rust International Airways".to_string(),
+            "LAX".to_string(),
+            "JFK".to_string(),
+            departure,
+            departure + Duration::hours(5),
+            &Aircraft::new("N1TEST".to_string(), "Boeing 737-800".to_string(), "Boeing".to_string(), 2020),
+        )
+    }
+
+    #[test]
+    fn test_price_for_passenger_types() {
+        let flight = test_flight();
+        let full = flight.get_price(&SeatClass::Economy);
+
+        assert_eq!(flight.get_price_for(&SeatClass::Economy, &PassengerType::Adult), full);
+        assert!((flight.get_price_for(&SeatClass::Economy, &PassengerType::Child) - full * 0.75).abs() < 1e-9);
+        assert!((flight.get_price_for(&SeatClass::Economy, &PassengerType::Infant) - full * 0.10).abs() < 1e-9);
+        assert!((flight.get_price_for(&SeatClass::Economy, &PassengerType::Senior) - full * 0.90).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_meal_service_follows_duration_unless_overridden() {
+        let mut flight = test_flight();
+        assert!(flight.has_meal_service());
+
+        flight.arrival_time = flight.departure_time + Duration::minutes(90);
+        assert!(!flight.has_meal_service());
+
+        flight.set_meal_service(Some(true));
+        assert!(flight.has_meal_service());
+        flight.set_meal_service(None);
+        assert!(!flight.has_meal_service());
+    }
+
+    // Two economy rows of six (3-3), no premium cabins
+    fn narrow_cabin() -> SeatConfiguration {
+        SeatConfiguration {
+            economy_rows: 2,
+            economy_seats_per_row: 6,
+            business_rows: 0,
+            business_seats_per_row: 4,
+            first_class_rows: 0,
+            first_class_seats_per_row: 4,
+        }
+    }
+
+    fn economy_booking(flight: &Flight, seat: Option<&str>) -> Booking {
+        let passenger = Passenger::new(
+            "Test".to_string(),
+            "Passenger".to_string(),
+            "test@example.com".to_string(),
+            "5551234567".to_string(),
+            "1990-01-01".to_string(),
+            PassengerType::Adult,
+        );
+        let mut booking = Booking::new(flight.id, passenger, SeatClass::Economy, 299.99, "Credit Card".to_string());
+        if let Some(seat) = seat {
+            booking.assign_seat(seat.to_string());
+        }
+        booking
+    }
+
+    fn seat_of(booking: &Booking) -> String {
+        booking.seat_assignment.as_ref().map(|s| s.seat_number.clone()).unwrap_or_default()
+    }
+
+    /// Empty middle seats with both neighbours taken
+    fn orphan_middle_seats(bookings: &[Booking]) -> Vec<String> {
+        let taken: HashSet<String> = bookings.iter().map(seat_of).collect();
+        let mut orphans = Vec::new();
+        for row in 1..=2 {
+            for (left, middle, right) in [('A', 'B', 'C'), ('D', 'E', 'F')] {
+                let seat = |letter| format!("{}{}", row, letter);
+                if !taken.contains(&seat(middle)) && taken.contains(&seat(left)) && taken.contains(&seat(right)) {
+                    orphans.push(seat(middle));
+                }
+            }
+        }
+        orphans
+    }
+
+    #[test]
+    fn test_optimize_seat_layout_closes_orphan_middle_seats() {
+        let flight = test_flight();
+        let mut bookings: Vec<Booking> = ["1A", "1C", "1D", "1F"]
+            .iter()
+            .map(|seat| economy_booking(&flight, Some(seat)))
+            .collect();
+        assert_eq!(orphan_middle_seats(&bookings), vec!["1B", "1E"]);
+
+        let changes = flight.optimize_seat_layout(&narrow_cabin(), &mut bookings);
+
+        assert!(orphan_middle_seats(&bookings).is_empty());
+        let seats: Vec<String> = bookings.iter().map(seat_of).collect();
+        assert_eq!(seats, vec!["1A", "1B", "1C", "1D"]);
+        assert_eq!(changes.len(), 3);
+        assert_eq!(changes[0].from.as_deref(), Some("1C"));
+        assert_eq!(changes[0].to, "1B");
+
+        // Already optimal, so a second pass moves nobody
+        assert!(flight.optimize_seat_layout(&narrow_cabin(), &mut bookings).is_empty());
+    }
+
+    #[test]
+    fn test_optimize_seat_layout_keeps_paid_seats_and_groups_together() {
+        let flight = test_flight();
+        let mut paid = economy_booking(&flight, None);
+        paid.select_seat("1B".to_string());
+
+        let group_id = Uuid::new_v4();
+        let mut bookings = vec![paid];
+        for _ in 0..3 {
+            let mut member = economy_booking(&flight, None);
+            member.group_id = Some(group_id);
+            bookings.push(member);
+        }
+        bookings.push(economy_booking(&flight, None));
+
+        let changes = flight.optimize_seat_layout(&narrow_cabin(), &mut bookings);
+
+        assert_eq!(seat_of(&bookings[0]), "1B");
+        assert!(bookings[0].has_fixed_seat());
+        let group_seats: Vec<String> = bookings[1..4].iter().map(seat_of).collect();
+        assert_eq!(group_seats, vec!["1D", "1E", "1F"]);
+        // The single passenger takes the seat left isolated next to the paid one
+        assert_eq!(seat_of(&bookings[4]), "1A");
+        assert_eq!(changes.len(), 4);
+        assert!(changes.iter().all(|c| c.from.is_none()));
+    }
+
+    #[test]
+    fn test_optimize_seat_layout_leaves_departed_flights_alone() {
+        let mut flight = test_flight();
+        let mut bookings = vec![economy_booking(&flight, Some("1A")), economy_booking(&flight, Some("1C"))];
+        flight.status = FlightStatus::Departed;
+
+        assert!(flight.optimize_seat_layout(&narrow_cabin(), &mut bookings).is_empty());
+        assert_eq!(seat_of(&bookings[1]), "1C");
+    }
+
+    #[test]
+    fn test_demand_multiplier_rises_with_load() {
+        let mut flight = test_flight();
+        let base = flight.get_price(&SeatClass::Economy);
+        assert_eq!(flight.demand_multiplier(&SeatClass::Economy), 1.0);
+
+        // 60% sold is still the base fare
+        let capacity = flight.class_capacity(&SeatClass::Economy);
+        flight.seat_availability.economy = capacity - capacity * 6 / 10;
+        assert!((flight.demand_multiplier(&SeatClass::Economy) - 1.0).abs() < 1e-9);
+
+        // One seat left: close to the +20% sold-out ceiling
+        flight.seat_availability.economy = 1;
+        let near_full = flight.demand_multiplier(&SeatClass::Economy);
+        assert!(near_full > 1.19 && near_full <= 1.2);
+        assert!((flight.get_price(&SeatClass::Economy) - base * near_full).abs() < 1e-9);
+
+        // Other classes are priced on their own load
+        assert_eq!(flight.demand_multiplier(&SeatClass::Business), 1.0);
+
+        // A manual multiplier replaces demand pricing rather than stacking on it, even at 1.0
+        flight.pricing.manual_override = true;
+        assert_eq!(flight.demand_multiplier(&SeatClass::Economy), 1.0);
+        assert_eq!(flight.get_price(&SeatClass::Economy), base);
+        flight.pricing.dynamic_multiplier = 1.5;
+        assert!((flight.get_price(&SeatClass::Economy) - base * 1.5).abs() < 1e-9);
+        flight.pricing.dynamic_multiplier = 1.0;
+        flight.pricing.manual_override = false;
+
+        flight.pricing.demand_pricing = false;
+        assert_eq!(flight.demand_multiplier(&SeatClass::Economy), 1.0);
+        assert_eq!(flight.get_price(&SeatClass::Economy), base);
+    }
+
+    #[test]
+    fn test_base_fares_scale_with_distance() {
+        use crate::config::pricing::{MIN_BUSINESS_FARE, MIN_ECONOMY_FARE, MIN_FIRST_CLASS_FARE};
+
+        let mut short_hop = test_flight();
+        short_hop.recompute_base_fares(300.0);
+        let mut transpacific = test_flight();
+        transpacific.recompute_base_fares(8800.0);
+
+        for class in [SeatClass::Economy, SeatClass::Business, SeatClass::FirstClass] {
+            assert!(transpacific.get_price(&class) > short_hop.get_price(&class) * 5.0);
+        }
+        assert!(transpacific.pricing.distance_based);
+
+        // Short routes pay the minimum fare
+        assert_eq!(short_hop.pricing.economy, MIN_ECONOMY_FARE);
+        assert_eq!(short_hop.pricing.business, MIN_BUSINESS_FARE);
+        assert_eq!(short_hop.pricing.first_class, MIN_FIRST_CLASS_FARE);
+        assert_eq!(transpacific.pricing.economy, 660.0);
+    }
+
+    #[test]
+    fn test_every_legal_status_transition() {
+        use FlightStatus::*;
+
+        let legal = [
+            (OnTime, Delayed(15)), (OnTime, Boarding), (OnTime, Cancelled),
+            (Delayed(15), OnTime), (Delayed(15), Delayed(45)), (Delayed(15), Boarding), (Delayed(15), Cancelled),
+            (Boarding, Delayed(20)), (Boarding, Departed), (Boarding, Cancelled),
+            (Departed, Arrived),
+            (Arrived, Arrived), (Cancelled, Cancelled),
+        ];
+        for (from, to) in legal {
+            let mut flight = test_flight();
+            flight.status = from.clone();
+            assert!(flight.try_transition(to.clone()).is_ok(), "{:?} -> {:?} should be allowed", from, to);
+            assert_eq!(flight.status, to);
+        }
+    }
+
+    #[test]
+    fn test_illegal_status_transitions_are_refused() {
+        use FlightStatus::*;
+
+        let illegal = [
+            (Arrived, Boarding), (Arrived, OnTime), (Departed, Boarding), (Departed, Cancelled),
+            (Departed, Delayed(10)), (Cancelled, OnTime), (Cancelled, Boarding), (OnTime, Departed),
+            (OnTime, Arrived), (Boarding, OnTime), (Boarding, Arrived),
+        ];
+        for (from, to) in illegal {
+            let mut flight = test_flight();
+            flight.status = from.clone();
+            let error = flight.try_transition(to.clone()).unwrap_err();
+            assert!(matches!(error, AirportError::InvalidStatusTransition { .. }), "{:?} -> {:?}", from, to);
+            assert_eq!(flight.status, from);
+        }
+
+        // A delay cannot be set once the aircraft has left
+        let mut flight = test_flight();
+        flight.status = Departed;
+        let arrival = flight.arrival_time;
+        assert!(flight.set_delay(30).is_err());
+        assert_eq!(flight.arrival_time, arrival);
+    }
+
+    #[test]
+    fn test_advance_to_takes_every_step() {
+        let mut flight = test_flight();
+        flight.advance_to(FlightStatus::Arrived).unwrap();
+        assert_eq!(flight.status, FlightStatus::Arrived);
+
+        // Never backwards, and a cancelled flight goes nowhere
+        flight.advance_to(FlightStatus::Boarding).unwrap();
+        assert_eq!(flight.status, FlightStatus::Arrived);
+        let mut cancelled = test_flight();
+        cancelled.status = FlightStatus::Cancelled;
+        assert!(cancelled.advance_to(FlightStatus::Departed).is_err());
+        assert_eq!(cancelled.status, FlightStatus::Cancelled);
+    }
+
+    #[test]
+    fn test_fuel_burn_and_cost_for_737() {
+        let flight = test_flight();
+        let aircraft = Aircraft::new("N1TEST".to_string(), "Boeing 737-800".to_string(), "Boeing".to_string(), 2020);
+
+        // 3.2 L/100km over 3,944 km
+        let liters = flight.estimated_fuel_liters(3944.0, &aircraft);
+        assert!((liters - 126.208).abs() < 1e-9);
+        assert!((flight.fuel_cost(3944.0, &aircraft, 0.85) - 107.2768).abs() < 1e-9);
+        assert_eq!(flight.fuel_cost(0.0, &aircraft, 0.85), 0.0);
+    }
+
+    #[test]
+    fn test_delay_past_limit_cancels_flight() {
+        let mut flight = test_flight();
+        let arrival = flight.arrival_time;
+        flight.set_delay(480).unwrap();
+        assert_eq!(flight.status, FlightStatus::Delayed(480));
+        assert_eq!(flight.arrival_time, arrival + Duration::minutes(480));
+
+        let mut flight = test_flight();
+        flight.set_delay(500).unwrap();
+        assert_eq!(flight.status, FlightStatus::Cancelled);
+
+        // Delays add up
+        let mut flight = test_flight();
+        flight.set_delay(300).unwrap();
+        flight.set_delay(120).unwrap();
+        assert_eq!(flight.status, FlightStatus::Delayed(420));
+        flight.set_delay(61).unwrap();
+        assert_eq!(flight.status, FlightStatus::Cancelled);
+    }
+
+    #[test]
+    fn test_negative_delay_brings_flight_forward() {
+        let mut flight = test_flight();
+        let (departure, arrival) = (flight.departure_time, flight.arrival_time);
+        flight.set_delay(0).unwrap();
+        assert_eq!(flight.status, FlightStatus::OnTime);
+        assert_eq!((flight.departure_time, flight.arrival_time), (departure, arrival));
+
+        flight.set_delay(-60).unwrap();
+        assert_eq!(flight.status, FlightStatus::OnTime);
+        assert_eq!(flight.departure_time, departure - Duration::minutes(60));
+        assert_eq!(flight.arrival_time, arrival - Duration::minutes(60));
+
+        // An announced delay is dropped when the flight is brought forward
+        let mut flight = test_flight();
+        let departure = flight.departure_time;
+        flight.set_delay(90).unwrap();
+        flight.set_delay(-30).unwrap();
+        assert_eq!(flight.status, FlightStatus::OnTime);
+        assert_eq!(flight.departure_time, departure - Duration::minutes(30));
+    }
+
+    #[test]
+    fn test_new_flight_takes_seats_from_aircraft_configuration() {
+        let aircraft = Aircraft::new("N777RA".to_string(), "Boeing 777-300".to_string(), "Boeing".to_string(), 2018);
+        let departure = Utc::now() + Duration::hours(6);
+        let flight = Flight::new(
+            "RIA777".to_string(),
+            "Rust International Airways".to_string(),
+            "LAX".to_string(),
+            "NRT".to_string(),
+            departure,
+            departure + Duration::hours(11),
+            &aircraft,
+        );
+
+        for class in [SeatClass::Economy, SeatClass::Business, SeatClass::FirstClass] {
+            assert_eq!(flight.get_available_seats(&class), aircraft.get_seats_by_class(&class));
+            assert_eq!(flight.class_capacity(&class), aircraft.get_seats_by_class(&class));
+        }
+        let (economy, _, _) = Flight::class_split(aircraft.total_capacity);
+        assert_ne!(flight.get_available_seats(&SeatClass::Economy), economy);
+        assert_eq!(flight.total_capacity, aircraft.total_capacity);
+    }
+
+    #[test]
+    fn test_base_price_override_keeps_multipliers() {
+        let aircraft = Aircraft::new("N1TEST".to_string(), "Boeing 737-800".to_string(), "Boeing".to_string(), 2020);
+        let departure = Utc::now() + Duration::hours(6);
+        let mut flight = Flight::new(
+            "RIA900".to_string(),
+            "Rust International Airways".to_string(),
+            "LAX".to_string(),
+            "SFO".to_string(),
+            departure,
+            departure + Duration::hours(1),
+            &aircraft,
+        );
+
+        flight.set_base_prices(120.0, 350.0, 700.0).unwrap();
+        flight.pricing.dynamic_multiplier = 1.5;
+        assert_eq!(flight.get_price(&SeatClass::Economy), 180.0);
+        assert_eq!(flight.get_price(&SeatClass::FirstClass), 1050.0);
+        assert!(flight.pricing.distance_based);
+        assert!(flight.set_base_prices(120.0, 0.0, 700.0).is_err());
+        assert_eq!(flight.pricing.business, 350.0);
+    }
+}
@@ -0,0 +1,105 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+use crate::config::loyalty::{
+    BUSINESS_MILES_MULTIPLIER, ECONOMY_MILES_MULTIPLIER, FIRST_CLASS_MILES_MULTIPLIER, GOLD_MILES, SILVER_MILES,
+};
+use crate::modules::flight::SeatClass;
+
+/// Frequent-flyer status. Ordered so higher tiers compare greater.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum LoyaltyTier {
+    Member,
+    Silver,
+    Gold,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LoyaltyAccount {
+    pub member_id: String,
+    pub name: String,
+    pub email: String,
+    pub miles: u64,
+    pub tier: LoyaltyTier,
+}
+
+impl LoyaltyTier {
+    /// Tier earned by a miles balance
+    pub fn for_miles(miles: u64) -> Self {
+        if miles >= GOLD_MILES {
+            LoyaltyTier::Gold
+        } else if miles >= SILVER_MILES {
+            LoyaltyTier::Silver
+        } else {
+            LoyaltyTier::Member
+        }
+    }
+
+    pub fn get_display(&self) -> String {
+        match self {
+            LoyaltyTier::Member => "Member".to_string(),
+            LoyaltyTier::Silver => "Silver 🥈".to_string(),
+            LoyaltyTier::Gold => "Gold 🥇".to_string(),
+        }
+    }
+}
+
+impl LoyaltyAccount {
+    pub fn new(name: String, email: String) -> Self {
+        Self {
+            member_id: format!("RIA{}", &Uuid::new_v4().simple().to_string()[..8].to_uppercase()),
+            name,
+            email,
+            miles: 0,
+            tier: LoyaltyTier::Member,
+        }
+    }
+
+    /// Strip the member's name and email. Miles and tier stay so programme totals still add up.
+    pub fn anonymize(&mut self) {
+        self.name = crate::modules::booking::REDACTED.to_string();
+        self.email.clear();
+    }
+
+    /// Credit miles, moving the account up a tier when it crosses a threshold
+    pub fn add_miles(&mut self, miles: u64) {
+        self.miles += miles;
+        self.tier = self.tier.max(LoyaltyTier::for_miles(self.miles));
+    }
+}
+
+/// Miles earned for flying `distance_km` in `class`
+pub fn miles_for_flight(distance_km: f64, class: &SeatClass) -> u64 {
+    let multiplier = match class {
+        SeatClass::Economy => ECONOMY_MILES_MULTIPLIER,
+        SeatClass::Business => BUSINESS_MILES_MULTIPLIER,
+        SeatClass::FirstClass => FIRST_CLASS_MILES_MULTIPLIER,
+    };
+    (distance_km * multiplier).round() as u64
+}
+
+/// Sort key for anyone queueing for a seat (upgrades, waitlists): higher tier first,
+/// then whoever has been waiting longest. Use with `sort_by_key`.
+pub fn priority_key(tier: LoyaltyTier, since: DateTime<Utc>) -> (std::cmp::Reverse<LoyaltyTier>, DateTime<Utc>) {
+    (std::cmp::Reverse(tier), since)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_miles_move_accounts_up_tiers() {
+        let mut account = LoyaltyAccount::new("Ada Lovelace".to_string(), "ada@example.com".to_string());
+        account.add_miles(24_999);
+        assert_eq!(account.tier, LoyaltyTier::Member);
+        account.add_miles(1);
+        assert_eq!(account.tier, LoyaltyTier::Silver);
+        account.add_miles(25_000);
+        assert_eq!(account.tier, LoyaltyTier::Gold);
+
+        assert_eq!(miles_for_flight(1000.0, &SeatClass::Economy), 1000);
+        assert_eq!(miles_for_flight(1000.0, &SeatClass::Business), 1500);
+        assert_eq!(miles_for_flight(1000.0, &SeatClass::FirstClass), 2000);
+    }
+}
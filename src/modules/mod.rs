@@ -1,5 +0,0 @@
-pub mod flight;
-pub mod booking;
-pub mod aircraft;
-pub mod airport;
-pub mod admin;
\ No newline at end of file
@@ -5,24 +5,224 @@ use crossterm::{
     style::{Color, Print, ResetColor, SetForegroundColor},
 };
 use colored::*;
-use std::io::{self, Write};
+use std::io::{self, IsTerminal, Write};
 use chrono::{DateTime, Utc};
+use crate::utils::{format_currency, format_duration, format_relative_time};
 use crate::modules::{
-    flight::{Flight, SeatClass},
-    aircraft::Aircraft,
+    flight::{self, Flight, PriceBreakdown, SeatClass},
+    aircraft::{Aircraft, SeatConfiguration},
     booking::Booking,
     airport::Airport,
     admin::{SystemMetrics, AdminAction},
+    currency::ExchangeQuote,
 };
+use crate::data::health::{HealthReport, HealthStatus};
+use crate::errors::AirportError;
+use crate::data::manager::{CateringRequirements, DateFare, FlightCancellation, SimulationReport, SpecialServicesReport, Upgrade};
 
-pub struct DisplayManager;
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Align {
+    Left,
+    Right,
+}
+
+type CellValue<'a, T> = Box<dyn Fn(&T) -> String + 'a>;
+type CellColor<'a, T> = Box<dyn Fn(&T, String) -> ColoredString + 'a>;
+
+/// One column of a `Table`: how to pull a cell out of a row and how to color it
+pub struct Column<'a, T> {
+    header: String,
+    width: usize,
+    align: Align,
+    value: CellValue<'a, T>,
+    color: CellColor<'a, T>,
+}
+
+impl<'a, T> Column<'a, T> {
+    pub fn new(header: &str, width: usize, value: impl Fn(&T) -> String + 'a) -> Self {
+        Self {
+            header: header.to_string(),
+            width,
+            align: Align::Left,
+            value: Box::new(value),
+            color: Box::new(|_, text| text.normal()),
+        }
+    }
+
+    pub fn align(mut self, align: Align) -> Self {
+        self.align = align;
+        self
+    }
+
+    /// Color for the cell; receives the row so colors can depend on e.g. status
+    pub fn color(mut self, color: impl Fn(&T, String) -> ColoredString + 'a) -> Self {
+        self.color = Box::new(color);
+        self
+    }
+
+    fn pad(&self, cell: ColoredString) -> String {
+        match self.align {
+            Align::Left => format!("{:<width$}", cell, width = self.width),
+            Align::Right => format!("{:>width$}", cell, width = self.width),
+        }
+    }
+}
+
+/// Column-based table renderer shared by the listing screens
+pub struct Table<'a, T> {
+    columns: Vec<Column<'a, T>>,
+}
+
+impl<'a, T> Default for Table<'a, T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a, T> Table<'a, T> {
+    pub fn new() -> Self {
+        Self { columns: Vec::new() }
+    }
+
+    pub fn column(mut self, column: Column<'a, T>) -> Self {
+        self.columns.push(column);
+        self
+    }
+
+    pub fn width(&self) -> usize {
+        self.columns.iter().map(|c| c.width).sum::<usize>() + self.columns.len().saturating_sub(1)
+    }
+
+    /// Header, separator and one line per row, each terminated by a newline
+    pub fn render(&self, rows: &[T]) -> String {
+        self.render_with_color(rows, true)
+    }
+
+    /// `render`, with all styling dropped when `color` is off
+    pub fn render_with_color(&self, rows: &[T], color: bool) -> String {
+        let style = |text: ColoredString| if color { text } else { text.clear() };
+        let header: Vec<String> = self.columns
+            .iter()
+            .map(|c| c.pad(style(c.header.bright_white().bold())))
+            .collect();
+
+        let mut output = format!("{}\n{}\n", header.join(" "), style("─".repeat(self.width()).bright_blue()));
+        for row in rows {
+            let cells: Vec<String> = self.columns
+                .iter()
+                .map(|c| c.pad(style((c.color)(row, (c.value)(row)))))
+                .collect();
+            output.push_str(&cells.join(" "));
+            output.push('\n');
+        }
+        output
+    }
+}
+
+/// Width of the attribute column in the flight comparison view
+const COMPARISON_LABEL_WIDTH: usize = 12;
+
+/// Terminal width assumed when it cannot be detected (e.g. output is piped)
+const DEFAULT_TERMINAL_WIDTH: usize = 80;
+
+/// Shorten `text` to at most `width` characters, marking the cut with "…"
+fn abbreviate(text: &str, width: usize) -> String {
+    if text.chars().count() <= width {
+        text.to_string()
+    } else {
+        let mut short: String = text.chars().take(width.saturating_sub(1)).collect();
+        short.push('…');
+        short
+    }
+}
+
+/// Environment variable that disables colored output when set to any non-empty value
+pub const NO_COLOR_ENV: &str = "NO_COLOR";
+
+/// Whether output should be colored: `--no-color` and a non-empty `NO_COLOR` always win,
+/// otherwise color is used only when writing to a terminal
+pub fn color_enabled(no_color_flag: bool, no_color_env: Option<&str>, is_terminal: bool) -> bool {
+    if no_color_flag || no_color_env.is_some_and(|value| !value.is_empty()) {
+        return false;
+    }
+    is_terminal
+}
+
+/// Decide on color for this process and apply it to every `colored` string; returns the decision
+pub fn init_color(no_color_flag: bool) -> bool {
+    let no_color_env = std::env::var(NO_COLOR_ENV).ok();
+    let enabled = color_enabled(no_color_flag, no_color_env.as_deref(), io::stdout().is_terminal());
+    colored::control::set_override(enabled);
+    enabled
+}
+
+/// Whether escape sequences (colors, screen clearing) may be written right now
+pub fn colors_active() -> bool {
+    colored::control::SHOULD_COLORIZE.should_colorize()
+}
+
+/// Page to show after the user answers the pager prompt on `page`, or `None` to stop.
+/// Moving back from the first page stays there; moving on from the last page stops.
+fn next_page(page: usize, page_count: usize, input: &str) -> Option<usize> {
+    match input.trim().to_lowercase().as_str() {
+        "q" => None,
+        "p" => Some(page.saturating_sub(1)),
+        "" | "n" if page + 1 < page_count => Some(page + 1),
+        "" | "n" => None,
+        _ => Some(page), // Unknown key: show the same page again
+    }
+}
+
+pub struct DisplayManager {
+    color_enabled: bool,
+    currency: Option<ExchangeQuote>, // None shows prices in USD
+}
+
+impl Default for DisplayManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
 impl DisplayManager {
+    /// Colored when the process-wide decision from `init_color` (a terminal, no `NO_COLOR`)
+    /// allows it
     pub fn new() -> Self {
-        Self
+        Self { color_enabled: colors_active(), currency: None }
+    }
+
+    /// Force color on or off for this display's tables, headers and screen clearing. The
+    /// process-wide `colored` setting is left to `init_color`.
+    pub fn new_with_color(enabled: bool) -> Self {
+        Self { color_enabled: enabled, currency: None }
+    }
+
+    pub fn color_enabled(&self) -> bool {
+        self.color_enabled
+    }
+
+    fn render_table<T>(&self, table: &Table<T>, rows: &[T]) -> String {
+        table.render_with_color(rows, self.color_enabled)
+    }
+
+    /// Show prices converted at `quote`, or in USD for None
+    pub fn set_currency(&mut self, quote: Option<ExchangeQuote>) {
+        self.currency = quote;
+    }
+
+    /// A USD amount in the display currency
+    pub fn money(&self, amount: f64) -> String {
+        match &self.currency {
+            Some(quote) => format_currency(amount * quote.rate, &quote.currency),
+            None => format_currency(amount, "USD"),
+        }
     }
 
     pub fn clear_screen(&self) -> Result<(), Box<dyn std::error::Error>> {
+        if !self.color_enabled {
+            // Plain output is meant for pipes and logs, where clearing only adds noise
+            return Ok(());
+        }
         let mut stdout = io::stdout();
         execute!(
             stdout,
@@ -36,7 +236,12 @@ impl DisplayManager {
         let mut stdout = io::stdout();
         let border_length = title.len() + 4;
         let border = "═".repeat(border_length);
-        
+
+        if !self.color_enabled {
+            println!("╔{}╗\n║ {} ║\n╚{}╝\n", border, title, border);
+            return Ok(());
+        }
+
         execute!(
             stdout,
             SetForegroundColor(Color::Cyan),
@@ -55,66 +260,183 @@ impl DisplayManager {
         Ok(())
     }
 
-    pub fn display_flights_table(&self, flights: &[&Flight]) -> Result<(), Box<dyn std::error::Error>> {
+    pub fn display_flights_table(&self, flights: &[&Flight], aircraft: &[Aircraft], airports: &[Airport]) -> Result<(), Box<dyn std::error::Error>> {
         if flights.is_empty() {
             println!("{}", "No flights found.".bright_yellow());
             return Ok(());
         }
 
         self.display_section_header("Flight Information")?;
-        
-        // Table header
-        println!(
-            "{:<10} {:<4} {:<6} {:<6} {:<8} {:<8} {:<15} {:<6} {:<12}",
-            "Flight".bright_white().bold(),
-            "Gate".bright_white().bold(),
-            "Origin".bright_white().bold(),
-            "Dest".bright_white().bold(),
-            "Departure".bright_white().bold(),
-            "Arrival".bright_white().bold(),
-            "Status".bright_white().bold(),
-            "Eco".bright_white().bold(),
-            "Bus/First".bright_white().bold()
-        );
-        println!("{}", "─".repeat(95).bright_blue());
-
-        // Table rows
-        for flight in flights {
-            let gate = flight.gate.as_deref().unwrap_or("--");
-            let status = flight.get_status_display();
-            let departure_time = flight.departure_time.format("%H:%M");
-            let arrival_time = flight.arrival_time.format("%H:%M");
-            
-            // Color code status
-            let status_colored = match flight.status {
-                crate::modules::flight::FlightStatus::OnTime => status.bright_green(),
-                crate::modules::flight::FlightStatus::Delayed(_) => status.bright_red(),
-                crate::modules::flight::FlightStatus::Boarding => status.bright_yellow(),
-                crate::modules::flight::FlightStatus::Departed => status.bright_blue(),
-                crate::modules::flight::FlightStatus::Arrived => status.bright_magenta(),
-                crate::modules::flight::FlightStatus::Cancelled => status.bright_red().bold(),
+        print!("{}", self.render_table(&Self::flights_table(Utc::now(), aircraft, airports), flights));
+        println!();
+        Ok(())
+    }
+
+    /// Flights table with an extra column for the fare in `seat_class`, as priced by `fare`
+    pub fn display_flights_table_with_price(
+        &self,
+        flights: &[&Flight],
+        aircraft: &[Aircraft],
+        airports: &[Airport],
+        seat_class: &SeatClass,
+        fare: impl Fn(&Flight) -> f64,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        if flights.is_empty() {
+            println!("{}", "No flights found.".bright_yellow());
+            return Ok(());
+        }
+
+        self.display_section_header("Flight Information")?;
+        let table = Self::flights_table(Utc::now(), aircraft, airports)
+            .column(Column::new(&format!("{:?}", seat_class), 10, move |f: &&Flight| self.money(fare(f)))
+                .align(Align::Right)
+                .color(|_, s| s.bright_green()));
+        print!("{}", self.render_table(&table, flights));
+        println!();
+        Ok(())
+    }
+
+    /// Flights table one page at a time, for lists too long to fit on screen
+    pub fn display_flights_table_paged(
+        &self,
+        flights: &[&Flight],
+        aircraft: &[Aircraft],
+        airports: &[Airport],
+        page_size: usize,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        if flights.is_empty() {
+            println!("{}", "No flights found.".bright_yellow());
+            return Ok(());
+        }
+
+        let table = Self::flights_table(Utc::now(), aircraft, airports);
+        self.display_pages(flights, page_size, "Flight Information", |page| self.render_table(&table, page))
+    }
+
+    /// Distance is the great-circle distance between the route's airports, "--" when
+    /// either airport is unknown
+    fn flights_table<'a>(now: DateTime<Utc>, aircraft: &'a [Aircraft], airports: &'a [Airport]) -> Table<'a, &'a Flight> {
+        use crate::modules::flight::FlightStatus;
+
+        Table::new()
+            .column(Column::new("Flight", 10, |f: &&Flight| f.flight_number.clone()).color(|_, s| s.bright_white()))
+            .column(Column::new("Gate", 4, |f: &&Flight| f.gate.clone().unwrap_or_else(|| "--".to_string())).color(|_, s| s.bright_cyan()))
+            .column(Column::new("Origin", 6, |f: &&Flight| f.origin.clone()).color(|_, s| s.bright_green()))
+            .column(Column::new("Dest", 6, |f: &&Flight| f.destination.clone()).color(|_, s| s.bright_green()))
+            .column(Column::new("Departure", 8, |f: &&Flight| f.departure_time.format("%H:%M").to_string()).color(|_, s| s.bright_blue()))
+            .column(Column::new("Departs", 18, move |f: &&Flight| match f.status {
+                FlightStatus::Cancelled => "--".to_string(),
+                _ => format_relative_time(f.departure_time, now),
+            }).color(|_, s| s.bright_yellow()))
+            .column(Column::new("Arrival", 8, |f: &&Flight| f.arrival_time.format("%H:%M").to_string()).color(|_, s| s.bright_blue()))
+            .column(Column::new("Dist", 8, move |f: &&Flight| {
+                let airport = |code: &str| airports.iter().find(|a| a.code == code);
+                match (airport(&f.origin), airport(&f.destination)) {
+                    (Some(origin), Some(destination)) => format!("{:.0} km", origin.get_distance_to(destination)),
+                    _ => "--".to_string(),
+                }
+            }).align(Align::Right).color(|_, s| s.bright_white()))
+            .column(Column::new("Duration", 8, |f: &&Flight| format_duration(f.duration())).align(Align::Right).color(|_, s| s.bright_white()))
+            .column(Column::new("Status", 15, |f: &&Flight| f.get_status_display()).color(|f, s| match f.status {
+                FlightStatus::OnTime => s.bright_green(),
+                FlightStatus::Delayed(_) => s.bright_red(),
+                FlightStatus::Boarding => s.bright_yellow(),
+                FlightStatus::Departed => s.bright_blue(),
+                FlightStatus::Arrived => s.bright_magenta(),
+                FlightStatus::Cancelled => s.bright_red().bold(),
+            }))
+            .column(Column::new("Eco", 6, |f: &&Flight| f.seat_availability.economy.to_string()).color(|_, s| s.bright_white()))
+            .column(Column::new("Bus/First", 12, |f: &&Flight| {
+                format!("{:<5}/{:<6}", f.seat_availability.business, f.seat_availability.first_class)
+            }).color(|_, s| s.bright_white()))
+            .column(Column::new("Wi-Fi", 5, move |f: &&Flight| {
+                let wifi = aircraft.iter().find(|a| a.id == f.aircraft_id).is_some_and(|a| a.has_wifi());
+                if wifi { "Yes" } else { "--" }.to_string()
+            }).color(|_, s| s.bright_cyan()))
+    }
+
+    /// Multi-leg itineraries with each leg, the layovers and the total travel time
+    pub fn display_itineraries(&self, itineraries: &[Vec<&Flight>]) -> Result<(), Box<dyn std::error::Error>> {
+        if itineraries.is_empty() {
+            println!("{}", "No itineraries found.".bright_yellow());
+            return Ok(());
+        }
+
+        self.display_section_header("Itineraries")?;
+        for (i, legs) in itineraries.iter().enumerate() {
+            let (first, last) = (legs[0], legs[legs.len() - 1]);
+            let stops = match legs.len() - 1 {
+                0 => "Nonstop".to_string(),
+                1 => "1 stop".to_string(),
+                n => format!("{} stops", n),
             };
+            println!("{} {}  {}  {}",
+                format!("{}.", i + 1).bright_white().bold(),
+                std::iter::once(first.origin.as_str())
+                    .chain(legs.iter().map(|f| f.destination.as_str()))
+                    .collect::<Vec<_>>()
+                    .join(" → ")
+                    .bright_green()
+                    .bold(),
+                stops.bright_yellow(),
+                format!("Total {}", format_duration(last.arrival_time - first.departure_time)).bright_cyan());
 
-            println!(
-                "{:<10} {:<4} {:<6} {:<6} {:<8} {:<8} {:<15} {:<6} {:<5}/{:<6}",
-                flight.flight_number.bright_white(),
-                gate.bright_cyan(),
-                flight.origin.bright_green(),
-                flight.destination.bright_green(),
-                departure_time.to_string().bright_blue(),
-                arrival_time.to_string().bright_blue(),
-                status_colored,
-                flight.seat_availability.economy.to_string().bright_white(),
-                flight.seat_availability.business.to_string().bright_white(),
-                flight.seat_availability.first_class.to_string().bright_white()
-            );
+            for (n, flight) in legs.iter().enumerate() {
+                if n > 0 {
+                    let layover = flight.departure_time - legs[n - 1].arrival_time;
+                    println!("     ⏳ {} layover in {}", format_duration(layover), flight.origin);
+                }
+                println!("     ✈️  {:<8} {} → {}  {} → {}",
+                    flight.flight_number.bright_white(),
+                    flight.origin,
+                    flight.destination,
+                    flight.departure_time.format("%m-%d %H:%M").to_string().bright_blue(),
+                    flight.arrival_time.format("%m-%d %H:%M UTC").to_string().bright_blue());
+            }
+            println!();
         }
-        
+        Ok(())
+    }
+
+    pub fn display_price_calendar(&self, results: &[DateFare]) -> Result<(), Box<dyn std::error::Error>> {
+        self.display_section_header("Flexible Dates - Lowest Fares")?;
+
+        let lowest = results
+            .iter()
+            .filter_map(|r| r.cheapest.as_ref().map(|(_, fare)| *fare))
+            .min_by(|a, b| a.total_cmp(b));
+
+        for result in results {
+            let date = result.date.format("%a %Y-%m-%d").to_string();
+            match &result.cheapest {
+                Some((flight_number, fare)) if Some(*fare) == lowest => println!(
+                    "{:<16} {:<10} {}  {}",
+                    date.bright_white().bold(),
+                    flight_number.bright_white(),
+                    format!("${:.2}", fare).bright_green().bold(),
+                    "★ Cheapest".bright_yellow().bold()
+                ),
+                Some((flight_number, fare)) => println!(
+                    "{:<16} {:<10} {}",
+                    date.bright_white(),
+                    flight_number.bright_white(),
+                    format!("${:.2}", fare).bright_white()
+                ),
+                None => println!("{:<16} {:<10} {}", date.bright_white(), "—", "—".dimmed()),
+            }
+        }
+
+        if lowest.is_none() {
+            println!("\n{}", "No bookable flights in this date range.".bright_yellow());
+        }
+
         println!();
         Ok(())
     }
 
-    pub fn display_flight_details(&self, flight: &Flight, aircraft: Option<&Aircraft>) -> Result<(), Box<dyn std::error::Error>> {
+    /// Full details of a flight. `distance_km` is the route length, when both airports are
+    /// known, and drives the operating economics section.
+    pub fn display_flight_details(&self, flight: &Flight, aircraft: Option<&Aircraft>, distance_km: Option<f64>) -> Result<(), Box<dyn std::error::Error>> {
         self.display_section_header(&format!("Flight {} Details", flight.flight_number))?;
         
         println!("{}  {}", "✈️ Flight:".bright_cyan().bold(), flight.flight_number.bright_white().bold());
@@ -123,8 +445,9 @@ impl DisplayManager {
             flight.origin.bright_green().bold(), 
             flight.destination.bright_green().bold());
         
-        println!("{}  {}", "🕐 Departure:".bright_cyan(), 
-            flight.departure_time.format("%Y-%m-%d %H:%M UTC").to_string().bright_white());
+        println!("{}  {} ({})", "🕐 Departure:".bright_cyan(), 
+            flight.departure_time.format("%Y-%m-%d %H:%M UTC").to_string().bright_white(),
+            format_relative_time(flight.departure_time, Utc::now()).bright_yellow());
         println!("{}  {}", "🕑 Arrival:".bright_cyan(), 
             flight.arrival_time.format("%Y-%m-%d %H:%M UTC").to_string().bright_white());
         println!("{}  {}", "⏱️ Duration:".bright_cyan(), 
@@ -133,6 +456,8 @@ impl DisplayManager {
                 flight.duration().num_minutes() % 60).bright_white());
         
         println!("{}  {}", "📍 Status:".bright_cyan(), flight.get_status_display());
+        println!("{}  {}", "🍽️ Meal Service:".bright_cyan(),
+            if flight.has_meal_service() { "Yes".bright_green() } else { "No".bright_white() });
         
         if let Some(gate) = &flight.gate {
             println!("{}  {}", "🚪 Gate:".bright_cyan(), gate.bright_white().bold());
@@ -140,15 +465,15 @@ impl DisplayManager {
 
         // Seat availability
         println!("\n{}", "💺 Seat Availability:".bright_cyan().bold());
-        println!("   Economy: {} seats (${:.2})", 
+        println!("   Economy: {} seats ({})", 
             flight.seat_availability.economy.to_string().bright_green(),
-            flight.get_price(&SeatClass::Economy));
-        println!("   Business: {} seats (${:.2})", 
+            self.money(flight.get_price(&SeatClass::Economy)));
+        println!("   Business: {} seats ({})", 
             flight.seat_availability.business.to_string().bright_yellow(),
-            flight.get_price(&SeatClass::Business));
-        println!("   First Class: {} seats (${:.2})", 
+            self.money(flight.get_price(&SeatClass::Business)));
+        println!("   First Class: {} seats ({})", 
             flight.seat_availability.first_class.to_string().bright_magenta(),
-            flight.get_price(&SeatClass::FirstClass));
+            self.money(flight.get_price(&SeatClass::FirstClass)));
 
         // Aircraft information
         if let Some(aircraft) = aircraft {
@@ -157,12 +482,102 @@ impl DisplayManager {
             println!("   Registration: {}", aircraft.registration.bright_white());
             println!("   Capacity: {} passengers", aircraft.total_capacity.to_string().bright_white());
             println!("   Status: {}", aircraft.get_status_display());
+
+            println!("\n{}", "🛋️ Cabin Comfort:".bright_cyan().bold());
+            for (label, class) in [("Economy", SeatClass::Economy), ("Business", SeatClass::Business), ("First Class", SeatClass::FirstClass)] {
+                println!("   {}: {}", label, aircraft.cabin_details(&class).get_summary().bright_white());
+            }
+
+            if let Some(distance) = distance_km {
+                println!("\n{}", "⛽ Operating Economics:".bright_cyan().bold());
+                println!("   Distance: {}", format!("{:.0} km", distance).bright_white());
+                println!("   Estimated Fuel: {}", format!("{:.0} L", flight.estimated_fuel_liters(distance, aircraft)).bright_white());
+                println!("   Fuel Cost: {} (at ${:.2}/L)",
+                    format!("${:.2}", flight.fuel_cost(distance, aircraft, crate::config::FUEL_PRICE_PER_LITER)).bright_yellow(),
+                    crate::config::FUEL_PRICE_PER_LITER);
+            }
         }
 
         println!();
         Ok(())
     }
 
+    /// Side-by-side comparison of a few flights, one column per flight. Columns shrink and
+    /// values are abbreviated to fit the terminal; missing aircraft data shows as "n/a".
+    pub fn display_flight_comparison(&self, flights: &[(&Flight, Option<&Aircraft>)], airports: &[Airport]) -> Result<(), Box<dyn std::error::Error>> {
+        self.display_section_header("Flight Comparison")?;
+        let terminal_width = crossterm::terminal::size().map_or(DEFAULT_TERMINAL_WIDTH, |(width, _)| width as usize);
+        print!("{}", Self::render_flight_comparison(flights, airports, terminal_width));
+        println!();
+        Ok(())
+    }
+
+    fn flight_comparison_rows(flights: &[(&Flight, Option<&Aircraft>)], airports: &[Airport]) -> Vec<(&'static str, Vec<String>)> {
+        let local = |code: &str, time: DateTime<Utc>| match airports.iter().find(|a| a.code == code) {
+            Some(airport) => format!("{} {}", airport.local_time(time).format("%a %H:%M"), code),
+            None => format!("{} UTC", time.format("%a %H:%M")),
+        };
+        let fare = |flight: &Flight, class: SeatClass| match flight.get_available_seats(&class) {
+            0 => "Sold out".to_string(),
+            seats => format!("${:.2} ({} left)", flight.get_price(&class), seats),
+        };
+        let cabin = |aircraft: Option<&Aircraft>, describe: &dyn Fn(&Aircraft) -> String| {
+            aircraft.map_or_else(|| "n/a".to_string(), describe)
+        };
+
+        let row = |label, value: &dyn Fn(&Flight, Option<&Aircraft>) -> String| {
+            (label, flights.iter().map(|(flight, aircraft)| value(flight, *aircraft)).collect())
+        };
+
+        vec![
+            row("Flight", &|f, _| f.flight_number.clone()),
+            row("Route", &|f, _| format!("{} → {}", f.origin, f.destination)),
+            row("Departs", &|f, _| local(&f.origin, f.departure_time)),
+            row("Arrives", &|f, _| local(&f.destination, f.arrival_time)),
+            row("Duration", &|f, _| format_duration(f.duration())),
+            row("Stops", &|_, _| "Nonstop".to_string()),
+            row("Status", &|f, _| f.get_status_display()),
+            row("Economy", &|f, _| fare(f, SeatClass::Economy)),
+            row("Business", &|f, _| fare(f, SeatClass::Business)),
+            row("First Class", &|f, _| fare(f, SeatClass::FirstClass)),
+            row("Aircraft", &|_, a| cabin(a, &|a| a.model.clone())),
+            row("Legroom", &|_, a| cabin(a, &|a| format!("{} cm", a.cabin_details(&SeatClass::Economy).seat_pitch_cm))),
+            row("Amenities", &|_, a| cabin(a, &|a| {
+                let amenities = a.cabin_details(&SeatClass::Economy).amenities();
+                if amenities.is_empty() { "None".to_string() } else { amenities.join(", ") }
+            })),
+        ]
+    }
+
+    fn render_flight_comparison(flights: &[(&Flight, Option<&Aircraft>)], airports: &[Airport], terminal_width: usize) -> String {
+        if flights.is_empty() {
+            return String::new();
+        }
+        let rows = Self::flight_comparison_rows(flights, airports);
+
+        // Share what is left after the label column (and " │ " separators) between the flights
+        let separators = 3 * flights.len();
+        let available = terminal_width.saturating_sub(COMPARISON_LABEL_WIDTH + separators) / flights.len();
+        let widths: Vec<usize> = (0..flights.len())
+            .map(|i| {
+                let longest = rows.iter().map(|(_, values)| values[i].chars().count()).max().unwrap_or(0);
+                longest.min(available.max(6))
+            })
+            .collect();
+
+        let mut output = String::new();
+        for (idx, (label, values)) in rows.iter().enumerate() {
+            output.push_str(&format!("{:<width$}", label.bright_cyan(), width = COMPARISON_LABEL_WIDTH));
+            for (value, width) in values.iter().zip(&widths) {
+                let cell = abbreviate(value, *width);
+                let cell = if idx == 0 { cell.bright_white().bold() } else { cell.normal() };
+                output.push_str(&format!(" │ {:<width$}", cell, width = *width));
+            }
+            output.push('\n');
+        }
+        output
+    }
+
     pub fn display_aircraft_table(&self, aircraft: &[&Aircraft]) -> Result<(), Box<dyn std::error::Error>> {
         if aircraft.is_empty() {
             println!("{}", "No aircraft found.".bright_yellow());
@@ -170,43 +585,49 @@ impl DisplayManager {
         }
 
         self.display_section_header("Aircraft Registry")?;
-        
-        // Table header
-        println!(
-            "{:<12} {:<20} {:<6} {:<8} {:<12} {:<15}",
-            "Registration".bright_white().bold(),
-            "Model".bright_white().bold(),
-            "Year".bright_white().bold(),
-            "Capacity".bright_white().bold(),
-            "Status".bright_white().bold(),
-            "Flight Hours".bright_white().bold()
-        );
-        println!("{}", "─".repeat(85).bright_blue());
-
-        // Table rows
-        for craft in aircraft {
-            let status_colored = match craft.status {
-                crate::modules::aircraft::AircraftStatus::Active => craft.get_status_display().bright_green(),
-                crate::modules::aircraft::AircraftStatus::Maintenance => craft.get_status_display().bright_red(),
-                crate::modules::aircraft::AircraftStatus::InFlight => craft.get_status_display().bright_blue(),
-                crate::modules::aircraft::AircraftStatus::Retired => craft.get_status_display().bright_red().dimmed(),
-            };
+        print!("{}", self.render_table(&Self::aircraft_table(), aircraft));
+        println!();
+        Ok(())
+    }
 
-            println!(
-                "{:<12} {:<20} {:<6} {:<8} {:<12} {:<15}",
-                craft.registration.bright_white(),
-                craft.model.bright_cyan(),
-                craft.year_manufactured.to_string().bright_white(),
-                craft.total_capacity.to_string().bright_white(),
-                status_colored,
-                format!("{:.1}h", craft.flight_hours).bright_white()
-            );
+    pub fn display_maintenance_schedule(&self, due: &[(&Aircraft, f64)]) -> Result<(), Box<dyn std::error::Error>> {
+        if due.is_empty() {
+            println!("{}", "No aircraft are due for maintenance.".bright_green());
+            return Ok(());
         }
-        
+
+        self.display_section_header("Maintenance Schedule")?;
+        let table = Table::new()
+            .column(Column::new("Registration", 12, |(a, _): &(&Aircraft, f64)| a.registration.clone()).color(|_, s| s.bright_white()))
+            .column(Column::new("Model", 20, |(a, _): &(&Aircraft, f64)| a.model.clone()).color(|_, s| s.bright_cyan()))
+            .column(Column::new("Flight Hours", 12, |(a, _): &(&Aircraft, f64)| format!("{:.1}", a.flight_hours)).align(Align::Right))
+            .column(Column::new("Hours Left", 10, |(_, remaining): &(&Aircraft, f64)| {
+                if *remaining <= 0.0 { "OVERDUE".to_string() } else { format!("{:.1}", remaining) }
+            }).align(Align::Right).color(|(_, remaining), s| if *remaining <= 0.0 { s.bright_red().bold() } else { s.bright_yellow() }))
+            .column(Column::new("Status", 12, |(a, _): &(&Aircraft, f64)| a.get_status_display()));
+        print!("{}", self.render_table(&table, due));
         println!();
         Ok(())
     }
 
+    fn aircraft_table<'a>() -> Table<'a, &'a Aircraft> {
+        use crate::modules::aircraft::AircraftStatus;
+
+        Table::new()
+            .column(Column::new("Registration", 12, |a: &&Aircraft| a.registration.clone()).color(|_, s| s.bright_white()))
+            .column(Column::new("Model", 20, |a: &&Aircraft| a.model.clone()).color(|_, s| s.bright_cyan()))
+            .column(Column::new("Year", 6, |a: &&Aircraft| a.year_manufactured.to_string()).color(|_, s| s.bright_white()))
+            .column(Column::new("Capacity", 8, |a: &&Aircraft| a.total_capacity.to_string()).color(|_, s| s.bright_white()))
+            .column(Column::new("Status", 12, |a: &&Aircraft| a.get_status_display()).color(|a, s| match a.status {
+                AircraftStatus::Active => s.bright_green(),
+                AircraftStatus::Maintenance => s.bright_red(),
+                AircraftStatus::InFlight => s.bright_blue(),
+                AircraftStatus::Retired => s.bright_red().dimmed(),
+                AircraftStatus::Grounded { .. } => s.bright_red().bold(),
+            }))
+            .column(Column::new("Flight Hours", 15, |a: &&Aircraft| format!("{:.1}h", a.flight_hours)).color(|_, s| s.bright_white()))
+    }
+
     pub fn display_aircraft_details(&self, aircraft: &Aircraft) -> Result<(), Box<dyn std::error::Error>> {
         self.display_section_header(&format!("Aircraft {} Details", aircraft.registration))?;
         
@@ -234,6 +655,12 @@ impl DisplayManager {
             aircraft.seat_configuration.first_class_seats_per_row);
         println!("   Total Capacity: {} passengers", aircraft.total_capacity.to_string().bright_white().bold());
 
+        // Cabin comfort
+        println!("\n{}", "🛋️ Cabin Comfort:".bright_cyan().bold());
+        for (label, class) in [("Economy", SeatClass::Economy), ("Business", SeatClass::Business), ("First Class", SeatClass::FirstClass)] {
+            println!("   {}: {}", label, aircraft.cabin_details(&class).get_summary().bright_white());
+        }
+
         // Performance specs
         println!("\n{}", "⚡ Performance Specifications:".bright_cyan().bold());
         println!("   Max Speed: {} km/h", aircraft.performance.max_speed_kmh.to_string().bright_white());
@@ -260,57 +687,123 @@ impl DisplayManager {
         }
 
         self.display_section_header("Booking Information")?;
-        
-        // Table header
-        println!(
-            "{:<12} {:<25} {:<8} {:<10} {:<15} {:<10}",
-            "Ticket #".bright_white().bold(),
-            "Passenger".bright_white().bold(),
-            "Class".bright_white().bold(),
-            "Seat".bright_white().bold(),
-            "Status".bright_white().bold(),
-            "Amount".bright_white().bold()
-        );
-        println!("{}", "─".repeat(85).bright_blue());
+        print!("{}", self.render_table(&Self::bookings_table(), bookings));
+        println!();
+        Ok(())
+    }
 
-        // Table rows
-        for booking in bookings {
-            let seat_info = match &booking.seat_assignment {
-                Some(seat) => seat.seat_number.clone(),
-                None => "Not assigned".to_string(),
-            };
+    /// Bookings table one page at a time, for lists too long to fit on screen
+    pub fn display_bookings_table_paged(&self, bookings: &[&Booking], page_size: usize) -> Result<(), Box<dyn std::error::Error>> {
+        if bookings.is_empty() {
+            println!("{}", "No bookings found.".bright_yellow());
+            return Ok(());
+        }
 
-            let status_colored = match booking.status {
-                crate::modules::booking::BookingStatus::Confirmed => booking.get_status_display().bright_green(),
-                crate::modules::booking::BookingStatus::CheckedIn => booking.get_status_display().bright_blue(),
-                crate::modules::booking::BookingStatus::Boarded => booking.get_status_display().bright_cyan(),
-                crate::modules::booking::BookingStatus::Completed => booking.get_status_display().bright_magenta(),
-                crate::modules::booking::BookingStatus::Cancelled => booking.get_status_display().bright_red(),
-                crate::modules::booking::BookingStatus::NoShow => booking.get_status_display().bright_red().bold(),
-            };
+        let table = Self::bookings_table();
+        self.display_pages(bookings, page_size, "Booking Information", |page| self.render_table(&table, page))
+    }
 
-            println!(
-                "{:<12} {:<25} {:<8} {:<10} {:<15} ${:<9.2}",
-                booking.ticket_number.bright_white(),
-                booking.passenger.full_name().bright_cyan(),
-                format!("{:?}", booking.seat_class).bright_yellow(),
-                seat_info.bright_white(),
-                status_colored,
-                booking.payment.total_amount
+    /// Show `rows` a page at a time: Enter or n for the next page, p for the previous one,
+    /// q to stop. Enter on the last page finishes.
+    fn display_pages<T>(
+        &self,
+        rows: &[T],
+        page_size: usize,
+        title: &str,
+        render: impl Fn(&[T]) -> String,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let pages: Vec<&[T]> = rows.chunks(page_size.max(1)).collect();
+        let mut page = 0;
+
+        loop {
+            self.display_section_header(title)?;
+            print!("{}", render(pages[page]));
+            if pages.len() == 1 {
+                println!();
+                return Ok(());
+            }
+
+            print!(
+                "\n{} {}",
+                format!("Page {} of {}", page + 1, pages.len()).bright_white().bold(),
+                "(Enter/n next, p previous, q quit):".bright_yellow().dimmed()
             );
+            io::stdout().flush()?;
+            let mut input = String::new();
+            io::stdin().read_line(&mut input)?;
+
+            match next_page(page, pages.len(), &input) {
+                Some(next) => page = next,
+                None => return Ok(()),
+            }
         }
-        
+    }
+
+    /// Gate manifest for a flight, in the order given
+    pub fn display_manifest(&self, flight_number: &str, bookings: &[&Booking]) -> Result<(), Box<dyn std::error::Error>> {
+        use crate::modules::booking::BookingStatus;
+
+        if bookings.is_empty() {
+            println!("{}", format!("No passengers booked on {}.", flight_number).bright_yellow());
+            return Ok(());
+        }
+
+        self.display_section_header(&format!("Passenger Manifest - {} ({} passengers)", flight_number, bookings.len()))?;
+        let table = Table::new()
+            .column(Column::new("Passenger", 25, |b: &&Booking| b.passenger.full_name()).color(|_, s| s.bright_cyan()))
+            .column(Column::new("Seat", 6, |b: &&Booking| b.seat_assignment.as_ref().map_or("-".to_string(), |s| s.seat_number.clone())).color(|_, s| s.bright_white()))
+            .column(Column::new("Class", 10, |b: &&Booking| format!("{:?}", b.seat_class)).color(|_, s| s.bright_yellow()))
+            .column(Column::new("Status", 15, |b: &&Booking| b.get_status_display()).color(|b, s| match b.status {
+                BookingStatus::CheckedIn | BookingStatus::Boarded => s.bright_green(),
+                BookingStatus::NoShow => s.bright_red(),
+                _ => s.bright_white(),
+            }))
+            .column(Column::new("Special Requirements", 30, |b: &&Booking| b.passenger.special_requirements.join(", ")).color(|_, s| s.bright_magenta()));
+        print!("{}", self.render_table(&table, bookings));
         println!();
         Ok(())
     }
 
-    pub fn display_booking_details(&self, booking: &Booking) -> Result<(), Box<dyn std::error::Error>> {
+    fn bookings_table<'a>() -> Table<'a, &'a Booking> {
+        use crate::modules::booking::BookingStatus;
+
+        Table::new()
+            .column(Column::new("Ticket #", 12, |b: &&Booking| b.ticket_number.clone()).color(|_, s| s.bright_white()))
+            .column(Column::new("Passenger", 25, |b: &&Booking| b.passenger.full_name()).color(|_, s| s.bright_cyan()))
+            .column(Column::new("Class", 8, |b: &&Booking| format!("{:?}", b.seat_class)).color(|_, s| s.bright_yellow()))
+            .column(Column::new("Seat", 10, |b: &&Booking| match &b.seat_assignment {
+                Some(seat) => seat.seat_number.clone(),
+                None => "Not assigned".to_string(),
+            }).color(|_, s| s.bright_white()))
+            .column(Column::new("Status", 15, |b: &&Booking| b.get_status_display()).color(|b, s| match b.status {
+                BookingStatus::Confirmed => s.bright_green(),
+                BookingStatus::CheckedIn => s.bright_blue(),
+                BookingStatus::Boarded => s.bright_cyan(),
+                BookingStatus::Completed => s.bright_magenta(),
+                BookingStatus::Cancelled => s.bright_red(),
+                BookingStatus::NoShow => s.bright_red().bold(),
+            }))
+            .column(Column::new("Amount", 10, |b: &&Booking| format!("${:<9.2}", b.payment.total_amount)))
+    }
+
+    /// Full details of one booking. `party` holds every booking in its group reservation,
+    /// listed with the combined fare; pass an empty slice for a single booking.
+    /// `companion` is the other leg when the booking is part of a round trip.
+    pub fn display_booking_details(
+        &self,
+        booking: &Booking,
+        party: &[&Booking],
+        companion: Option<&Booking>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
         self.display_section_header(&format!("Booking {} Details", booking.ticket_number))?;
         
         println!("{}  {}", "🎫 Ticket Number:".bright_cyan().bold(), booking.ticket_number.bright_white().bold());
         println!("{}  {}", "📅 Booking Date:".bright_cyan(), 
             booking.booking_date.format("%Y-%m-%d %H:%M UTC").to_string().bright_white());
         println!("{}  {}", "📊 Status:".bright_cyan(), booking.get_status_display());
+        if let Some(other_leg) = companion {
+            println!("{}  {}", "🔁 Round Trip With:".bright_cyan(), other_leg.ticket_number.bright_white().bold());
+        }
 
         // Passenger information
         println!("\n{}", "👤 Passenger Information:".bright_cyan().bold());
@@ -323,6 +816,10 @@ impl DisplayManager {
             println!("   Passport: {}", passport.bright_white());
         }
 
+        if party.len() > 1 {
+            self.display_group_members(party)?;
+        }
+
         // Seat information
         println!("\n{}", "💺 Seat Information:".bright_cyan().bold());
         println!("   Class: {:?}", booking.seat_class);
@@ -336,17 +833,32 @@ impl DisplayManager {
 
         // Payment information
         println!("\n{}", "💳 Payment Information:".bright_cyan().bold());
-        println!("   Total Amount: ${:.2}", booking.payment.total_amount.to_string().bright_green().bold());
+        println!("   Total Amount: {}", format!("${:.2}", booking.payment.total_amount).bright_green().bold());
         println!("   Currency: {}", booking.payment.currency.bright_white());
+        if let Some(quote) = &booking.payment.quote {
+            println!("   Quoted As: {} (at {:.4} {} per USD)",
+                format_currency(booking.payment.total_amount * quote.rate, &quote.currency).bright_white(),
+                quote.rate,
+                quote.currency);
+        }
         println!("   Payment Method: {}", booking.payment.payment_method.bright_white());
         println!("   Transaction ID: {}", booking.payment.transaction_id.bright_white());
         println!("   Payment Date: {}", 
             booking.payment.payment_date.format("%Y-%m-%d %H:%M UTC").to_string().bright_white());
+        if booking.payment.refunded_amount > 0.0 {
+            println!("   Refunded: {}", format!("${:.2}", booking.payment.refunded_amount).bright_yellow().bold());
+        }
 
         // Baggage and services
         println!("\n{}", "🧳 Additional Information:".bright_cyan().bold());
-        println!("   Baggage Count: {} pieces", booking.baggage_count.to_string().bright_white());
+        println!("   Baggage: {} cabin, {} checked", 
+            booking.cabin_bags.to_string().bright_white(),
+            booking.checked_bags.to_string().bright_white());
+        if booking.excess_bag_fee() > 0.0 {
+            println!("   Excess Baggage Fee: {}", format!("${:.2}", booking.excess_bag_fee()).bright_yellow());
+        }
         
+        println!("   Meal: {}", booking.meal_preference.get_display().bright_white());
         if !booking.special_services.is_empty() {
             println!("   Special Services: {}", booking.special_services.join(", ").bright_white());
         }
@@ -365,10 +877,71 @@ impl DisplayManager {
             println!("   Boarding: {}", boarding_time.format("%Y-%m-%d %H:%M UTC").to_string().bright_white());
         }
 
+        if !booking.history.is_empty() {
+            println!("\n{}", "📜 History:".bright_cyan().bold());
+            for event in &booking.history {
+                println!("   {}  {}", event.timestamp.format("%Y-%m-%d %H:%M UTC").to_string().bright_black(), event.description.bright_white());
+            }
+        }
+
         println!();
         Ok(())
     }
 
+    pub fn display_group_members(&self, party: &[&Booking]) -> Result<(), Box<dyn std::error::Error>> {
+        println!("\n{}", format!("👥 Group Reservation ({} passengers):", party.len()).bright_cyan().bold());
+        for member in party {
+            println!("   {:<10} {:<24} {:<8} {:>10}  {}",
+                member.ticket_number,
+                member.passenger.full_name(),
+                format!("{:?}", member.passenger.passenger_type),
+                format!("${:.2}", member.payment.total_amount),
+                member.get_status_display());
+        }
+        let total: f64 = party.iter().map(|b| b.payment.total_amount).sum();
+        println!("   {:<44} {:>10}", "Group Total".bold(), format!("${:.2}", total).bright_green().bold());
+        Ok(())
+    }
+
+    /// Seat map from `DataManager::get_seat_map`, one row per line with gaps for the aisles
+    pub fn display_seat_map(&self, seat_map: &[(String, bool)], seat_config: &SeatConfiguration) -> Result<(), Box<dyn std::error::Error>> {
+        let taken: std::collections::HashMap<&str, bool> = seat_map.iter().map(|(seat, taken)| (seat.as_str(), *taken)).collect();
+
+        for (class, label) in [(SeatClass::FirstClass, "First Class"), (SeatClass::Business, "Business"), (SeatClass::Economy, "Economy")] {
+            let blocks = flight::cabin_seat_blocks(seat_config, &class);
+            if blocks.is_empty() {
+                continue;
+            }
+
+            println!("\n{}", format!("💺 {}", label).bright_cyan().bold());
+            let mut current_row = None;
+            for block in &blocks {
+                let row: String = block[0].chars().take_while(|c| c.is_ascii_digit()).collect();
+                if current_row.as_ref() != Some(&row) {
+                    if current_row.is_some() {
+                        println!();
+                    }
+                    print!("   {:>3} ", row.bright_white());
+                    current_row = Some(row);
+                } else {
+                    print!("  "); // Aisle
+                }
+                for seat in block {
+                    let letter = &seat[seat.len() - 1..];
+                    match taken.get(seat.as_str()) {
+                        Some(false) => print!(" {}", letter.bright_green().bold()),
+                        _ => print!(" {}", "·".bright_red()),
+                    }
+                }
+            }
+            println!();
+        }
+
+        println!("\n   {} free   {} taken   Window seats are at either end of a row, aisle seats beside the gaps",
+            "A".bright_green().bold(), "·".bright_red());
+        Ok(())
+    }
+
     pub fn display_airports_table(&self, airports: &[&Airport]) -> Result<(), Box<dyn std::error::Error>> {
         if airports.is_empty() {
             println!("{}", "No airports found.".bright_yellow());
@@ -376,34 +949,39 @@ impl DisplayManager {
         }
 
         self.display_section_header("Airport Directory")?;
-        
-        // Table header
-        println!(
-            "{:<6} {:<35} {:<15} {:<15} {:<12}",
-            "Code".bright_white().bold(),
-            "Name".bright_white().bold(),
-            "City".bright_white().bold(),
-            "Country".bright_white().bold(),
-            "Type".bright_white().bold()
-        );
-        println!("{}", "─".repeat(85).bright_blue());
+        print!("{}", self.render_table(&Self::airports_table(), airports));
+        println!();
+        Ok(())
+    }
 
-        // Table rows
-        for airport in airports {
-            println!(
-                "{:<6} {:<35} {:<15} {:<15} {:<12}",
-                airport.code.bright_green().bold(),
-                airport.name.bright_white(),
-                airport.city.bright_cyan(),
-                airport.country.bright_yellow(),
-                airport.get_size_display()
-            );
+    /// Airports with their distance from a point, nearest first
+    pub fn display_nearby_airports(&self, airports: &[(&Airport, f64)]) -> Result<(), Box<dyn std::error::Error>> {
+        if airports.is_empty() {
+            println!("{}", "No airports within that radius.".bright_yellow());
+            return Ok(());
         }
-        
+
+        self.display_section_header("Nearby Airports")?;
+        let table = Table::new()
+            .column(Column::new("Code", 6, |(a, _): &(&Airport, f64)| a.code.clone()).color(|_, s| s.bright_green().bold()))
+            .column(Column::new("Name", 35, |(a, _): &(&Airport, f64)| a.name.clone()).color(|_, s| s.bright_white()))
+            .column(Column::new("City", 15, |(a, _): &(&Airport, f64)| a.city.clone()).color(|_, s| s.bright_cyan()))
+            .column(Column::new("Distance", 10, |(_, km): &(&Airport, f64)| format!("{:.0} km", km)).color(|_, s| s.bright_yellow()));
+        print!("{}", self.render_table(&table, airports));
         println!();
         Ok(())
     }
 
+    fn airports_table<'a>() -> Table<'a, &'a Airport> {
+        Table::new()
+            .column(Column::new("Code", 6, |a: &&Airport| a.code.clone()).color(|_, s| s.bright_green().bold()))
+            .column(Column::new("Name", 35, |a: &&Airport| a.name.clone()).color(|_, s| s.bright_white()))
+            .column(Column::new("City", 15, |a: &&Airport| a.city.clone()).color(|_, s| s.bright_cyan()))
+            .column(Column::new("Country", 15, |a: &&Airport| a.country.clone()).color(|_, s| s.bright_yellow()))
+            .column(Column::new("Type", 12, |a: &&Airport| a.get_size_display()))
+            .column(Column::new("Min Conn", 9, |a: &&Airport| format!("{} min", a.min_connection_minutes)).color(|_, s| s.bright_white()))
+    }
+
     pub fn display_system_metrics(&self, metrics: &SystemMetrics) -> Result<(), Box<dyn std::error::Error>> {
         self.display_section_header("System Status Dashboard")?;
         
@@ -420,14 +998,25 @@ impl DisplayManager {
         
         println!("\n{}", "🎫 Booking Statistics:".bright_cyan().bold());
         println!("   Total Bookings: {}", metrics.total_bookings.to_string().bright_white().bold());
+        if !metrics.no_shows.is_empty() {
+            println!("   No-Shows: {} across {} flight(s)",
+                metrics.no_shows.values().sum::<u32>().to_string().bright_red(),
+                metrics.no_shows.len());
+        }
         
         println!("\n{}", "💰 Revenue:".bright_cyan().bold());
-        println!("   Today: ${:.2}", metrics.revenue_today.to_string().bright_green().bold());
-        println!("   This Month: ${:.2}", metrics.revenue_month.to_string().bright_green().bold());
+        println!("   Today: {}", format!("${:.2}", metrics.revenue_today).bright_green().bold());
+        println!("   Yesterday: {}", format!("${:.2}", metrics.revenue_yesterday).bright_white());
+        println!("   This Month: {}", format!("${:.2}", metrics.revenue_month).bright_green().bold());
         
         if metrics.average_load_factor > 0.0 {
             println!("\n{}", "📈 Performance:".bright_cyan().bold());
-            println!("   Average Load Factor: {:.1}%", (metrics.average_load_factor * 100.0).to_string().bright_white());
+            println!("   Average Load Factor: {}", format!("{:.1}%", metrics.average_load_factor).bright_white());
+        }
+
+        if metrics.projected_fuel_cost > 0.0 {
+            println!("\n{}", "⛽ Operating Costs:".bright_cyan().bold());
+            println!("   Projected Fuel (scheduled flights): {}", format!("${:.2}", metrics.projected_fuel_cost).bright_yellow());
         }
         
         println!("\n{}", "🕐 Last Updated:".bright_cyan().bold());
@@ -437,6 +1026,23 @@ impl DisplayManager {
         Ok(())
     }
 
+    pub fn display_revenue_by_route(&self, routes: &[(String, f64, u32)]) -> Result<(), Box<dyn std::error::Error>> {
+        if routes.is_empty() {
+            println!("{}", "No bookings yet.".bright_yellow());
+            return Ok(());
+        }
+
+        self.display_section_header("Revenue by Route")?;
+        let table = Table::new()
+            .column(Column::new("Route", 10, |(route, _, _): &(String, f64, u32)| route.clone()).color(|_, s| s.bright_green().bold()))
+            .column(Column::new("Bookings", 8, |(_, _, count): &(String, f64, u32)| count.to_string()).align(Align::Right))
+            .column(Column::new("Revenue", 14, |(_, revenue, _): &(String, f64, u32)| format!("${:.2}", revenue)).align(Align::Right).color(|_, s| s.bright_yellow()))
+            .column(Column::new("Per Booking", 12, |(_, revenue, count): &(String, f64, u32)| format!("${:.2}", revenue / *count as f64)).align(Align::Right));
+        print!("{}", self.render_table(&table, routes));
+        println!();
+        Ok(())
+    }
+
     pub fn display_admin_log(&self, actions: &[&AdminAction], limit: usize) -> Result<(), Box<dyn std::error::Error>> {
         self.display_section_header(&format!("Recent Admin Actions (Last {})", limit))?;
         
@@ -453,6 +1059,196 @@ impl DisplayManager {
         Ok(())
     }
 
+    pub fn display_health_report(&self, report: &HealthReport) -> Result<(), Box<dyn std::error::Error>> {
+        self.display_section_header("System Self-Check")?;
+
+        for check in &report.checks {
+            let status = match check.status {
+                HealthStatus::Pass => check.get_status_display().bright_green(),
+                HealthStatus::Warn => check.get_status_display().bright_yellow(),
+                HealthStatus::Fail => check.get_status_display().bright_red().bold(),
+            };
+            println!("{:<10} {:<22} {}", status, check.name.bright_white().bold(), check.detail);
+        }
+
+        println!("\n{}", report.get_summary().bright_cyan());
+        println!();
+        Ok(())
+    }
+
+    /// Impact of a flight cancellation; `dry_run` marks a preview where nothing has changed yet
+    pub fn display_flight_cancellation(&self, summary: &FlightCancellation, dry_run: bool) -> Result<(), Box<dyn std::error::Error>> {
+        let title = if dry_run {
+            format!("Dry Run - Cancel Flight {}", summary.flight_number)
+        } else {
+            format!("Flight {} Cancelled", summary.flight_number)
+        };
+        self.display_section_header(&title)?;
+
+        if summary.already_cancelled {
+            println!("{}", "This flight is already cancelled - nothing to do.".bright_yellow());
+            return Ok(());
+        }
+
+        let verb = if dry_run { "Will be" } else { "Were" };
+        println!("{} {}", "Reason:".bright_white().bold(), summary.reason);
+        println!("{} {}", format!("Bookings {} cancelled:", verb.to_lowercase()).bright_white().bold(), summary.bookings_cancelled);
+        println!("{} ${}", format!("{} refunded:", verb).bright_white().bold(), format!("{:.2}", summary.total_refunded).bright_green());
+        if !summary.notifications.is_empty() {
+            println!("\n{}", "Passenger notifications:".bright_cyan().bold());
+            for notification in &summary.notifications {
+                println!("  📧 {}", notification);
+            }
+        }
+        if dry_run {
+            println!("\n{}", "Nothing has been changed yet.".bright_yellow());
+        }
+        println!();
+        Ok(())
+    }
+
+    /// Concise "what changed" summary after a simulation tick; prints nothing for an empty report
+    pub fn display_simulation_report(&self, report: &SimulationReport) -> Result<(), Box<dyn std::error::Error>> {
+        if report.is_empty() {
+            return Ok(());
+        }
+
+        println!("{} {}", "🔄 Since last update:".bright_blue().bold(), report.get_summary().bright_white());
+        for change in &report.flight_changes {
+            println!("   ✈️  {:<10} {:?} → {:?}", change.flight_number.bright_white(), change.from, change.to);
+        }
+        for change in &report.aircraft_changes {
+            println!("   🛩️  {:<10} {:?} → {:?}", change.registration.bright_white(), change.from, change.to);
+        }
+        for flight_number in &report.cancellations {
+            println!("   {} {}", "❌ Cancelled:".bright_red(), flight_number);
+        }
+        for notification in &report.notifications {
+            println!("   📣 {}", notification.bright_cyan());
+        }
+        println!();
+        Ok(())
+    }
+
+    pub fn display_upgrades(&self, flight_number: &str, upgrades: &[Upgrade]) -> Result<(), Box<dyn std::error::Error>> {
+        self.display_section_header(&format!("Upgrades - {}", flight_number))?;
+
+        if upgrades.is_empty() {
+            println!("{}", "No eligible passengers or no open premium seats.".bright_yellow());
+            return Ok(());
+        }
+
+        for upgrade in upgrades {
+            println!("   ⬆️  {:<10} {:<25} {:<10} {:?} → {:?}",
+                upgrade.ticket_number.bright_white(),
+                upgrade.passenger_name,
+                upgrade.tier.get_display().bright_yellow(),
+                upgrade.from,
+                upgrade.to);
+        }
+        println!();
+        Ok(())
+    }
+
+    pub fn display_special_services_report(&self, report: &SpecialServicesReport) -> Result<(), Box<dyn std::error::Error>> {
+        self.display_section_header(&format!("Special Services - Flight {}", report.flight_number))?;
+
+        if report.items.is_empty() {
+            println!("{}", "No special requirements on this flight.".bright_yellow());
+            return Ok(());
+        }
+
+        println!("{} {}", "📋 Summary:".bright_cyan().bold(), report.get_summary().bright_white());
+        println!();
+
+        for (i, item) in report.items.iter().enumerate() {
+            let status = match (item.fulfilled, item.critical) {
+                (true, _) => "Fulfilled ✅".bright_green(),
+                (false, true) => "CRITICAL - Pending ⚠️".bright_red().bold(),
+                (false, false) => "Pending ⏳".bright_yellow(),
+            };
+            println!(
+                "{:>3}. {:<10} {:<22} {:<28} {}",
+                i + 1,
+                item.ticket_number.bright_white(),
+                item.passenger_name,
+                item.requirement,
+                status
+            );
+        }
+
+        let critical = report.unfulfilled_critical().len();
+        if critical > 0 {
+            println!("\n{}", format!("⚠️ {} critical requirement(s) still need attention before boarding", critical).bright_red().bold());
+        }
+
+        println!();
+        Ok(())
+    }
+
+    pub fn display_catering_requirements(&self, catering: &CateringRequirements) -> Result<(), Box<dyn std::error::Error>> {
+        self.display_section_header(&format!("Catering - Flight {}", catering.flight_number))?;
+
+        if !catering.meal_service {
+            println!("{}", "No meal service on this flight.".bright_yellow());
+            println!();
+            return Ok(());
+        }
+
+        println!("{}", "🍽️ Meals by Class:".bright_cyan().bold());
+        println!("   Economy: {}", catering.economy_meals.to_string().bright_white());
+        println!("   Business: {}", catering.business_meals.to_string().bright_white());
+        println!("   First Class: {}", catering.first_class_meals.to_string().bright_white());
+        println!("   Total: {}", catering.total_meals().to_string().bright_green().bold());
+
+        if !catering.meal_options.is_empty() {
+            println!("\n{}  {}", "📋 Menu:".bright_cyan().bold(), catering.meal_options.join(", ").bright_white());
+        }
+
+        if !catering.special_meals.is_empty() {
+            println!("\n{}", "🥗 Special Meals (included above):".bright_cyan().bold());
+            for (category, count) in &catering.special_meals {
+                println!("   {}: {}", category, count.to_string().bright_yellow().bold());
+            }
+        }
+
+        println!();
+        Ok(())
+    }
+
+    pub fn display_price_breakdown(&self, breakdown: &PriceBreakdown) -> Result<(), Box<dyn std::error::Error>> {
+        println!("{}", "💰 Price Breakdown:".bright_cyan().bold());
+        for component in &breakdown.components {
+            let step = match component.multiplier {
+                Some(multiplier) => format!("× {:.2}", multiplier),
+                None => String::new(),
+            };
+            println!("   {:<36} {:>8}  {}", component.label, step, self.money(component.running_total).bright_white());
+        }
+        println!("   {:<36} {:>8}  {}", "Total".bold(), "", self.money(breakdown.total).bright_green().bold());
+        Ok(())
+    }
+
+    /// An error from the data layer, with a hint on what to try next where there is one
+    pub fn airport_error_message(error: &AirportError) -> String {
+        let hint = match error {
+            AirportError::NoSeatsAvailable { .. } => Some("try another seat class or join the waitlist"),
+            AirportError::FlightNotAvailable { .. } => Some("search for another flight on this route"),
+            AirportError::FlightNotFound { .. } | AirportError::FlightNumberNotFound { .. } => Some("check the flight number"),
+            AirportError::BookingNotFound { .. } => Some("check the ticket number"),
+            AirportError::InsufficientPermissions { .. } => Some("log in with an account that has access"),
+            _ => None,
+        };
+        match hint {
+            Some(hint) => format!("{} ({})", error, hint),
+            None => error.to_string(),
+        }
+    }
+
+    pub fn display_airport_error(&self, context: &str, error: &AirportError) -> Result<(), Box<dyn std::error::Error>> {
+        self.display_error_message(&format!("{}: {}", context, Self::airport_error_message(error)))
+    }
+
     pub fn display_success_message(&self, message: &str) -> Result<(), Box<dyn std::error::Error>> {
         println!("\n{} {}", "✅".bright_green(), message.bright_green().bold());
         Ok(())
@@ -480,4 +1276,200 @@ impl DisplayManager {
         io::stdin().read_line(&mut input)?;
         Ok(())
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::booking::{Passenger, PassengerType};
+    use uuid::Uuid;
+
+    /// Rendered text without color escape sequences. `colored` is switched process-wide,
+    /// so other tests may leave it on while these run.
+    fn plain(text: &str) -> String {
+        let mut output = String::new();
+        let mut chars = text.chars();
+        while let Some(c) = chars.next() {
+            if c == '\x1b' {
+                chars.by_ref().find(|c| *c == 'm');
+            } else {
+                output.push(c);
+            }
+        }
+        output
+    }
+
+    // Rows formatted exactly as the hand-written tables did before `Table` existed
+    #[test]
+    fn test_tables_match_previous_layout() {
+        let airport = Airport::new(
+            "LAX".to_string(),
+            "KLAX".to_string(),
+            "Los Angeles International Airport".to_string(),
+            "Los Angeles".to_string(),
+            "United States".to_string(),
+            "America/Los_Angeles".to_string(),
+            33.9425,
+            -118.4081,
+            38,
+        );
+        let airports_expected = format!(
+            "{:<6} {:<35} {:<15} {:<15} {:<12} {:<9}\n{}\n{:<6} {:<35} {:<15} {:<15} {:<12} {:<9}\n",
+            "Code", "Name", "City", "Country", "Type", "Min Conn",
+            "─".repeat(97),
+            airport.code, airport.name, airport.city, airport.country, airport.get_size_display(), "90 min"
+        );
+        assert_eq!(plain(&DisplayManager::airports_table().render(&[&airport])), airports_expected);
+
+        let passenger = Passenger::new(
+            "Ada".to_string(),
+            "Lovelace".to_string(),
+            "ada@example.com".to_string(),
+            "5551234567".to_string(),
+            "1990-01-01".to_string(),
+            PassengerType::Adult,
+        );
+        let mut booking = Booking::new(Uuid::new_v4(), passenger, SeatClass::Business, 899.99, "Credit Card".to_string());
+        booking.ticket_number = "RIA123456".to_string();
+        booking.assign_seat("3C".to_string());
+        let bookings_expected = format!(
+            "{:<12} {:<25} {:<8} {:<10} {:<15} {:<10}\n{}\n{:<12} {:<25} {:<8} {:<10} {:<15} ${:<9.2}\n",
+            "Ticket #", "Passenger", "Class", "Seat", "Status", "Amount",
+            "─".repeat(85),
+            "RIA123456", "Ada Lovelace", "Business", "3C", booking.get_status_display(), 899.99
+        );
+        assert_eq!(plain(&DisplayManager::bookings_table().render(&[&booking])), bookings_expected);
+
+        let aircraft = Aircraft::new("N1TEST".to_string(), "Boeing 737-800".to_string(), "Boeing".to_string(), 2020);
+        let now = Utc::now();
+        let departure = now + chrono::Duration::days(3);
+        let flight = Flight::new(
+            "RIA101".to_string(),
+            "Rust International Airways".to_string(),
+            "LAX".to_string(),
+            "JFK".to_string(),
+            departure,
+            departure + chrono::Duration::minutes(330),
+            &aircraft,
+        );
+        let seats = &flight.seat_availability;
+        let flights_expected = format!(
+            "{:<10} {:<4} {:<6} {:<6} {:<8} {:<18} {:<8} {:>8} {:>8} {:<15} {:<6} {:<12} {:<5}\n{}\n\
+             {:<10} {:<4} {:<6} {:<6} {:<8} {:<18} {:<8} {:>8} {:>8} {:<15} {:<6} {:<5}/{:<6} {:<5}\n",
+            "Flight", "Gate", "Origin", "Dest", "Departure", "Departs", "Arrival", "Dist", "Duration", "Status", "Eco", "Bus/First", "Wi-Fi",
+            "─".repeat(126),
+            "RIA101", "--", "LAX", "JFK", departure.format("%H:%M").to_string(), format_relative_time(departure, now),
+            flight.arrival_time.format("%H:%M").to_string(), "--", "5h 30m", flight.get_status_display(),
+            seats.economy, seats.business, seats.first_class, if aircraft.has_wifi() { "Yes" } else { "--" }
+        );
+        let aircraft_list = [aircraft.clone()];
+        assert_eq!(plain(&DisplayManager::flights_table(now, &aircraft_list, &[]).render(&[&flight])), flights_expected);
+    }
+
+    #[test]
+    fn test_flight_comparison_fits_terminal_and_handles_missing_aircraft() {
+        let aircraft = Aircraft::new("N1TEST".to_string(), "Boeing 777-300".to_string(), "Boeing".to_string(), 2015);
+        let departure = Utc::now() + chrono::Duration::days(3);
+        let flight = |number: &str| Flight::new(
+            number.to_string(),
+            "Rust International Airways".to_string(),
+            "LAX".to_string(),
+            "JFK".to_string(),
+            departure,
+            departure + chrono::Duration::minutes(330),
+            &aircraft,
+        );
+        let (first, second) = (flight("RIA101"), flight("RIA202"));
+        let flights = [(&first, Some(&aircraft)), (&second, None)];
+
+        let wide = plain(&DisplayManager::render_flight_comparison(&flights, &[], 200));
+        let lines: Vec<&str> = wide.lines().collect();
+        assert!(lines[0].contains("RIA101") && lines[0].contains("RIA202"));
+        assert!(wide.contains("Boeing 777-300"));
+        assert!(wide.contains("5h 30m"));
+        let aircraft_row = lines.iter().find(|l| l.starts_with("Aircraft")).unwrap();
+        assert!(aircraft_row.trim_end().ends_with("n/a"));
+
+        let narrow = plain(&DisplayManager::render_flight_comparison(&flights, &[], 40));
+        assert!(narrow.lines().all(|line| line.chars().count() <= 40));
+        assert!(narrow.contains('…'));
+    }
+
+    #[test]
+    fn test_flights_table_shows_distance_and_duration() {
+        let airport = |code: &str, lat: f64, lon: f64| Airport::new(
+            code.to_string(), format!("K{}", code), format!("{} Airport", code), "City".to_string(),
+            "United States".to_string(), "UTC".to_string(), lat, lon, 10,
+        );
+        let airports = [airport("LAX", 33.9425, -118.4081), airport("JFK", 40.6413, -73.7781)];
+        let departure = Utc::now() + chrono::Duration::days(3);
+        let mut flight = Flight::new(
+            "RIA101".to_string(),
+            "Rust International Airways".to_string(),
+            "LAX".to_string(),
+            "JFK".to_string(),
+            departure,
+            departure + chrono::Duration::minutes(330),
+            &Aircraft::new("N1TEST".to_string(), "Boeing 737-800".to_string(), "Boeing".to_string(), 2020),
+        );
+
+        let distance = format!("{:.0} km", airports[0].get_distance_to(&airports[1]));
+        let rendered = plain(&DisplayManager::flights_table(Utc::now(), &[], &airports).render(&[&flight]));
+        let row = rendered.lines().nth(2).unwrap();
+        assert!(row.contains(&format!(" {:>8} {:>8} ", distance, "5h 30m")));
+
+        flight.destination = "ZZZ".to_string();
+        let rendered = plain(&DisplayManager::flights_table(Utc::now(), &[], &airports).render(&[&flight]));
+        assert!(rendered.lines().nth(2).unwrap().contains(&format!(" {:>8} {:>8} ", "--", "5h 30m")));
+    }
+
+    #[test]
+    fn test_color_decision() {
+        assert!(color_enabled(false, None, true));
+        assert!(!color_enabled(false, None, false));
+        assert!(!color_enabled(true, None, true));
+        assert!(!color_enabled(false, Some("1"), true));
+        // An empty NO_COLOR does not count as set
+        assert!(color_enabled(false, Some(""), true));
+    }
+
+    #[test]
+    fn test_output_has_no_escape_codes_with_color_off() {
+        // The choice stays on the instance; only `init_color` touches the process-wide setting
+        let global = colors_active();
+        assert!(DisplayManager::new_with_color(true).color_enabled());
+        let display = DisplayManager::new_with_color(false);
+        assert!(!display.color_enabled());
+        assert_eq!(colors_active(), global);
+
+        let table = Table::new()
+            .column(Column::new("Status", 10, |s: &&str| s.to_string()).color(|_, text| text.bright_red().bold()))
+            .column(Column::new("Fare", 8, |_: &&str| format!("{:.2}", 99.5)).align(Align::Right).color(|_, text| text.bright_green()));
+        let output = display.render_table(&table, &["Delayed", "Boarding"]);
+
+        assert!(!output.contains('\x1b'));
+        assert!(output.contains("Delayed"));
+    }
+
+    #[test]
+    fn test_pager_navigation() {
+        assert_eq!(next_page(0, 3, "\n"), Some(1));
+        assert_eq!(next_page(1, 3, "N"), Some(2));
+        assert_eq!(next_page(2, 3, ""), None);
+        assert_eq!(next_page(2, 3, "p"), Some(1));
+        assert_eq!(next_page(0, 3, "p"), Some(0));
+        assert_eq!(next_page(1, 3, "q"), None);
+        assert_eq!(next_page(1, 3, "x"), Some(1));
+    }
+
+    #[test]
+    fn test_airport_error_message_adds_hints() {
+        let sold_out = AirportError::NoSeatsAvailable { class: SeatClass::Business };
+        assert_eq!(
+            DisplayManager::airport_error_message(&sold_out),
+            "No seats available in Business (try another seat class or join the waitlist)"
+        );
+
+        let delay = AirportError::DelayOutOfRange { minutes: 2000, min: 0, max: 1440 };
+        assert_eq!(DisplayManager::airport_error_message(&delay), delay.to_string());
+    }
+}
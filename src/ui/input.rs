@@ -1,15 +1,23 @@
 use colored::*;
 use std::io::{self, Write};
-use chrono::{DateTime, Utc, NaiveDate, TimeZone};
-use uuid::Uuid;
+use chrono::{DateTime, Utc, NaiveDate, NaiveTime, TimeZone, Weekday};
 use crate::modules::{
     flight::SeatClass,
-    booking::{Passenger, PassengerType},
+    booking::{MealPreference, Passenger, PassengerType},
     airport::Airport,
 };
 
+/// Optional origin, destination and travel date collected by the custom search prompt
+pub type SearchCriteria = (Option<String>, Option<String>, Option<DateTime<Utc>>, Option<SeatClass>);
+
 pub struct InputManager;
 
+impl Default for InputManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl InputManager {
     pub fn new() -> Self {
         Self
@@ -155,6 +163,17 @@ impl InputManager {
         }
     }
 
+    pub fn get_meal_preference_input(&self, passenger_name: &str) -> Result<MealPreference, Box<dyn std::error::Error>> {
+        println!("\n{}", format!("Meal for {}:", passenger_name).bright_cyan().bold());
+        for (i, meal) in MealPreference::ALL.iter().enumerate() {
+            println!("  {} - {}", (i + 1).to_string().bright_green().bold(), meal.get_display());
+        }
+        println!();
+
+        let choice = self.get_number_input_with_range("Select meal (1-7):", 1, MealPreference::ALL.len())?;
+        Ok(MealPreference::ALL[choice - 1])
+    }
+
     pub fn get_date_input(&self, prompt: &str) -> Result<DateTime<Utc>, Box<dyn std::error::Error>> {
         println!("\n{}", "Date format: YYYY-MM-DD (e.g., 2025-06-15)".bright_blue().dimmed());
         
@@ -175,6 +194,28 @@ impl InputManager {
         }
     }
 
+    pub fn get_time_input(&self, prompt: &str) -> Result<NaiveTime, Box<dyn std::error::Error>> {
+        loop {
+            let input = self.get_string_input(prompt)?;
+            match NaiveTime::parse_from_str(&input, "%H:%M") {
+                Ok(time) => return Ok(time),
+                Err(_) => println!("{} Invalid time. Please use HH:MM (24-hour)", "❌".bright_red()),
+            }
+        }
+    }
+
+    /// Comma-separated weekday names such as "Mon,Wed,Fri"
+    pub fn get_weekdays_input(&self, prompt: &str) -> Result<Vec<Weekday>, Box<dyn std::error::Error>> {
+        loop {
+            let input = self.get_string_input(prompt)?;
+            let days: Result<Vec<Weekday>, _> = input.split(',').map(|day| day.trim().parse::<Weekday>()).collect();
+            match days {
+                Ok(days) => return Ok(days),
+                _ => println!("{} Please list days such as Mon,Wed,Fri", "❌".bright_red()),
+            }
+        }
+    }
+
     pub fn get_email_input(&self, prompt: &str) -> Result<String, Box<dyn std::error::Error>> {
         self.get_string_input_with_validation(
             prompt,
@@ -265,7 +306,7 @@ impl InputManager {
         self.get_number_input_with_range(prompt, min, max)
     }
 
-    pub fn get_flight_search_criteria(&self, airports: &[Airport]) -> Result<(Option<String>, Option<String>, Option<DateTime<Utc>>), Box<dyn std::error::Error>> {
+    pub fn get_flight_search_criteria(&self, airports: &[Airport]) -> Result<SearchCriteria, Box<dyn std::error::Error>> {
         println!("\n{}", "═══ Flight Search ═══".bright_cyan().bold());
         
         let origin = if self.get_yes_no_input("Do you want to search by origin airport?")? {
@@ -286,7 +327,13 @@ impl InputManager {
             None
         };
 
-        Ok((origin, destination, date))
+        let seat_class = if self.get_yes_no_input("Only show flights with seats in a particular class?")? {
+            Some(self.get_seat_class_input()?)
+        } else {
+            None
+        };
+
+        Ok((origin, destination, date, seat_class))
     }
 
     pub fn get_admin_credentials(&self) -> Result<(String, String), Box<dyn std::error::Error>> {
@@ -330,8 +377,9 @@ impl InputManager {
     }
 
     pub fn get_delay_minutes_input(&self) -> Result<i32, Box<dyn std::error::Error>> {
+        use crate::config::delays::{MAX_DELAY_MINUTES, MIN_DELAY_MINUTES};
         println!("\n{}", "Enter delay in minutes (0 = on time, negative = early):".bright_cyan());
-        self.get_number_input_with_range("Delay minutes:", -60, 480) // -1 hour to +8 hours
+        self.get_number_input_with_range("Delay minutes:", MIN_DELAY_MINUTES, MAX_DELAY_MINUTES)
     }
 
     pub fn get_pricing_multiplier_input(&self) -> Result<f64, Box<dyn std::error::Error>> {
@@ -339,6 +387,15 @@ impl InputManager {
         self.get_number_input_with_range("Multiplier:", 0.1, 5.0)
     }
 
+    /// Base fares for economy, business and first class
+    pub fn get_base_prices_input(&self) -> Result<(f64, f64, f64), Box<dyn std::error::Error>> {
+        println!("\n{}", "Base fares in USD, before pricing multipliers:".bright_cyan());
+        let economy = self.get_number_input_with_range("Economy:", 1.0, 100_000.0)?;
+        let business = self.get_number_input_with_range("Business:", 1.0, 100_000.0)?;
+        let first_class = self.get_number_input_with_range("First class:", 1.0, 100_000.0)?;
+        Ok((economy, business, first_class))
+    }
+
     pub fn display_search_options(&self) -> Result<(), Box<dyn std::error::Error>> {
         println!("\n{}", "Search Options:".bright_cyan().bold());
         println!("  {} - Search all flights", "1".bright_green());
@@ -347,6 +404,10 @@ impl InputManager {
         println!("  {} - Search by route (origin + destination)", "4".bright_green());
         println!("  {} - Search by date", "5".bright_green());
         println!("  {} - Custom search (multiple criteria)", "6".bright_green());
+        println!("  {} - Flexible dates (lowest fare per day)", "7".bright_green());
+        println!("  {} - Search by aircraft type", "8".bright_green());
+        println!("  {} - Connecting flights (multi-stop)", "9".bright_green());
+        println!("  {} - Search within a budget", "10".bright_green());
         println!("  {} - Back to main menu", "0".bright_red());
         Ok(())
     }
@@ -359,6 +420,18 @@ impl InputManager {
         println!("  {} - View Admin Log", "4".bright_blue());
         println!("  {} - Aircraft Management", "5".bright_blue());
         println!("  {} - Create Backup", "6".bright_magenta());
+        println!("  {} - Run Self-Check", "7".bright_magenta());
+        println!("  {} - Special Services Report", "8".bright_blue());
+        println!("  {} - Catering Requirements", "9".bright_blue());
+        println!("  {} - Cancel Flight", "10".bright_red());
+        println!("  {} - Anonymize Passenger Data", "11".bright_red());
+        println!("  {} - Process Upgrades", "12".bright_yellow());
+        println!("  {} - Export Reports (CSV)", "13".bright_magenta());
+        println!("  {} - Create Admin Account", "14".bright_red());
+        println!("  {} - Restore Backup", "15".bright_red());
+        println!("  {} - Repair Data Integrity", "16".bright_red());
+        println!("  {} - Export System Snapshot", "17".bright_magenta());
+        println!("  {} - Generate Weekly Schedule", "18".bright_yellow());
         println!("  {} - Logout", "0".bright_red());
         Ok(())
     }
@@ -367,6 +440,37 @@ impl InputManager {
         self.get_yes_no_input(&format!("Are you sure you want to {}?", action))
     }
 
+    /// Guard for irreversible actions: the admin must type `typed_phrase` exactly (e.g. the
+    /// flight number). Mistypes get another try up to `DESTRUCTIVE_CONFIRMATION_ATTEMPTS`;
+    /// an empty answer aborts straight away.
+    pub fn confirm_destructive(&self, action: &str, typed_phrase: &str) -> Result<bool, Box<dyn std::error::Error>> {
+        use crate::config::DESTRUCTIVE_CONFIRMATION_ATTEMPTS;
+
+        println!("\n{} {}", "⚠️  This will".bright_red().bold(), format!("{}. It cannot be undone.", action).bright_red().bold());
+        for attempt in 1..=DESTRUCTIVE_CONFIRMATION_ATTEMPTS {
+            print!("{} ", format!("Type {} to confirm (leave blank to abort):", typed_phrase.bright_white().bold()).bright_yellow());
+            io::stdout().flush()?;
+
+            let mut input = String::new();
+            io::stdin().read_line(&mut input)?;
+            let input = input.trim();
+
+            if input == typed_phrase {
+                return Ok(true);
+            }
+            if input.is_empty() {
+                break;
+            }
+            let remaining = DESTRUCTIVE_CONFIRMATION_ATTEMPTS - attempt;
+            if remaining > 0 {
+                println!("{}", format!("❌ That does not match - {} attempt(s) left.", remaining).bright_red());
+            }
+        }
+
+        println!("{}", "Aborted - nothing was changed.".bright_yellow());
+        Ok(false)
+    }
+
     pub fn display_loading_message(&self, message: &str) -> Result<(), Box<dyn std::error::Error>> {
         print!("{} {}...", "🔄".bright_blue(), message.bright_blue());
         io::stdout().flush()?;
@@ -1,23 +1,19 @@
-use crate::data::manager::DataManager;
+use crate::data::manager::{ClassAlternative, DataManager, SimulationReport, SortKey};
 use crate::ui::{display::DisplayManager, input::InputManager};
-use crate::modules::{
-    flight::{Flight, SeatClass},
-    booking::{Passenger, PassengerType},
-};
-use crossterm::{
-    execute,
-    terminal::{Clear, ClearType},
-    cursor,
-};
+use crate::modules::flight::{Flight, FlightStatus, FlightTemplate, SeatClass};
+use crate::modules::admin::AdminLevel;
+use crate::modules::aircraft::{AircraftStatus, SeatConfiguration};
+use crate::modules::loyalty::LoyaltyTier;
+use crate::config;
+use crate::errors::AirportError;
 use colored::*;
-use std::io::{self, Write};
 use std::error::Error;
-use uuid::Uuid;
 
 pub struct MainMenu {
     data_manager: DataManager,
     display: DisplayManager,
     input: InputManager,
+    last_update: SimulationReport,
 }
 
 impl MainMenu {
@@ -26,13 +22,16 @@ impl MainMenu {
             data_manager,
             display: DisplayManager::new(),
             input: InputManager::new(),
+            last_update: SimulationReport::default(),
         }
     }
 
     pub async fn run(&mut self) -> Result<(), Box<dyn Error>> {
+        self.choose_display_currency()?;
+
         loop {
             // Update real-time simulation
-            self.data_manager.update_simulation().await?;
+            self.last_update = self.data_manager.update_simulation().await?;
             
             self.display_main_menu()?;
             
@@ -47,7 +46,13 @@ impl MainMenu {
                 6 => self.admin_panel().await?,
                 7 => {
                     self.display.display_info_message("Saving data and exiting...")?;
-                    self.data_manager.save_all_data().await?;
+                    if let Err(e) = self.data_manager.save_all_data().await {
+                        // Keep the session alive so in-memory changes aren't lost
+                        self.display.display_error_message(&format!("Failed to save data: {}", e))?;
+                        if !self.input.get_yes_no_input("Exit anyway and discard unsaved changes?")? {
+                            continue;
+                        }
+                    }
                     println!("\n{}", "Thank you for using Rust International Airport! Safe travels! ✈️".bright_green().bold());
                     break;
                 }
@@ -60,6 +65,23 @@ impl MainMenu {
         Ok(())
     }
 
+    /// Prices are shown in the chosen currency; bookings are still charged in USD
+    fn choose_display_currency(&mut self) -> Result<(), Box<dyn Error>> {
+        let currencies = self.data_manager.currency.currencies().join("/");
+        loop {
+            let input = self.input.get_string_input(&format!("Display currency [USD] ({}):", currencies))?;
+            if input.trim().is_empty() {
+                break;
+            }
+            match self.data_manager.set_display_currency(&input) {
+                Ok(()) => break,
+                Err(e) => self.display.display_error_message(&e)?,
+            }
+        }
+        self.display.set_currency(self.data_manager.display_quote());
+        Ok(())
+    }
+
     fn display_main_menu(&self) -> Result<(), Box<dyn Error>> {
         self.display.clear_screen()?;
 
@@ -81,6 +103,7 @@ impl MainMenu {
         let metrics = self.data_manager.get_system_metrics();
         println!("\n{} {}", "📊 System Status:".bright_blue().bold(), metrics.get_summary().bright_white());
         println!();
+        self.display.display_simulation_report(&self.last_update)?;
 
         Ok(())
     }
@@ -91,14 +114,64 @@ impl MainMenu {
         self.display.display_header("Flight Search")?;
 
         self.input.display_search_options()?;
-        let search_type = self.input.get_menu_choice("Select search type:", 0, 6)?;
+        let search_type = self.input.get_menu_choice("Select search type:", 0, 10)?;
 
         if search_type == 0 {
             return Ok(());
         }
 
+        if search_type == 7 {
+            // Flexible dates: price calendar around the chosen date
+            let airports = self.data_manager.get_all_airports();
+            let origin = self.input.get_airport_code_input("Origin Airport:", airports)?;
+            let destination = self.input.get_airport_code_input("Destination Airport:", airports)?;
+            let date = self.input.get_date_input("Preferred Travel Date:")?;
+            let window = self.input.get_menu_choice("Days either side (1-7):", 1, 7)?;
+            let seat_class = self.input.get_seat_class_input()?;
+
+            let results = self.data_manager.search_flexible_dates(&origin, &destination, date, window, &seat_class);
+            self.display.clear_screen()?;
+            self.display.display_header(&format!("{} → {} ({:?})", origin, destination, seat_class))?;
+            self.display.display_price_calendar(&results)?;
+            self.display.pause_for_user()?;
+            return Ok(());
+        }
+
+        if search_type == 9 {
+            // Itineraries with connections, e.g. LAX → JFK → CDG
+            let airports = self.data_manager.get_all_airports();
+            let origin = self.input.get_airport_code_input("Origin Airport:", airports)?;
+            let destination = self.input.get_airport_code_input("Destination Airport:", airports)?;
+            let max_stops = self.input.get_menu_choice("Maximum stops (1-3):", 1, 3)?;
+
+            let itineraries = self.data_manager.find_connecting_flights(&origin, &destination, max_stops as usize);
+            self.display.clear_screen()?;
+            self.display.display_header(&format!("{} → {}", origin, destination))?;
+            self.display.display_itineraries(&itineraries)?;
+            self.display.pause_for_user()?;
+            return Ok(());
+        }
+
+        if search_type == 10 {
+            // Budget search: only flights at or under the traveller's price cap
+            let airports = self.data_manager.get_all_airports();
+            let (origin, destination, date, seat_class) = self.input.get_flight_search_criteria(airports)?;
+            let seat_class = match seat_class {
+                Some(class) => class,
+                None => self.input.get_seat_class_input()?,
+            };
+            let max_price: f64 = self.input.get_number_input_with_range("Maximum price per passenger ($):", 0.0, 100_000.0)?;
+
+            let flights = self.data_manager.search_flights_by_price(origin.as_deref(), destination.as_deref(), date, &seat_class, max_price);
+            self.display.clear_screen()?;
+            self.display.display_header(&format!("{:?} fares up to ${:.2}", seat_class, max_price))?;
+            self.display.display_flights_table_with_price(&flights, &self.data_manager.database.aircraft, &self.data_manager.database.airports, &seat_class, |f| self.data_manager.get_fare(f, &seat_class))?;
+            self.display.pause_for_user()?;
+            return Ok(());
+        }
+
         let airports = self.data_manager.get_all_airports();
-        let flights = match search_type {
+        let mut flights = match search_type {
             1 => {
                 // Show all available flights
                 self.data_manager.get_available_flights()
@@ -126,30 +199,65 @@ impl MainMenu {
             }
             6 => {
                 // Custom search
-                let (origin, destination, date) = self.input.get_flight_search_criteria(airports)?;
-                self.data_manager.search_flights(
+                let (origin, destination, date, seat_class) = self.input.get_flight_search_criteria(airports)?;
+                self.data_manager.search_flights_with_class(
                     origin.as_deref(),
                     destination.as_deref(),
-                    date
+                    date,
+                    seat_class.as_ref(),
                 )
             }
+            8 => {
+                // Search by aircraft model, e.g. "A380" or "737"
+                let mut models: Vec<&str> = self.data_manager.database.aircraft.iter().map(|a| a.model.as_str()).collect();
+                models.sort();
+                models.dedup();
+                println!("\n{} {}", "Fleet:".bright_cyan(), models.join(", "));
+                let model = self.input.get_string_input("Aircraft type:")?;
+                self.data_manager.search_flights_by_aircraft_model(&model)
+            }
             _ => return Ok(()),
         };
 
+        if flights.len() >= 2 {
+            println!("\n{}", "Sort results by:".bright_cyan().bold());
+            println!("  {} - Departure time", "1".bright_green());
+            println!("  {} - Flight duration", "2".bright_green());
+            println!("  {} - Economy price", "3".bright_green());
+            println!("  {} - Load factor", "4".bright_green());
+            println!("  {} - Keep current order", "0".bright_yellow());
+            let key = match self.input.get_menu_choice("Sort by:", 0, 4)? {
+                1 => Some(SortKey::DepartureTime),
+                2 => Some(SortKey::Duration),
+                3 => Some(SortKey::PriceEconomy),
+                4 => Some(SortKey::LoadFactor),
+                _ => None,
+            };
+            if let Some(key) = key {
+                let ascending = !self.input.get_yes_no_input("Highest first?")?;
+                self.data_manager.sort_flights(&mut flights, key, ascending);
+            }
+        }
+
         self.display.clear_screen()?;
         self.display.display_header("Search Results")?;
-        self.display.display_flights_table(&flights)?;
+        self.display.display_flights_table(&flights, &self.data_manager.database.aircraft, &self.data_manager.database.airports)?;
 
-        if !flights.is_empty() {
-            if self.input.get_yes_no_input("Would you like to view details for a specific flight?")? {
-                let flight_number = self.input.get_flight_number_input()?;
-                if let Some(flight) = self.data_manager.get_flight_by_number(&flight_number) {
-                    let aircraft = self.data_manager.get_aircraft_for_flight(flight.id);
-                    self.display.clear_screen()?;
-                    self.display.display_flight_details(flight, aircraft)?;
-                } else {
-                    self.display.display_error_message("Flight not found!")?;
-                }
+        if flights.len() >= 2 && self.input.get_yes_no_input("Compare flights side by side?")? {
+            self.compare_flights(&flights)?;
+        }
+
+        if !flights.is_empty()
+            && self.input.get_yes_no_input("Would you like to view details for a specific flight?")?
+        {
+            let flight_number = self.input.get_flight_number_input()?;
+            if let Some(flight) = self.data_manager.get_flight_by_number(&flight_number) {
+                let aircraft = self.data_manager.get_aircraft_for_flight(flight.id);
+                let distance = self.data_manager.route_distance_km(&flight.origin, &flight.destination);
+                self.display.clear_screen()?;
+                self.display.display_flight_details(flight, aircraft, distance)?;
+            } else {
+                self.display.display_error_message("Flight not found!")?;
             }
         }
 
@@ -157,6 +265,35 @@ impl MainMenu {
         Ok(())
     }
 
+    /// Pick 2-3 flights from search results by number and show them side by side
+    fn compare_flights(&self, results: &[&Flight]) -> Result<(), Box<dyn Error>> {
+        let input = self.input.get_string_input("Flight numbers to compare (2-3, separated by spaces or commas):")?;
+        let mut selected: Vec<&Flight> = Vec::new();
+        for number in input.split(|c: char| c == ',' || c.is_whitespace()).filter(|n| !n.is_empty()) {
+            match results.iter().find(|f| f.flight_number.eq_ignore_ascii_case(number)) {
+                Some(flight) if !selected.iter().any(|f| f.id == flight.id) => selected.push(flight),
+                Some(_) => {}
+                None => {
+                    self.display.display_error_message(&format!("{} is not in the search results", number.to_uppercase()))?;
+                    return Ok(());
+                }
+            }
+        }
+
+        if !(2..=3).contains(&selected.len()) {
+            self.display.display_error_message("Please choose 2 or 3 different flights")?;
+            return Ok(());
+        }
+
+        let compared: Vec<_> = selected
+            .iter()
+            .map(|f| (*f, self.data_manager.get_aircraft_by_id(f.aircraft_id)))
+            .collect();
+        self.display.clear_screen()?;
+        self.display.display_flight_comparison(&compared, self.data_manager.get_all_airports())?;
+        Ok(())
+    }
+
     // 2. Book a Flight
     async fn book_flight(&mut self) -> Result<(), Box<dyn Error>> {
         self.display.clear_screen()?;
@@ -170,7 +307,7 @@ impl MainMenu {
             return Ok(());
         }
 
-        self.display.display_flights_table(&available_flights)?;
+        self.display.display_flights_table(&available_flights, &self.data_manager.database.aircraft, &self.data_manager.database.airports)?;
 
         // Get flight selection
         let flight_number = self.input.get_flight_number_input()?;
@@ -191,26 +328,110 @@ impl MainMenu {
 
         // Show flight details
         let aircraft = self.data_manager.get_aircraft_for_flight(flight.id);
-        self.display.display_flight_details(flight, aircraft)?;
+        self.display.display_flight_details(flight, aircraft, None)?;
 
         // Get seat class
-        let seat_class = self.input.get_seat_class_input()?;
+        let mut seat_class = self.input.get_seat_class_input()?;
 
-        // Check seat availability
+        // Check seat availability and offer other classes when sold out
         if flight.get_available_seats(&seat_class) == 0 {
-            self.display.display_error_message("No seats available in the selected class.")?;
-            self.display.pause_for_user()?;
-            return Ok(());
+            // Offer the nearest cheaper class outright before the full list
+            let downgrade = self.data_manager.suggest_downgrade(flight.id, &seat_class);
+            let take_downgrade = match &downgrade {
+                Some((lower, fare)) => self.input.get_yes_no_input(&format!(
+                    "{:?} is full — book {:?} for {} instead?",
+                    seat_class, lower, self.display.money(*fare)
+                ))?,
+                None => {
+                    self.display.display_warning_message(&format!("{:?} is full on this flight.", seat_class))?;
+                    false
+                }
+            };
+
+            if let Some((lower, _)) = downgrade.filter(|_| take_downgrade) {
+                seat_class = lower;
+            } else {
+                let alternatives = self.data_manager.suggest_alternative_class(flight.id, &seat_class)?;
+                let classes: Vec<(SeatClass, f64)> = alternatives
+                    .iter()
+                    .filter_map(|alternative| match alternative {
+                        ClassAlternative::Class { class, price_difference, .. } => Some((class.clone(), *price_difference)),
+                        ClassAlternative::Waitlist => None,
+                    })
+                    .collect();
+
+                if classes.is_empty() {
+                    self.display.display_info_message("All classes are sold out - only the waitlist remains for this flight.")?;
+                } else {
+                    println!("\n{}", "Available alternatives:".bright_cyan().bold());
+                }
+                for (i, (class, difference)) in classes.iter().enumerate() {
+                    let sign = if *difference >= 0.0 { "+" } else { "-" };
+                    println!("  {} - {:?} ({}${:.2})", (i + 1).to_string().bright_green(), class, sign, difference.abs());
+                }
+                let waitlist_choice = classes.len() as u32 + 1;
+                println!("  {} - Join the {:?} waitlist", waitlist_choice.to_string().bright_yellow(), seat_class);
+                println!("  {} - Cancel booking", "0".bright_red());
+
+                let choice = self.input.get_menu_choice("Select option:", 0, waitlist_choice)?;
+                if choice == 0 {
+                    self.display.display_info_message("Booking cancelled.")?;
+                    self.display.pause_for_user()?;
+                    return Ok(());
+                }
+                if choice == waitlist_choice {
+                    let flight_id = flight.id;
+                    let passenger = self.input.get_passenger_info_input()?;
+                    match self.data_manager.join_waitlist(flight_id, passenger, seat_class) {
+                        Ok(position) => self.display.display_success_message(&format!(
+                            "You're on the waitlist at position {}. You'll be booked automatically if a seat frees up.",
+                            position
+                        ))?,
+                        Err(e) => self.display.display_error_message(&format!("Could not join the waitlist: {}", e))?,
+                    }
+                    self.display.pause_for_user()?;
+                    return Ok(());
+                }
+                seat_class = classes[choice as usize - 1].0.clone();
+            }
         }
 
-        // Get passenger information
-        let passenger = self.input.get_passenger_info_input()?;
+        // Get passenger information for everyone travelling
+        let party_size = self.input.get_number_input_with_range::<u32>(
+            &format!("Number of passengers (1-{}):", config::MAX_GROUP_SIZE),
+            1,
+            config::MAX_GROUP_SIZE,
+        )?;
+        let mut passengers = Vec::new();
+        for i in 1..=party_size {
+            if party_size > 1 {
+                println!("\n{}", format!("Passenger {} of {}", i, party_size).bright_cyan().bold());
+            }
+            passengers.push(self.input.get_passenger_info_input()?);
+        }
+
+        // A single traveller can add the return leg for the round-trip discount
+        let mut return_flight = None;
+        if passengers.len() == 1 {
+            let options = self.data_manager.get_return_flights(flight.id);
+            let prompt = format!(
+                "Add a return flight? Round trips save {:.0}%",
+                config::pricing::ROUND_TRIP_DISCOUNT * 100.0
+            );
+            if !options.is_empty() && self.input.get_yes_no_input(&prompt)? {
+                self.display.display_flights_table(&options, &self.data_manager.database.aircraft, &self.data_manager.database.airports)?;
+                let number = self.input.get_flight_number_input()?;
+                match options.iter().find(|f| f.flight_number.eq_ignore_ascii_case(&number)) {
+                    Some(f) => return_flight = Some(*f),
+                    None => self.display.display_warning_message("That flight is not a return option - booking one way only.")?,
+                }
+            }
+        }
 
         // Show booking summary
         self.display.clear_screen()?;
         self.display.display_header("Booking Summary")?;
         
-        let price = flight.get_price(&seat_class);
         println!("{}", "═══ Booking Details ═══".bright_cyan().bold());
         println!("Flight: {} ({})", flight.flight_number.bright_white().bold(), flight.airline.bright_white());
         println!("Route: {} → {}", flight.origin.bright_green(), flight.destination.bright_green());
@@ -218,27 +439,139 @@ impl MainMenu {
         println!("Time: {} → {}", 
             flight.departure_time.format("%H:%M").to_string().bright_blue(),
             flight.arrival_time.format("%H:%M").to_string().bright_blue());
-        println!("Passenger: {}", passenger.full_name().bright_white().bold());
         println!("Class: {:?}", seat_class);
-        println!("Price: ${:.2}", price.to_string().bright_green().bold());
+        println!();
+        if let [passenger] = passengers.as_slice() {
+            let breakdown = self.data_manager.price_breakdown(flight.id, &seat_class, &passenger.passenger_type)?;
+            println!("Passenger: {}", passenger.full_name().bright_white().bold());
+            println!();
+            self.display.display_price_breakdown(&breakdown)?;
+            if let Some(inbound) = return_flight {
+                let return_fare = self.data_manager.get_passenger_fare(inbound, &seat_class, &passenger.passenger_type);
+                let total = (breakdown.total + return_fare) * (1.0 - config::pricing::ROUND_TRIP_DISCOUNT);
+                println!();
+                println!("Return: {} {} → {} on {}",
+                    inbound.flight_number.bright_white().bold(),
+                    inbound.origin.bright_green(),
+                    inbound.destination.bright_green(),
+                    inbound.departure_time.format("%Y-%m-%d %H:%M").to_string().bright_blue());
+                println!("   {:<36} {:>8}  {}", "Return fare", "", self.display.money(return_fare).bright_white());
+                println!("   {:<36} {:>8}  {}", "Round trip total".bold(),
+                    format!("-{:.0}%", config::pricing::ROUND_TRIP_DISCOUNT * 100.0),
+                    self.display.money(total).bright_green().bold());
+            }
+        } else {
+            println!("{}", "Passengers:".bright_cyan().bold());
+            let mut total = 0.0;
+            for passenger in &passengers {
+                let fare = self.data_manager.get_passenger_fare(flight, &seat_class, &passenger.passenger_type);
+                total += fare;
+                println!("   {:<24} {:<8} {:>10}", passenger.full_name(), format!("{:?}", passenger.passenger_type), self.display.money(fare));
+            }
+            println!("   {:<33} {:>10}", "Total".bold(), self.display.money(total).bright_green().bold());
+        }
         println!();
 
         // Confirm booking
         if self.input.confirm_action("complete this booking")? {
-            match self.data_manager.create_booking(flight.id, passenger, seat_class) {
-                Ok(booking_id) => {
-                    if let Some(booking) = self.data_manager.get_booking_by_id(booking_id) {
-                        self.display.display_success_message("Booking completed successfully!")?;
-                        println!("\n{}", "═══ Your Ticket ═══".bright_green().bold());
-                        println!("Ticket Number: {}", booking.ticket_number.bright_white().bold());
-                        println!("Please save this ticket number for your records.");
-                        
-                        // Auto-assign seat
+            let booked = if let Some(return_id) = return_flight.map(|f| f.id) {
+                self.data_manager.create_round_trip(flight.id, return_id, passengers.remove(0), seat_class)
+                    .map(|trip_id| self.data_manager.get_trip_bookings(trip_id).iter().map(|b| b.id).collect())
+            } else if passengers.len() == 1 {
+                self.data_manager.create_booking(flight.id, passengers.remove(0), seat_class)
+                    .map(|booking_id| vec![booking_id])
+            } else {
+                self.data_manager.create_group_booking(flight.id, passengers, seat_class)
+                    .map(|group_id| self.data_manager.get_group_bookings(group_id).iter().map(|b| b.id).collect())
+            };
+
+            match booked {
+                Ok(booking_ids) => {
+                    self.display.display_success_message("Booking completed successfully!")?;
+                    println!("\n{}", "═══ Your Tickets ═══".bright_green().bold());
+                    for booking_id in &booking_ids {
+                        if let Some(booking) = self.data_manager.get_booking_by_id(*booking_id) {
+                            println!("Ticket Number: {}  {}", booking.ticket_number.bright_white().bold(), booking.passenger.full_name());
+                        }
+                    }
+                    println!("Please save your ticket numbers for your records.");
+
+                    // Checked baggage is weighed once per traveller and applies to every leg
+                    let mut weighed = Vec::new();
+                    for booking_id in &booking_ids {
+                        let Some(booking) = self.data_manager.get_booking_by_id(*booking_id) else { continue };
+                        let (ticket_number, passenger_id) = (booking.ticket_number.clone(), booking.passenger.id);
+                        let weight = match weighed.iter().find(|(id, _)| *id == passenger_id) {
+                            Some((_, weight)) => *weight,
+                            None => {
+                                let weight: u32 = self.input.get_number_input(&format!(
+                                    "Checked baggage weight for {} in kg ({} kg included, 0 for none):",
+                                    booking.passenger.full_name(),
+                                    booking.baggage_weight_allowance()
+                                ))?;
+                                weighed.push((passenger_id, weight));
+                                weight
+                            }
+                        };
+                        match self.data_manager.declare_checked_baggage(&ticket_number, weight) {
+                            Ok(fee) if fee > 0.0 => self.display.display_warning_message(&format!(
+                                "Overweight baggage fee of ${:.2} added to ticket {}", fee, ticket_number
+                            ))?,
+                            Ok(_) => {}
+                            Err(e) => self.display.display_error_message(&e)?,
+                        }
+                    }
+
+                    // Likewise one meal choice per traveller, served on every leg
+                    let mut meals = Vec::new();
+                    for booking_id in &booking_ids {
+                        let Some(booking) = self.data_manager.get_booking_by_id(*booking_id) else { continue };
+                        let (ticket_number, passenger_id) = (booking.ticket_number.clone(), booking.passenger.id);
+                        let meal = match meals.iter().find(|(id, _)| *id == passenger_id) {
+                            Some((_, meal)) => *meal,
+                            None => {
+                                let meal = self.input.get_meal_preference_input(&booking.passenger.full_name())?;
+                                meals.push((passenger_id, meal));
+                                meal
+                            }
+                        };
+                        if let Err(e) = self.data_manager.set_meal_preference(&ticket_number, meal) {
+                            self.display.display_error_message(&e)?;
+                        }
+                    }
+
+                    if self.input.get_yes_no_input("Choose your seats now?")? {
+                        let tickets: Vec<String> = booking_ids
+                            .iter()
+                            .filter_map(|id| self.data_manager.get_booking_by_id(*id))
+                            .filter(|b| b.passenger.passenger_type.occupies_seat())
+                            .map(|b| b.ticket_number.clone())
+                            .collect();
+                        for ticket_number in &tickets {
+                            self.choose_seat(ticket_number)?;
+                        }
+                    } else {
                         println!("\n{}", "ℹ️ Seat assignment will be completed at check-in.".bright_blue());
                     }
+
+                    let upgradable: Vec<String> = booking_ids
+                        .iter()
+                        .filter_map(|id| self.data_manager.get_booking_by_id(*id))
+                        .filter(|b| b.seat_class != SeatClass::FirstClass)
+                        .map(|b| b.ticket_number.clone())
+                        .collect();
+                    if !upgradable.is_empty()
+                        && self.input.get_yes_no_input("Silver and Gold members: be considered for a free upgrade if premium seats open up?")?
+                    {
+                        for ticket_number in &upgradable {
+                            if let Err(e) = self.data_manager.set_upgrade_opt_in(ticket_number, true) {
+                                self.display.display_error_message(&e)?;
+                            }
+                        }
+                    }
                 }
                 Err(e) => {
-                    self.display.display_error_message(&format!("Booking failed: {}", e))?;
+                    self.display.display_airport_error("Booking failed", &e)?;
                 }
             }
         } else {
@@ -258,10 +591,22 @@ impl MainMenu {
         println!("  {} - View booking details", "1".bright_green());
         println!("  {} - Cancel booking", "2".bright_red());
         println!("  {} - View all bookings", "3".bright_blue());
+        println!("  {} - Preview confirmation message", "4".bright_blue());
+        println!("  {} - Export my data", "5".bright_blue());
+        println!("  {} - Check waitlist position", "6".bright_blue());
+        println!("  {} - Choose seat", "7".bright_green());
+        println!("  {} - Print boarding pass", "8".bright_green());
+        println!("  {} - Check in", "9".bright_green());
+        println!("  {} - Board flight", "10".bright_green());
+        println!("  {} - Join the loyalty program", "11".bright_magenta());
+        println!("  {} - Check loyalty balance", "12".bright_magenta());
+        println!("  {} - Change seat class", "13".bright_green());
+        println!("  {} - Find my bookings", "14".bright_blue());
+        println!("  {} - Add checked bags", "15".bright_green());
         println!("  {} - Back to main menu", "0".bright_yellow());
         println!();
 
-        let choice = self.input.get_menu_choice("Select option:", 0, 3)?;
+        let choice = self.input.get_menu_choice("Select option:", 0, 15)?;
 
         match choice {
             0 => return Ok(()),
@@ -270,10 +615,11 @@ impl MainMenu {
                 let ticket_number = self.input.get_ticket_number_input()?;
                 if let Some(booking) = self.data_manager.get_booking_by_ticket(&ticket_number) {
                     if let Some(flight) = self.data_manager.get_flight_by_id(booking.flight_id) {
+                        let party = booking.group_id.map(|id| self.data_manager.get_group_bookings(id)).unwrap_or_default();
                         self.display.clear_screen()?;
-                        self.display.display_booking_details(booking)?;
+                        self.display.display_booking_details(booking, &party, self.data_manager.get_trip_companion(booking))?;
                         self.display.display_flight_details(flight, 
-                            self.data_manager.get_aircraft_for_flight(flight.id))?;
+                            self.data_manager.get_aircraft_for_flight(flight.id), None)?;
                     }
                 } else {
                     self.display.display_error_message("Booking not found!")?;
@@ -283,17 +629,60 @@ impl MainMenu {
                 // Cancel booking
                 let ticket_number = self.input.get_ticket_number_input()?;
                 if let Some(booking) = self.data_manager.get_booking_by_ticket(&ticket_number) {
-                    self.display.display_booking_details(booking)?;
+                    let party = booking.group_id.map(|id| self.data_manager.get_group_bookings(id)).unwrap_or_default();
+                    self.display.display_booking_details(booking, &party, self.data_manager.get_trip_companion(booking))?;
                     
                     if booking.can_be_modified() {
-                        if self.input.confirm_action("cancel this booking")? {
-                            match self.data_manager.cancel_booking(&ticket_number) {
-                                Ok(()) => {
-                                    self.display.display_success_message("Booking cancelled successfully!")?;
+                        let group_id = match booking.group_id {
+                            Some(group_id) if party.len() > 1
+                                && self.input.get_yes_no_input("Cancel the whole group reservation?")? => Some(group_id),
+                            _ => None,
+                        };
+                        if let Some(group_id) = group_id {
+                            match self.data_manager.cancel_group_booking(group_id) {
+                                Ok(cancelled) => {
+                                    let refund: f64 = cancelled.iter().map(|c| c.refund).sum();
+                                    self.display.display_success_message(&format!(
+                                        "Group reservation cancelled - {} bookings released. Refund: ${:.2}",
+                                        cancelled.len(), refund
+                                    ))?;
                                 }
                                 Err(e) => {
-                                    self.display.display_error_message(&format!("Cancellation failed: {}", e))?;
+                                    self.display.display_airport_error("Cancellation failed", &e)?;
+                                }
+                            }
+                        } else if self.input.confirm_action("cancel this booking")? {
+                            match self.data_manager.cancel_booking(&ticket_number) {
+                                Ok(cancellation) => {
+                                    self.display.display_success_message(&format!(
+                                        "Booking cancelled successfully! Refund: ${:.2}",
+                                        cancellation.refund
+                                    ))?;
+                                    if cancellation.promoted.is_some() {
+                                        self.display.display_info_message("The freed seat went to a passenger on the waitlist.")?;
+                                    }
+                                    if let Some(return_ticket) = cancellation.companion_ticket {
+                                        if self.input.get_yes_no_input(&format!("Also cancel the other leg of this round trip ({})?", return_ticket))? {
+                                            match self.data_manager.cancel_booking(&return_ticket) {
+                                                Ok(other_leg) => self.display.display_success_message(&format!(
+                                                    "Booking {} cancelled too. Refund: ${:.2}",
+                                                    return_ticket, other_leg.refund
+                                                ))?,
+                                                Err(e) => self.display.display_airport_error("Cancellation failed", &e)?,
+                                            }
+                                        }
+                                    }
                                 }
+                                Err(e @ AirportError::FlightAlreadyDeparted { .. }) => {
+                                    self.display.display_airport_error("Cancellation failed", &e)?;
+                                    if self.input.get_yes_no_input("Mark this booking as completed instead?")? {
+                                        match self.data_manager.complete_booking(&ticket_number) {
+                                            Ok(()) => self.display.display_success_message("Booking marked as completed.")?,
+                                            Err(e) => self.display.display_error_message(&e)?,
+                                        }
+                                    }
+                                }
+                                Err(e) => self.display.display_airport_error("Cancellation failed", &e)?,
                             }
                         }
                     } else {
@@ -308,7 +697,173 @@ impl MainMenu {
                 let all_bookings: Vec<&_> = self.data_manager.database.bookings.iter().collect();
                 self.display.clear_screen()?;
                 self.display.display_header("All Bookings")?;
-                self.display.display_bookings_table(&all_bookings)?;
+                self.display.display_bookings_table_paged(&all_bookings, config::TABLE_PAGE_SIZE)?;
+            }
+            4 => {
+                // Preview the confirmation email and optionally save it
+                let ticket_number = self.input.get_ticket_number_input()?;
+                match self.data_manager.confirmation_message(&ticket_number) {
+                    Ok(message) => {
+                        self.display.clear_screen()?;
+                        self.display.display_header(&format!("Confirmation - {}", ticket_number))?;
+                        println!("{}", message);
+
+                        if self.input.get_yes_no_input("Save this message to a file?")? {
+                            match self.data_manager.save_confirmation_message(&ticket_number).await {
+                                Ok(path) => self.display.display_success_message(&format!("Saved to {}", path))?,
+                                Err(e) => self.display.display_error_message(&format!("Failed to save message: {}", e))?,
+                            }
+                        }
+                    }
+                    Err(e) => {
+                        self.display.display_error_message(&e)?;
+                    }
+                }
+            }
+            5 => {
+                // Export everything stored about a passenger
+                let email = self.input.get_email_input("Email address used for booking:")?;
+                match self.data_manager.export_passenger_data(&email) {
+                    Ok(json) => {
+                        // Keep the email out of the file name
+                        let file_name = format!("passenger_data_{}.json", chrono::Utc::now().format("%Y%m%d_%H%M%S"));
+                        match self.data_manager.persistence.save_export(&file_name, &json).await {
+                            Ok(path) => self.display.display_success_message(&format!("Your data was exported to {}", path))?,
+                            Err(e) => self.display.display_error_message(&format!("Failed to export data: {}", e))?,
+                        }
+                    }
+                    Err(e) => {
+                        self.display.display_error_message(&e)?;
+                    }
+                }
+            }
+            6 => {
+                // Where a passenger stands on a flight's waitlist
+                let flight_number = self.input.get_flight_number_input()?;
+                let email = self.input.get_email_input("Email address used for the waitlist:")?;
+                match self.data_manager.get_flight_by_number(&flight_number) {
+                    Some(flight) => {
+                        let positions = self.data_manager.waitlist_positions(flight.id, &email);
+                        if positions.is_empty() {
+                            self.display.display_info_message(&format!("No waitlist entries for {} on {}.", email, flight.flight_number))?;
+                        }
+                        for (entry, position) in positions {
+                            println!("{}  {:?} waitlist position {}",
+                                entry.passenger.full_name().bright_white().bold(),
+                                entry.seat_class,
+                                position.to_string().bright_yellow().bold());
+                        }
+                    }
+                    None => self.display.display_error_message("Flight not found!")?,
+                }
+            }
+            7 => {
+                // Pick a specific seat from the seat map
+                let ticket_number = self.input.get_ticket_number_input()?;
+                self.choose_seat(&ticket_number)?;
+            }
+            8 => {
+                // Boarding pass, available once checked in
+                let ticket_number = self.input.get_ticket_number_input()?;
+                match self.data_manager.boarding_pass(&ticket_number) {
+                    Ok(pass) => {
+                        println!("\n{}", pass);
+
+                        if self.input.get_yes_no_input("Save the boarding pass to a file?")? {
+                            let path = std::path::Path::new(&self.data_manager.persistence.export_dir())
+                                .join(format!("boarding_pass_{}.txt", ticket_number));
+                            match self.data_manager.export_boarding_pass(&ticket_number, &path) {
+                                Ok(()) => self.display.display_success_message(&format!("Saved to {}", path.display()))?,
+                                Err(e) => self.display.display_error_message(&format!("Failed to save boarding pass: {}", e))?,
+                            }
+                        }
+                    }
+                    Err(e) => self.display.display_error_message(&e)?,
+                }
+            }
+            9 => {
+                // Online check-in
+                let ticket_number = self.input.get_ticket_number_input()?;
+                match self.data_manager.check_in_booking(&ticket_number) {
+                    Ok(()) => self.display.display_success_message("Checked in! You can now print your boarding pass.")?,
+                    Err(e) => self.display.display_error_message(&format!("Check-in failed: {}", e))?,
+                }
+            }
+            10 => {
+                // Board at the gate
+                let ticket_number = self.input.get_ticket_number_input()?;
+                match self.data_manager.board_booking(&ticket_number) {
+                    Ok(()) => self.display.display_success_message("Boarded - have a pleasant flight!")?,
+                    Err(e) => self.display.display_error_message(&format!("Boarding failed: {}", e))?,
+                }
+            }
+            11 => {
+                // Frequent-flyer enrollment
+                let name = self.input.get_name_input("Full Name:")?;
+                let email = self.input.get_email_input("Email:")?;
+                match self.data_manager.enroll_loyalty(&name, &email) {
+                    Ok(member_id) => self.display.display_success_message(&format!(
+                        "Welcome to the loyalty program! Your member number is {}", member_id
+                    ))?,
+                    Err(e) => self.display.display_error_message(&format!("Enrollment failed: {}", e))?,
+                }
+            }
+            12 => {
+                // Miles balance and tier
+                let email = self.input.get_email_input("Email:")?;
+                match self.data_manager.get_loyalty_account(&email) {
+                    Some(account) => {
+                        println!("\n{}", "═══ Loyalty Account ═══".bright_cyan().bold());
+                        println!("Member:  {} ({})", account.name.bright_white().bold(), account.member_id);
+                        println!("Miles:   {}", account.miles.to_string().bright_green());
+                        println!("Tier:    {}", account.tier.get_display());
+                        let next = match account.tier {
+                            LoyaltyTier::Member => Some(("Silver", config::loyalty::SILVER_MILES)),
+                            LoyaltyTier::Silver => Some(("Gold", config::loyalty::GOLD_MILES)),
+                            LoyaltyTier::Gold => None,
+                        };
+                        if let Some((tier, threshold)) = next {
+                            println!("Next:    {} more miles to {}", threshold.saturating_sub(account.miles), tier);
+                        }
+                    }
+                    None => self.display.display_error_message("No loyalty account for that email - enroll to start earning miles.")?,
+                }
+            }
+            13 => {
+                // Upgrade or downgrade to another cabin
+                let ticket_number = self.input.get_ticket_number_input()?;
+                let new_class = self.input.get_seat_class_input()?;
+                match self.data_manager.change_seat_class(&ticket_number, new_class.clone()) {
+                    Ok(charge) if charge >= 0.0 => self.display.display_success_message(&format!(
+                        "Moved to {:?} - ${:.2} charged. Choose a new seat from the seat map.", new_class, charge
+                    ))?,
+                    Ok(charge) => self.display.display_success_message(&format!(
+                        "Moved to {:?} - ${:.2} credited. Choose a new seat from the seat map.", new_class, -charge
+                    ))?,
+                    Err(e) => self.display.display_error_message(&format!("Class change failed: {}", e))?,
+                }
+            }
+            14 => {
+                // Look up bookings without the ticket number
+                let query = self.input.get_string_input("Passenger name or email:")?;
+                let bookings = self.data_manager.find_bookings_by_passenger(&query);
+                if bookings.is_empty() {
+                    self.display.display_info_message("No bookings found for that name or email.")?;
+                } else {
+                    self.display.display_header(&format!("Bookings matching \"{}\"", query.trim()))?;
+                    self.display.display_bookings_table(&bookings)?;
+                }
+            }
+            15 => {
+                // Extra checked bags, limited by the room left in the hold
+                let ticket_number = self.input.get_ticket_number_input()?;
+                let count = self.input.get_number_input_with_range::<u32>("Bags to add (1-5):", 1, 5)?;
+                match self.data_manager.add_checked_bags(&ticket_number, count) {
+                    Ok(room) => self.display.display_success_message(&format!(
+                        "{} bag(s) added to {} - {} kg of hold space left on the flight", count, ticket_number, room
+                    ))?,
+                    Err(e) => self.display.display_error_message(&format!("Could not add bags: {}", e))?,
+                }
             }
             _ => {}
         }
@@ -317,6 +872,37 @@ impl MainMenu {
         Ok(())
     }
 
+    /// Show the seat map for a booking's flight and assign the seat the passenger picks
+    fn choose_seat(&mut self, ticket_number: &str) -> Result<(), Box<dyn Error>> {
+        let Some(booking) = self.data_manager.get_booking_by_ticket(ticket_number) else {
+            self.display.display_error_message("Booking not found!")?;
+            return Ok(());
+        };
+        let Some(aircraft) = self.data_manager.get_aircraft_for_flight(booking.flight_id) else {
+            self.display.display_error_message("Aircraft not found for this flight!")?;
+            return Ok(());
+        };
+
+        let seat_map = self.data_manager.get_seat_map(booking.flight_id);
+        println!("\n{}", format!("Seat selection for {} ({:?})", booking.passenger.full_name(), booking.seat_class).bright_cyan().bold());
+        self.display.display_seat_map(&seat_map, &aircraft.seat_configuration)?;
+
+        loop {
+            let seat_number = self.input.get_string_input("Seat number (blank to skip):")?;
+            if seat_number.trim().is_empty() {
+                self.display.display_info_message("Seat assignment will be completed at check-in.")?;
+                return Ok(());
+            }
+            match self.data_manager.assign_specific_seat(ticket_number, &seat_number) {
+                Ok(()) => {
+                    self.display.display_success_message(&format!("Seat {} confirmed.", seat_number.trim().to_uppercase()))?;
+                    return Ok(());
+                }
+                Err(e) => self.display.display_error_message(&e)?,
+            }
+        }
+    }
+
     // 4. Flight Info
     async fn flight_info(&mut self) -> Result<(), Box<dyn Error>> {
         self.display.clear_screen()?;
@@ -327,10 +913,13 @@ impl MainMenu {
         println!("  {} - View all flights", "2".bright_blue());
         println!("  {} - View departures from airport", "3".bright_yellow());
         println!("  {} - View arrivals to airport", "4".bright_yellow());
+        println!("  {} - Find airports near a location", "5".bright_magenta());
+        println!("  {} - View passenger manifest (admin)", "6".bright_red());
+        println!("  {} - Find airports by service", "7".bright_magenta());
         println!("  {} - Back to main menu", "0".bright_red());
         println!();
 
-        let choice = self.input.get_menu_choice("Select option:", 0, 4)?;
+        let choice = self.input.get_menu_choice("Select option:", 0, 7)?;
 
         match choice {
             0 => return Ok(()),
@@ -339,8 +928,9 @@ impl MainMenu {
                 let flight_number = self.input.get_flight_number_input()?;
                 if let Some(flight) = self.data_manager.get_flight_by_number(&flight_number) {
                     let aircraft = self.data_manager.get_aircraft_for_flight(flight.id);
+                    let distance = self.data_manager.route_distance_km(&flight.origin, &flight.destination);
                     self.display.clear_screen()?;
-                    self.display.display_flight_details(flight, aircraft)?;
+                    self.display.display_flight_details(flight, aircraft, distance)?;
                 } else {
                     self.display.display_error_message("Flight not found!")?;
                 }
@@ -350,7 +940,7 @@ impl MainMenu {
                 let all_flights: Vec<&_> = self.data_manager.database.flights.iter().collect();
                 self.display.clear_screen()?;
                 self.display.display_header("All Flights")?;
-                self.display.display_flights_table(&all_flights)?;
+                self.display.display_flights_table_paged(&all_flights, &self.data_manager.database.aircraft, &self.data_manager.database.airports, config::TABLE_PAGE_SIZE)?;
             }
             3 => {
                 // Departures from airport
@@ -358,7 +948,7 @@ impl MainMenu {
                 let departures = self.data_manager.get_departures_from_airport(&airport_code);
                 self.display.clear_screen()?;
                 self.display.display_header(&format!("Departures from {}", airport_code))?;
-                self.display.display_flights_table(&departures)?;
+                self.display.display_flights_table(&departures, &self.data_manager.database.aircraft, &self.data_manager.database.airports)?;
             }
             4 => {
                 // Arrivals to airport
@@ -366,7 +956,48 @@ impl MainMenu {
                 let arrivals = self.data_manager.get_arrivals_to_airport(&airport_code);
                 self.display.clear_screen()?;
                 self.display.display_header(&format!("Arrivals to {}", airport_code))?;
-                self.display.display_flights_table(&arrivals)?;
+                self.display.display_flights_table(&arrivals, &self.data_manager.database.aircraft, &self.data_manager.database.airports)?;
+            }
+            5 => {
+                // Airports near a location
+                let lat = self.input.get_number_input_with_range("Latitude (-90 to 90):", -90.0, 90.0)?;
+                let lon = self.input.get_number_input_with_range("Longitude (-180 to 180):", -180.0, 180.0)?;
+                let radius = self.input.get_number_input_with_range("Search radius (km):", 1.0, 20_000.0)?;
+                let nearby = self.data_manager.airports_within_radius(lat, lon, radius);
+                self.display.clear_screen()?;
+                self.display.display_header("Nearby Airports")?;
+                match self.data_manager.find_nearest_airport(lat, lon) {
+                    Some((airport, distance)) if nearby.is_empty() => {
+                        self.display.display_info_message(&format!(
+                            "No airports within {:.0} km - the nearest is {} ({}), {:.0} km away",
+                            radius, airport.code, airport.name, distance
+                        ))?;
+                    }
+                    _ => self.display.display_nearby_airports(&nearby)?,
+                }
+            }
+            6 => {
+                // Gate manifest, for staff only
+                if !self.data_manager.is_admin_authenticated() {
+                    self.display.display_error_message("Log in through the Admin Panel to view manifests")?;
+                } else {
+                    let flight_number = self.input.get_flight_number_input()?;
+                    if self.data_manager.get_flight_by_number(&flight_number).is_some() {
+                        let manifest = self.data_manager.get_manifest(&flight_number);
+                        self.display.clear_screen()?;
+                        self.display.display_manifest(&flight_number, &manifest)?;
+                    } else {
+                        self.display.display_error_message("Flight not found!")?;
+                    }
+                }
+            }
+            7 => {
+                // Services and terminal amenities, e.g. "Lounges" or "Car Rental"
+                let service = self.input.get_string_input("Service (e.g., Lounges):")?;
+                let airports = self.data_manager.airports_with_service(&service);
+                self.display.clear_screen()?;
+                self.display.display_header(&format!("Airports with \"{}\"", service.trim()))?;
+                self.display.display_airports_table(&airports)?;
             }
             _ => {}
         }
@@ -384,10 +1015,11 @@ impl MainMenu {
         println!("  {} - View all aircraft", "1".bright_green());
         println!("  {} - View specific aircraft details", "2".bright_blue());
         println!("  {} - View available aircraft", "3".bright_yellow());
+        println!("  {} - Maintenance schedule", "4".bright_magenta());
         println!("  {} - Back to main menu", "0".bright_red());
         println!();
 
-        let choice = self.input.get_menu_choice("Select option:", 0, 3)?;
+        let choice = self.input.get_menu_choice("Select option:", 0, 4)?;
 
         match choice {
             0 => return Ok(()),
@@ -415,6 +1047,14 @@ impl MainMenu {
                 self.display.display_header("Available Aircraft")?;
                 self.display.display_aircraft_table(&available_aircraft)?;
             }
+            4 => {
+                // Aircraft nearing their next maintenance check
+                let within: f64 = self.input.get_number_input("Show aircraft due within how many flight hours?")?;
+                let due = self.data_manager.maintenance_due(within);
+                self.display.clear_screen()?;
+                self.display.display_header("Maintenance Schedule")?;
+                self.display.display_maintenance_schedule(&due)?;
+            }
             _ => {}
         }
 
@@ -448,7 +1088,7 @@ impl MainMenu {
             self.display.display_header(&format!("Admin Panel - {}", self.data_manager.admin_panel.current_admin_name()))?;
             
             self.input.display_admin_menu()?;
-            let choice = self.input.get_menu_choice("Select option:", 0, 6)?;
+            let choice = self.input.get_menu_choice("Select option:", 0, 18)?;
 
             match choice {
                 0 => {
@@ -463,14 +1103,19 @@ impl MainMenu {
                     let metrics = self.data_manager.get_system_metrics();
                     self.display.display_system_metrics(metrics)?;
                     
-                    let (total_flights, on_time, delayed, cancelled) = self.data_manager.get_flight_statistics();
-                    let (total_bookings, confirmed, cancelled_bookings) = self.data_manager.get_booking_statistics();
+                    let (total_flights, on_time, _, _) = self.data_manager.get_flight_statistics();
+                    let (total_bookings, confirmed, _) = self.data_manager.get_booking_statistics();
                     
                     println!("\n{}", "📈 Additional Statistics:".bright_cyan().bold());
                     println!("Flight Performance: {}/{} on time ({:.1}%)", 
                         on_time, total_flights, (on_time as f64 / total_flights as f64) * 100.0);
                     println!("Booking Success Rate: {}/{} confirmed ({:.1}%)", 
                         confirmed, total_bookings, (confirmed as f64 / total_bookings as f64) * 100.0);
+
+                    if self.input.get_yes_no_input("Show revenue by route?")? {
+                        let routes = self.data_manager.revenue_by_route();
+                        self.display.display_revenue_by_route(&routes)?;
+                    }
                 }
                 2 => {
                     // Set flight delay
@@ -478,46 +1123,111 @@ impl MainMenu {
                     let delay_minutes = self.input.get_delay_minutes_input()?;
                     
                     match self.data_manager.set_flight_delay(&flight_number, delay_minutes) {
-                        Ok(()) => {
-                            self.display.display_success_message(&format!("Flight {} delay updated to {} minutes", flight_number, delay_minutes))?;
-                        }
+                        Ok(()) => match self.data_manager.get_flight_by_number(&flight_number).map(|f| &f.status) {
+                            Some(FlightStatus::Cancelled) => self.display.display_warning_message(&format!(
+                                "Flight {} was cancelled - its total delay would exceed {} minutes. Its bookings have not been refunded.",
+                                flight_number,
+                                config::delays::MAX_DELAY_MINUTES
+                            ))?,
+                            Some(FlightStatus::Delayed(total)) if *total != delay_minutes => self.display.display_success_message(&format!(
+                                "Flight {} delayed a further {} minutes ({} in total)",
+                                flight_number, delay_minutes, total
+                            ))?,
+                            Some(FlightStatus::OnTime) if delay_minutes < 0 => self.display.display_success_message(&format!(
+                                "Flight {} brought forward {} minutes",
+                                flight_number, -delay_minutes
+                            ))?,
+                            _ => self.display.display_success_message(&format!("Flight {} delay updated to {} minutes", flight_number, delay_minutes))?,
+                        },
                         Err(e) => {
-                            self.display.display_error_message(&format!("Failed to set delay: {}", e))?;
+                            self.display.display_airport_error("Failed to set delay", &e)?;
                         }
                     }
                 }
                 3 => {
-                    // Set dynamic pricing
+                    // Set dynamic pricing: demand pricing, or a manual multiplier that replaces it
                     let flight_number = self.input.get_flight_number_input()?;
-                    let multiplier = self.input.get_pricing_multiplier_input()?;
-                    
-                    match self.data_manager.set_dynamic_pricing(&flight_number, multiplier) {
+                    let enabled = self.input.get_yes_no_input("Raise fares automatically as the flight fills (demand pricing)?")?;
+                    match self.data_manager.set_demand_pricing(&flight_number, enabled) {
+                        Ok(()) if enabled => self.display.display_success_message(&format!(
+                            "Demand pricing enabled for flight {}", flight_number
+                        ))?,
                         Ok(()) => {
-                            self.display.display_success_message(&format!("Flight {} pricing multiplier set to {:.2}", flight_number, multiplier))?;
-                        }
-                        Err(e) => {
-                            self.display.display_error_message(&format!("Failed to set pricing: {}", e))?;
+                            let multiplier = self.input.get_pricing_multiplier_input()?;
+                            match self.data_manager.set_dynamic_pricing(&flight_number, multiplier) {
+                                Ok(()) => self.display.display_success_message(&format!(
+                                    "Flight {} pricing multiplier set to {:.2}", flight_number, multiplier
+                                ))?,
+                                Err(e) => self.display.display_error_message(&format!("Failed to set pricing: {}", e))?,
+                            }
+
+                            if self.input.get_yes_no_input("Override this flight's base fares?")? {
+                                let (economy, business, first_class) = self.input.get_base_prices_input()?;
+                                match self.data_manager.set_base_prices(&flight_number, economy, business, first_class) {
+                                    Ok(()) => self.display.display_success_message(&format!("Base fares updated for flight {}", flight_number))?,
+                                    Err(e) => self.display.display_error_message(&format!("Failed to set base fares: {}", e))?,
+                                }
+                            }
                         }
+                        Err(e) => self.display.display_error_message(&format!("Failed to set demand pricing: {}", e))?,
                     }
                 }
                 4 => {
                     // View admin log
                     self.display.clear_screen()?;
                     self.display.display_header("Admin Action Log")?;
-                    let recent_actions = self.data_manager.admin_panel.get_recent_actions(20);
-                    self.display.display_admin_log(&recent_actions, 20)?;
+                    let limit = self.input.get_menu_choice(
+                        &format!("How many recent entries to show (1-{})?", crate::config::MAX_ADMIN_LOG_ENTRIES),
+                        1,
+                        crate::config::MAX_ADMIN_LOG_ENTRIES as u32,
+                    )? as usize;
+                    let panel = &self.data_manager.admin_panel;
+                    let actions = if self.input.get_yes_no_input("Filter the log?")? {
+                        let action_type = self.input.get_string_input("Action type, e.g. SET_PRICING (blank for any):")?;
+                        let username = self.input.get_string_input("Admin username (blank for any):")?;
+                        let hours: u32 = self.input.get_number_input("Only the last N hours (0 for all time):")?;
+
+                        let admin_id = panel.credentials
+                            .iter()
+                            .find(|c| c.user.username.eq_ignore_ascii_case(&username))
+                            .map(|c| c.user.id);
+                        if !username.is_empty() && admin_id.is_none() {
+                            self.display.display_error_message(&format!("No admin named {}", username))?;
+                            Vec::new()
+                        } else {
+                            let since = (hours > 0).then(|| chrono::Utc::now() - chrono::Duration::hours(hours as i64));
+                            panel.filter_actions((!action_type.is_empty()).then_some(action_type.as_str()), admin_id, since)
+                        }
+                    } else {
+                        panel.get_recent_actions(limit)
+                    };
+                    self.display.display_admin_log(&actions, limit)?;
                 }
                 5 => {
-                    // Aircraft management placeholder
-                    self.display.display_info_message("Aircraft management features coming soon!")?;
+                    // Aircraft management
+                    self.aircraft_management()?;
                 }
                 6 => {
                     // Create backup
                     self.input.display_loading_message("Creating backup")?;
                     match self.data_manager.create_backup().await {
-                        Ok(backup_path) => {
+                        Ok((backup_path, size)) => {
                             self.input.clear_loading_message()?;
-                            self.display.display_success_message(&format!("Backup created: {}", backup_path))?;
+                            self.display.display_success_message(&format!(
+                                "Backup created: {} ({:.1} KB compressed)",
+                                backup_path,
+                                size as f64 / 1024.0
+                            ))?;
+
+                            let keep = crate::config::BACKUPS_TO_KEEP;
+                            if self.data_manager.list_backups().len() > keep
+                                && self.input.get_yes_no_input(&format!("Delete older backups, keeping the newest {}?", keep))?
+                            {
+                                match self.data_manager.prune_backups(keep) {
+                                    Ok(removed) => self.display.display_success_message(&format!("Removed {} old backup(s)", removed))?,
+                                    Err(e) => self.display.display_error_message(&format!("Failed to prune backups: {}", e))?,
+                                }
+                            }
                         }
                         Err(e) => {
                             self.input.clear_loading_message()?;
@@ -525,6 +1235,195 @@ impl MainMenu {
                         }
                     }
                 }
+                7 => {
+                    // Run self-check
+                    self.display.clear_screen()?;
+                    self.display.display_header("System Self-Check")?;
+                    let report = self.data_manager.self_check().await;
+                    self.display.display_health_report(&report)?;
+                }
+                8 => {
+                    // Special services report and fulfillment
+                    let flight_number = self.input.get_flight_number_input()?;
+                    loop {
+                        let report = match self.data_manager.special_services_report(&flight_number) {
+                            Ok(report) => report,
+                            Err(e) => {
+                                self.display.display_error_message(&e)?;
+                                break;
+                            }
+                        };
+                        self.display.display_special_services_report(&report)?;
+
+                        if report.items.iter().all(|i| i.fulfilled) {
+                            break;
+                        }
+                        let selection = self.input.get_menu_choice(
+                            "Mark item as fulfilled (0 to finish):", 0, report.items.len() as u32)?;
+                        if selection == 0 {
+                            break;
+                        }
+                        let item = &report.items[selection as usize - 1];
+                        if let Err(e) = self.data_manager.mark_requirement_fulfilled(&item.ticket_number, &item.requirement) {
+                            self.display.display_error_message(&e)?;
+                        }
+                    }
+                }
+                9 => {
+                    // Catering requirements and meal service override
+                    let flight_number = self.input.get_flight_number_input()?;
+                    match self.data_manager.catering_requirements(&flight_number) {
+                        Ok(catering) => {
+                            self.display.display_catering_requirements(&catering)?;
+
+                            if self.input.get_yes_no_input("Change meal service for this flight?")? {
+                                println!("  {} - Always serve a meal", "1".bright_green());
+                                println!("  {} - No meal service", "2".bright_red());
+                                println!("  {} - Automatic (based on flight duration)", "3".bright_blue());
+                                let meal_service = match self.input.get_menu_choice("Select option:", 1, 3)? {
+                                    1 => Some(true),
+                                    2 => Some(false),
+                                    _ => None,
+                                };
+                                match self.data_manager.set_meal_service(&flight_number, meal_service) {
+                                    Ok(()) => self.display.display_success_message("Meal service updated")?,
+                                    Err(e) => self.display.display_error_message(&format!("Failed to update meal service: {}", e))?,
+                                }
+                            }
+                        }
+                        Err(e) => {
+                            self.display.display_error_message(&e)?;
+                        }
+                    }
+                }
+                10 => {
+                    // Cancel a flight: dry run first, then a typed confirmation
+                    let flight_number = self.input.get_flight_number_input()?;
+                    let reason = self.input.get_string_input("Reason for cancellation (sent to passengers):")?;
+                    match self.data_manager.preview_cancel_flight(&flight_number, &reason) {
+                        Ok(preview) => {
+                            self.display.clear_screen()?;
+                            self.display.display_flight_cancellation(&preview, true)?;
+
+                            if !preview.already_cancelled
+                                && self.input.confirm_destructive(&format!("cancel flight {}", flight_number), &flight_number)?
+                            {
+                                if preview.bookings_cancelled > 0 {
+                                    self.rebook_affected_passengers(&flight_number)?;
+                                }
+                                match self.data_manager.cancel_flight(&flight_number, reason) {
+                                    Ok(summary) => self.display.display_flight_cancellation(&summary, false)?,
+                                    Err(e) => self.display.display_error_message(&format!("Cancellation failed: {}", e))?,
+                                }
+                            }
+                        }
+                        Err(e) => {
+                            self.display.display_error_message(&e)?;
+                        }
+                    }
+                }
+                11 => {
+                    // Erase a passenger's personal data; typing the email confirms
+                    let email = self.input.get_email_input("Passenger email:")?;
+                    if self.input.confirm_destructive("anonymize all data for this passenger", &email)? {
+                        match self.data_manager.anonymize_passenger(&email) {
+                            Ok(count) => self.display.display_success_message(&format!(
+                                "Personal data removed from {} bookings", count
+                            ))?,
+                            Err(e) => self.display.display_error_message(&e)?,
+                        }
+                    }
+                }
+                12 => {
+                    // Complimentary upgrades for loyalty members into open premium seats
+                    let flight_number = self.input.get_flight_number_input()?;
+                    match self.data_manager.process_upgrades(&flight_number) {
+                        Ok(upgrades) => self.display.display_upgrades(&flight_number, &upgrades)?,
+                        Err(e) => self.display.display_error_message(&format!("Upgrades failed: {}", e))?,
+                    }
+                }
+                13 => {
+                    // Spreadsheet-friendly reports
+                    match self.data_manager.export_reports_csv().await {
+                        Ok(paths) => {
+                            for path in paths {
+                                self.display.display_success_message(&format!("Exported {}", path))?;
+                            }
+                        }
+                        Err(e) => self.display.display_error_message(&format!("Export failed: {}", e))?,
+                    }
+                }
+                14 => {
+                    // New admin account (SuperAdmin only)
+                    let username = self.input.get_string_input("Username:")?;
+                    let full_name = self.input.get_name_input("Full Name:")?;
+                    let email = self.input.get_email_input("Email:")?;
+                    println!("  {} - Super Admin", "1".bright_red());
+                    println!("  {} - Flight Manager", "2".bright_yellow());
+                    println!("  {} - Aircraft Manager", "3".bright_blue());
+                    println!("  {} - Finance Manager", "4".bright_green());
+                    println!("  {} - Viewer", "5".bright_white());
+                    let level = match self.input.get_menu_choice("Access level:", 1, 5)? {
+                        1 => AdminLevel::SuperAdmin,
+                        2 => AdminLevel::FlightManager,
+                        3 => AdminLevel::AircraftManager,
+                        4 => AdminLevel::FinanceManager,
+                        _ => AdminLevel::Viewer,
+                    };
+                    let password = self.input.get_password_input("Password:")?;
+
+                    match self.data_manager.create_admin(&username, &full_name, &email, level, &password) {
+                        Ok(admin) => self.display.display_success_message(&format!("Admin account {} created", admin.username))?,
+                        Err(e) => self.display.display_error_message(&format!("Failed to create account: {}", e))?,
+                    }
+                }
+                15 => {
+                    // Roll back to an earlier backup
+                    let backups = self.data_manager.list_backups();
+                    if backups.is_empty() {
+                        self.display.display_info_message("No backups found")?;
+                    } else {
+                        for (i, backup) in backups.iter().enumerate() {
+                            println!("  {} - {}", (i + 1).to_string().bright_cyan(), backup);
+                        }
+                        let pick = self.input.get_menu_choice("Backup to restore (0 to cancel):", 0, backups.len() as u32)? as usize;
+                        if pick > 0 {
+                            let timestamp = &backups[pick - 1];
+                            if self.input.confirm_destructive(&format!("replace the current data with backup {}", timestamp), timestamp)? {
+                                match self.data_manager.restore_backup(timestamp).await {
+                                    Ok(()) => self.display.display_success_message(&format!("Restored backup {}", timestamp))?,
+                                    Err(e) => self.display.display_error_message(&format!("Restore failed: {}", e))?,
+                                }
+                            }
+                        }
+                    }
+                }
+                16 => {
+                    // Preview integrity repairs, then apply them only on confirmation
+                    match self.data_manager.repair_data_integrity(true).await {
+                        Ok(actions) if actions.is_empty() => self.display.display_success_message("No repairs needed")?,
+                        Ok(actions) => {
+                            for action in &actions {
+                                println!("  • {}", action);
+                            }
+                            if self.input.confirm_destructive(&format!("apply these {} repairs to the data files", actions.len()), "REPAIR")? {
+                                match self.data_manager.repair_data_integrity(false).await {
+                                    Ok(applied) => self.display.display_success_message(&format!("Applied {} repairs", applied.len()))?,
+                                    Err(e) => self.display.display_error_message(&format!("Repair failed: {}", e))?,
+                                }
+                            }
+                        }
+                        Err(e) => self.display.display_error_message(&format!("Repair failed: {}", e))?,
+                    }
+                }
+                17 => {
+                    // One-file dump of everything, for bug reports
+                    match self.data_manager.export_snapshot().await {
+                        Ok(path) => self.display.display_success_message(&format!("Snapshot written to {}", path))?,
+                        Err(e) => self.display.display_error_message(&format!("Snapshot failed: {}", e))?,
+                    }
+                }
+                18 => self.generate_weekly_schedule()?,
                 _ => {
                     self.display.display_error_message("Invalid option!")?;
                 }
@@ -537,4 +1436,214 @@ impl MainMenu {
 
         Ok(())
     }
+
+    /// Offered before a flight is cancelled: move its passengers to a later flight on the
+    /// same route. Anyone who can't be moved is refunded by the cancellation as usual.
+    fn rebook_affected_passengers(&mut self, flight_number: &str) -> Result<(), Box<dyn Error>> {
+        let options = self.data_manager.rebooking_options(flight_number);
+        if options.is_empty() {
+            return self.display.display_info_message("No later flights on this route to rebook passengers onto.");
+        }
+        if !self.input.get_yes_no_input("Rebook affected passengers?")? {
+            return Ok(());
+        }
+        self.display.display_flights_table(&options, &self.data_manager.database.aircraft, &self.data_manager.database.airports)?;
+        let new_flight_number = self.input.get_flight_number_input()?.trim().to_uppercase();
+
+        let Some(flight_id) = self.data_manager.get_flight_by_number(flight_number).map(|f| f.id) else {
+            return Ok(());
+        };
+        let tickets: Vec<String> = self.data_manager.database.bookings
+            .iter()
+            .filter(|b| b.flight_id == flight_id && b.can_be_modified())
+            .map(|b| b.ticket_number.clone())
+            .collect();
+        let mut rebooked = 0;
+        for ticket in &tickets {
+            match self.data_manager.rebook(ticket, &new_flight_number) {
+                Ok(()) => rebooked += 1,
+                Err(e) => self.display.display_warning_message(&format!("{} not rebooked: {}", ticket, e))?,
+            }
+        }
+        self.display.display_success_message(&format!(
+            "{} of {} passengers rebooked onto {}", rebooked, tickets.len(), new_flight_number
+        ))
+    }
+
+    /// Build a recurring template and create a week of flights from it
+    fn generate_weekly_schedule(&mut self) -> Result<(), Box<dyn Error>> {
+        let flight_number = self.input.get_flight_number_input()?.trim().to_uppercase();
+        let airline = match self.input.get_string_input("Airline [Rust International Airways]:")? {
+            name if name.is_empty() => "Rust International Airways".to_string(),
+            name => name,
+        };
+        let origin = self.input.get_airport_code_input("Origin Airport:", &self.data_manager.database.airports)?;
+        let destination = self.input.get_airport_code_input("Destination Airport:", &self.data_manager.database.airports)?;
+        let departure_time = self.input.get_time_input("Departure time, UTC (HH:MM):")?;
+        let duration_minutes = self.input.get_number_input_with_range("Flight duration (minutes):", 1, 24 * 60)?;
+        let days = self.input.get_weekdays_input("Days of the week (e.g., Mon,Wed,Fri):")?;
+        let start = self.input.get_date_input("First day of the week:")?.date_naive();
+
+        let mut template = FlightTemplate {
+            flight_number,
+            airline,
+            origin,
+            destination,
+            aircraft_id: uuid::Uuid::nil(),
+            departure_time,
+            duration_minutes,
+            days,
+        };
+        let registration = self.input.get_string_input("Aircraft Registration (e.g., N123RIA, blank to pick by route preference):")?;
+        let aircraft = if registration.is_empty() {
+            // Pick for the first flight of the week; later ones are checked by create_flight
+            start.iter_days()
+                .take(7)
+                .find(|date| template.runs_on(*date))
+                .and_then(|date| {
+                    let (departure, arrival) = template.times_on(date);
+                    self.data_manager.auto_assign_aircraft(&template.origin, &template.destination, departure, arrival)
+                })
+        } else {
+            self.data_manager.get_aircraft_by_registration(&registration)
+        };
+        let Some((aircraft_id, registration)) = aircraft.map(|a| (a.id, a.registration.clone())) else {
+            return self.display.display_error_message(&if registration.is_empty() {
+                "No free aircraft can fly this route".to_string()
+            } else {
+                format!("Aircraft {} not found", registration)
+            });
+        };
+        template.aircraft_id = aircraft_id;
+        self.display.display_info_message(&format!("Flying the schedule with {}", registration))?;
+        match self.data_manager.generate_from_template(&template, start, start + chrono::Duration::days(6)) {
+            Ok(created) => {
+                let numbers: Vec<&str> = created
+                    .iter()
+                    .filter_map(|id| self.data_manager.get_flight_by_id(*id))
+                    .map(|f| f.flight_number.as_str())
+                    .collect();
+                self.display.display_success_message(&format!("Created {} flights: {}", created.len(), numbers.join(", ")))
+            }
+            Err(e) => self.display.display_error_message(&format!("Schedule generation failed: {}", e)),
+        }
+    }
+
+    fn aircraft_management(&mut self) -> Result<(), Box<dyn Error>> {
+        self.display.clear_screen()?;
+        self.display.display_header("Aircraft Management")?;
+        let fleet: Vec<&_> = self.data_manager.database.aircraft.iter().collect();
+        self.display.display_aircraft_table(&fleet)?;
+
+        println!("{}", "Aircraft Management Options:".bright_cyan().bold());
+        println!("  {} - Change aircraft status", "1".bright_yellow());
+        println!("  {} - Record maintenance", "2".bright_blue());
+        println!("  {} - Reassign a flight's aircraft", "3".bright_magenta());
+        println!("  {} - Ground an aircraft (safety hold)", "4".bright_red());
+        println!("  {} - Lift a grounding", "5".bright_green());
+        println!("  {} - Edit seat configuration", "6".bright_magenta());
+        println!("  {} - Back", "0".bright_red());
+        println!();
+
+        match self.input.get_menu_choice("Select option:", 0, 6)? {
+            1 => {
+                let registration = self.input.get_string_input("Aircraft Registration (e.g., N123RIA):")?;
+                println!("  {} - Active", "1".bright_green());
+                println!("  {} - Maintenance", "2".bright_yellow());
+                println!("  {} - Retired", "3".bright_red());
+                let status = match self.input.get_menu_choice("New status:", 1, 3)? {
+                    1 => AircraftStatus::Active,
+                    2 => AircraftStatus::Maintenance,
+                    _ => AircraftStatus::Retired,
+                };
+
+                match self.data_manager.set_aircraft_status(&registration, status) {
+                    Ok(()) => self.display.display_success_message(&format!("Aircraft {} status updated", registration))?,
+                    Err(e) => self.display.display_error_message(&format!("Failed to change status: {}", e))?,
+                }
+            }
+            2 => {
+                let registration = self.input.get_string_input("Aircraft Registration (e.g., N123RIA):")?;
+                let hours: f64 = self.input.get_number_input("Maintenance hours performed:")?;
+
+                match self.data_manager.record_maintenance(&registration, hours) {
+                    Ok(()) => {
+                        if let Some(aircraft) = self.data_manager.get_aircraft_by_registration(&registration) {
+                            self.display.display_success_message(&format!(
+                                "Maintenance recorded - {} is {}",
+                                aircraft.registration,
+                                aircraft.get_status_display()
+                            ))?;
+                        }
+                    }
+                    Err(e) => self.display.display_error_message(&format!("Failed to record maintenance: {}", e))?,
+                }
+            }
+            3 => {
+                let flight_number = self.input.get_flight_number_input()?;
+                let registration = self.input.get_string_input("New Aircraft Registration:")?;
+
+                match self.data_manager.reassign_aircraft(&flight_number, &registration) {
+                    Ok(()) => self.display.display_success_message(&format!("Flight {} reassigned to {}", flight_number, registration))?,
+                    Err(e) => self.display.display_error_message(&format!("Failed to reassign aircraft: {}", e))?,
+                }
+            }
+            4 => {
+                let registration = self.input.get_string_input("Aircraft Registration (e.g., N123RIA):")?;
+                let reason = self.input.get_string_input("Reason for grounding:")?;
+
+                match self.data_manager.ground_aircraft(&registration, reason) {
+                    Ok(()) => self.display.display_success_message(&format!("Aircraft {} grounded", registration))?,
+                    Err(e) => self.display.display_error_message(&format!("Failed to ground aircraft: {}", e))?,
+                }
+            }
+            5 => {
+                let registration = self.input.get_string_input("Aircraft Registration (e.g., N123RIA):")?;
+
+                match self.data_manager.unground_aircraft(&registration) {
+                    Ok(()) => self.display.display_success_message(&format!("Grounding lifted for {}", registration))?,
+                    Err(e) => self.display.display_error_message(&format!("Failed to lift grounding: {}", e))?,
+                }
+            }
+            6 => {
+                let registration = self.input.get_string_input("Aircraft Registration (e.g., N123RIA):")?;
+                let Some(aircraft) = self.data_manager.get_aircraft_by_registration(&registration) else {
+                    return self.display.display_error_message(&format!("Aircraft {} not found", registration));
+                };
+                let current = &aircraft.seat_configuration;
+                println!("Current cabins: First {}×{}, Business {}×{}, Economy {}×{} ({} seats)",
+                    current.first_class_rows, current.first_class_seats_per_row,
+                    current.business_rows, current.business_seats_per_row,
+                    current.economy_rows, current.economy_seats_per_row,
+                    aircraft.total_capacity);
+
+                let seat_configuration = SeatConfiguration {
+                    first_class_rows: self.input.get_number_input_with_range("First class rows:", 0, 100)?,
+                    first_class_seats_per_row: self.input.get_number_input_with_range("First class seats per row:", 0, 10)?,
+                    business_rows: self.input.get_number_input_with_range("Business rows:", 0, 100)?,
+                    business_seats_per_row: self.input.get_number_input_with_range("Business seats per row:", 0, 10)?,
+                    economy_rows: self.input.get_number_input_with_range("Economy rows:", 0, 100)?,
+                    economy_seats_per_row: self.input.get_number_input_with_range("Economy seats per row:", 0, 10)?,
+                };
+
+                let upcoming = self.data_manager.upcoming_flights_for_aircraft(&registration).len();
+                let update_flights = upcoming > 0 && {
+                    self.display.display_warning_message(&format!(
+                        "{} is assigned to {} upcoming flight(s) sold against the current cabins", registration, upcoming
+                    ))?;
+                    self.input.get_yes_no_input("Resize those flights to the new cabins?")?
+                };
+
+                match self.data_manager.set_seat_configuration(&registration, seat_configuration, update_flights) {
+                    Ok(updated) => self.display.display_success_message(&format!(
+                        "Seat configuration for {} updated ({} flights resized)", registration, updated
+                    ))?,
+                    Err(e) => self.display.display_error_message(&format!("Failed to update seat configuration: {}", e))?,
+                }
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
 }
\ No newline at end of file
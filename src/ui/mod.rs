@@ -1,3 +0,0 @@
-pub mod menu;
-pub mod display;
-pub mod input;
\ No newline at end of file